@@ -1,21 +1,28 @@
 //! Root application component with layout and routing.
 
 use leptos::prelude::*;
+use wasm_bindgen::JsCast;
 
+use crate::components::error_banner::ErrorBanner;
+use crate::components::shortcuts_overlay::ShortcutsOverlay;
 use crate::components::sidebar::Sidebar;
-use crate::ipc::commands::tauri_invoke_no_args;
+use crate::ipc::commands::{tauri_invoke, tauri_invoke_no_args};
 use crate::ipc::events::tauri_listen;
-use crate::pages::control::ControlPage;
+use crate::ipc::reconnect::{resubscribe_on_filter_change, watch_for_disconnects};
+use crate::pages::compare::ComparePage;
+use crate::pages::control::{refresh_executions, toggle_execution_detail, ControlPage};
 use crate::pages::history::HistoryPage;
 use crate::pages::inventory::InventoryPage;
 use crate::pages::monitor::MonitorPage;
-use crate::state::{AgentEventDto, AppState, DaemonStatusDto, InventoryDto, Page,
+use crate::pages::settings::SettingsPage;
+use crate::state::{AgentEventDto, AppState, DaemonStatusDto, InventoryDto, Page, SettingsDto,
     TreeEdge, TreeNode, TreeNodeStatus, TreeNodeType};
 
 #[component]
 pub fn App() -> impl IntoView {
     let state = AppState::new();
     provide_context(state.clone());
+    state.theme.get_untracked().apply();
 
     // Load inventory on mount
     {
@@ -34,10 +41,16 @@ pub fn App() -> impl IntoView {
         });
     }
 
-    // Ping daemon on mount
+    // Load settings, then ping the daemon they point at
     {
         let state = state.clone();
         wasm_bindgen_futures::spawn_local(async move {
+            match tauri_invoke_no_args::<SettingsDto>("get_settings").await {
+                Ok(settings) => state.settings.set(settings),
+                Err(e) => {
+                    web_sys::console::error_1(&format!("Settings load failed: {e}").into());
+                }
+            }
             match tauri_invoke_no_args::<DaemonStatusDto>("ping_daemon").await {
                 Ok(status) => state.daemon_status.set(status),
                 Err(_) => {
@@ -51,6 +64,17 @@ pub fn App() -> impl IntoView {
         });
     }
 
+    // Reconnect and resubscribe when the daemon event stream drops
+    watch_for_disconnects(state.clone());
+    // Resubscribe when the event filter changes what the daemon must send
+    resubscribe_on_filter_change(state.clone());
+
+    // Global keyboard shortcuts
+    {
+        let state = state.clone();
+        let _ = window_event_listener(leptos::ev::keydown, move |ev| handle_shortcut(&state, &ev));
+    }
+
     // Listen for agent events and update execution state
     {
         let state = state.clone();
@@ -157,6 +181,9 @@ pub fn App() -> impl IntoView {
                                             if let Some(ref mut data) = node.event_data {
                                                 if let Some(obj) = data.as_object_mut() {
                                                     obj.insert("tool_output".to_string(), output.clone());
+                                                    if let Some(flag) = event.data.get("tool_output_truncated") {
+                                                        obj.insert("tool_output_truncated".to_string(), flag.clone());
+                                                    }
                                                 }
                                             }
                                         }
@@ -321,22 +348,120 @@ pub fn App() -> impl IntoView {
                 }
             }
 
-            // Always append to the event log
-            state.events.update(|evts| evts.push(event));
+            // Always append to the event log (batched)
+            state.queue_event(event);
         });
     }
 
     let current_page = state.current_page;
+    let reconnecting = state.reconnecting;
 
     view! {
         <Sidebar />
+        <ShortcutsOverlay />
         <main class="main-content">
+            {move || reconnecting.get().then(|| view! {
+                <ErrorBanner
+                    title="Reconnecting"
+                    message="Lost connection to the daemon — reconnecting…".to_string()
+                />
+            })}
             {move || match current_page.get() {
                 Page::Inventory => view! { <InventoryPage /> }.into_any(),
                 Page::Monitor => view! { <MonitorPage /> }.into_any(),
                 Page::Control => view! { <ControlPage /> }.into_any(),
                 Page::History => view! { <HistoryPage /> }.into_any(),
+                Page::Compare => view! { <ComparePage /> }.into_any(),
+                Page::Settings => view! { <SettingsPage /> }.into_any(),
             }}
         </main>
     }
 }
+
+/// Dispatch a global keyboard shortcut. Keys typed into form fields or
+/// combined with modifiers are left alone.
+fn handle_shortcut(state: &AppState, ev: &web_sys::KeyboardEvent) {
+    if ev.ctrl_key() || ev.meta_key() || ev.alt_key() {
+        return;
+    }
+    let typing = ev
+        .target()
+        .and_then(|t| t.dyn_into::<web_sys::Element>().ok())
+        .map(|el| matches!(el.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT"))
+        .unwrap_or(false);
+    if typing {
+        return;
+    }
+
+    match ev.key().as_str() {
+        "?" => state.show_shortcuts.update(|show| *show = !*show),
+        "Escape" => state.show_shortcuts.set(false),
+        "/" => {
+            ev.prevent_default();
+            state.current_page.set(Page::Inventory);
+            // Wait for the page to render before focusing its search box
+            request_animation_frame(focus_search);
+        }
+        "j" => move_selection(state, 1),
+        "k" => move_selection(state, -1),
+        "Enter" => {
+            if let Some(eid) = state.selected_execution.get_untracked() {
+                toggle_execution_detail(state, eid);
+            }
+        }
+        "p" => toggle_pause_selected(state),
+        _ => {}
+    }
+}
+
+fn focus_search() {
+    let input = web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.query_selector(".search-input").ok().flatten())
+        .and_then(|el| el.dyn_into::<web_sys::HtmlElement>().ok());
+    if let Some(input) = input {
+        let _ = input.focus();
+    }
+}
+
+/// Move the keyboard cursor through the execution list by `delta` rows.
+fn move_selection(state: &AppState, delta: i32) {
+    let execs = state.executions.get_untracked();
+    if execs.is_empty() {
+        return;
+    }
+    let current = state
+        .selected_execution
+        .get_untracked()
+        .and_then(|id| execs.iter().position(|e| e.execution_id == id));
+    let next = match current {
+        Some(i) => (i as i32 + delta).clamp(0, execs.len() as i32 - 1) as usize,
+        None => 0,
+    };
+    state.selected_execution.set(Some(execs[next].execution_id.clone()));
+    state.current_page.set(Page::Control);
+}
+
+fn toggle_pause_selected(state: &AppState) {
+    let Some(eid) = state.selected_execution.get_untracked() else {
+        return;
+    };
+    let exec_state = state
+        .executions
+        .get_untracked()
+        .into_iter()
+        .find(|e| e.execution_id == eid)
+        .map(|e| e.state);
+    let cmd = match exec_state.as_deref() {
+        Some("running") => "pause_execution",
+        Some("paused") => "resume_execution",
+        _ => return,
+    };
+
+    let state = state.clone();
+    wasm_bindgen_futures::spawn_local(async move {
+        let args = serde_json::json!({"execution_id": eid});
+        let _: Result<String, _> = tauri_invoke(cmd, &args).await;
+        refresh_executions(&state).await;
+    });
+}
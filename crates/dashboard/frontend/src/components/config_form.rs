@@ -3,14 +3,18 @@
 use leptos::prelude::*;
 use std::sync::Arc;
 
+use crate::state::AppState;
+
 #[component]
 pub fn ConfigForm(
     on_submit: Arc<dyn Fn(String, i32, f32, String) + Send + Sync>,
 ) -> impl IntoView {
+    let defaults = expect_context::<AppState>().settings.get_untracked();
+
     let task = RwSignal::new(String::new());
-    let max_iter = RwSignal::new(3_i32);
-    let threshold = RwSignal::new(70.0_f32);
-    let model = RwSignal::new("sonnet".to_string());
+    let max_iter = RwSignal::new(defaults.default_max_iterations);
+    let threshold = RwSignal::new(defaults.default_quality_threshold);
+    let model = RwSignal::new(defaults.default_model);
     let task_error = RwSignal::new(Option::<String>::None);
     let submitting = RwSignal::new(false);
 
@@ -0,0 +1,77 @@
+//! Small "Copy" button that writes text to the clipboard.
+
+use gloo_timers::future::TimeoutFuture;
+use leptos::prelude::*;
+
+use crate::ipc::clipboard::write_clipboard;
+use crate::ipc::commands::tauri_invoke;
+
+/// How long the button shows "Copied" after a successful write.
+const COPIED_FEEDBACK_MS: u32 = 1_500;
+
+/// Copies `text` on click. The click does not propagate, so the button can
+/// sit inside clickable rows (e.g. expandable log entries).
+#[component]
+pub fn CopyButton(#[prop(into)] text: String) -> impl IntoView {
+    let copied = RwSignal::new(false);
+
+    view! {
+        <button class="btn-copy" title="Copy to clipboard" on:click=move |ev| {
+            ev.stop_propagation();
+            let text = text.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                copy_with_feedback(&text, copied).await;
+            });
+        }>
+            {move || if copied.get() { "Copied" } else { "Copy" }}
+        </button>
+    }
+}
+
+/// Copies a truncated tool result in full: the untruncated output is fetched
+/// from the daemon first, falling back to `text` (the shown, truncated part)
+/// once the daemon no longer has it.
+#[component]
+pub fn FullOutputCopyButton(
+    execution_id: String,
+    tool_use_id: String,
+    #[prop(into)] text: String,
+) -> impl IntoView {
+    let copied = RwSignal::new(false);
+
+    view! {
+        <button class="btn-copy" title="Copy the full output to clipboard" on:click=move |ev| {
+            ev.stop_propagation();
+            let args = serde_json::json!({
+                "execution_id": execution_id,
+                "tool_use_id": tool_use_id,
+            });
+            let fallback = text.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let text = match tauri_invoke::<_, String>("get_tool_output", &args).await {
+                    Ok(full) => full,
+                    Err(e) => {
+                        web_sys::console::warn_1(
+                            &format!("Copying truncated output: {e}").into(),
+                        );
+                        fallback
+                    }
+                };
+                copy_with_feedback(&text, copied).await;
+            });
+        }>
+            {move || if copied.get() { "Copied" } else { "Copy" }}
+        </button>
+    }
+}
+
+async fn copy_with_feedback(text: &str, copied: RwSignal<bool>) {
+    match write_clipboard(text).await {
+        Ok(()) => {
+            copied.set(true);
+            TimeoutFuture::new(COPIED_FEEDBACK_MS).await;
+            copied.set(false);
+        }
+        Err(e) => web_sys::console::error_1(&e.into()),
+    }
+}
@@ -1,7 +1,32 @@
-//! Diff view component for showing file changes with old/new content.
+//! Diff view component for showing file changes with old/new content,
+//! syntax-highlighted by the file's extension.
 
 use leptos::prelude::*;
 
+use crate::highlight::{highlight, Language};
+
+/// Code block, token-colored when the language is known and the text is
+/// small enough; plain otherwise.
+#[component]
+fn CodeBlock(text: String, language: Option<Language>) -> impl IntoView {
+    let Some(lines) = highlight(&text, language) else {
+        return view! { <pre class="diff-content">{text}</pre> }.into_any();
+    };
+
+    let spans = lines
+        .into_iter()
+        .enumerate()
+        .flat_map(|(i, tokens)| {
+            let newline = (i > 0).then(|| view! { <span>"\n"</span> }.into_any());
+            newline.into_iter().chain(tokens.into_iter().map(|(kind, token)| {
+                view! { <span class=kind.class()>{token.to_string()}</span> }.into_any()
+            }))
+        })
+        .collect_view();
+
+    view! { <pre class="diff-content highlighted">{spans}</pre> }.into_any()
+}
+
 #[component]
 pub fn DiffView(
     #[prop(into)] file_path: String,
@@ -11,6 +36,7 @@ pub fn DiffView(
     #[prop(into, default = String::new())] content: String,
 ) -> impl IntoView {
     let path_display = file_path.clone();
+    let language = Language::from_path(&file_path);
 
     match action.as_str() {
         "edit" => {
@@ -22,7 +48,7 @@ pub fn DiffView(
                         Some(view! {
                             <div class="diff-block diff-removed">
                                 <div class="diff-block-header">"Removed"</div>
-                                <pre class="diff-content">{old_display}</pre>
+                                <CodeBlock text=old_display language=language />
                             </div>
                         })
                     } else {
@@ -33,7 +59,7 @@ pub fn DiffView(
                         Some(view! {
                             <div class="diff-block diff-added">
                                 <div class="diff-block-header">"Added"</div>
-                                <pre class="diff-content">{new_display}</pre>
+                                <CodeBlock text=new_display language=language />
                             </div>
                         })
                     } else {
@@ -48,7 +74,7 @@ pub fn DiffView(
                     <div class="diff-header">"New File: " {path_display}</div>
                     <div class="diff-block diff-added">
                         <div class="diff-block-header">"New File"</div>
-                        <pre class="diff-content">{content}</pre>
+                        <CodeBlock text=content language=language />
                     </div>
                 </div>
             }.into_any()
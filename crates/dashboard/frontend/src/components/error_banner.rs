@@ -6,12 +6,15 @@ use leptos::prelude::*;
 pub fn ErrorBanner(
     message: String,
     #[prop(optional)] retry: Option<Callback<()>>,
+    /// Heading shown above the message; defaults to "Error".
+    #[prop(optional, into)] title: Option<String>,
 ) -> impl IntoView {
+    let title = title.unwrap_or_else(|| "Error".to_string());
     view! {
         <div class="error-banner">
             <div class="error-icon">"⚠️"</div>
             <div class="error-content">
-                <strong>"Error"</strong>
+                <strong>{title}</strong>
                 <p>{message}</p>
             </div>
             {retry.map(|cb| view! {
@@ -1,47 +1,322 @@
 //! Scrolling event log component.
+//!
+//! Rows have a fixed height so the log can be virtualized: only the rows in
+//! (or near) the viewport are rendered, however many events are loaded.
 
 use leptos::prelude::*;
 
-use crate::state::{AgentEventDto, AppState};
+use crate::components::copy_button::CopyButton;
+use crate::components::search_filter::SearchInput;
+use crate::state::{event_search_fields, AgentEventDto, AppState, EventCategory};
+
+/// Height of one log row in px; must match `.event-log-entry` in main.css.
+const ROW_HEIGHT: f64 = 28.0;
+
+/// Rows that fit in the log's max height (400px).
+const VIEWPORT_ROWS: usize = 15;
+
+/// Extra rows rendered above and below the viewport.
+const OVERSCAN_ROWS: usize = 10;
+
+/// Characters of the event payload shown per row.
+const PREVIEW_CHARS: usize = 120;
+
+/// Log level options for the dropdown: (proto `LogLevel` value, label).
+const LOG_LEVELS: &[(i32, &str)] = &[
+    (0, "All levels"),
+    (1, "Debug+"),
+    (2, "Info+"),
+    (3, "Warn+"),
+    (4, "Error"),
+];
+
+/// Search box, category chips and log-level dropdown bound to
+/// `AppState::event_filter`.
+#[component]
+fn EventFilterBar() -> impl IntoView {
+    let state = expect_context::<AppState>();
+    let filter = state.event_filter;
+
+    let chip_class = move |category: EventCategory| {
+        move || {
+            if filter.get().is_shown(category) {
+                "filter-btn active"
+            } else {
+                "filter-btn"
+            }
+        }
+    };
+
+    view! {
+        <div class="search-bar event-search-bar">
+            <SearchInput
+                value=Signal::derive(move || filter.get().query)
+                on_input=Callback::new(move |query| filter.update(|f| f.query = query))
+                placeholder="Search tool calls, errors, messages..."
+            />
+        </div>
+        <div class="event-filter-bar">
+            {EventCategory::ALL.into_iter().map(|category| view! {
+                <button
+                    class={chip_class(category)}
+                    on:click=move |_| filter.update(|f| f.toggle(category))
+                >
+                    {category.label()}
+                </button>
+            }).collect_view()}
+            <select
+                class="form-select event-level-select"
+                on:change=move |ev| {
+                    if let Ok(level) = event_target_value(&ev).parse() {
+                        filter.update(|f| f.min_log_level = level);
+                    }
+                }
+            >
+                {LOG_LEVELS.iter().map(|(value, label)| view! {
+                    <option
+                        value=value.to_string()
+                        selected=move || filter.get().min_log_level == *value
+                    >
+                        {*label}
+                    </option>
+                }).collect_view()}
+            </select>
+        </div>
+    }
+}
+
+/// Split `text` into (segment, is_match) pieces around case-insensitive
+/// occurrences of `query`.
+fn highlight_segments(text: &str, query: &str) -> Vec<(String, bool)> {
+    let query = query.trim();
+    if query.is_empty() {
+        return vec![(text.to_string(), false)];
+    }
+    // ASCII lowering keeps byte offsets aligned with `text`
+    let haystack = text.to_ascii_lowercase();
+    let needle = query.to_ascii_lowercase();
+
+    let mut segments = Vec::new();
+    let mut start = 0;
+    while let Some(pos) = haystack[start..].find(&needle) {
+        let begin = start + pos;
+        let end = begin + needle.len();
+        if begin > start {
+            segments.push((text[start..begin].to_string(), false));
+        }
+        segments.push((text[begin..end].to_string(), true));
+        start = end;
+    }
+    if start < text.len() {
+        segments.push((text[start..].to_string(), false));
+    }
+    segments
+}
+
+/// Up to `PREVIEW_CHARS` characters of `text`, starting a little before the
+/// first match of `query` so highlighted hits stay visible.
+fn preview(text: &str, query: &str) -> String {
+    let query = query.trim().to_ascii_lowercase();
+    let match_at = if query.is_empty() {
+        None
+    } else {
+        text.to_ascii_lowercase().find(&query)
+    };
+    let skip = match_at
+        .map(|byte| text[..byte].chars().count().saturating_sub(20))
+        .unwrap_or(0);
+
+    let total = text.chars().count();
+    let body: String = text.chars().skip(skip).take(PREVIEW_CHARS).collect();
+    let prefix = if skip > 0 { "..." } else { "" };
+    let suffix = if skip + PREVIEW_CHARS < total { "..." } else { "" };
+    format!("{prefix}{body}{suffix}")
+}
+
+/// Text with query matches wrapped in `<mark>`.
+#[component]
+fn Highlighted(text: String, query: String) -> impl IntoView {
+    highlight_segments(&text, &query)
+        .into_iter()
+        .map(|(segment, hit)| {
+            if hit {
+                view! { <mark class="search-hit">{segment}</mark> }.into_any()
+            } else {
+                segment.into_any()
+            }
+        })
+        .collect_view()
+}
+
+/// Index range of rows to render for a scroll offset.
+fn visible_range(scroll_top: f64, total: usize) -> std::ops::Range<usize> {
+    let first = (scroll_top / ROW_HEIGHT).floor().max(0.0) as usize;
+    let start = first.saturating_sub(OVERSCAN_ROWS).min(total);
+    let end = (first + VIEWPORT_ROWS + OVERSCAN_ROWS).min(total);
+    start..end
+}
+
+#[component]
+fn EventRow(event: AgentEventDto, query: String) -> impl IntoView {
+    // Copy the full payload, not the truncated preview
+    let full = serde_json::to_string_pretty(&event.data).unwrap_or_default();
+    // Lead with the searchable summary/message when there is one
+    let text = event_search_fields(&event)
+        .next()
+        .map(str::to_string)
+        .unwrap_or_else(|| event.data.to_string());
+    let data_str = preview(&text, &query);
+
+    view! {
+        <div class="event-log-entry" class:decision=event.event_type == "decision_made">
+            <span class="event-type">
+                <Highlighted text=event.event_type.clone() query=query.clone() />
+            </span>
+            <span class="event-data">
+                <Highlighted text=data_str query=query />
+            </span>
+            <CopyButton text=full />
+        </div>
+    }
+}
 
 #[component]
 pub fn EventLog() -> impl IntoView {
     let state = expect_context::<AppState>();
+    let log_ref = NodeRef::<leptos::html::Div>::new();
+    let scroll_top = RwSignal::new(0.0_f64);
+    // Follow new events until the user scrolls up
+    let stick_to_bottom = RwSignal::new(true);
+
+    // Indices into `state.events` of matching events, oldest first
+    let matching = Memo::new(move |_| {
+        let filter = state.event_filter.get();
+        state.events.with(|evts| {
+            evts.iter()
+                .enumerate()
+                .filter(|(_, evt)| filter.matches(evt))
+                .map(|(i, _)| i)
+                .collect::<Vec<usize>>()
+        })
+    });
+
+    // Keep the newest event in view after each batch while following
+    Effect::new(move |_| {
+        matching.track();
+        if stick_to_bottom.get_untracked() {
+            request_animation_frame(move || {
+                if let Some(el) = log_ref.get_untracked() {
+                    el.set_scroll_top(el.scroll_height());
+                }
+            });
+        }
+    });
 
-    let events = move || {
-        let evts = state.events.get();
-        // Show most recent first, limit to 100
-        evts.into_iter().rev().take(100).collect::<Vec<_>>()
+    let on_scroll = move |_| {
+        if let Some(el) = log_ref.get_untracked() {
+            scroll_top.set(el.scroll_top() as f64);
+            let from_bottom = el.scroll_height() - el.scroll_top() - el.client_height();
+            stick_to_bottom.set(from_bottom as f64 <= ROW_HEIGHT);
+        }
+    };
+
+    let result_count = move || {
+        let shown = matching.with(|m| m.len());
+        let total = state.events.with(|e| e.len());
+        format!("{shown} of {total} events")
     };
 
     view! {
-        <div class="event-log">
+        <EventFilterBar />
+        <div class="event-result-count">{result_count}</div>
+        <div class="event-log" node_ref=log_ref on:scroll=on_scroll>
             {move || {
-                let evts = events();
-                if evts.is_empty() {
-                    view! {
+                let query = state.event_filter.with(|f| f.query.clone());
+                let total = matching.with(|m| m.len());
+                if total == 0 {
+                    let (title, hint) = if state.events.with(|e| e.is_empty()) {
+                        ("No events yet", "Events will appear here when an execution is running.")
+                    } else {
+                        ("No matching events", "Try a different search or enable more event types.")
+                    };
+                    return view! {
                         <div class="empty-state">
-                            <h3>"No events yet"</h3>
-                            <p>"Events will appear here when an execution is running."</p>
+                            <h3>{title}</h3>
+                            <p>{hint}</p>
                         </div>
-                    }.into_any()
-                } else {
-                    evts.into_iter().map(|evt| {
-                        let data_str = evt.data.to_string();
-                        let data_str = if data_str.len() > 120 {
-                            format!("{}...", &data_str[..120])
-                        } else {
-                            data_str
-                        };
-                        view! {
-                            <div class="event-log-entry">
-                                <span class="event-type">{evt.event_type.clone()}</span>
-                                <span class="event-data">{data_str}</span>
-                            </div>
-                        }
-                    }).collect_view().into_any()
+                    }.into_any();
                 }
+
+                let range = visible_range(scroll_top.get(), total);
+                let above = range.start as f64 * ROW_HEIGHT;
+                let below = (total - range.end) as f64 * ROW_HEIGHT;
+                let rows = matching.with(|m| {
+                    state.events.with(|evts| {
+                        m[range].iter().map(|&i| evts[i].clone()).collect::<Vec<_>>()
+                    })
+                });
+                view! {
+                    <div style=format!("height: {above}px;")></div>
+                    {rows.into_iter().map(|event| view! {
+                        <EventRow event=event query=query.clone() />
+                    }).collect_view()}
+                    <div style=format!("height: {below}px;")></div>
+                }.into_any()
             }}
         </div>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments(parts: &[(&str, bool)]) -> Vec<(String, bool)> {
+        parts.iter().map(|(s, hit)| (s.to_string(), *hit)).collect()
+    }
+
+    #[test]
+    fn test_highlight_segments_case_insensitive() {
+        assert_eq!(
+            highlight_segments("Running cargo TEST now", " test "),
+            segments(&[("Running cargo ", false), ("TEST", true), (" now", false)])
+        );
+        assert_eq!(highlight_segments("no hits", "zzz"), segments(&[("no hits", false)]));
+        assert_eq!(highlight_segments("text", ""), segments(&[("text", false)]));
+    }
+
+    #[test]
+    fn test_highlight_segments_multiple_and_overlapping() {
+        assert_eq!(
+            highlight_segments("ab-AB-ab", "ab"),
+            segments(&[("ab", true), ("-", false), ("AB", true), ("-", false), ("ab", true)])
+        );
+        // Matches do not overlap: "aaa" holds one "aa" plus a leftover "a"
+        assert_eq!(highlight_segments("aaa", "aa"), segments(&[("aa", true), ("a", false)]));
+    }
+
+    #[test]
+    fn test_highlight_segments_non_ascii_text() {
+        assert_eq!(
+            highlight_segments("résumé SUMMARY", "sum"),
+            segments(&[("ré", false), ("sum", true), ("é ", false), ("SUM", true), ("MARY", false)])
+        );
+    }
+
+    #[test]
+    fn test_preview_truncates_on_char_boundaries() {
+        let short = "short message";
+        assert_eq!(preview(short, ""), short);
+
+        let long = "ä".repeat(PREVIEW_CHARS + 30);
+        let shown = preview(&long, "");
+        assert_eq!(shown, format!("{}...", "ä".repeat(PREVIEW_CHARS)));
+
+        // The window starts 20 characters before a late match
+        let text = format!("{}NEEDLE{}", "é".repeat(30), "ü".repeat(200));
+        let shown = preview(&text, "needle");
+        assert!(shown.starts_with(&format!("...{}NEEDLE", "é".repeat(20))), "{shown}");
+        assert!(shown.ends_with("ü..."));
+        assert_eq!(shown.chars().count(), PREVIEW_CHARS + 6);
+    }
+}
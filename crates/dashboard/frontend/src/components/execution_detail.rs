@@ -1,8 +1,10 @@
 //! Expandable execution detail panel component.
 
 use leptos::prelude::*;
+use crate::components::copy_button::{CopyButton, FullOutputCopyButton};
 use crate::components::diff_view::DiffView;
 use crate::components::execution_tree::ExecutionTree;
+use crate::ipc::commands::tauri_invoke;
 use crate::state::{AppState, ExecutionDetailDto, ScoreDimensionDto, RunInstructionsDto, AgentEventDto};
 
 #[component]
@@ -40,6 +42,7 @@ fn DetailContent(detail: ExecutionDetailDto) -> impl IntoView {
     let detail_for_log = detail.clone();
     let detail_for_files = detail.clone();
     let detail_for_quality = detail.clone();
+    let execution_id = detail.execution_id.clone();
 
     view! {
         <div class="exec-detail-panel">
@@ -58,6 +61,8 @@ fn DetailContent(detail: ExecutionDetailDto) -> impl IntoView {
                 None
             }}
 
+            <ExportEvents execution_id=execution_id />
+
             <div class="detail-tab-bar">
                 <button
                     class=move || if active_tab.get() == 0 { "detail-tab active" } else { "detail-tab" }
@@ -104,7 +109,7 @@ fn DetailContent(detail: ExecutionDetailDto) -> impl IntoView {
                     }
                     2 => {
                         let d = detail_for_files.clone();
-                        view! { <FilesChangedTab files_written=d.files_written.clone() files_edited=d.files_edited.clone() events=d.events.clone() /> }.into_any()
+                        view! { <FilesChangedTab files_written=d.files_written.clone() files_edited=d.files_edited.clone() external_sources=d.external_sources.clone() events=d.events.clone() /> }.into_any()
                     }
                     3 => {
                         let d = detail_for_quality.clone();
@@ -120,10 +125,49 @@ fn DetailContent(detail: ExecutionDetailDto) -> impl IntoView {
     }
 }
 
-/// Copy text to clipboard via the Web Clipboard API.
-fn copy_to_clipboard(text: &str) {
-    if let Some(clipboard) = web_sys::window().map(|w| w.navigator().clipboard()) {
-        let _ = clipboard.write_text(text);
+/// "Export CSV" / "Export JSON" buttons that save the execution's event
+/// log through the backend's save dialog.
+#[component]
+fn ExportEvents(execution_id: String) -> impl IntoView {
+    let exporting = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+    let saved = RwSignal::new(None::<String>);
+
+    let export = move |format: &'static str| {
+        let execution_id = execution_id.clone();
+        exporting.set(true);
+        error.set(None);
+        saved.set(None);
+        wasm_bindgen_futures::spawn_local(async move {
+            let args = serde_json::json!({"execution_id": execution_id, "format": format});
+            match tauri_invoke::<_, Option<String>>("export_execution_events", &args).await {
+                Ok(path) => saved.set(path),
+                Err(e) => error.set(Some(e)),
+            }
+            exporting.set(false);
+        });
+    };
+    let export_json = export.clone();
+
+    view! {
+        <div class="detail-export">
+            <button
+                class="btn btn-secondary"
+                disabled=move || exporting.get()
+                on:click=move |_| export("csv")
+            >
+                "Export CSV"
+            </button>
+            <button
+                class="btn btn-secondary"
+                disabled=move || exporting.get()
+                on:click=move |_| export_json("json")
+            >
+                "Export JSON"
+            </button>
+            {move || saved.get().map(|path| view! { <span class="detail-export-saved">"Saved to " {path}</span> })}
+            {move || error.get().map(|e| view! { <span class="detail-export-error">{e}</span> })}
+        </div>
     }
 }
 
@@ -133,7 +177,6 @@ fn CopyableCommand(#[prop(into)] label: String, #[prop(into)] command: String) -
         return view! { <div></div> }.into_any();
     }
 
-    let cmd_for_copy = command.clone();
     let cmd_for_display = command.clone();
 
     view! {
@@ -141,9 +184,7 @@ fn CopyableCommand(#[prop(into)] label: String, #[prop(into)] command: String) -
             <div class="log-label">{label}</div>
             <div class="copyable-cmd">
                 <code>{cmd_for_display}</code>
-                <button class="btn-copy" on:click=move |_| {
-                    copy_to_clipboard(&cmd_for_copy);
-                }>"Copy"</button>
+                <CopyButton text=command />
             </div>
         </div>
     }.into_any()
@@ -242,7 +283,18 @@ fn LogEntry(index: usize, event: AgentEventDto) -> impl IntoView {
         .map(|v| serde_json::to_string_pretty(v).unwrap_or_default())
         .unwrap_or_default();
 
+    // The daemon cuts long results; copying one fetches the full output
+    let output_truncated = event.data.get("tool_output_truncated")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let tool_use_id = event.data.get("tool_use_id")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    let execution_id = event.execution_id.clone();
+
     let has_details = !tool_input.is_empty() || !tool_output.is_empty();
+    let event_json = serde_json::to_string_pretty(&event.data).unwrap_or_default();
     let tool_input_display = tool_input.clone();
     let tool_output_display = tool_output.clone();
 
@@ -256,6 +308,7 @@ fn LogEntry(index: usize, event: AgentEventDto) -> impl IntoView {
                 <span class="log-entry-num">{index + 1}</span>
                 <span class="log-entry-tool">{tool_name.clone()}</span>
                 <span class="log-entry-summary">{summary.clone()}</span>
+                <CopyButton text=event_json />
                 {if has_details {
                     Some(view! {
                         <span style="font-size: 11px; color: var(--text-muted);">
@@ -272,12 +325,26 @@ fn LogEntry(index: usize, event: AgentEventDto) -> impl IntoView {
                 }
                 let ti = tool_input_display.clone();
                 let to = tool_output_display.clone();
+                let copy_output = if output_truncated && !tool_use_id.is_empty() {
+                    view! {
+                        <FullOutputCopyButton
+                            execution_id=execution_id.clone()
+                            tool_use_id=tool_use_id.clone()
+                            text=to.clone()
+                        />
+                    }.into_any()
+                } else {
+                    view! { <CopyButton text=to.clone() /> }.into_any()
+                };
                 view! {
                     <div class="log-entry-detail">
                         {if !ti.is_empty() {
                             let ti2 = ti.clone();
                             Some(view! {
-                                <div class="log-label">"Input"</div>
+                                <div class="log-block-header">
+                                    <div class="log-label">"Input"</div>
+                                    <CopyButton text=ti.clone() />
+                                </div>
                                 <pre class="log-pre">{ti2}</pre>
                             })
                         } else {
@@ -286,7 +353,17 @@ fn LogEntry(index: usize, event: AgentEventDto) -> impl IntoView {
                         {if !to.is_empty() {
                             let to2 = to.clone();
                             Some(view! {
-                                <div class="log-label">"Output"</div>
+                                <div class="log-block-header">
+                                    <div class="log-label">
+                                        "Output"
+                                        {output_truncated.then(|| view! {
+                                            <span class="log-truncated" title="Only the first 2000 characters are shown; Copy fetches the full output">
+                                                " (truncated)"
+                                            </span>
+                                        })}
+                                    </div>
+                                    {copy_output}
+                                </div>
                                 <pre class="log-pre">{to2}</pre>
                             })
                         } else {
@@ -358,11 +435,16 @@ fn extract_diff_entries(events: &[AgentEventDto]) -> Vec<FileDiffEntry> {
 }
 
 #[component]
-fn FilesChangedTab(files_written: Vec<String>, files_edited: Vec<String>, events: Vec<AgentEventDto>) -> impl IntoView {
+fn FilesChangedTab(
+    files_written: Vec<String>,
+    files_edited: Vec<String>,
+    external_sources: Vec<String>,
+    events: Vec<AgentEventDto>,
+) -> impl IntoView {
     let diff_entries = extract_diff_entries(&events);
     let has_diffs = !diff_entries.is_empty();
 
-    if files_written.is_empty() && files_edited.is_empty() && !has_diffs {
+    if files_written.is_empty() && files_edited.is_empty() && external_sources.is_empty() && !has_diffs {
         return view! {
             <div class="detail-section-empty">"No file changes recorded."</div>
         }.into_any();
@@ -432,6 +514,17 @@ fn FilesChangedTab(files_written: Vec<String>, files_edited: Vec<String>, events
                     </div>
                 }
             }).collect_view()}
+
+            // Web pages fetched and searches made
+            {(!external_sources.is_empty()).then(|| view! {
+                <div class="log-label" style="margin-top: 12px;">"External Sources"</div>
+                {external_sources.into_iter().map(|source| view! {
+                    <div class="file-entry">
+                        <span class="file-action file-external">"W"</span>
+                        <span class="file-path">{source}</span>
+                    </div>
+                }).collect_view()}
+            })}
         </div>
     }.into_any()
 }
@@ -2,6 +2,8 @@
 
 use leptos::prelude::*;
 
+use crate::state::{AppState, Theme};
+
 #[component]
 pub fn PageHeader(
     #[prop(into)] title: String,
@@ -9,8 +11,34 @@ pub fn PageHeader(
 ) -> impl IntoView {
     view! {
         <div class="page-header">
-            <h2>{title}</h2>
-            <p>{subtitle}</p>
+            <div class="page-header-text">
+                <h2>{title}</h2>
+                <p>{subtitle}</p>
+            </div>
+            <ThemeToggle />
         </div>
     }
 }
+
+/// Button switching between the dark and light themes.
+#[component]
+pub fn ThemeToggle() -> impl IntoView {
+    let state = expect_context::<AppState>();
+    let theme = state.theme;
+
+    view! {
+        <button
+            class="btn btn-secondary theme-toggle"
+            title=move || match theme.get() {
+                Theme::Dark => "Switch to light theme",
+                Theme::Light => "Switch to dark theme",
+            }
+            on:click=move |_| state.toggle_theme()
+        >
+            {move || match theme.get() {
+                Theme::Dark => "☀ Light",
+                Theme::Light => "☾ Dark",
+            }}
+        </button>
+    }
+}
@@ -2,6 +2,7 @@
 
 pub mod agent_card;
 pub mod config_form;
+pub mod copy_button;
 pub mod empty_state;
 pub mod error_banner;
 pub mod event_log;
@@ -10,6 +11,7 @@ pub mod iteration_progress;
 pub mod loading_spinner;
 pub mod score_chart;
 pub mod search_filter;
+pub mod shortcuts_overlay;
 pub mod sidebar;
 pub mod status_badge;
 pub mod execution_detail;
@@ -4,13 +4,15 @@ use leptos::prelude::*;
 
 use crate::state::{AgentEventDto, AppState};
 
+/// Score progression of `events`, or of the live event stream when not given.
 #[component]
-pub fn ScoreChart() -> impl IntoView {
+pub fn ScoreChart(#[prop(optional, into)] events: Option<Signal<Vec<AgentEventDto>>>) -> impl IntoView {
     let state = expect_context::<AppState>();
+    let live = state.events;
+    let events = events.unwrap_or_else(|| Signal::derive(move || live.get()));
 
     let scores = move || {
-        state
-            .events
+        events
             .get()
             .iter()
             .filter(|e| e.event_type == "score_updated" || e.event_type == "iteration_completed")
@@ -1,4 +1,5 @@
-//! Search bar and filter buttons for inventory.
+//! Search bar and filter buttons for inventory, plus the shared search
+//! input also used by the event log.
 
 use leptos::prelude::*;
 
@@ -8,11 +9,6 @@ use crate::state::AppState;
 pub fn SearchFilter() -> impl IntoView {
     let state = expect_context::<AppState>();
 
-    let on_input = move |ev: leptos::ev::Event| {
-        let val = event_target_value(&ev);
-        state.search_query.set(val);
-    };
-
     let kinds = vec!["agent", "trait", "extension", "command", "skill", "mode"];
 
     let set_filter = move |kind: &'static str| {
@@ -38,12 +34,10 @@ pub fn SearchFilter() -> impl IntoView {
 
     view! {
         <div class="search-bar">
-            <input
-                class="search-input"
-                type="text"
+            <SearchInput
+                value=Signal::derive(move || state.search_query.get())
+                on_input=Callback::new(move |val| state.search_query.set(val))
                 placeholder="Search agents, commands, skills..."
-                on:input=on_input
-                prop:value={move || state.search_query.get()}
             />
             {kinds.into_iter().map(|k| view! {
                 <button class={is_active(k)} on:click={set_filter(k)}>{k}</button>
@@ -51,3 +45,21 @@ pub fn SearchFilter() -> impl IntoView {
         </div>
     }
 }
+
+/// Text input reporting every keystroke through `on_input`.
+#[component]
+pub fn SearchInput(
+    #[prop(into)] value: Signal<String>,
+    on_input: Callback<String>,
+    #[prop(into)] placeholder: String,
+) -> impl IntoView {
+    view! {
+        <input
+            class="search-input"
+            type="text"
+            placeholder=placeholder
+            on:input=move |ev| on_input.run(event_target_value(&ev))
+            prop:value=move || value.get()
+        />
+    }
+}
@@ -0,0 +1,37 @@
+//! Keyboard shortcuts help overlay, toggled with `?`.
+
+use leptos::prelude::*;
+
+use crate::state::AppState;
+
+/// Key / description pairs shown in the overlay.
+const SHORTCUTS: &[(&str, &str)] = &[
+    ("/", "Focus search"),
+    ("j / k", "Next / previous execution"),
+    ("Enter", "Open or close the selected execution"),
+    ("p", "Pause or resume the selected execution"),
+    ("?", "Show or hide this help"),
+    ("Esc", "Close this help"),
+];
+
+#[component]
+pub fn ShortcutsOverlay() -> impl IntoView {
+    let state = expect_context::<AppState>();
+    let show = state.show_shortcuts;
+
+    move || {
+        show.get().then(|| view! {
+            <div class="shortcuts-overlay" on:click=move |_| show.set(false)>
+                <div class="shortcuts-panel" on:click=|ev| ev.stop_propagation()>
+                    <h3>"Keyboard Shortcuts"</h3>
+                    {SHORTCUTS.iter().map(|(key, desc)| view! {
+                        <div class="shortcut-row">
+                            <kbd>{*key}</kbd>
+                            <span>{*desc}</span>
+                        </div>
+                    }).collect_view()}
+                </div>
+            </div>
+        })
+    }
+}
@@ -63,6 +63,14 @@ pub fn Sidebar() -> impl IntoView {
                     <span class="nav-icon">"@"</span>
                     <span>"History"</span>
                 </div>
+                <div class={is_active(Page::Compare)} on:click={set_page(Page::Compare)}>
+                    <span class="nav-icon">"="</span>
+                    <span>"Compare"</span>
+                </div>
+                <div class={is_active(Page::Settings)} on:click={set_page(Page::Settings)}>
+                    <span class="nav-icon">"*"</span>
+                    <span>"Settings"</span>
+                </div>
             </nav>
 
             <div class="sidebar-footer">
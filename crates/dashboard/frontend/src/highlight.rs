@@ -0,0 +1,247 @@
+//! Minimal line-based syntax highlighter for diff and file previews.
+//!
+//! Recognizes keywords, strings, numbers and line comments per language;
+//! block comments and multi-line strings are not tracked across lines.
+
+use std::path::Path;
+
+/// Larger inputs are shown as plain text to keep rendering fast.
+pub const MAX_HIGHLIGHT_BYTES: usize = 64 * 1024;
+
+/// Languages with token rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    Python,
+    JavaScript,
+    Go,
+    Shell,
+    Json,
+    Yaml,
+    Toml,
+    Css,
+}
+
+impl Language {
+    /// Language for a file path, from its extension. `None` for unknown
+    /// or missing extensions.
+    pub fn from_path(path: &str) -> Option<Self> {
+        let ext = Path::new(path).extension()?.to_str()?.to_ascii_lowercase();
+        let lang = match ext.as_str() {
+            "rs" => Language::Rust,
+            "py" | "pyi" => Language::Python,
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" => Language::JavaScript,
+            "go" => Language::Go,
+            "sh" | "bash" | "zsh" => Language::Shell,
+            "json" => Language::Json,
+            "yml" | "yaml" => Language::Yaml,
+            "toml" => Language::Toml,
+            "css" | "scss" => Language::Css,
+            _ => return None,
+        };
+        Some(lang)
+    }
+
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            Language::Rust => &[
+                "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else",
+                "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop",
+                "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self",
+                "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+                "where", "while",
+            ],
+            Language::Python => &[
+                "and", "as", "assert", "async", "await", "break", "class", "continue", "def",
+                "del", "elif", "else", "except", "False", "finally", "for", "from", "global",
+                "if", "import", "in", "is", "lambda", "None", "nonlocal", "not", "or", "pass",
+                "raise", "return", "True", "try", "while", "with", "yield",
+            ],
+            Language::JavaScript => &[
+                "async", "await", "break", "case", "catch", "class", "const", "continue",
+                "default", "delete", "else", "export", "extends", "false", "finally", "for",
+                "from", "function", "if", "import", "in", "instanceof", "interface", "let",
+                "new", "null", "return", "switch", "this", "throw", "true", "try", "type",
+                "typeof", "undefined", "var", "while", "yield",
+            ],
+            Language::Go => &[
+                "break", "case", "chan", "const", "continue", "default", "defer", "else",
+                "false", "for", "func", "go", "if", "import", "interface", "map", "nil",
+                "package", "range", "return", "select", "struct", "switch", "true", "type",
+                "var",
+            ],
+            Language::Shell => &[
+                "case", "do", "done", "elif", "else", "esac", "export", "fi", "for",
+                "function", "if", "in", "local", "return", "then", "until", "while",
+            ],
+            Language::Json | Language::Yaml | Language::Toml => {
+                &["true", "false", "null", "yes", "no"]
+            }
+            Language::Css => &["important", "inherit", "initial", "none", "auto"],
+        }
+    }
+
+    fn line_comments(self) -> &'static [&'static str] {
+        match self {
+            Language::Rust | Language::JavaScript | Language::Go => &["//"],
+            Language::Python | Language::Shell | Language::Yaml | Language::Toml => &["#"],
+            Language::Json | Language::Css => &[],
+        }
+    }
+
+    fn quotes(self) -> &'static [char] {
+        match self {
+            // `'` starts lifetimes too, so only `"` strings are colored
+            Language::Rust => &['"'],
+            Language::JavaScript => &['"', '\'', '`'],
+            Language::Go => &['"', '`'],
+            Language::Json => &['"'],
+            _ => &['"', '\''],
+        }
+    }
+}
+
+/// Token category; maps to a `tok-*` CSS class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Plain,
+    Keyword,
+    String,
+    Number,
+    Comment,
+}
+
+impl TokenKind {
+    pub fn class(self) -> &'static str {
+        match self {
+            TokenKind::Plain => "tok-plain",
+            TokenKind::Keyword => "tok-keyword",
+            TokenKind::String => "tok-string",
+            TokenKind::Number => "tok-number",
+            TokenKind::Comment => "tok-comment",
+        }
+    }
+}
+
+/// Split one line into colored spans. Adjacent plain text is merged.
+pub fn highlight_line(line: &str, lang: Language) -> Vec<(TokenKind, &str)> {
+    let mut tokens: Vec<(TokenKind, &str)> = Vec::new();
+    let mut plain_start = 0;
+    let mut i = 0;
+    while i < line.len() {
+        let rest = &line[i..];
+        let c = rest.chars().next().unwrap_or_default();
+
+        let token = if lang.line_comments().iter().any(|p| rest.starts_with(p)) {
+            Some((TokenKind::Comment, line.len()))
+        } else if lang.quotes().contains(&c) {
+            Some((TokenKind::String, i + string_len(rest, c)))
+        } else if c.is_ascii_digit() {
+            let is_digit = |ch: char| ch.is_ascii_alphanumeric() || ch == '.' || ch == '_';
+            Some((TokenKind::Number, i + word_len(rest, is_digit)))
+        } else if c.is_alphabetic() || c == '_' {
+            // Identifiers are consumed whole, so digits inside them (`utf8`)
+            // never start a number
+            let end = i + word_len(rest, |ch| ch.is_alphanumeric() || ch == '_');
+            let word = &line[i..end];
+            if lang.keywords().contains(&word) {
+                Some((TokenKind::Keyword, end))
+            } else {
+                // Identifiers stay in the current plain run
+                i = end;
+                continue;
+            }
+        } else {
+            None
+        };
+
+        match token {
+            Some((kind, end)) => {
+                if plain_start < i {
+                    tokens.push((TokenKind::Plain, &line[plain_start..i]));
+                }
+                tokens.push((kind, &line[i..end]));
+                i = end;
+                plain_start = end;
+            }
+            None => i += c.len_utf8(),
+        }
+    }
+    if plain_start < line.len() {
+        tokens.push((TokenKind::Plain, &line[plain_start..]));
+    }
+    tokens
+}
+
+/// Highlighted lines of `text`, or `None` when it should be shown plain
+/// (unknown language or larger than `MAX_HIGHLIGHT_BYTES`).
+pub fn highlight(text: &str, lang: Option<Language>) -> Option<Vec<Vec<(TokenKind, &str)>>> {
+    let lang = lang?;
+    if text.len() > MAX_HIGHLIGHT_BYTES {
+        return None;
+    }
+    Some(text.lines().map(|line| highlight_line(line, lang)).collect())
+}
+
+/// Byte length of the string literal at the start of `s`, including
+/// quotes. Unterminated strings run to the end of the line.
+fn string_len(s: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (i, c) in s.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == quote {
+            return i + c.len_utf8();
+        }
+    }
+    s.len()
+}
+
+fn word_len(s: &str, is_word: impl Fn(char) -> bool) -> usize {
+    s.char_indices()
+        .find(|&(_, c)| !is_word(c))
+        .map_or(s.len(), |(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_from_path() {
+        assert_eq!(Language::from_path("src/main.rs"), Some(Language::Rust));
+        assert_eq!(Language::from_path("web/app.tsx"), Some(Language::JavaScript));
+        assert_eq!(Language::from_path("config/superclaud.yml"), Some(Language::Yaml));
+        assert_eq!(Language::from_path("SCRIPT.SH"), Some(Language::Shell));
+        assert_eq!(Language::from_path("Cargo.Toml"), Some(Language::Toml));
+        assert_eq!(Language::from_path("notes.md"), None);
+        assert_eq!(Language::from_path("Makefile"), None);
+        assert_eq!(Language::from_path(".gitignore"), None);
+    }
+
+    #[test]
+    fn test_highlight_line_tokens() {
+        assert_eq!(
+            highlight_line("let utf8 = \"a\\\"b\"; // done", Language::Rust),
+            vec![
+                (TokenKind::Keyword, "let"),
+                (TokenKind::Plain, " utf8 = "),
+                (TokenKind::String, "\"a\\\"b\""),
+                (TokenKind::Plain, "; "),
+                (TokenKind::Comment, "// done"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_large_or_unknown_input_is_plain() {
+        assert!(highlight("fn main() {}", None).is_none());
+
+        let at_limit = "x".repeat(MAX_HIGHLIGHT_BYTES);
+        assert!(highlight(&at_limit, Some(Language::Rust)).is_some());
+        let over_limit = "x".repeat(MAX_HIGHLIGHT_BYTES + 1);
+        assert!(highlight(&over_limit, Some(Language::Rust)).is_none());
+    }
+}
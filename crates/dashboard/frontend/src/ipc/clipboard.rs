@@ -0,0 +1,13 @@
+//! Clipboard access via the Web Clipboard API.
+
+use wasm_bindgen_futures::JsFuture;
+
+/// Write `text` to the system clipboard.
+pub async fn write_clipboard(text: &str) -> Result<(), String> {
+    let window = web_sys::window().ok_or_else(|| "No window".to_string())?;
+    let promise = window.navigator().clipboard().write_text(text);
+    JsFuture::from(promise)
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("Clipboard write failed: {:?}", e))
+}
@@ -1,4 +1,6 @@
 //! IPC layer: Tauri invoke() and listen() bindings for WASM.
 
+pub mod clipboard;
 pub mod commands;
 pub mod events;
+pub mod reconnect;
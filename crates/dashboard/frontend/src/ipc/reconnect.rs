@@ -0,0 +1,125 @@
+//! Automatic reconnection after the daemon event stream drops.
+//!
+//! The backend emits `stream-disconnected` when a gRPC event stream ends
+//! (typically a daemon restart). We then ping the daemon with exponential
+//! backoff, resubscribe to active executions from the last event each
+//! stream delivered, and refetch the expanded execution's detail.
+//!
+//! The same resync runs when the event filter changes which event types
+//! the daemon must send, since subscriptions filter server-side.
+
+use leptos::prelude::*;
+use serde::Deserialize;
+
+use crate::ipc::commands::{tauri_invoke, tauri_invoke_no_args};
+use crate::ipc::events::tauri_listen;
+use crate::state::{AppState, DaemonStatusDto, ExecutionDetailDto, ExecutionSummaryDto};
+
+const INITIAL_BACKOFF_MS: u32 = 500;
+const MAX_BACKOFF_MS: u32 = 30_000;
+
+/// Payload of the backend `stream-disconnected` event.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StreamDisconnectedDto {
+    pub execution_id: String,
+    #[serde(default)]
+    pub reason: String,
+    /// Last event sequence the dropped stream delivered.
+    #[serde(default)]
+    pub last_sequence: Option<u64>,
+}
+
+/// Register the disconnect listener. Call once at app start.
+pub fn watch_for_disconnects(state: AppState) {
+    tauri_listen("stream-disconnected", move |payload: StreamDisconnectedDto| {
+        web_sys::console::warn_1(
+            &format!(
+                "Event stream for {} disconnected: {}",
+                payload.execution_id, payload.reason
+            )
+            .into(),
+        );
+        if let Some(sequence) = payload.last_sequence {
+            state.resume_sequences.update_value(|resume| {
+                resume.insert(payload.execution_id.clone(), sequence + 1);
+            });
+        }
+        // Several streams usually drop together; one reconnect loop is enough.
+        if state.reconnecting.get_untracked() {
+            return;
+        }
+        state.reconnecting.set(true);
+        state.daemon_status.update(|s| s.online = false);
+
+        let state = state.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            reconnect_with_backoff(&state).await;
+        });
+    });
+}
+
+/// Resubscribe with the new wire filter whenever toggling a category
+/// changes `EventFilter::wire_event_types`. Call once at app start.
+pub fn resubscribe_on_filter_change(state: AppState) {
+    let filter = state.event_filter;
+    let wire_types = Memo::new(move |_| filter.with(|f| f.wire_event_types()));
+    Effect::new(move |previous: Option<()>| {
+        wire_types.track();
+        // Subscriptions made so far already used the initial filter
+        if previous.is_none() || state.reconnecting.get_untracked() {
+            return;
+        }
+        let state = state.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            resync(&state).await;
+        });
+    });
+}
+
+async fn reconnect_with_backoff(state: &AppState) {
+    let mut delay_ms = INITIAL_BACKOFF_MS;
+    loop {
+        gloo_timers::future::TimeoutFuture::new(delay_ms).await;
+        if let Ok(status) = tauri_invoke_no_args::<DaemonStatusDto>("ping_daemon").await {
+            if status.online {
+                state.daemon_status.set(status);
+                break;
+            }
+        }
+        delay_ms = (delay_ms * 2).min(MAX_BACKOFF_MS);
+    }
+
+    resync(state).await;
+    state.reconnecting.set(false);
+}
+
+/// Resubscribe to live executions and refetch state missed while offline.
+async fn resync(state: &AppState) {
+    let list_args = serde_json::json!({"include_completed": true});
+    if let Ok(execs) =
+        tauri_invoke::<_, Vec<ExecutionSummaryDto>>("list_executions", &list_args).await
+    {
+        let event_types = state.event_filter.get_untracked().wire_event_types();
+        for exec in execs.iter().filter(|e| e.state == "running" || e.state == "paused") {
+            let from_sequence = state
+                .resume_sequences
+                .try_update_value(|resume| resume.remove(&exec.execution_id))
+                .flatten();
+            let sub_args = serde_json::json!({
+                "execution_id": exec.execution_id,
+                "include_history": false,
+                "event_types": event_types,
+                "from_sequence": from_sequence,
+            });
+            let _: Result<(), _> = tauri_invoke("subscribe_events", &sub_args).await;
+        }
+        state.executions.set(execs);
+    }
+
+    if let Some(eid) = state.expanded_execution.get_untracked() {
+        let args = serde_json::json!({"execution_id": eid});
+        if let Ok(detail) = tauri_invoke::<_, ExecutionDetailDto>("get_execution_detail", &args).await {
+            state.execution_detail.set(Some(detail));
+        }
+    }
+}
@@ -2,6 +2,7 @@
 
 pub mod app;
 pub mod components;
+pub mod highlight;
 pub mod ipc;
 pub mod pages;
 pub mod state;
@@ -0,0 +1,294 @@
+//! Compare page — two executions side by side.
+
+use leptos::prelude::*;
+
+use crate::components::header::PageHeader;
+use crate::components::score_chart::ScoreChart;
+use crate::components::status_badge::StatusBadge;
+use crate::ipc::commands::tauri_invoke;
+use crate::state::{AppState, ExecutionDetailDto};
+
+/// Which way a metric should move to count as better.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Better {
+    Higher,
+    Lower,
+    /// Informational only; no winner is picked.
+    Neither,
+}
+
+/// Winner of a single metric.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Winner {
+    Left,
+    Right,
+    Tie,
+}
+
+/// One row of the comparison table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricRow {
+    pub label: &'static str,
+    pub left: f64,
+    pub right: f64,
+    pub better: Better,
+    /// Display precision
+    pub decimals: usize,
+}
+
+impl MetricRow {
+    /// `None` for informational metrics.
+    pub fn winner(&self) -> Option<Winner> {
+        let ordering = match self.better {
+            Better::Higher => self.left.partial_cmp(&self.right)?,
+            Better::Lower => self.right.partial_cmp(&self.left)?,
+            Better::Neither => return None,
+        };
+        Some(match ordering {
+            std::cmp::Ordering::Greater => Winner::Left,
+            std::cmp::Ordering::Less => Winner::Right,
+            std::cmp::Ordering::Equal => Winner::Tie,
+        })
+    }
+
+    pub fn format(&self, value: f64) -> String {
+        format!("{:.*}", self.decimals, value)
+    }
+}
+
+/// Total of the `duration_seconds` reported by completed iterations.
+fn duration_seconds(detail: &ExecutionDetailDto) -> f64 {
+    detail
+        .events
+        .iter()
+        .filter(|e| e.event_type == "iteration_completed")
+        .filter_map(|e| e.data.get("duration_seconds").and_then(|v| v.as_f64()))
+        .sum()
+}
+
+/// Metric-by-metric comparison of two executions.
+pub fn compare_metrics(left: &ExecutionDetailDto, right: &ExecutionDetailDto) -> Vec<MetricRow> {
+    let row = |label, better, decimals, value: fn(&ExecutionDetailDto) -> f64| MetricRow {
+        label,
+        left: value(left),
+        right: value(right),
+        better,
+        decimals,
+    };
+
+    vec![
+        row("Score", Better::Higher, 1, |d| d.current_score as f64),
+        row("Iterations", Better::Lower, 0, |d| d.current_iteration as f64),
+        row("Cost (USD)", Better::Lower, 4, |d| d.total_cost_usd),
+        row("Input tokens", Better::Lower, 0, |d| d.total_input_tokens as f64),
+        row("Output tokens", Better::Lower, 0, |d| d.total_output_tokens as f64),
+        row("Duration (s)", Better::Lower, 1, duration_seconds),
+        row("Tests passed", Better::Higher, 0, |d| d.tests_passed as f64),
+        row("Tests failed", Better::Lower, 0, |d| d.tests_failed as f64),
+        row("Files written", Better::Neither, 0, |d| d.files_written.len() as f64),
+        row("Files edited", Better::Neither, 0, |d| d.files_edited.len() as f64),
+        row("Commands run", Better::Neither, 0, |d| d.commands_run as f64),
+    ]
+}
+
+async fn fetch_detail(execution_id: &str) -> Result<ExecutionDetailDto, String> {
+    let args = serde_json::json!({"execution_id": execution_id});
+    tauri_invoke::<_, ExecutionDetailDto>("get_execution_detail", &args).await
+}
+
+#[component]
+pub fn ComparePage() -> impl IntoView {
+    let state = expect_context::<AppState>();
+
+    let left_id = RwSignal::new(String::new());
+    let right_id = RwSignal::new(String::new());
+    let details = RwSignal::new(None::<(ExecutionDetailDto, ExecutionDetailDto)>);
+    let loading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    let compare = move |_| {
+        let (left, right) = (left_id.get().trim().to_string(), right_id.get().trim().to_string());
+        if left.is_empty() || right.is_empty() {
+            error.set(Some("Enter two execution IDs to compare.".to_string()));
+            return;
+        }
+        loading.set(true);
+        error.set(None);
+        wasm_bindgen_futures::spawn_local(async move {
+            match (fetch_detail(&left).await, fetch_detail(&right).await) {
+                (Ok(l), Ok(r)) => details.set(Some((l, r))),
+                (Err(e), _) | (_, Err(e)) => {
+                    details.set(None);
+                    error.set(Some(e));
+                }
+            }
+            loading.set(false);
+        });
+    };
+
+    view! {
+        <div>
+            <PageHeader
+                title="Compare Executions"
+                subtitle="Scores, cost and evidence of two runs side by side"
+            />
+
+            <datalist id="execution-ids">
+                {move || state.executions.get().into_iter().map(|exec| view! {
+                    <option value=exec.execution_id.clone()>{exec.task.clone()}</option>
+                }).collect_view()}
+            </datalist>
+
+            <div class="compare-controls">
+                <input
+                    class="form-input"
+                    list="execution-ids"
+                    placeholder="Execution A"
+                    on:input=move |ev| left_id.set(event_target_value(&ev))
+                    prop:value=move || left_id.get()
+                />
+                <input
+                    class="form-input"
+                    list="execution-ids"
+                    placeholder="Execution B"
+                    on:input=move |ev| right_id.set(event_target_value(&ev))
+                    prop:value=move || right_id.get()
+                />
+                <button class="btn btn-primary" disabled=move || loading.get() on:click=compare>
+                    "Compare"
+                </button>
+            </div>
+
+            {move || error.get().map(|e| view! {
+                <div class="error-banner">
+                    <span class="error-icon">"!"</span>
+                    <div class="error-content">
+                        <strong>"Comparison failed"</strong>
+                        <p>{e}</p>
+                    </div>
+                </div>
+            })}
+
+            {move || {
+                if loading.get() {
+                    return view! {
+                        <div class="loading"><div class="spinner"></div></div>
+                    }.into_any();
+                }
+                match details.get() {
+                    None => view! { <div></div> }.into_any(),
+                    Some((left, right)) => view! { <Comparison left=left right=right /> }.into_any(),
+                }
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn Comparison(left: ExecutionDetailDto, right: ExecutionDetailDto) -> impl IntoView {
+    let rows = compare_metrics(&left, &right);
+    let cell_class = |row: &MetricRow, side: Winner| match row.winner() {
+        Some(w) if w == side => "compare-value winner",
+        _ => "compare-value",
+    };
+
+    view! {
+        <table class="compare-table">
+            <thead>
+                <tr>
+                    <th>"Metric"</th>
+                    <th>
+                        <div class="compare-task">{left.task.clone()}</div>
+                        <StatusBadge status=left.state.clone() />
+                    </th>
+                    <th>
+                        <div class="compare-task">{right.task.clone()}</div>
+                        <StatusBadge status=right.state.clone() />
+                    </th>
+                </tr>
+            </thead>
+            <tbody>
+                {rows.into_iter().map(|row| view! {
+                    <tr>
+                        <td class="compare-label">{row.label}</td>
+                        <td class=cell_class(&row, Winner::Left)>{row.format(row.left)}</td>
+                        <td class=cell_class(&row, Winner::Right)>{row.format(row.right)}</td>
+                    </tr>
+                }).collect_view()}
+            </tbody>
+        </table>
+
+        <div class="two-col">
+            <div>
+                <h3 class="compare-chart-title">{format!("Score: {}", left.execution_id)}</h3>
+                <ScoreChart events=Signal::stored(left.events) />
+            </div>
+            <div>
+                <h3 class="compare-chart-title">{format!("Score: {}", right.execution_id)}</h3>
+                <ScoreChart events=Signal::stored(right.events) />
+            </div>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(left: f64, right: f64, better: Better) -> MetricRow {
+        MetricRow { label: "Metric", left, right, better, decimals: 0 }
+    }
+
+    fn detail(value: serde_json::Value) -> ExecutionDetailDto {
+        let mut base = serde_json::json!({
+            "execution_id": "e1",
+            "task": "task",
+            "state": "completed",
+            "current_iteration": 1,
+            "current_score": 0.0,
+        });
+        base.as_object_mut().unwrap().extend(value.as_object().unwrap().clone());
+        serde_json::from_value(base).unwrap()
+    }
+
+    #[test]
+    fn test_winner_follows_direction() {
+        assert_eq!(row(80.0, 70.0, Better::Higher).winner(), Some(Winner::Left));
+        assert_eq!(row(70.0, 80.0, Better::Higher).winner(), Some(Winner::Right));
+        assert_eq!(row(0.5, 0.2, Better::Lower).winner(), Some(Winner::Right));
+        assert_eq!(row(0.2, 0.5, Better::Lower).winner(), Some(Winner::Left));
+        assert_eq!(row(1.0, 9.0, Better::Neither).winner(), None);
+    }
+
+    #[test]
+    fn test_winner_ties_and_nan() {
+        assert_eq!(row(3.0, 3.0, Better::Higher).winner(), Some(Winner::Tie));
+        assert_eq!(row(3.0, 3.0, Better::Lower).winner(), Some(Winner::Tie));
+        assert_eq!(row(f64::NAN, 3.0, Better::Higher).winner(), None);
+        assert_eq!(row(3.0, f64::NAN, Better::Lower).winner(), None);
+    }
+
+    #[test]
+    fn test_compare_metrics_reads_both_sides() {
+        let left = detail(serde_json::json!({
+            "current_score": 85.0,
+            "total_cost_usd": 0.5,
+            "tests_passed": 4,
+            "events": [
+                {"execution_id": "e1", "event_type": "iteration_completed", "data": {"duration_seconds": 10.0}},
+                {"execution_id": "e1", "event_type": "iteration_completed", "data": {"duration_seconds": 5.5}},
+                {"execution_id": "e1", "event_type": "tool_invoked", "data": {"duration_seconds": 99.0}},
+            ],
+        }));
+        let right = detail(serde_json::json!({"current_score": 70.0, "total_cost_usd": 0.25}));
+
+        let rows = compare_metrics(&left, &right);
+        let find = |label| rows.iter().find(|r| r.label == label).unwrap();
+        assert_eq!(find("Score").winner(), Some(Winner::Left));
+        assert_eq!(find("Cost (USD)").winner(), Some(Winner::Right));
+        assert_eq!(find("Iterations").winner(), Some(Winner::Tie));
+        assert_eq!((find("Duration (s)").left, find("Duration (s)").right), (15.5, 0.0));
+        assert_eq!(find("Tests passed").left, 4.0);
+        assert_eq!(find("Files written").winner(), None);
+    }
+}
@@ -12,7 +12,7 @@ use crate::ipc::commands::tauri_invoke;
 use crate::state::{AgentEventDto, AppState, ExecutionDetailDto, ExecutionTree,
     TreeEdge, TreeNode, TreeNodeStatus, TreeNodeType};
 
-async fn refresh_executions(state: &AppState) {
+pub(crate) async fn refresh_executions(state: &AppState) {
     let list_args = serde_json::json!({"include_completed": true});
     if let Ok(execs) = tauri_invoke::<_, Vec<crate::state::ExecutionSummaryDto>>(
         "list_executions",
@@ -24,7 +24,7 @@ async fn refresh_executions(state: &AppState) {
     }
 }
 
-async fn fetch_execution_detail(state: &AppState, execution_id: &str) {
+pub(crate) async fn fetch_execution_detail(state: &AppState, execution_id: &str) {
     state.detail_loading.set(true);
     let args = serde_json::json!({"execution_id": execution_id});
     match tauri_invoke::<_, ExecutionDetailDto>("get_execution_detail", &args).await {
@@ -42,6 +42,26 @@ async fn fetch_execution_detail(state: &AppState, execution_id: &str) {
     state.detail_loading.set(false);
 }
 
+/// Expand an execution row (fetching its detail) or collapse it if already open.
+pub(crate) fn toggle_execution_detail(state: &AppState, execution_id: String) {
+    if state.expanded_execution.get_untracked().as_deref() == Some(&execution_id) {
+        // Collapse
+        state.expanded_execution.set(None);
+        state.execution_detail.set(None);
+        state.execution_tree.set(ExecutionTree::default());
+        state.selected_tree_node.set(None);
+    } else {
+        // Expand — clear stale tree from previous execution
+        state.execution_tree.set(ExecutionTree::default());
+        state.selected_tree_node.set(None);
+        state.expanded_execution.set(Some(execution_id.clone()));
+        let s = state.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            fetch_execution_detail(&s, &execution_id).await;
+        });
+    }
+}
+
 /// Build an ExecutionTree from a list of historical events.
 /// This replays the same logic that app.rs uses for real-time events,
 /// ensuring the tree is populated when viewing completed executions.
@@ -95,6 +115,9 @@ fn build_tree_from_events(events: &[AgentEventDto]) -> ExecutionTree {
                             if let Some(ref mut data) = node.event_data {
                                 if let Some(obj) = data.as_object_mut() {
                                     obj.insert("tool_output".to_string(), output.clone());
+                                    if let Some(flag) = event.data.get("tool_output_truncated") {
+                                        obj.insert("tool_output_truncated".to_string(), flag.clone());
+                                    }
                                 }
                             }
                         }
@@ -217,7 +240,10 @@ pub fn ControlPage() -> impl IntoView {
                 match result {
                     Ok(resp) => {
                         if let Some(exec_id) = resp.get("execution_id").and_then(|v| v.as_str()) {
-                            let sub_args = serde_json::json!({"execution_id": exec_id});
+                            let sub_args = serde_json::json!({
+                                "execution_id": exec_id,
+                                "event_types": state.event_filter.get_untracked().wire_event_types(),
+                            });
                             let _: Result<(), _> =
                                 tauri_invoke("subscribe_events", &sub_args).await;
                         }
@@ -294,6 +320,7 @@ pub fn ControlPage() -> impl IntoView {
                             let eid_resume = exec.execution_id.clone();
                             let eid_expand = exec.execution_id.clone();
                             let eid_check = exec.execution_id.clone();
+                            let eid_selected = exec.execution_id.clone();
                             let eid_for_detail = exec.execution_id.clone();
                             let score_str = format!("{:.0}%", exec.current_score);
                             let is_running = exec.state == "running";
@@ -308,26 +335,21 @@ pub fn ControlPage() -> impl IntoView {
 
                             view! {
                                 <div class="exec-item-wrapper">
-                                    <div class="exec-item" style="flex-wrap: wrap;" on:click=move |_| {
-                                        let current = state_click.expanded_execution.get();
-                                        let eid = eid_expand.clone();
-                                        if current.as_deref() == Some(&eid) {
-                                            // Collapse
-                                            state_click.expanded_execution.set(None);
-                                            state_click.execution_detail.set(None);
-                                            state_click.execution_tree.set(crate::state::ExecutionTree::default());
-                                            state_click.selected_tree_node.set(None);
-                                        } else {
-                                            // Expand — clear stale tree from previous execution
-                                            state_click.execution_tree.set(crate::state::ExecutionTree::default());
-                                            state_click.selected_tree_node.set(None);
-                                            state_click.expanded_execution.set(Some(eid.clone()));
-                                            let s = state_click.clone();
-                                            wasm_bindgen_futures::spawn_local(async move {
-                                                fetch_execution_detail(&s, &eid).await;
-                                            });
+                                    <div
+                                        class=move || {
+                                            let selected = state_inner.selected_execution.get();
+                                            if selected.as_deref() == Some(eid_selected.as_str()) {
+                                                "exec-item selected"
+                                            } else {
+                                                "exec-item"
+                                            }
+                                        }
+                                        style="flex-wrap: wrap;"
+                                        on:click=move |_| {
+                                            state_click.selected_execution.set(Some(eid_expand.clone()));
+                                            toggle_execution_detail(&state_click, eid_expand.clone());
                                         }
-                                    }>
+                                    >
                                         <span class="exec-chevron">
                                             {move || {
                                                 let expanded = state_inner.expanded_execution.get();
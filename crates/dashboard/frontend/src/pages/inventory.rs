@@ -4,6 +4,7 @@ use leptos::prelude::*;
 
 use crate::components::agent_card::AgentCard;
 use crate::components::empty_state::EmptyState;
+use crate::components::error_banner::ErrorBanner;
 use crate::components::header::PageHeader;
 use crate::components::loading_spinner::LoadingSpinner;
 use crate::components::search_filter::SearchFilter;
@@ -82,6 +83,22 @@ pub fn InventoryPage() -> impl IntoView {
                 </div>
             </div>
 
+            {move || {
+                let errors = state.inventory.get().errors;
+                (!errors.is_empty()).then(|| {
+                    let title = match errors.len() {
+                        1 => "1 config failed to parse".to_string(),
+                        n => format!("{} configs failed to parse", n),
+                    };
+                    let message = errors
+                        .iter()
+                        .map(|e| format!("{}: {}", e.path, e.message))
+                        .collect::<Vec<_>>()
+                        .join("; ");
+                    view! { <ErrorBanner title=title message=message /> }
+                })
+            }}
+
             <SearchFilter />
 
             {move || {
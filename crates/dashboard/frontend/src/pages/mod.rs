@@ -1,6 +1,8 @@
 //! Page components — one per dashboard tab.
 
+pub mod compare;
 pub mod control;
 pub mod history;
 pub mod inventory;
 pub mod monitor;
+pub mod settings;
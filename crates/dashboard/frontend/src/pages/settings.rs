@@ -0,0 +1,196 @@
+//! Settings page — daemon connection and execution defaults.
+
+use leptos::prelude::*;
+
+use crate::components::error_banner::ErrorBanner;
+use crate::components::header::PageHeader;
+use crate::ipc::commands::{tauri_invoke, tauri_invoke_no_args};
+use crate::state::{AppState, DaemonStatusDto, SettingsDto};
+
+/// Mirror of the backend checks so bad input is caught before saving.
+fn validate(settings: &SettingsDto) -> Result<(), String> {
+    let Some((host, port)) = settings.daemon_address.rsplit_once(':') else {
+        return Err("Daemon address must be host:port".to_string());
+    };
+    if host.trim().is_empty() {
+        return Err("Daemon address is missing a host".to_string());
+    }
+    if !matches!(port.parse::<u16>(), Ok(p) if p > 0) {
+        return Err(format!("Invalid daemon port: {port}"));
+    }
+    if !(1..=10).contains(&settings.default_max_iterations) {
+        return Err("Max iterations must be between 1 and 10".to_string());
+    }
+    if !(0.0..=100.0).contains(&settings.default_quality_threshold) {
+        return Err("Quality threshold must be between 0 and 100".to_string());
+    }
+    Ok(())
+}
+
+#[component]
+pub fn SettingsPage() -> impl IntoView {
+    let state = expect_context::<AppState>();
+
+    // Edit a local draft; only commit to AppState once saved
+    let draft = RwSignal::new(state.settings.get_untracked());
+    let error = RwSignal::new(Option::<String>::None);
+    let saved = RwSignal::new(false);
+    let saving = RwSignal::new(false);
+
+    let handle_save = {
+        let state = state.clone();
+        move |_| {
+            let settings = draft.get();
+            if let Err(e) = validate(&settings) {
+                error.set(Some(e));
+                return;
+            }
+            error.set(None);
+            saving.set(true);
+
+            let state = state.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let args = serde_json::json!({ "settings": settings });
+                match tauri_invoke::<_, SettingsDto>("save_settings", &args).await {
+                    Ok(applied) => {
+                        state.settings.set(applied);
+                        saved.set(true);
+                        // Re-check the daemon in case the address changed
+                        if let Ok(status) = tauri_invoke_no_args::<DaemonStatusDto>("ping_daemon").await {
+                            state.daemon_status.set(status);
+                        }
+                    }
+                    Err(e) => error.set(Some(format!("Failed to save settings: {e}"))),
+                }
+                saving.set(false);
+            });
+        }
+    };
+
+    let on_edit = move || {
+        saved.set(false);
+        error.set(None);
+    };
+
+    view! {
+        <div>
+            <PageHeader
+                title="Settings"
+                subtitle="Daemon connection and execution defaults"
+            />
+
+            {move || error.get().map(|msg| view! { <ErrorBanner message=msg /> })}
+
+            <div class="card" style="margin-bottom: 16px;">
+                <div class="form-group">
+                    <label class="form-label">"Daemon Address"</label>
+                    <input
+                        class="form-input"
+                        type="text"
+                        placeholder="127.0.0.1:50051"
+                        on:input=move |ev| {
+                            draft.update(|s| s.daemon_address = event_target_value(&ev));
+                            on_edit();
+                        }
+                        prop:value=move || draft.get().daemon_address
+                    />
+                </div>
+
+                <div style="display: grid; grid-template-columns: 1fr 1fr 1fr; gap: 12px;">
+                    <div class="form-group">
+                        <label class="form-label">"Default Max Iterations"</label>
+                        <input
+                            class="form-input"
+                            type="number"
+                            min="1" max="10"
+                            on:input=move |ev| {
+                                if let Ok(v) = event_target_value(&ev).parse() {
+                                    draft.update(|s| s.default_max_iterations = v);
+                                }
+                                on_edit();
+                            }
+                            prop:value=move || draft.get().default_max_iterations.to_string()
+                        />
+                    </div>
+
+                    <div class="form-group">
+                        <label class="form-label">"Default Quality Threshold"</label>
+                        <input
+                            class="form-input"
+                            type="number"
+                            min="0" max="100" step="5"
+                            on:input=move |ev| {
+                                if let Ok(v) = event_target_value(&ev).parse() {
+                                    draft.update(|s| s.default_quality_threshold = v);
+                                }
+                                on_edit();
+                            }
+                            prop:value=move || format!("{:.0}", draft.get().default_quality_threshold)
+                        />
+                    </div>
+
+                    <div class="form-group">
+                        <label class="form-label">"Default Model"</label>
+                        <select
+                            class="form-select"
+                            on:change=move |ev| {
+                                draft.update(|s| s.default_model = event_target_value(&ev));
+                                on_edit();
+                            }
+                        >
+                            <option value="sonnet" selected=move || draft.get().default_model == "sonnet">"Sonnet"</option>
+                            <option value="opus" selected=move || draft.get().default_model == "opus">"Opus"</option>
+                            <option value="haiku" selected=move || draft.get().default_model == "haiku">"Haiku"</option>
+                        </select>
+                    </div>
+                </div>
+
+                <div class="form-group">
+                    <label class="form-label">"Inventory Excludes"</label>
+                    <input
+                        class="form-input"
+                        type="text"
+                        placeholder=".git, node_modules, target, vendor"
+                        on:change=move |ev| {
+                            let excludes = event_target_value(&ev)
+                                .split(',')
+                                .map(|p| p.trim().to_string())
+                                .filter(|p| !p.is_empty())
+                                .collect();
+                            draft.update(|s| s.inventory_excludes = excludes);
+                            on_edit();
+                        }
+                        prop:value=move || draft.get().inventory_excludes.join(", ")
+                    />
+                </div>
+
+                <div class="form-group">
+                    <label class="form-checkbox">
+                        <input
+                            type="checkbox"
+                            on:change=move |ev| {
+                                draft.update(|s| s.notify_on_completion = event_target_checked(&ev));
+                                on_edit();
+                            }
+                            prop:checked=move || draft.get().notify_on_completion
+                        />
+                        "Notify me when a subscribed execution completes or fails"
+                    </label>
+                </div>
+
+                <div style="display: flex; align-items: center; gap: 12px;">
+                    <button
+                        class="btn btn-primary"
+                        on:click=handle_save
+                        disabled=move || saving.get()
+                    >
+                        {move || if saving.get() { "Saving..." } else { "Save Settings" }}
+                    </button>
+                    {move || saved.get().then(|| view! {
+                        <span style="font-size: 12px; color: var(--success);">"Saved"</span>
+                    })}
+                </div>
+            </div>
+        </div>
+    }
+}
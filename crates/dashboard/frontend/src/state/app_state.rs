@@ -1,7 +1,16 @@
 //! Global application state using Leptos signals.
 
+use gloo_timers::future::TimeoutFuture;
 use leptos::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How long incoming events are collected before `AppState::events` is
+/// updated, so a burst causes one re-render instead of hundreds.
+pub const EVENT_BATCH_MS: u32 = 50;
+
+/// Oldest events are dropped beyond this many.
+pub const MAX_RETAINED_EVENTS: usize = 10_000;
 
 /// Which page is currently active.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -10,6 +19,8 @@ pub enum Page {
     Monitor,
     Control,
     History,
+    Compare,
+    Settings,
 }
 
 impl Default for Page {
@@ -38,20 +49,165 @@ pub struct InventoryItemDto {
     pub source_file: String,
 }
 
+/// Config file the inventory scanner could not parse (matches backend).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventoryErrorDto {
+    pub path: String,
+    pub message: String,
+}
+
 /// Inventory DTO (matches backend).
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct InventoryDto {
     pub items: Vec<InventoryItemDto>,
+    #[serde(default)]
+    pub errors: Vec<InventoryErrorDto>,
 }
 
 /// Agent event DTO (matches backend emit).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AgentEventDto {
     pub execution_id: String,
     pub event_type: String,
     pub data: serde_json::Value,
 }
 
+/// Event log filter chip. Events outside every category (e.g. state
+/// changes) are always shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventCategory {
+    Tool,
+    Score,
+    File,
+    Test,
+    Error,
+    Log,
+}
+
+impl EventCategory {
+    pub const ALL: [EventCategory; 6] = [
+        EventCategory::Tool,
+        EventCategory::Score,
+        EventCategory::File,
+        EventCategory::Test,
+        EventCategory::Error,
+        EventCategory::Log,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            EventCategory::Tool => "Tool",
+            EventCategory::Score => "Score",
+            EventCategory::File => "File",
+            EventCategory::Test => "Test",
+            EventCategory::Error => "Error",
+            EventCategory::Log => "Log",
+        }
+    }
+
+    /// Backend event types belonging to this category.
+    pub fn event_types(self) -> &'static [&'static str] {
+        match self {
+            EventCategory::Tool => &[
+                "tool_invoked",
+                "subagent_spawned",
+                "subagent_completed",
+                "decision_made",
+            ],
+            EventCategory::Score => &["score_updated", "iteration_started", "iteration_completed"],
+            EventCategory::File => &["file_changed", "artifact_written"],
+            EventCategory::Test => &["test_result"],
+            EventCategory::Error => &["error"],
+            EventCategory::Log => &["log_message"],
+        }
+    }
+
+    pub fn of(event_type: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.event_types().contains(&event_type))
+    }
+
+    /// Categories the app itself depends on (execution list, score and
+    /// tree updates), so they are never dropped on the wire.
+    fn required_on_wire(self) -> bool {
+        matches!(self, EventCategory::Tool | EventCategory::Score)
+    }
+}
+
+/// Event log filter: hidden categories, a minimum log level (`LogLevel`
+/// proto value; 0 shows everything) and a free-text search query.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventFilter {
+    pub hidden: Vec<EventCategory>,
+    pub min_log_level: i32,
+    pub query: String,
+}
+
+impl EventFilter {
+    pub fn is_shown(&self, category: EventCategory) -> bool {
+        !self.hidden.contains(&category)
+    }
+
+    pub fn toggle(&mut self, category: EventCategory) {
+        if let Some(pos) = self.hidden.iter().position(|c| *c == category) {
+            self.hidden.remove(pos);
+        } else {
+            self.hidden.push(category);
+        }
+    }
+
+    pub fn matches(&self, event: &AgentEventDto) -> bool {
+        if !self.matches_query(event) {
+            return false;
+        }
+        let Some(category) = EventCategory::of(&event.event_type) else {
+            return true;
+        };
+        if !self.is_shown(category) {
+            return false;
+        }
+        if category == EventCategory::Log && self.min_log_level > 0 {
+            let level = event.data.get("level").and_then(|v| v.as_i64()).unwrap_or(0);
+            return level >= self.min_log_level as i64;
+        }
+        true
+    }
+
+    /// Case-insensitive substring match of the query against the event
+    /// type and its summary/message text. An empty query matches anything.
+    pub fn matches_query(&self, event: &AgentEventDto) -> bool {
+        let query = self.query.trim();
+        if query.is_empty() {
+            return true;
+        }
+        let query = query.to_lowercase();
+        std::iter::once(event.event_type.as_str())
+            .chain(event_search_fields(event))
+            .any(|text| text.to_lowercase().contains(&query))
+    }
+
+    /// Event types to request when subscribing. Empty means "all", which
+    /// is what we send unless a droppable category is hidden.
+    pub fn wire_event_types(&self) -> Vec<String> {
+        if self.hidden.iter().all(|c| c.required_on_wire()) {
+            return Vec::new();
+        }
+        let mut types = vec!["state_changed".to_string()];
+        for category in EventCategory::ALL {
+            if category.required_on_wire() || self.is_shown(category) {
+                types.extend(category.event_types().iter().map(|t| t.to_string()));
+            }
+        }
+        types
+    }
+}
+
+/// Text fields searched by the event log query.
+pub fn event_search_fields(event: &AgentEventDto) -> impl Iterator<Item = &str> {
+    ["summary", "message", "tool_name", "path", "reason"]
+        .into_iter()
+        .filter_map(|key| event.data.get(key).and_then(|v| v.as_str()))
+}
+
 /// Execution summary DTO.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionSummaryDto {
@@ -130,6 +286,8 @@ pub struct ExecutionDetailDto {
     #[serde(default)]
     pub tests_failed: i32,
     #[serde(default)]
+    pub external_sources: Vec<String>,
+    #[serde(default)]
     pub events: Vec<AgentEventDto>,
     pub run_instructions: Option<RunInstructionsDto>,
     #[serde(default)]
@@ -188,13 +346,147 @@ pub struct DaemonStatusDto {
     pub active_executions: i32,
 }
 
+/// Dashboard settings DTO (matches backend `DashboardSettings`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SettingsDto {
+    pub daemon_address: String,
+    pub default_model: String,
+    pub default_max_iterations: i32,
+    pub default_quality_threshold: f32,
+    pub notify_on_completion: bool,
+    pub inventory_excludes: Vec<String>,
+}
+
+impl Default for SettingsDto {
+    fn default() -> Self {
+        Self {
+            daemon_address: "127.0.0.1:50051".to_string(),
+            default_model: "sonnet".to_string(),
+            default_max_iterations: 3,
+            default_quality_threshold: 70.0,
+            notify_on_completion: true,
+            inventory_excludes: [".git", "node_modules", "target", "vendor"]
+                .map(String::from)
+                .to_vec(),
+        }
+    }
+}
+
+/// Color scheme. Applied as `data-theme` on `<html>`, which selects the
+/// CSS variables in `main.css`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+    Dark,
+    Light,
+}
+
+impl Theme {
+    /// `localStorage` key holding the user's explicit choice.
+    const STORAGE_KEY: &'static str = "superclaude-theme";
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Theme::Dark => "dark",
+            Theme::Light => "light",
+        }
+    }
+
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "dark" => Some(Theme::Dark),
+            "light" => Some(Theme::Light),
+            _ => None,
+        }
+    }
+
+    pub fn toggled(self) -> Self {
+        match self {
+            Theme::Dark => Theme::Light,
+            Theme::Light => Theme::Dark,
+        }
+    }
+
+    /// The saved choice, or the OS `prefers-color-scheme` when none is saved.
+    pub fn initial() -> Self {
+        let Some(window) = web_sys::window() else {
+            return Theme::Dark;
+        };
+        let saved = window
+            .local_storage()
+            .ok()
+            .flatten()
+            .and_then(|storage| storage.get_item(Self::STORAGE_KEY).ok().flatten())
+            .and_then(|value| Self::from_str(&value));
+        saved.unwrap_or_else(|| {
+            let prefers_light = window
+                .match_media("(prefers-color-scheme: light)")
+                .ok()
+                .flatten()
+                .is_some_and(|query| query.matches());
+            if prefers_light {
+                Theme::Light
+            } else {
+                Theme::Dark
+            }
+        })
+    }
+
+    /// Restyle the page; CSS variables update every component without a reload.
+    pub fn apply(self) {
+        if let Some(root) = web_sys::window()
+            .and_then(|w| w.document())
+            .and_then(|d| d.document_element())
+        {
+            let _ = root.set_attribute("data-theme", self.as_str());
+        }
+    }
+
+    fn save(self) {
+        if let Some(storage) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+            let _ = storage.set_item(Self::STORAGE_KEY, self.as_str());
+        }
+    }
+}
+
+/// Events received but not yet committed to `AppState::events`.
+#[derive(Debug, Default)]
+pub struct EventBatch {
+    pending: Vec<AgentEventDto>,
+}
+
+impl EventBatch {
+    /// Queue an event. Returns true when it starts a new batch, i.e. the
+    /// caller should schedule a flush.
+    pub fn push(&mut self, event: AgentEventDto) -> bool {
+        self.pending.push(event);
+        self.pending.len() == 1
+    }
+
+    /// Take everything queued, leaving the batch empty.
+    pub fn take(&mut self) -> Vec<AgentEventDto> {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+/// Append `batch` to `events`, dropping the oldest beyond `max`.
+pub fn append_bounded(events: &mut Vec<AgentEventDto>, batch: Vec<AgentEventDto>, max: usize) {
+    events.extend(batch);
+    if events.len() > max {
+        let excess = events.len() - max;
+        events.drain(..excess);
+    }
+}
+
 /// Global app state — provided at the root via `provide_context`.
 #[derive(Clone)]
 pub struct AppState {
     pub current_page: RwSignal<Page>,
     pub inventory: RwSignal<InventoryDto>,
     pub inventory_loading: RwSignal<bool>,
+    /// Live event log, updated in batches by `queue_event`.
     pub events: RwSignal<Vec<AgentEventDto>>,
+    /// Events waiting for the next batch flush into `events`.
+    pub event_batch: StoredValue<EventBatch>,
     pub executions: RwSignal<Vec<ExecutionSummaryDto>>,
     pub daemon_status: RwSignal<DaemonStatusDto>,
     pub search_query: RwSignal<String>,
@@ -211,6 +503,21 @@ pub struct AppState {
     pub selected_tree_node: RwSignal<Option<String>>,
     /// Source of the last received event (e.g., "heartbeat", "assistant").
     pub last_event_source: RwSignal<String>,
+    /// True while the event stream is down and we're trying to reconnect.
+    pub reconnecting: RwSignal<bool>,
+    /// Per execution, the event sequence to resume from after its stream
+    /// dropped (consumed by the next resubscribe).
+    pub resume_sequences: StoredValue<HashMap<String, u64>>,
+    /// Execution highlighted by keyboard navigation (by execution_id).
+    pub selected_execution: RwSignal<Option<String>>,
+    /// Whether the keyboard shortcuts overlay is visible.
+    pub show_shortcuts: RwSignal<bool>,
+    /// Persisted dashboard settings, loaded from the backend on startup.
+    pub settings: RwSignal<SettingsDto>,
+    /// Which events the event log displays (and subscriptions request).
+    pub event_filter: RwSignal<EventFilter>,
+    /// Active color theme.
+    pub theme: RwSignal<Theme>,
 }
 
 impl AppState {
@@ -220,6 +527,7 @@ impl AppState {
             inventory: RwSignal::new(InventoryDto::default()),
             inventory_loading: RwSignal::new(true),
             events: RwSignal::new(Vec::new()),
+            event_batch: StoredValue::new(EventBatch::default()),
             executions: RwSignal::new(Vec::new()),
             daemon_status: RwSignal::new(DaemonStatusDto::default()),
             search_query: RwSignal::new(String::new()),
@@ -230,6 +538,114 @@ impl AppState {
             execution_tree: RwSignal::new(ExecutionTree::default()),
             selected_tree_node: RwSignal::new(None),
             last_event_source: RwSignal::new(String::new()),
+            reconnecting: RwSignal::new(false),
+            resume_sequences: StoredValue::new(HashMap::new()),
+            selected_execution: RwSignal::new(None),
+            show_shortcuts: RwSignal::new(false),
+            settings: RwSignal::new(SettingsDto::default()),
+            event_filter: RwSignal::new(EventFilter::default()),
+            theme: RwSignal::new(Theme::initial()),
+        }
+    }
+
+    /// Queue a live event for the event log. The first event of a batch
+    /// schedules a flush `EVENT_BATCH_MS` later.
+    pub fn queue_event(&self, event: AgentEventDto) {
+        let batch = self.event_batch;
+        let events = self.events;
+        if !batch.try_update_value(|b| b.push(event)).unwrap_or(false) {
+            return;
+        }
+        wasm_bindgen_futures::spawn_local(async move {
+            TimeoutFuture::new(EVENT_BATCH_MS).await;
+            let pending = batch.try_update_value(|b| b.take()).unwrap_or_default();
+            events.update(|evts| append_bounded(evts, pending, MAX_RETAINED_EVENTS));
+        });
+    }
+
+    /// Switch between dark and light, remembering the choice.
+    pub fn toggle_theme(&self) {
+        let theme = self.theme.get_untracked().toggled();
+        theme.apply();
+        theme.save();
+        self.theme.set(theme);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(n: usize) -> AgentEventDto {
+        AgentEventDto {
+            execution_id: "exec-1".to_string(),
+            event_type: "log_message".to_string(),
+            data: serde_json::json!({"message": format!("event {n}")}),
+        }
+    }
+
+    #[test]
+    fn test_event_batch_push_and_take() {
+        let mut batch = EventBatch::default();
+        assert!(batch.push(event(0)), "first event starts a batch");
+        assert!(!batch.push(event(1)));
+        assert!(!batch.push(event(2)));
+
+        assert_eq!(batch.take(), vec![event(0), event(1), event(2)]);
+        assert!(batch.take().is_empty(), "take drains the batch");
+        assert!(batch.push(event(3)), "the next push starts a new batch");
+    }
+
+    #[test]
+    fn test_append_bounded_drops_oldest() {
+        let mut events = vec![event(0), event(1)];
+        append_bounded(&mut events, vec![event(2)], 5);
+        assert_eq!(events, vec![event(0), event(1), event(2)]);
+
+        append_bounded(&mut events, vec![event(3), event(4), event(5)], 4);
+        assert_eq!(events, vec![event(2), event(3), event(4), event(5)]);
+
+        append_bounded(&mut events, (6..12).map(event).collect(), 3);
+        assert_eq!(events, vec![event(9), event(10), event(11)]);
+    }
+
+    #[test]
+    fn test_matches_query() {
+        let tool = AgentEventDto {
+            execution_id: "exec-1".to_string(),
+            event_type: "tool_invoked".to_string(),
+            data: serde_json::json!({"tool_name": "Bash", "summary": "cargo test --workspace"}),
+        };
+        let query = |q: &str| EventFilter { query: q.to_string(), ..EventFilter::default() };
+
+        assert!(query("").matches_query(&tool));
+        assert!(query("   ").matches_query(&tool));
+        assert!(query("CARGO Test").matches_query(&tool));
+        assert!(query(" bash ").matches_query(&tool));
+        assert!(query("TOOL_invoked").matches_query(&tool));
+        assert!(!query("clippy").matches_query(&tool));
+
+        // Only the searchable fields are matched, not every payload value
+        let file = AgentEventDto {
+            execution_id: "exec-1".to_string(),
+            event_type: "file_changed".to_string(),
+            data: serde_json::json!({"path": "src/Überblick.rs", "node_id": "toolu_hidden"}),
+        };
+        assert!(query("überblick").matches_query(&file));
+        assert!(!query("toolu_hidden").matches_query(&file));
+    }
+
+    #[test]
+    fn test_theme_toggle_and_parse() {
+        assert_eq!(Theme::Dark.toggled(), Theme::Light);
+        assert_eq!(Theme::Light.toggled(), Theme::Dark);
+        assert_eq!(Theme::Dark.toggled().toggled(), Theme::Dark);
+
+        for theme in [Theme::Dark, Theme::Light] {
+            assert_eq!(Theme::from_str(theme.as_str()), Some(theme));
         }
+        assert_eq!(Theme::from_str("Dark"), None);
+        assert_eq!(Theme::from_str(""), None);
+        assert_eq!(Theme::from_str("solarized"), None);
     }
 }
@@ -1,22 +1,76 @@
 use superclaude_proto::super_claude_service_client::SuperClaudeServiceClient;
 use superclaude_proto::*;
-use tonic::transport::Channel;
+use tonic::metadata::{Ascii, MetadataValue};
+use tonic::service::interceptor::InterceptedService;
+use tonic::service::Interceptor;
+use tonic::transport::{Certificate, Channel, ClientTlsConfig};
 use tonic::Streaming;
-use anyhow::Result;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Shared secret the daemon's TCP listener may require
+const AUTH_TOKEN_ENV: &str = "SUPERCLAUDE_AUTH_TOKEN";
+/// PEM CA certificate to verify a TLS daemon against; its presence enables TLS
+const TLS_CA_ENV: &str = "SUPERCLAUDE_TLS_CA";
+/// Server name to verify instead of the host in the daemon address
+const TLS_DOMAIN_ENV: &str = "SUPERCLAUDE_TLS_DOMAIN";
+
+/// Client TLS config trusting the PEM at `ca`; `None` (plaintext) without one.
+fn client_tls_config(ca: Option<&Path>, domain: Option<String>) -> Result<Option<ClientTlsConfig>> {
+    let Some(ca) = ca else {
+        return Ok(None);
+    };
+    let pem = std::fs::read(ca)
+        .with_context(|| format!("Failed to read TLS CA certificate {}", ca.display()))?;
+    let mut config = ClientTlsConfig::new().ca_certificate(Certificate::from_pem(pem));
+    if let Some(domain) = domain {
+        config = config.domain_name(domain);
+    }
+    Ok(Some(config))
+}
+
+/// Attaches `authorization: Bearer <token>` to every call when a token is set.
+#[derive(Clone)]
+pub struct AuthHeader(Option<MetadataValue<Ascii>>);
+
+impl Interceptor for AuthHeader {
+    fn call(&mut self, mut request: tonic::Request<()>) -> Result<tonic::Request<()>, tonic::Status> {
+        if let Some(value) = &self.0 {
+            request.metadata_mut().insert("authorization", value.clone());
+        }
+        Ok(request)
+    }
+}
 
 #[derive(Clone)]
 pub struct GrpcClient {
-    client: SuperClaudeServiceClient<Channel>,
+    client: SuperClaudeServiceClient<InterceptedService<Channel, AuthHeader>>,
 }
 
 impl GrpcClient {
+    /// Connect over TLS when `SUPERCLAUDE_TLS_CA` is set, plaintext otherwise.
     pub async fn connect(addr: &str) -> Result<Self> {
-        let channel = Channel::from_shared(format!("http://{}", addr))?
-            .connect()
-            .await?;
-        
+        let env = |key| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+        let ca = env(TLS_CA_ENV).map(PathBuf::from);
+        let channel = match client_tls_config(ca.as_deref(), env(TLS_DOMAIN_ENV))? {
+            Some(tls) => {
+                Channel::from_shared(format!("https://{}", addr))?
+                    .tls_config(tls)?
+                    .connect()
+                    .await?
+            }
+            None => Channel::from_shared(format!("http://{}", addr))?.connect().await?,
+        };
+
+        let auth = std::env::var(AUTH_TOKEN_ENV)
+            .ok()
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .map(|t| format!("Bearer {t}").parse())
+            .transpose()?;
+
         Ok(Self {
-            client: SuperClaudeServiceClient::new(channel),
+            client: SuperClaudeServiceClient::with_interceptor(channel, AuthHeader(auth)),
         })
     }
 
@@ -52,6 +106,11 @@ impl GrpcClient {
         Ok(response.into_inner())
     }
 
+    pub async fn get_status(&mut self, req: GetStatusRequest) -> Result<GetStatusResponse> {
+        let response = self.client.get_status(tonic::Request::new(req)).await?;
+        Ok(response.into_inner())
+    }
+
     pub async fn list_executions(&mut self, req: ListExecutionsRequest) -> Result<ListExecutionsResponse> {
         let response = self.client.list_executions(tonic::Request::new(req)).await?;
         Ok(response.into_inner())
@@ -67,8 +126,28 @@ impl GrpcClient {
         Ok(response.into_inner())
     }
 
+    pub async fn get_tool_output(&mut self, req: GetToolOutputRequest) -> Result<GetToolOutputResponse> {
+        let response = self.client.get_tool_output(tonic::Request::new(req)).await?;
+        Ok(response.into_inner())
+    }
+
     pub async fn send_input(&mut self, req: SendInputRequest) -> Result<SendInputResponse> {
         let response = self.client.send_input(tonic::Request::new(req)).await?;
         Ok(response.into_inner())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_tls_config_needs_readable_ca() {
+        assert!(client_tls_config(None, Some("localhost".to_string())).unwrap().is_none());
+        assert!(client_tls_config(Some(Path::new("/nonexistent/ca.pem")), None).is_err());
+
+        let ca = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../superclaude-daemon/testdata/tls/cert.pem");
+        assert!(client_tls_config(Some(&ca), None).unwrap().is_some());
+    }
+}
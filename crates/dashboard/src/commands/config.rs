@@ -18,7 +18,7 @@ pub async fn ping_daemon(state: tauri::State<'_, AppState>) -> Result<DaemonStat
     };
 
     if needs_connect {
-        match GrpcClient::connect("127.0.0.1:50051").await {
+        match GrpcClient::connect(&state.daemon_address()).await {
             Ok(client) => {
                 *state.grpc_client.write() = Some(client);
             }
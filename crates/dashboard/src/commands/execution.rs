@@ -1,11 +1,16 @@
 //! Execution lifecycle Tauri commands.
 
+use std::time::SystemTime;
+
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_dialog::DialogExt;
 use tokio_stream::StreamExt;
-use tracing::warn;
+use tracing::{debug, warn};
 
+use crate::notify;
 use crate::state::AppState;
+use superclaude_core::export::{export_events, ExportFormat, ExportedEvent};
 use superclaude_proto::*;
 
 /// DTO for execution config coming from the frontend.
@@ -15,6 +20,8 @@ pub struct ExecutionConfigDto {
     pub quality_threshold: Option<f32>,
     pub model: Option<String>,
     pub timeout_seconds: Option<f32>,
+    pub env_allowlist: Option<Vec<String>>,
+    pub max_cost_usd: Option<f64>,
 }
 
 /// DTO for start execution response.
@@ -78,11 +85,24 @@ pub struct ExecutionDetailDto {
     pub commands_run: i32,
     pub tests_passed: i32,
     pub tests_failed: i32,
+    /// URLs fetched and web searches made.
+    pub external_sources: Vec<String>,
     pub events: Vec<AgentEventDto>,
     pub run_instructions: Option<RunInstructionsDto>,
     pub score_breakdown: Vec<ScoreDimensionDto>,
 }
 
+/// Payload of the `stream-disconnected` event emitted when a daemon event
+/// stream ends, so the frontend can reconnect and resubscribe.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamDisconnectedDto {
+    pub execution_id: String,
+    pub reason: String,
+    /// Sequence of the last event forwarded, so the resubscribe can resume
+    /// right after it. `None` if nothing was forwarded.
+    pub last_sequence: Option<u64>,
+}
+
 /// DTO for agent events emitted to the frontend.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentEventDto {
@@ -134,6 +154,11 @@ pub async fn start_execution(
                 return Err(format!("timeout_seconds must be 10-36000, got {}", timeout));
             }
         }
+        if let Some(budget) = c.max_cost_usd {
+            if !budget.is_finite() || budget < 0.0 {
+                return Err(format!("max_cost_usd must be >= 0, got {}", budget));
+            }
+        }
     }
 
     let mut client = state.get_client().await.map_err(|e| e.to_string())?;
@@ -145,6 +170,12 @@ pub async fn start_execution(
         timeout_seconds: c.timeout_seconds.unwrap_or(300.0),
         pal_review_enabled: true,
         min_improvement: 5.0,
+        env_allowlist: c.env_allowlist.map(|vars| EnvAllowlist { vars }),
+        max_repeated_tool_calls: 5,
+        stop_on_loop: false,
+        max_cost_usd: c.max_cost_usd.unwrap_or(0.0),
+        metrics_dir: String::new(),
+        max_event_history: 0,
     });
 
     let resp = client
@@ -215,6 +246,7 @@ pub async fn list_executions(
         .list_executions(ListExecutionsRequest {
             include_completed,
             limit: 100,
+            ..Default::default()
         })
         .await
         .map_err(|e| format!("gRPC error: {e}"))?;
@@ -243,9 +275,19 @@ pub async fn list_executions(
 }
 
 /// Subscribe to execution events — streams events via Tauri emit().
+///
+/// `include_history` defaults to true; reconnecting clients pass false and
+/// refetch the detail instead so replayed history isn't duplicated.
+/// `from_sequence` resumes after a dropped stream: the daemon replays
+/// retained events from that sequence on (pass the last seen sequence + 1).
+/// Subscribing again (e.g. with different `event_types`) replaces the
+/// execution's previous stream.
 #[tauri::command(rename_all = "snake_case")]
 pub async fn subscribe_events(
     execution_id: String,
+    include_history: Option<bool>,
+    event_types: Option<Vec<String>>,
+    from_sequence: Option<u64>,
     app_handle: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
@@ -254,30 +296,128 @@ pub async fn subscribe_events(
     let mut stream = client
         .stream_events(StreamEventsRequest {
             execution_id: execution_id.clone(),
-            include_history: true,
+            include_history: include_history.unwrap_or(true),
+            event_types: event_types.unwrap_or_default(),
+            from_sequence: from_sequence.unwrap_or(0),
+            from_timestamp: None,
         })
         .await
         .map_err(|e| format!("gRPC error: {e}"))?;
 
     // Spawn a task to forward events
-    tokio::spawn(async move {
-        while let Some(Ok(event)) = stream.next().await {
-            let (event_type, data) = format_event(&event);
-            let dto = AgentEventDto {
-                execution_id: event.execution_id.clone(),
-                event_type,
-                data,
-            };
-            if let Err(e) = app_handle.emit("agent-event", &dto) {
-                warn!("Failed to emit agent event: {}", e);
-                break; // Stop forwarding if frontend is gone
+    let generation = state.next_stream_generation();
+    let stream_execution_id = execution_id.clone();
+    let task = tokio::spawn(async move {
+        let mut finished = false;
+        // A resumed stream that drops before forwarding anything still
+        // resumes from the same point next time
+        let mut last_sequence = from_sequence.and_then(|s| s.checked_sub(1)).filter(|s| *s > 0);
+        let reason = loop {
+            match stream.next().await {
+                Some(Ok(event)) => {
+                    finished |= is_terminal_transition(&event);
+                    last_sequence = Some(event.sequence);
+                    let (event_type, data) = format_event(&event);
+                    let dto = AgentEventDto {
+                        execution_id: event.execution_id.clone(),
+                        event_type,
+                        data,
+                    };
+                    if let Err(e) = app_handle.emit("agent-event", &dto) {
+                        warn!("Failed to emit agent event: {}", e);
+                        return; // Stop forwarding if frontend is gone
+                    }
+                    let enabled = app_handle.state::<AppState>().settings.read().notify_on_completion;
+                    if enabled && notify::warrants_notification(&event, SystemTime::now()) {
+                        let app_handle = app_handle.clone();
+                        let execution_id = event.execution_id.clone();
+                        tokio::spawn(async move {
+                            notify::notify_finished(&app_handle, &execution_id).await;
+                        });
+                    }
+                }
+                Some(Err(status)) => break format!("Stream error: {}", status.message()),
+                None if finished || execution_finished(&app_handle, &execution_id).await => {
+                    // Normal end of a finished execution's stream
+                    debug!(execution_id = %execution_id, "Event stream ended");
+                    app_handle
+                        .state::<AppState>()
+                        .unregister_event_stream(&execution_id, generation);
+                    return;
+                }
+                None => break "Stream closed".to_string(),
             }
-        }
+        };
+
+        // The daemon went away — drop the cached client so the next call
+        // reconnects, and let the frontend know it should resubscribe.
+        warn!(execution_id = %execution_id, reason = %reason, "Event stream disconnected");
+        let app_state = app_handle.state::<AppState>();
+        app_state.unregister_event_stream(&execution_id, generation);
+        *app_state.grpc_client.write() = None;
+        let _ = app_handle.emit(
+            "stream-disconnected",
+            &StreamDisconnectedDto {
+                execution_id,
+                reason,
+                last_sequence,
+            },
+        );
     });
+    state.register_event_stream(&stream_execution_id, generation, task.abort_handle());
 
     Ok(())
 }
 
+const TERMINAL_STATES: [ExecutionState; 3] = [
+    ExecutionState::Completed,
+    ExecutionState::Failed,
+    ExecutionState::Cancelled,
+];
+
+/// Whether `event` moves its execution into a terminal state.
+fn is_terminal_transition(event: &AgentEvent) -> bool {
+    matches!(
+        &event.event,
+        Some(agent_event::Event::StateChanged(change))
+            if TERMINAL_STATES.iter().any(|s| *s as i32 == change.new_state)
+    )
+}
+
+/// Whether the daemon reports `execution_id` as finished. `false` when it
+/// cannot be asked, so an unreachable daemon still counts as a disconnect.
+async fn execution_finished(app_handle: &AppHandle, execution_id: &str) -> bool {
+    let Ok(mut client) = app_handle.state::<AppState>().get_client().await else {
+        return false;
+    };
+    client
+        .get_status(GetStatusRequest {
+            execution_id: execution_id.to_string(),
+        })
+        .await
+        .ok()
+        .and_then(|resp| resp.status)
+        .is_some_and(|status| TERMINAL_STATES.iter().any(|s| *s as i32 == status.state))
+}
+
+/// Untruncated output of a tool result the daemon cut, for copying.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_tool_output(
+    execution_id: String,
+    tool_use_id: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let mut client = state.get_client().await.map_err(|e| e.to_string())?;
+    let resp = client
+        .get_tool_output(GetToolOutputRequest {
+            execution_id,
+            tool_use_id,
+        })
+        .await
+        .map_err(|e| format!("gRPC error: {e}"))?;
+    Ok(resp.output)
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub async fn get_execution_detail(
     execution_id: String,
@@ -355,12 +495,68 @@ pub async fn get_execution_detail(
         commands_run: ev.map(|e| e.commands_run).unwrap_or(0),
         tests_passed: ev.map(|e| e.tests_passed).unwrap_or(0),
         tests_failed: ev.map(|e| e.tests_failed).unwrap_or(0),
+        external_sources: ev.map(|e| e.external_sources.clone()).unwrap_or_default(),
         events,
         run_instructions,
         score_breakdown,
     })
 }
 
+/// Serialize an execution's events as `csv` or `json` and write them where
+/// the user picks in a native save dialog. Returns the saved path, or `None`
+/// if the dialog was cancelled.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn export_execution_events(
+    execution_id: String,
+    format: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    let format: ExportFormat = format.parse().map_err(|e| format!("{e}"))?;
+
+    let mut client = state.get_client().await.map_err(|e| e.to_string())?;
+    let resp = client
+        .get_execution_detail(GetExecutionDetailRequest {
+            execution_id: execution_id.clone(),
+        })
+        .await
+        .map_err(|e| format!("gRPC error: {e}"))?;
+
+    let events: Vec<ExportedEvent> = resp
+        .events
+        .iter()
+        .map(|e| {
+            let timestamp = e.timestamp.as_ref().and_then(|t| {
+                chrono::DateTime::from_timestamp(t.seconds, t.nanos.max(0) as u32)
+            });
+            let (event_type, data) = format_event(e);
+            ExportedEvent::new(timestamp, event_type, data)
+        })
+        .collect();
+
+    let contents = export_events(&events, format)
+        .map_err(|e| format!("Failed to export events: {e}"))?;
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    app.dialog()
+        .file()
+        .set_file_name(format!("execution-{}-events.{}", execution_id, format.extension()))
+        .add_filter(format.extension().to_uppercase(), &[format.extension()])
+        .save_file(move |path| {
+            let _ = tx.send(path);
+        });
+    let Some(path) = rx.await.ok().flatten() else {
+        return Ok(None);
+    };
+    let path = path
+        .into_path()
+        .map_err(|e| format!("Invalid save location: {e}"))?;
+    tokio::fs::write(&path, contents)
+        .await
+        .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+    Ok(Some(path.display().to_string()))
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub async fn send_execution_input(
     execution_id: String,
@@ -410,6 +606,7 @@ fn format_event(event: &AgentEvent) -> (String, serde_json::Value) {
                 "blocked": e.blocked,
                 "tool_input": e.tool_input,
                 "tool_output": e.tool_output,
+                "tool_output_truncated": e.tool_output_truncated,
                 "tool_use_id": e.tool_use_id,
                 "node_id": e.node_id,
                 "parent_node_id": e.parent_node_id,
@@ -505,6 +702,42 @@ fn format_event(event: &AgentEvent) -> (String, serde_json::Value) {
                 "title": e.title,
             }),
         ),
+        Some(agent_event::Event::DecisionMade(e)) => (
+            "decision_made".to_string(),
+            serde_json::json!({
+                "decision_type": e.decision_type,
+                "title": e.title,
+                "question": e.question,
+                "summary": e.summary,
+                "tool_use_id": e.tool_use_id,
+                "node_id": e.node_id,
+            }),
+        ),
         None => ("unknown".to_string(), serde_json::Value::Null),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_changed(new_state: ExecutionState) -> AgentEvent {
+        AgentEvent {
+            event: Some(agent_event::Event::StateChanged(StateChanged {
+                old_state: ExecutionState::Running as i32,
+                new_state: new_state as i32,
+                reason: String::new(),
+            })),
+            ..AgentEvent::default()
+        }
+    }
+
+    #[test]
+    fn test_terminal_transition_ends_stream_without_reconnect() {
+        for state in TERMINAL_STATES {
+            assert!(is_terminal_transition(&state_changed(state)), "{state:?}");
+        }
+        assert!(!is_terminal_transition(&state_changed(ExecutionState::Paused)));
+        assert!(!is_terminal_transition(&AgentEvent::default()));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::state::AppState;
 use serde::Serialize;
-use superclaude_core::{inventory, types::InventoryItem};
+use superclaude_core::types::{InventoryError, InventoryItem};
 
 #[derive(Debug, Clone, Serialize)]
 pub struct InventoryItemDto {
@@ -24,6 +24,8 @@ pub struct InventoryItemDto {
 #[derive(Debug, Clone, Serialize)]
 pub struct InventoryDto {
     pub items: Vec<InventoryItemDto>,
+    /// Config files that failed to parse.
+    pub errors: Vec<InventoryError>,
 }
 
 impl From<InventoryItem> for InventoryItemDto {
@@ -42,32 +44,24 @@ impl From<InventoryItem> for InventoryItemDto {
     }
 }
 
-#[tauri::command]
+/// Scan the inventory, reparsing only files changed since the last call
+/// unless `force_refresh` is set.
+#[tauri::command(rename_all = "snake_case")]
 pub async fn get_inventory(
+    force_refresh: Option<bool>,
     state: tauri::State<'_, AppState>,
 ) -> Result<InventoryDto, String> {
-    // Check cache first
-    {
-        let cache = state.inventory_cache.read();
-        if let Some(items) = cache.as_ref() {
-            let dto_items = items.iter().map(|i| i.clone().into()).collect();
-            return Ok(InventoryDto { items: dto_items });
-        }
-    }
+    let inv = state
+        .inventory_scanner
+        .lock()
+        .scan(&state.project_root, force_refresh.unwrap_or(false))
+        .map_err(|e| format!("Failed to scan inventory: {}", e))?;
 
-    // Scan filesystem
-    match inventory::scan_all(&state.project_root) {
-        Ok(inv) => {
-            let items = inv.items;
-            // Update cache
-            *state.inventory_cache.write() = Some(items.clone());
-
-            // Convert to DTO
-            let dto_items = items.into_iter().map(|i| i.into()).collect();
-            Ok(InventoryDto { items: dto_items })
-        }
-        Err(e) => Err(format!("Failed to scan inventory: {}", e)),
-    }
+    let dto_items = inv.items.into_iter().map(|i| i.into()).collect();
+    Ok(InventoryDto {
+        items: dto_items,
+        errors: inv.errors,
+    })
 }
 
 #[tauri::command]
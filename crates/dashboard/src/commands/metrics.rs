@@ -1,9 +1,11 @@
 //! Historical metrics Tauri commands.
 
+use std::time::Duration;
+
 use tauri::State;
 
 use crate::state::AppState;
-use superclaude_core::metrics_reader;
+use superclaude_core::metrics_reader::{self, MetricsBucket};
 use superclaude_core::types::MetricEvent;
 
 /// Get all historical events from `.superclaude_metrics/events.jsonl`.
@@ -15,6 +17,19 @@ pub async fn get_historical_events(
         .map_err(|e| format!("Failed to read events: {e}"))
 }
 
+/// Get events appended to `.superclaude_metrics/events.jsonl` since the
+/// previous call. The first call returns the whole file.
+#[tauri::command]
+pub async fn get_new_historical_events(
+    state: State<'_, AppState>,
+) -> Result<Vec<MetricEvent>, String> {
+    state
+        .events_cursor
+        .lock()
+        .read_new_events(&state.project_root)
+        .map_err(|e| format!("Failed to read events: {e}"))
+}
+
 /// Get all historical metrics from `.superclaude_metrics/metrics.jsonl`.
 #[tauri::command]
 pub async fn get_historical_metrics(
@@ -24,6 +39,18 @@ pub async fn get_historical_metrics(
         .map_err(|e| format!("Failed to read metrics: {e}"))
 }
 
+/// Get cost, token, file-change and test totals from events.jsonl,
+/// bucketed into windows of `bucket_seconds`.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_metrics_summary(
+    bucket_seconds: u64,
+    state: State<'_, AppState>,
+) -> Result<Vec<MetricsBucket>, String> {
+    let events = metrics_reader::read_events(&state.project_root)
+        .map_err(|e| format!("Failed to read events: {e}"))?;
+    Ok(metrics_reader::aggregate(&events, Duration::from_secs(bucket_seconds)))
+}
+
 /// Get events for a specific execution.
 #[tauri::command(rename_all = "snake_case")]
 pub async fn get_execution_events(
@@ -2,3 +2,4 @@ pub mod config;
 pub mod execution;
 pub mod inventory;
 pub mod metrics;
+pub mod settings;
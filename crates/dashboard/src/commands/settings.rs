@@ -0,0 +1,38 @@
+use crate::settings::DashboardSettings;
+use crate::state::AppState;
+
+#[tauri::command]
+pub async fn get_settings(state: tauri::State<'_, AppState>) -> Result<DashboardSettings, String> {
+    Ok(state.settings.read().clone())
+}
+
+/// Validate, persist, and apply new settings. Changing the daemon address
+/// drops the current connection so the next call reconnects; changing the
+/// inventory excludes starts a fresh scanner.
+#[tauri::command]
+pub async fn save_settings(
+    state: tauri::State<'_, AppState>,
+    settings: DashboardSettings,
+) -> Result<DashboardSettings, String> {
+    settings.validate()?;
+    settings
+        .save(&state.settings_path)
+        .map_err(|e| format!("{e:#}"))?;
+
+    let (address_changed, excludes_changed) = {
+        let current = state.settings.read();
+        (
+            current.daemon_address != settings.daemon_address,
+            current.inventory_excludes != settings.inventory_excludes,
+        )
+    };
+    *state.settings.write() = settings.clone();
+    if address_changed {
+        *state.grpc_client.write() = None;
+    }
+    if excludes_changed {
+        *state.inventory_scanner.lock() = settings.inventory_scanner()?;
+    }
+
+    Ok(settings)
+}
@@ -3,8 +3,11 @@
 
 mod bridge;
 mod commands;
+mod notify;
+mod settings;
 mod state;
 
+use settings::DashboardSettings;
 use state::AppState;
 use std::path::PathBuf;
 use tauri::Manager;
@@ -31,14 +34,20 @@ fn main() {
 
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_dialog::init())
         .setup(|app| {
-            let state = AppState::new(project_root);
+            let settings_path = app.path().app_config_dir()?.join("settings.json");
+            let settings = DashboardSettings::load_or_default(&settings_path);
+            let state = AppState::new(project_root, settings_path, settings);
             app.manage(state);
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             commands::config::ping_daemon,
             commands::config::get_daemon_config,
+            commands::settings::get_settings,
+            commands::settings::save_settings,
             commands::inventory::get_inventory,
             commands::inventory::get_agent_config,
             commands::execution::start_execution,
@@ -48,9 +57,13 @@ fn main() {
             commands::execution::list_executions,
             commands::execution::subscribe_events,
             commands::execution::get_execution_detail,
+            commands::execution::get_tool_output,
+            commands::execution::export_execution_events,
             commands::execution::send_execution_input,
             commands::metrics::get_historical_events,
+            commands::metrics::get_new_historical_events,
             commands::metrics::get_historical_metrics,
+            commands::metrics::get_metrics_summary,
             commands::metrics::get_execution_events,
         ])
         .run(tauri::generate_context!())
@@ -0,0 +1,146 @@
+//! Desktop notifications for executions that finish while subscribed.
+
+use std::time::{Duration, SystemTime};
+
+use superclaude_proto::{agent_event, AgentEvent, ExecutionState, GetStatusRequest};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+use tracing::warn;
+
+use crate::state::AppState;
+
+/// State changes older than this are history replayed on subscribe, not
+/// something that just happened.
+const REPLAY_CUTOFF: Duration = Duration::from_secs(60);
+
+/// Whether `event` is a fresh transition into `Completed` or `Failed`.
+pub fn warrants_notification(event: &AgentEvent, now: SystemTime) -> bool {
+    let Some(agent_event::Event::StateChanged(change)) = &event.event else {
+        return false;
+    };
+    let finished = [ExecutionState::Completed as i32, ExecutionState::Failed as i32];
+    if change.old_state == change.new_state || !finished.contains(&change.new_state) {
+        return false;
+    }
+
+    // Events without a timestamp are treated as live
+    let Some(ts) = &event.timestamp else {
+        return true;
+    };
+    let at = SystemTime::UNIX_EPOCH
+        + Duration::from_secs(ts.seconds.max(0) as u64)
+        + Duration::from_nanos(ts.nanos.max(0) as u64);
+    now.duration_since(at).map_or(true, |age| age <= REPLAY_CUTOFF)
+}
+
+/// Notify with the execution's task, final score and cost.
+pub async fn notify_finished(app_handle: &AppHandle, execution_id: &str) {
+    let state = app_handle.state::<AppState>();
+    let status = match state.get_client().await {
+        Ok(mut client) => client
+            .get_status(GetStatusRequest {
+                execution_id: execution_id.to_string(),
+            })
+            .await
+            .map(|resp| resp.status.unwrap_or_default()),
+        Err(e) => Err(e),
+    };
+    let status = match status {
+        Ok(status) => status,
+        Err(e) => {
+            warn!(execution_id = %execution_id, "Failed to fetch status for notification: {e:#}");
+            return;
+        }
+    };
+
+    let title = if status.state == ExecutionState::Failed as i32 {
+        "Execution failed"
+    } else {
+        "Execution completed"
+    };
+    let body = format!(
+        "{}\nScore {:.1} · Cost ${:.4}",
+        status.task, status.current_score, status.total_cost_usd
+    );
+    if let Err(e) = app_handle
+        .notification()
+        .builder()
+        .title(title)
+        .body(body)
+        .show()
+    {
+        warn!("Failed to show notification: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use superclaude_proto::{LogMessage, StateChanged};
+
+    const NOW_SECS: u64 = 1_700_000_000;
+
+    fn now() -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(NOW_SECS)
+    }
+
+    fn event(event: agent_event::Event, age_secs: Option<u64>) -> AgentEvent {
+        AgentEvent {
+            execution_id: "exec-1".to_string(),
+            timestamp: age_secs.map(|age| prost_types::Timestamp {
+                seconds: (NOW_SECS - age) as i64,
+                nanos: 0,
+            }),
+            sequence: 1,
+            event: Some(event),
+        }
+    }
+
+    fn state_change(old: ExecutionState, new: ExecutionState, age_secs: Option<u64>) -> AgentEvent {
+        event(
+            agent_event::Event::StateChanged(StateChanged {
+                old_state: old as i32,
+                new_state: new as i32,
+                reason: String::new(),
+            }),
+            age_secs,
+        )
+    }
+
+    #[test]
+    fn test_fresh_terminal_transition_notifies() {
+        for new in [ExecutionState::Completed, ExecutionState::Failed] {
+            assert!(warrants_notification(&state_change(ExecutionState::Running, new, Some(5)), now()));
+        }
+    }
+
+    #[test]
+    fn test_other_events_do_not_notify() {
+        let log = event(agent_event::Event::LogMessage(LogMessage::default()), Some(0));
+        assert!(!warrants_notification(&log, now()));
+        assert!(!warrants_notification(&AgentEvent::default(), now()));
+
+        let unchanged = state_change(ExecutionState::Completed, ExecutionState::Completed, Some(0));
+        assert!(!warrants_notification(&unchanged, now()));
+
+        let paused = state_change(ExecutionState::Running, ExecutionState::Paused, Some(0));
+        assert!(!warrants_notification(&paused, now()));
+    }
+
+    #[test]
+    fn test_replayed_transition_does_not_notify() {
+        let cutoff = REPLAY_CUTOFF.as_secs();
+        let at_cutoff = state_change(ExecutionState::Running, ExecutionState::Completed, Some(cutoff));
+        assert!(warrants_notification(&at_cutoff, now()));
+
+        let stale =
+            state_change(ExecutionState::Running, ExecutionState::Completed, Some(cutoff + 1));
+        assert!(!warrants_notification(&stale, now()));
+    }
+
+    #[test]
+    fn test_missing_timestamp_is_treated_as_live() {
+        let untimed = state_change(ExecutionState::Running, ExecutionState::Failed, None);
+        assert!(warrants_notification(&untimed, now()));
+    }
+}
@@ -0,0 +1,184 @@
+//! Persisted dashboard settings (daemon address, execution defaults and
+//! notifications).
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use superclaude_core::inventory::{InventoryScanner, DEFAULT_EXCLUDES};
+
+/// Models the daemon accepts for executions.
+pub const AVAILABLE_MODELS: &[&str] = &["sonnet", "opus", "haiku"];
+
+/// User-configurable dashboard settings, stored as JSON in the app config dir.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DashboardSettings {
+    /// Daemon gRPC address as `host:port`.
+    pub daemon_address: String,
+    pub default_model: String,
+    pub default_max_iterations: i32,
+    pub default_quality_threshold: f32,
+    /// Show a desktop notification when a subscribed execution finishes.
+    pub notify_on_completion: bool,
+    /// Paths the inventory scan skips (see `InventoryScanner::with_excludes`).
+    pub inventory_excludes: Vec<String>,
+}
+
+impl Default for DashboardSettings {
+    fn default() -> Self {
+        Self {
+            daemon_address: "127.0.0.1:50051".to_string(),
+            default_model: "sonnet".to_string(),
+            default_max_iterations: 3,
+            default_quality_threshold: 70.0,
+            notify_on_completion: true,
+            inventory_excludes: DEFAULT_EXCLUDES.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+}
+
+impl DashboardSettings {
+    /// Load settings from `path`, falling back to defaults if the file is
+    /// missing or invalid.
+    pub fn load_or_default(path: &Path) -> Self {
+        match Self::load(path) {
+            Ok(settings) => settings,
+            Err(e) => {
+                if path.exists() {
+                    tracing::warn!(path = %path.display(), "Ignoring invalid settings file: {e:#}");
+                }
+                Self::default()
+            }
+        }
+    }
+
+    fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let settings: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        settings.validate().map_err(anyhow::Error::msg)?;
+        Ok(settings)
+    }
+
+    /// Write settings to `path` as pretty JSON, creating parent dirs.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Check every field, returning a user-facing message for the first
+    /// invalid one.
+    pub fn validate(&self) -> Result<(), String> {
+        let (host, port) = self
+            .daemon_address
+            .rsplit_once(':')
+            .ok_or_else(|| "Daemon address must be host:port".to_string())?;
+        if host.trim().is_empty() {
+            return Err("Daemon address is missing a host".to_string());
+        }
+        match port.parse::<u16>() {
+            Ok(p) if p > 0 => {}
+            _ => return Err(format!("Invalid daemon port: {port}")),
+        }
+        if !AVAILABLE_MODELS.contains(&self.default_model.as_str()) {
+            return Err(format!(
+                "Unknown model '{}' (expected one of: {})",
+                self.default_model,
+                AVAILABLE_MODELS.join(", ")
+            ));
+        }
+        if !(1..=10).contains(&self.default_max_iterations) {
+            return Err("Max iterations must be between 1 and 10".to_string());
+        }
+        if !(0.0..=100.0).contains(&self.default_quality_threshold) {
+            return Err("Quality threshold must be between 0 and 100".to_string());
+        }
+        self.inventory_scanner()?;
+        Ok(())
+    }
+
+    /// Inventory scanner honoring `inventory_excludes`.
+    pub fn inventory_scanner(&self) -> Result<InventoryScanner, String> {
+        InventoryScanner::new()
+            .with_excludes(&self.inventory_excludes)
+            .map_err(|e| format!("{e:#}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with(f: impl FnOnce(&mut DashboardSettings)) -> DashboardSettings {
+        let mut settings = DashboardSettings::default();
+        f(&mut settings);
+        settings
+    }
+
+    #[test]
+    fn test_default_settings_are_valid() {
+        assert!(DashboardSettings::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_daemon_address() {
+        for address in ["localhost", ":50051", "  :50051"] {
+            let settings = with(|s| s.daemon_address = address.to_string());
+            assert!(settings.validate().is_err(), "accepted {address:?}");
+        }
+        let settings = with(|s| s.daemon_address = "[::1]:50051".to_string());
+        assert!(settings.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_out_of_range_port() {
+        for address in ["127.0.0.1:0", "127.0.0.1:65536", "127.0.0.1:-1", "127.0.0.1:port"] {
+            let settings = with(|s| s.daemon_address = address.to_string());
+            let err = settings.validate().unwrap_err();
+            assert!(err.starts_with("Invalid daemon port"), "{address}: {err}");
+        }
+        let settings = with(|s| s.daemon_address = "127.0.0.1:65535".to_string());
+        assert!(settings.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_model() {
+        let settings = with(|s| s.default_model = "gpt-4".to_string());
+        let err = settings.validate().unwrap_err();
+        assert!(err.contains("gpt-4"));
+        for model in AVAILABLE_MODELS {
+            let settings = with(|s| s.default_model = model.to_string());
+            assert!(settings.validate().is_ok(), "rejected {model}");
+        }
+    }
+
+    #[test]
+    fn test_validate_iteration_and_threshold_bounds() {
+        for iterations in [1, 10] {
+            assert!(with(|s| s.default_max_iterations = iterations).validate().is_ok());
+        }
+        for iterations in [0, 11, -1] {
+            assert!(with(|s| s.default_max_iterations = iterations).validate().is_err());
+        }
+        for threshold in [0.0, 100.0] {
+            assert!(with(|s| s.default_quality_threshold = threshold).validate().is_ok());
+        }
+        for threshold in [-0.1, 100.1, f32::NAN] {
+            assert!(with(|s| s.default_quality_threshold = threshold).validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_exclude_pattern() {
+        let settings = with(|s| s.inventory_excludes = vec!["[unclosed".to_string()]);
+        let err = settings.validate().unwrap_err();
+        assert!(err.contains("[unclosed"));
+    }
+}
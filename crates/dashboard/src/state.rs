@@ -1,24 +1,74 @@
 use crate::bridge::grpc_client::GrpcClient;
+use crate::settings::DashboardSettings;
 use anyhow::{Context, Result};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
 use std::path::PathBuf;
-use superclaude_core::types::InventoryItem;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::task::AbortHandle;
+use superclaude_core::inventory::InventoryScanner;
+use superclaude_core::metrics_reader::MetricsCursor;
 
 pub struct AppState {
     pub grpc_client: RwLock<Option<GrpcClient>>,
     pub project_root: PathBuf,
-    pub inventory_cache: RwLock<Option<Vec<InventoryItem>>>,
+    /// Reparses only inventory files changed since the previous scan.
+    pub inventory_scanner: Mutex<InventoryScanner>,
+    pub settings: RwLock<DashboardSettings>,
+    /// Where `settings` is persisted.
+    pub settings_path: PathBuf,
+    /// How far `get_new_historical_events` has read events.jsonl.
+    pub events_cursor: Mutex<MetricsCursor>,
+    /// Forwarding task of each execution's event stream, with the
+    /// generation it was registered under.
+    event_streams: Mutex<HashMap<String, (u64, AbortHandle)>>,
+    next_stream_generation: AtomicU64,
 }
 
 impl AppState {
-    pub fn new(project_root: PathBuf) -> Self {
+    pub fn new(project_root: PathBuf, settings_path: PathBuf, settings: DashboardSettings) -> Self {
         Self {
             grpc_client: RwLock::new(None),
             project_root,
-            inventory_cache: RwLock::new(None),
+            inventory_scanner: Mutex::new(settings.inventory_scanner().unwrap_or_default()),
+            settings: RwLock::new(settings),
+            settings_path,
+            events_cursor: Mutex::new(MetricsCursor::new()),
+            event_streams: Mutex::new(HashMap::new()),
+            next_stream_generation: AtomicU64::new(0),
         }
     }
 
+    /// Generation for a new event stream registration.
+    pub fn next_stream_generation(&self) -> u64 {
+        self.next_stream_generation.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Record the forwarding task for `execution_id`, aborting the stream it
+    /// replaces so a resubscription never delivers events twice.
+    pub fn register_event_stream(&self, execution_id: &str, generation: u64, task: AbortHandle) {
+        let replaced = self
+            .event_streams
+            .lock()
+            .insert(execution_id.to_string(), (generation, task));
+        if let Some((_, previous)) = replaced {
+            previous.abort();
+        }
+    }
+
+    /// Forget a finished stream, unless a newer one has replaced it.
+    pub fn unregister_event_stream(&self, execution_id: &str, generation: u64) {
+        let mut streams = self.event_streams.lock();
+        if streams.get(execution_id).is_some_and(|(g, _)| *g == generation) {
+            streams.remove(execution_id);
+        }
+    }
+
+    /// Configured daemon address (`host:port`).
+    pub fn daemon_address(&self) -> String {
+        self.settings.read().daemon_address.clone()
+    }
+
     /// Get a cloned gRPC client, connecting if necessary.
     pub async fn get_client(&self) -> Result<GrpcClient> {
         // Check if client exists
@@ -30,7 +80,7 @@ impl AppState {
         }
 
         // Connect if not connected
-        let client = GrpcClient::connect("127.0.0.1:50051")
+        let client = GrpcClient::connect(&self.daemon_address())
             .await
             .context("Failed to connect to daemon")?;
 
@@ -2,26 +2,76 @@ use crate::hangman_art::HangmanArt;
 use crate::word_list::WordList;
 use anyhow::{bail, Result};
 use colored::*;
+use rand::seq::SliceRandom;
 use std::collections::HashSet;
+use std::str::FromStr;
 
-const MAX_INCORRECT_GUESSES: usize = 6;
+/// Number of drawings after the empty gallows in `HangmanArt`
+const ART_STAGES: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    /// Incorrect guesses allowed before the game is lost.
+    pub fn max_incorrect_guesses(self) -> usize {
+        match self {
+            Difficulty::Easy => 8,
+            Difficulty::Medium => 6,
+            Difficulty::Hard => 4,
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "1" | "e" | "easy" => Ok(Difficulty::Easy),
+            "2" | "m" | "medium" => Ok(Difficulty::Medium),
+            "3" | "h" | "hard" => Ok(Difficulty::Hard),
+            other => bail!("Unknown difficulty '{}'", other),
+        }
+    }
+}
+
+impl std::fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Difficulty::Easy => write!(f, "easy"),
+            Difficulty::Medium => write!(f, "medium"),
+            Difficulty::Hard => write!(f, "hard"),
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct Game {
     word: String,
+    category: String,
     guessed_letters: HashSet<char>,
     incorrect_guesses: usize,
+    max_incorrect_guesses: usize,
+    hint_used: bool,
 }
 
 impl Game {
-    pub fn new() -> Self {
-        let word_list = WordList::new();
-        let word = word_list.get_random_word();
+    /// Start a game with a word from `category`, or from any category when `None`.
+    pub fn new(word_list: &WordList, difficulty: Difficulty, category: Option<&str>) -> Self {
+        let choice = word_list.get_random_word(category);
 
         Self {
-            word,
+            word: choice.word,
+            category: choice.category,
             guessed_letters: HashSet::new(),
             incorrect_guesses: 0,
+            max_incorrect_guesses: difficulty.max_incorrect_guesses(),
+            hint_used: false,
         }
     }
 
@@ -44,6 +94,33 @@ impl Game {
         }
     }
 
+    /// Reveal a random letter not yet guessed. Allowed once per game and
+    /// costs one incorrect guess, so it is refused when it would lose the game.
+    pub fn use_hint(&mut self) -> Result<char> {
+        if self.hint_used {
+            bail!("You already used your hint");
+        }
+        if self.incorrect_guesses + 1 >= self.max_incorrect_guesses {
+            bail!("Not enough guesses left for a hint");
+        }
+
+        let hidden: Vec<char> = self
+            .word
+            .chars()
+            .filter(|ch| !self.guessed_letters.contains(ch))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let Some(&letter) = hidden.choose(&mut rand::thread_rng()) else {
+            bail!("Every letter is already revealed");
+        };
+
+        self.guessed_letters.insert(letter);
+        self.incorrect_guesses += 1;
+        self.hint_used = true;
+        Ok(letter)
+    }
+
     pub fn is_game_over(&self) -> bool {
         self.is_won() || self.is_lost()
     }
@@ -55,19 +132,27 @@ impl Game {
     }
 
     pub fn is_lost(&self) -> bool {
-        self.incorrect_guesses >= MAX_INCORRECT_GUESSES
+        self.incorrect_guesses >= self.max_incorrect_guesses
+    }
+
+    /// Art stage for the current misses, scaled so the drawing completes
+    /// exactly on the last allowed miss.
+    fn art_stage(&self) -> usize {
+        (self.incorrect_guesses * ART_STAGES / self.max_incorrect_guesses).min(ART_STAGES)
     }
 
     pub fn display_state(&self) {
-        println!("\n{}", HangmanArt::get_art(self.incorrect_guesses));
+        println!("\n{}", HangmanArt::get_art(self.art_stage()));
 
         println!(
             "\n{} {} / {}",
             "Incorrect guesses:".bright_yellow(),
             self.incorrect_guesses.to_string().bright_red().bold(),
-            MAX_INCORRECT_GUESSES
+            self.max_incorrect_guesses
         );
 
+        println!("\n{} {}", "Category:".bright_blue(), self.category);
+
         let word_display = self.get_word_display();
         println!("\n{} {}", "Word:".bright_cyan(), word_display);
 
@@ -82,7 +167,7 @@ impl Game {
             println!("\n{}", "🎉 CONGRATULATIONS! YOU WON! 🎉".bright_green().bold());
             println!("\n{} {}", "The word was:".bright_white(), self.word.bright_green().bold());
         } else {
-            println!("\n{}", HangmanArt::get_art(self.incorrect_guesses));
+            println!("\n{}", HangmanArt::get_art(self.art_stage()));
             println!("\n{}", "💀 GAME OVER! YOU LOST! 💀".bright_red().bold());
             println!("\n{} {}", "The word was:".bright_white(), self.word.bright_yellow().bold());
         }
@@ -132,7 +217,7 @@ mod tests {
 
     #[test]
     fn test_new_game() {
-        let game = Game::new();
+        let game = Game::new(&WordList::new(), Difficulty::Medium, None);
         assert!(!game.word.is_empty());
         assert_eq!(game.incorrect_guesses, 0);
         assert!(game.guessed_letters.is_empty());
@@ -140,7 +225,7 @@ mod tests {
 
     #[test]
     fn test_correct_guess() {
-        let mut game = Game::new();
+        let mut game = Game::new(&WordList::new(), Difficulty::Medium, None);
         let word = game.word.clone();
         let first_char = word.chars().next().unwrap();
 
@@ -152,7 +237,7 @@ mod tests {
 
     #[test]
     fn test_incorrect_guess() {
-        let mut game = Game::new();
+        let mut game = Game::new(&WordList::new(), Difficulty::Medium, None);
 
         // Find a letter not in the word
         let letter = ('a'..='z')
@@ -167,7 +252,7 @@ mod tests {
 
     #[test]
     fn test_duplicate_guess() {
-        let mut game = Game::new();
+        let mut game = Game::new(&WordList::new(), Difficulty::Medium, None);
         let word = game.word.clone();
         let first_char = word.chars().next().unwrap();
 
@@ -179,14 +264,14 @@ mod tests {
 
     #[test]
     fn test_game_lost() {
-        let mut game = Game::new();
+        let mut game = Game::new(&WordList::new(), Difficulty::Medium, None);
 
-        // Make MAX_INCORRECT_GUESSES wrong guesses
+        // Make max_incorrect_guesses wrong guesses
         let mut wrong_letters = Vec::new();
         for ch in 'a'..='z' {
             if !game.word.contains(ch) {
                 wrong_letters.push(ch);
-                if wrong_letters.len() >= MAX_INCORRECT_GUESSES {
+                if wrong_letters.len() >= game.max_incorrect_guesses {
                     break;
                 }
             }
@@ -199,4 +284,62 @@ mod tests {
         assert!(game.is_lost());
         assert!(game.is_game_over());
     }
+
+    #[test]
+    fn test_difficulty_guess_limits() {
+        assert_eq!(Difficulty::Easy.max_incorrect_guesses(), 8);
+        assert_eq!(Difficulty::Medium.max_incorrect_guesses(), 6);
+        assert_eq!(Difficulty::Hard.max_incorrect_guesses(), 4);
+
+        assert_eq!("easy".parse::<Difficulty>().unwrap(), Difficulty::Easy);
+        assert_eq!(" M ".parse::<Difficulty>().unwrap(), Difficulty::Medium);
+        assert_eq!("3".parse::<Difficulty>().unwrap(), Difficulty::Hard);
+        assert!("extreme".parse::<Difficulty>().is_err());
+    }
+
+    #[test]
+    fn test_hard_game_lost_after_four_misses() {
+        let mut game = Game::new(&WordList::parse("rust").unwrap(), Difficulty::Hard, None);
+        for letter in ['a', 'b', 'c'] {
+            game.make_guess(letter).unwrap();
+        }
+        assert!(!game.is_lost());
+        game.make_guess('d').unwrap();
+        assert!(game.is_lost());
+        assert_eq!(game.art_stage(), ART_STAGES);
+    }
+
+    #[test]
+    fn test_new_game_uses_category() {
+        let word_list = WordList::new();
+        let game = Game::new(&word_list, Difficulty::Medium, Some("animals"));
+        assert_eq!(game.category, "Animals");
+        assert!(word_list.category("animals").unwrap().words.contains(&game.word));
+    }
+
+    #[test]
+    fn test_hint_reveals_unguessed_letter_once() {
+        let mut game = Game::new(&WordList::parse("banana").unwrap(), Difficulty::Medium, None);
+        game.make_guess('a').unwrap();
+
+        let letter = game.use_hint().unwrap();
+        assert!(letter == 'b' || letter == 'n');
+        assert!(game.guessed_letters.contains(&letter));
+        assert_eq!(game.incorrect_guesses, 1);
+
+        assert!(game.use_hint().is_err());
+        assert_eq!(game.incorrect_guesses, 1);
+        assert!(game.make_guess(letter).is_err(), "hinted letter counts as guessed");
+    }
+
+    #[test]
+    fn test_hint_refused_when_it_would_lose() {
+        let mut game = Game::new(&WordList::parse("rust").unwrap(), Difficulty::Hard, None);
+        for letter in ['a', 'b', 'c'] {
+            game.make_guess(letter).unwrap();
+        }
+        assert!(game.use_hint().is_err());
+        assert!(!game.hint_used);
+        assert!(!game.is_lost());
+    }
 }
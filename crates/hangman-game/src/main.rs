@@ -1,21 +1,44 @@
 mod game;
 mod hangman_art;
+mod session;
 mod word_list;
 
 use anyhow::Result;
 use colored::*;
-use game::Game;
+use game::{Difficulty, Game};
+use session::Session;
 use std::io::{self, Write};
+use std::path::PathBuf;
+use word_list::{WordList, CUSTOM_CATEGORY};
 
+/// Usage: `hangman [WORDS_FILE]`, where WORDS_FILE lists one word per line.
 fn main() -> Result<()> {
-    display_welcome();
+    let word_list = match std::env::args_os().nth(1).map(PathBuf::from) {
+        Some(path) => WordList::from_file(&path)?,
+        None => WordList::new(),
+    };
+
+    let mut session = Session::new();
+    display_welcome(&session);
+    let difficulty = choose_difficulty()?;
+    let category = choose_category(&word_list)?;
+    println!(
+        "\n{}",
+        format!(
+            "You have {} incorrect guesses before you lose!\n",
+            difficulty.max_incorrect_guesses()
+        )
+        .yellow()
+    );
 
     loop {
-        let mut game = Game::new();
+        let mut game = Game::new(&word_list, difficulty, category.as_deref());
         play_game(&mut game)?;
+        session.record(game.is_won());
+        session.display_tally();
 
         if !play_again()? {
-            display_goodbye();
+            display_goodbye(&session);
             break;
         }
     }
@@ -23,18 +46,87 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn display_welcome() {
+fn display_welcome(session: &Session) {
     println!("\n{}", "╔═══════════════════════════════════════╗".bright_cyan());
     println!("{}", "║     WELCOME TO HANGMAN GAME!          ║".bright_cyan());
     println!("{}", "╚═══════════════════════════════════════╝".bright_cyan());
+    if session.games_played() > 0 {
+        session.display_tally();
+    }
     println!("\n{}", "Guess the word letter by letter.".yellow());
-    println!("{}", "You have 6 incorrect guesses before you lose!\n".yellow());
 }
 
-fn display_goodbye() {
+fn choose_difficulty() -> Result<Difficulty> {
+    println!("\n{}", "Choose a difficulty:".bright_white().bold());
+    for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+        println!(
+            "  {}. {} ({} incorrect guesses)",
+            difficulty as u8 + 1,
+            difficulty,
+            difficulty.max_incorrect_guesses()
+        );
+    }
+
+    loop {
+        print!("\n{}", "Difficulty [1-3, default 2]: ".bright_white().bold());
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        if input.trim().is_empty() {
+            return Ok(Difficulty::Medium);
+        }
+        match input.parse() {
+            Ok(difficulty) => return Ok(difficulty),
+            Err(e) => println!("{}", format!("{}. Enter easy, medium, or hard.", e).red()),
+        }
+    }
+}
+
+/// Ask for a category; `None` means words from every category.
+fn choose_category(word_list: &WordList) -> Result<Option<String>> {
+    let categories = word_list.categories();
+    if categories.len() == 1 && categories[0].name == CUSTOM_CATEGORY {
+        return Ok(None);
+    }
+
+    println!("\n{}", "Choose a category:".bright_white().bold());
+    println!("  0. any");
+    for (i, category) in categories.iter().enumerate() {
+        println!("  {}. {}", i + 1, category.name.to_lowercase());
+    }
+
+    loop {
+        print!("\n{}", "Category [number or name, default any]: ".bright_white().bold());
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let input = input.trim();
+
+        if input.is_empty() || input == "0" || input.eq_ignore_ascii_case("any") {
+            return Ok(None);
+        }
+        let by_number = input
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| n.checked_sub(1))
+            .and_then(|i| categories.get(i));
+        match by_number.or_else(|| word_list.category(input)) {
+            Some(category) => return Ok(Some(category.name.clone())),
+            None => println!("{}", format!("Unknown category '{}'", input).red()),
+        }
+    }
+}
+
+fn display_goodbye(session: &Session) {
     println!("\n{}", "╔═══════════════════════════════════════╗".bright_cyan());
     println!("{}", "║     THANKS FOR PLAYING HANGMAN!       ║".bright_cyan());
     println!("{}", "╚═══════════════════════════════════════╝".bright_cyan());
+    if session.games_played() > 0 {
+        session.display_tally();
+    }
     println!();
 }
 
@@ -42,18 +134,27 @@ fn play_game(game: &mut Game) -> Result<()> {
     while !game.is_game_over() {
         game.display_state();
 
-        let guess = get_user_guess()?;
-
-        match game.make_guess(guess) {
-            Ok(true) => {
-                println!("\n{}", "✓ Correct guess!".bright_green().bold());
-            }
-            Ok(false) => {
-                println!("\n{}", "✗ Wrong guess!".bright_red().bold());
-            }
-            Err(e) => {
-                println!("\n{}", format!("⚠ {}", e).yellow());
-            }
+        match get_user_input()? {
+            UserInput::Guess(letter) => match game.make_guess(letter) {
+                Ok(true) => {
+                    println!("\n{}", "✓ Correct guess!".bright_green().bold());
+                }
+                Ok(false) => {
+                    println!("\n{}", "✗ Wrong guess!".bright_red().bold());
+                }
+                Err(e) => {
+                    println!("\n{}", format!("⚠ {}", e).yellow());
+                }
+            },
+            UserInput::Hint => match game.use_hint() {
+                Ok(letter) => {
+                    let hint = format!("💡 Hint: the word contains '{}' (costs one guess)", letter);
+                    println!("\n{}", hint.bright_blue().bold());
+                }
+                Err(e) => {
+                    println!("\n{}", format!("⚠ {}", e).yellow());
+                }
+            },
         }
 
         println!("\n{}", "─".repeat(50).bright_black());
@@ -63,9 +164,14 @@ fn play_game(game: &mut Game) -> Result<()> {
     Ok(())
 }
 
-fn get_user_guess() -> Result<char> {
+enum UserInput {
+    Guess(char),
+    Hint,
+}
+
+fn get_user_input() -> Result<UserInput> {
     loop {
-        print!("\n{}", "Enter your guess (single letter): ".bright_white().bold());
+        print!("\n{}", "Enter your guess (single letter, ? for a hint): ".bright_white().bold());
         io::stdout().flush()?;
 
         let mut input = String::new();
@@ -73,6 +179,10 @@ fn get_user_guess() -> Result<char> {
 
         let input = input.trim();
 
+        if input == "?" {
+            return Ok(UserInput::Hint);
+        }
+
         if input.len() != 1 {
             println!("{}", "Please enter exactly one letter!".red());
             continue;
@@ -85,7 +195,7 @@ fn get_user_guess() -> Result<char> {
             continue;
         }
 
-        return Ok(ch.to_ascii_lowercase());
+        return Ok(UserInput::Guess(ch.to_ascii_lowercase()));
     }
 }
 
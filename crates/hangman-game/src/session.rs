@@ -0,0 +1,77 @@
+use colored::*;
+
+/// Wins, losses, and streaks across the rounds of one run of the game.
+/// Kept in memory only.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Session {
+    wins: u32,
+    losses: u32,
+    /// Consecutive wins up to the latest round
+    current_streak: u32,
+    best_streak: u32,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, won: bool) {
+        if won {
+            self.wins += 1;
+            self.current_streak += 1;
+            self.best_streak = self.best_streak.max(self.current_streak);
+        } else {
+            self.losses += 1;
+            self.current_streak = 0;
+        }
+    }
+
+    pub fn games_played(&self) -> u32 {
+        self.wins + self.losses
+    }
+
+    pub fn display_tally(&self) {
+        println!(
+            "\n{} {} {} {} {} {} {} {}",
+            "Session:".bright_white().bold(),
+            self.wins.to_string().bright_green().bold(),
+            "won,".bright_white(),
+            self.losses.to_string().bright_red().bold(),
+            "lost, streak".bright_white(),
+            self.current_streak.to_string().bright_yellow().bold(),
+            "(best".bright_white(),
+            format!("{})", self.best_streak).bright_yellow(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_session_is_empty() {
+        let session = Session::new();
+        assert_eq!(session.games_played(), 0);
+        assert_eq!(session.current_streak, 0);
+    }
+
+    #[test]
+    fn test_record_outcomes() {
+        let mut session = Session::new();
+        let expected_streaks = [1, 2, 0, 1, 2, 3, 0];
+        for (won, streak) in [true, true, false, true, true, true, false]
+            .into_iter()
+            .zip(expected_streaks)
+        {
+            session.record(won);
+            assert_eq!(session.current_streak, streak);
+        }
+
+        assert_eq!(session.wins, 5);
+        assert_eq!(session.losses, 2);
+        assert_eq!(session.games_played(), 7);
+        assert_eq!(session.best_streak, 3);
+    }
+}
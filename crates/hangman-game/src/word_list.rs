@@ -1,189 +1,337 @@
+use anyhow::{bail, Context, Result};
 use rand::seq::SliceRandom;
 use rand::thread_rng;
+use std::path::Path;
+
+/// Words of one theme, e.g. "Animals".
+#[derive(Debug, Clone, PartialEq)]
+pub struct Category {
+    pub name: String,
+    pub words: Vec<String>,
+}
+
+/// A randomly chosen word and the category it came from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordChoice {
+    pub word: String,
+    pub category: String,
+}
+
+/// Category for words loaded from a file.
+pub const CUSTOM_CATEGORY: &str = "Custom";
+
+const BUILTIN_WORDS: &[(&str, &[&str])] = &[
+    (
+        "Animals",
+        &[
+            "elephant",
+            "giraffe",
+            "kangaroo",
+            "penguin",
+            "dolphin",
+            "cheetah",
+            "rhinoceros",
+            "alligator",
+            "butterfly",
+            "octopus",
+            "flamingo",
+            "leopard",
+            "walrus",
+            "zebra",
+            "hamster",
+        ],
+    ),
+    (
+        "Food",
+        &[
+            "pizza",
+            "hamburger",
+            "spaghetti",
+            "chocolate",
+            "strawberry",
+            "avocado",
+            "blueberry",
+            "watermelon",
+            "pineapple",
+            "broccoli",
+            "cinnamon",
+            "paprika",
+            "vanilla",
+            "croissant",
+            "pretzel",
+        ],
+    ),
+    (
+        "Technology",
+        &[
+            "computer",
+            "keyboard",
+            "internet",
+            "algorithm",
+            "software",
+            "database",
+            "javascript",
+            "python",
+            "blockchain",
+            "cybersecurity",
+            "artificial",
+            "quantum",
+            "microchip",
+            "bandwidth",
+            "encryption",
+        ],
+    ),
+    (
+        "Nature",
+        &[
+            "mountain",
+            "volcano",
+            "hurricane",
+            "rainbow",
+            "waterfall",
+            "lightning",
+            "avalanche",
+            "earthquake",
+            "tsunami",
+            "glacier",
+            "canyon",
+            "desert",
+            "forest",
+            "meadow",
+            "cliff",
+        ],
+    ),
+    (
+        "Objects",
+        &[
+            "umbrella",
+            "telescope",
+            "microscope",
+            "camera",
+            "guitar",
+            "piano",
+            "trumpet",
+            "saxophone",
+            "violin",
+            "drums",
+            "binoculars",
+            "compass",
+            "parachute",
+            "bicycle",
+            "helicopter",
+        ],
+    ),
+    (
+        "Professions",
+        &[
+            "architect",
+            "astronaut",
+            "musician",
+            "engineer",
+            "scientist",
+            "veterinarian",
+            "firefighter",
+            "detective",
+            "journalist",
+            "photographer",
+            "magician",
+            "librarian",
+            "carpenter",
+            "plumber",
+            "electrician",
+        ],
+    ),
+    (
+        "Countries",
+        &[
+            "brazil",
+            "canada",
+            "egypt",
+            "france",
+            "germany",
+            "iceland",
+            "india",
+            "japan",
+            "kenya",
+            "mexico",
+            "norway",
+            "portugal",
+            "singapore",
+            "thailand",
+            "vietnam",
+        ],
+    ),
+    (
+        "Abstract",
+        &[
+            "democracy",
+            "imagination",
+            "philosophy",
+            "revolution",
+            "knowledge",
+            "adventure",
+            "harmony",
+            "mystery",
+            "creativity",
+            "freedom",
+            "justice",
+            "wisdom",
+            "courage",
+            "patience",
+            "gratitude",
+        ],
+    ),
+    (
+        "Geography",
+        &[
+            "continent",
+            "antarctica",
+            "archipelago",
+            "peninsula",
+            "equator",
+            "latitude",
+            "longitude",
+            "hemisphere",
+            "plateau",
+            "savanna",
+            "tundra",
+            "prairie",
+            "oasis",
+            "fjord",
+            "lagoon",
+        ],
+    ),
+    (
+        "Sports",
+        &[
+            "basketball",
+            "volleyball",
+            "badminton",
+            "gymnastics",
+            "swimming",
+            "marathon",
+            "wrestling",
+            "archery",
+            "fencing",
+            "skateboarding",
+            "surfing",
+            "cycling",
+            "boxing",
+            "karate",
+            "judo",
+        ],
+    ),
+    (
+        "Space",
+        &[
+            "galaxy",
+            "nebula",
+            "asteroid",
+            "comet",
+            "meteor",
+            "satellite",
+            "telescope",
+            "supernova",
+            "constellation",
+            "blackhole",
+            "universe",
+            "planet",
+            "orbit",
+            "gravity",
+            "cosmos",
+        ],
+    ),
+];
 
 #[derive(Debug)]
 pub struct WordList {
-    words: Vec<&'static str>,
+    categories: Vec<Category>,
 }
 
 impl WordList {
     pub fn new() -> Self {
         Self {
-            words: vec![
-                // Animals
-                "elephant",
-                "giraffe",
-                "kangaroo",
-                "penguin",
-                "dolphin",
-                "cheetah",
-                "rhinoceros",
-                "alligator",
-                "butterfly",
-                "octopus",
-                "flamingo",
-                "leopard",
-                "walrus",
-                "zebra",
-                "hamster",
-                // Food
-                "pizza",
-                "hamburger",
-                "spaghetti",
-                "chocolate",
-                "strawberry",
-                "avocado",
-                "blueberry",
-                "watermelon",
-                "pineapple",
-                "broccoli",
-                "cinnamon",
-                "paprika",
-                "vanilla",
-                "croissant",
-                "pretzel",
-                // Technology
-                "computer",
-                "keyboard",
-                "internet",
-                "algorithm",
-                "software",
-                "database",
-                "javascript",
-                "python",
-                "blockchain",
-                "cybersecurity",
-                "artificial",
-                "quantum",
-                "microchip",
-                "bandwidth",
-                "encryption",
-                // Nature
-                "mountain",
-                "volcano",
-                "hurricane",
-                "rainbow",
-                "waterfall",
-                "lightning",
-                "avalanche",
-                "earthquake",
-                "tsunami",
-                "glacier",
-                "canyon",
-                "desert",
-                "forest",
-                "meadow",
-                "cliff",
-                // Objects
-                "umbrella",
-                "telescope",
-                "microscope",
-                "camera",
-                "guitar",
-                "piano",
-                "trumpet",
-                "saxophone",
-                "violin",
-                "drums",
-                "binoculars",
-                "compass",
-                "parachute",
-                "bicycle",
-                "helicopter",
-                // Professions
-                "architect",
-                "astronaut",
-                "musician",
-                "engineer",
-                "scientist",
-                "veterinarian",
-                "firefighter",
-                "detective",
-                "journalist",
-                "photographer",
-                "magician",
-                "librarian",
-                "carpenter",
-                "plumber",
-                "electrician",
-                // Abstract concepts
-                "democracy",
-                "imagination",
-                "philosophy",
-                "revolution",
-                "knowledge",
-                "adventure",
-                "harmony",
-                "mystery",
-                "creativity",
-                "freedom",
-                "justice",
-                "wisdom",
-                "courage",
-                "patience",
-                "gratitude",
-                // Geography
-                "continent",
-                "antarctica",
-                "archipelago",
-                "peninsula",
-                "equator",
-                "latitude",
-                "longitude",
-                "hemisphere",
-                "plateau",
-                "savanna",
-                "tundra",
-                "prairie",
-                "oasis",
-                "fjord",
-                "lagoon",
-                // Sports
-                "basketball",
-                "volleyball",
-                "badminton",
-                "gymnastics",
-                "swimming",
-                "marathon",
-                "wrestling",
-                "archery",
-                "fencing",
-                "skateboarding",
-                "surfing",
-                "cycling",
-                "boxing",
-                "karate",
-                "judo",
-                // Space
-                "galaxy",
-                "nebula",
-                "asteroid",
-                "comet",
-                "meteor",
-                "satellite",
-                "telescope",
-                "supernova",
-                "constellation",
-                "blackhole",
-                "universe",
-                "planet",
-                "orbit",
-                "gravity",
-                "cosmos",
-            ],
+            categories: BUILTIN_WORDS
+                .iter()
+                .map(|(name, words)| Category {
+                    name: name.to_string(),
+                    words: words.iter().map(|w| w.to_string()).collect(),
+                })
+                .collect(),
         }
     }
 
-    pub fn get_random_word(&self) -> String {
+    /// Load one word per line from `path`. See [`WordList::parse`].
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read word list {}", path.display()))?;
+        Self::parse(&contents).with_context(|| format!("Invalid word list {}", path.display()))
+    }
+
+    /// Parse one word per line into the [`CUSTOM_CATEGORY`] category. Blank
+    /// lines and lines starting with `#` are skipped; every other line must
+    /// be a single word of letters a-z (case-insensitive).
+    pub fn parse(contents: &str) -> Result<Self> {
+        let mut words = Vec::new();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if !line.chars().all(|ch| ch.is_ascii_alphabetic()) {
+                bail!("line {}: '{}' must contain only letters a-z", index + 1, line);
+            }
+            words.push(line.to_ascii_lowercase());
+        }
+
+        if words.is_empty() {
+            bail!("no words found");
+        }
+        Ok(Self {
+            categories: vec![Category {
+                name: CUSTOM_CATEGORY.to_string(),
+                words,
+            }],
+        })
+    }
+
+    pub fn categories(&self) -> &[Category] {
+        &self.categories
+    }
+
+    /// Look up a category by name, ignoring case.
+    pub fn category(&self, name: &str) -> Option<&Category> {
+        self.categories
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Pick a random word from `category`, or from any category when `None`
+    /// or the category is unknown.
+    pub fn get_random_word(&self, category: Option<&str>) -> WordChoice {
         let mut rng = thread_rng();
-        self.words
+        let candidates: Vec<&Category> = match category.and_then(|name| self.category(name)) {
+            Some(category) => vec![category],
+            None => self.categories.iter().collect(),
+        };
+
+        candidates
+            .iter()
+            .flat_map(|c| c.words.iter().map(move |w| (c, w)))
+            .collect::<Vec<_>>()
             .choose(&mut rng)
-            .unwrap_or(&"hangman")
-            .to_string()
+            .map(|(c, w)| WordChoice {
+                word: w.to_string(),
+                category: c.name.clone(),
+            })
+            .unwrap_or_else(|| WordChoice {
+                word: "hangman".to_string(),
+                category: CUSTOM_CATEGORY.to_string(),
+            })
     }
 
     pub fn get_word_count(&self) -> usize {
-        self.words.len()
+        self.categories.iter().map(|c| c.words.len()).sum()
     }
 }
 
@@ -206,7 +354,7 @@ mod tests {
     #[test]
     fn test_get_random_word() {
         let word_list = WordList::new();
-        let word = word_list.get_random_word();
+        let word = word_list.get_random_word(None).word;
         assert!(!word.is_empty());
         assert!(word.chars().all(|c| c.is_ascii_lowercase()));
     }
@@ -218,10 +366,67 @@ mod tests {
 
         // Get 20 random words - should get some variety
         for _ in 0..20 {
-            words.insert(word_list.get_random_word());
+            words.insert(word_list.get_random_word(None).word);
         }
 
         // We should get at least a few different words (not all the same)
         assert!(words.len() > 1);
     }
+
+    #[test]
+    fn test_parse_valid_lines() {
+        let word_list = WordList::parse("# my words\nRust\n\n  cargo  \ntrait\n").unwrap();
+        assert_eq!(word_list.categories().len(), 1);
+        assert_eq!(word_list.categories()[0].name, CUSTOM_CATEGORY);
+        assert_eq!(word_list.categories()[0].words, vec!["rust", "cargo", "trait"]);
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_lines() {
+        for contents in ["rust\ntwo words\n", "rust\nc3po\n", "naïve\n", "café"] {
+            assert!(WordList::parse(contents).is_err(), "{contents:?}");
+        }
+
+        let err = WordList::parse("ok\nnot-ok\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn test_parse_requires_words() {
+        assert!(WordList::parse("").is_err());
+        assert!(WordList::parse("# only a comment\n\n").is_err());
+    }
+
+    #[test]
+    fn test_from_file() {
+        let path = std::env::temp_dir().join(format!("hangman-words-{}.txt", std::process::id()));
+        std::fs::write(&path, "ferris\ncrab\n").unwrap();
+        let word_list = WordList::from_file(&path);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(word_list.unwrap().get_word_count(), 2);
+        assert!(WordList::from_file(Path::new("/nonexistent/words.txt")).is_err());
+    }
+
+    #[test]
+    fn test_category_lookup() {
+        let word_list = WordList::new();
+        assert!(word_list.categories().len() >= 3);
+
+        let animals = word_list.category("animals").unwrap();
+        assert_eq!(animals.name, "Animals");
+        assert!(animals.words.contains(&"elephant".to_string()));
+        assert!(word_list.category(" COUNTRIES ").is_some());
+        assert!(word_list.category("dinosaurs").is_none());
+
+        for _ in 0..20 {
+            let choice = word_list.get_random_word(Some("technology"));
+            assert_eq!(choice.category, "Technology");
+            assert!(word_list.category("technology").unwrap().words.contains(&choice.word));
+        }
+
+        // Unknown categories fall back to any word
+        let choice = word_list.get_random_word(Some("dinosaurs"));
+        assert!(word_list.category(&choice.category).is_some());
+    }
 }
@@ -1,8 +1,107 @@
+use std::sync::OnceLock;
+
+use clap::Parser;
 use macroquad::prelude::*;
 
-const GRID_SIZE: i32 = 20;
 const CELL_SIZE: f32 = 30.0;
-const GAME_SPEED: f32 = 0.15;
+const MIN_GRID_SIZE: i32 = 10;
+const MAX_GRID_SIZE: i32 = 60;
+const MIN_SPEED: f64 = 0.02;
+const MAX_SPEED: f64 = 2.0;
+/// Points per level; each food is worth 10
+const POINTS_PER_LEVEL: u32 = 50;
+/// Each level multiplies the step interval by this factor
+const LEVEL_SPEEDUP: f64 = 0.9;
+/// Fastest step interval reached by levelling up
+const MIN_STEP_SECONDS: f64 = 0.05;
+/// Cells kept free in front of the starting head so a new game can't
+/// open straight into an obstacle
+const OBSTACLE_FREE_LANE: i32 = 3;
+/// Narrow grids still need room for the menu text
+const MIN_WINDOW_WIDTH: i32 = 600;
+
+/// Classic Snake with a graphical window
+#[derive(Debug, Parser)]
+#[command(name = "snake-game", version)]
+struct Args {
+    /// Cells per side of the square board (10-60)
+    #[arg(
+        long,
+        default_value_t = 20,
+        value_parser = clap::value_parser!(i32).range(MIN_GRID_SIZE as i64..=MAX_GRID_SIZE as i64)
+    )]
+    grid_size: i32,
+
+    /// Seconds per snake step; lower is faster (0.02-2.0)
+    #[arg(long, default_value_t = 0.15, value_parser = parse_speed)]
+    speed: f64,
+
+    /// Wrap through walls instead of dying at them
+    #[arg(long)]
+    wrap: bool,
+
+    /// Static wall blocks scattered on the board each game (capped at a
+    /// quarter of the board)
+    #[arg(long, default_value_t = 0)]
+    obstacles: u32,
+}
+
+fn parse_speed(value: &str) -> Result<f64, String> {
+    let speed: f64 = value
+        .parse()
+        .map_err(|_| format!("`{value}` is not a number of seconds"))?;
+    if !(MIN_SPEED..=MAX_SPEED).contains(&speed) {
+        return Err(format!("must be between {MIN_SPEED} and {MAX_SPEED} seconds per step"));
+    }
+    Ok(speed)
+}
+
+/// Level reached at `score`, starting at 1.
+fn level_for_score(score: u32) -> u32 {
+    1 + score / POINTS_PER_LEVEL
+}
+
+/// Seconds between steps at `score`: `base` sped up by 10% per level,
+/// but never faster than `MIN_STEP_SECONDS` (or `base`, if already faster).
+fn step_interval(base: f64, score: u32) -> f64 {
+    let levels = (level_for_score(score) - 1).min(i32::MAX as u32) as i32;
+    (base * LEVEL_SPEEDUP.powi(levels)).max(base.min(MIN_STEP_SECONDS))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct GameConfig {
+    grid_size: i32,
+    /// Seconds between snake steps
+    step_seconds: f64,
+    /// Snake wraps around the board edges (toroidal board)
+    wrap: bool,
+    /// Obstacles placed at the start of each game
+    obstacles: u32,
+}
+
+impl From<Args> for GameConfig {
+    fn from(args: Args) -> Self {
+        Self {
+            grid_size: args.grid_size,
+            step_seconds: args.speed,
+            wrap: args.wrap,
+            obstacles: args.obstacles,
+        }
+    }
+}
+
+impl GameConfig {
+    /// Parsed once from the command line; invalid arguments print usage and exit.
+    fn get() -> Self {
+        static CONFIG: OnceLock<GameConfig> = OnceLock::new();
+        *CONFIG.get_or_init(|| Args::parse().into())
+    }
+
+    fn window_size(&self) -> (i32, i32) {
+        let board = self.grid_size as f32 * CELL_SIZE;
+        (((board + 100.0) as i32).max(MIN_WINDOW_WIDTH), (board + 200.0) as i32)
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Direction {
@@ -87,6 +186,13 @@ impl Snake {
         self.growing = true;
     }
 
+    /// Bring a head that left the board back in on the opposite edge.
+    fn wrap_head(&mut self, grid_size: i32) {
+        let head = &mut self.body[0];
+        head.x = head.x.rem_euclid(grid_size);
+        head.y = head.y.rem_euclid(grid_size);
+    }
+
     fn collides_with_self(&self) -> bool {
         let head = self.head();
         self.body.iter().skip(1).any(|&pos| pos == head)
@@ -97,7 +203,7 @@ impl Snake {
     }
 }
 
-#[derive(PartialEq)]
+#[derive(Debug, PartialEq)]
 enum GameState {
     Menu,
     Playing,
@@ -107,6 +213,10 @@ enum GameState {
 
 struct Game {
     grid_size: i32,
+    step_seconds: f64,
+    wrap: bool,
+    obstacle_count: u32,
+    obstacles: Vec<Position>,
     snake: Snake,
     food: Position,
     score: u32,
@@ -116,10 +226,15 @@ struct Game {
 }
 
 impl Game {
-    fn new(grid_size: i32) -> Self {
+    fn new(config: GameConfig) -> Self {
+        let grid_size = config.grid_size;
         let start_pos = Position::new(grid_size / 2, grid_size / 2);
         let mut game = Self {
             grid_size,
+            step_seconds: config.step_seconds,
+            wrap: config.wrap,
+            obstacle_count: config.obstacles,
+            obstacles: Vec::new(),
             snake: Snake::new(start_pos),
             food: Position::new(0, 0),
             score: 0,
@@ -137,24 +252,56 @@ impl Game {
                 rand::gen_range(0, self.grid_size),
                 rand::gen_range(0, self.grid_size),
             );
-            if !self.snake.contains(food_pos) {
+            if !self.snake.contains(food_pos) && !self.obstacles.contains(&food_pos) {
                 self.food = food_pos;
                 break;
             }
         }
     }
 
+    /// Scatter `obstacle_count` obstacles on free cells, away from the snake
+    /// and the lane in front of its head.
+    fn place_obstacles(&mut self) {
+        self.obstacles.clear();
+        let max = (self.grid_size * self.grid_size / 4) as usize;
+        let count = (self.obstacle_count as usize).min(max);
+
+        let head = self.snake.head();
+        let in_lane = |pos: Position| {
+            (1..=OBSTACLE_FREE_LANE).any(|step| {
+                let mut ahead = head;
+                for _ in 0..step {
+                    ahead = ahead.move_in_direction(self.snake.direction);
+                }
+                ahead == pos
+            })
+        };
+
+        while self.obstacles.len() < count {
+            let pos = Position::new(
+                rand::gen_range(0, self.grid_size),
+                rand::gen_range(0, self.grid_size),
+            );
+            if !self.snake.contains(pos) && !in_lane(pos) && !self.obstacles.contains(&pos) {
+                self.obstacles.push(pos);
+            }
+        }
+    }
+
     fn update(&mut self, current_time: f64) {
         if self.state != GameState::Playing {
             return;
         }
 
-        if current_time - self.last_update < GAME_SPEED as f64 {
+        if current_time - self.last_update < step_interval(self.step_seconds, self.score) {
             return;
         }
 
         self.last_update = current_time;
         self.snake.advance();
+        if self.wrap {
+            self.snake.wrap_head(self.grid_size);
+        }
 
         let head = self.snake.head();
         if head.x < 0 || head.x >= self.grid_size || head.y < 0 || head.y >= self.grid_size {
@@ -162,7 +309,7 @@ impl Game {
             return;
         }
 
-        if self.snake.collides_with_self() {
+        if self.snake.collides_with_self() || self.obstacles.contains(&head) {
             self.game_over();
             return;
         }
@@ -185,6 +332,7 @@ impl Game {
         let start_pos = Position::new(self.grid_size / 2, self.grid_size / 2);
         self.snake = Snake::new(start_pos);
         self.score = 0;
+        self.place_obstacles();
         self.spawn_food();
         self.state = GameState::Playing;
         self.last_update = get_time();
@@ -252,6 +400,7 @@ impl Game {
             }
             GameState::Playing | GameState::Paused => {
                 self.draw_grid(offset_x, offset_y);
+                self.draw_obstacles(offset_x, offset_y);
                 self.draw_food(offset_x, offset_y);
                 self.draw_snake(offset_x, offset_y);
                 self.draw_hud();
@@ -262,6 +411,7 @@ impl Game {
             }
             GameState::GameOver => {
                 self.draw_grid(offset_x, offset_y);
+                self.draw_obstacles(offset_x, offset_y);
                 self.draw_food(offset_x, offset_y);
                 self.draw_snake(offset_x, offset_y);
                 self.draw_hud();
@@ -291,6 +441,15 @@ impl Game {
         draw_rectangle_lines(offset_x, offset_y, grid_width, grid_height, 3.0, LIGHTGRAY);
     }
 
+    fn draw_obstacles(&self, offset_x: f32, offset_y: f32) {
+        for pos in &self.obstacles {
+            let px = offset_x + pos.x as f32 * CELL_SIZE;
+            let py = offset_y + pos.y as f32 * CELL_SIZE;
+            draw_rectangle(px + 1.0, py + 1.0, CELL_SIZE - 2.0, CELL_SIZE - 2.0, Color::from_rgba(110, 90, 70, 255));
+            draw_rectangle_lines(px + 1.0, py + 1.0, CELL_SIZE - 2.0, CELL_SIZE - 2.0, 2.0, Color::from_rgba(160, 135, 105, 255));
+        }
+    }
+
     fn draw_food(&self, offset_x: f32, offset_y: f32) {
         let px = offset_x + self.food.x as f32 * CELL_SIZE + CELL_SIZE / 2.0;
         let py = offset_y + self.food.y as f32 * CELL_SIZE + CELL_SIZE / 2.0;
@@ -340,6 +499,14 @@ impl Game {
         }
     }
 
+    fn mode_label(&self) -> &'static str {
+        if self.wrap {
+            "Mode: Wrap-around"
+        } else {
+            "Mode: Classic walls"
+        }
+    }
+
     fn draw_hud(&self) {
         let title = "SNAKE GAME";
         let title_size = 40.0;
@@ -352,6 +519,14 @@ impl Game {
         let length_text = format!("Length: {}", self.snake.body.len());
         draw_text(&length_text, 30.0, screen_height() - 30.0, 25.0, LIGHTGRAY);
 
+        let level_text = format!("Level: {}", level_for_score(self.score));
+        let level_width = measure_text(&level_text, None, 30, 1.0).width;
+        draw_text(&level_text, (screen_width() - level_width) / 2.0, screen_height() - 60.0, 30.0, SKYBLUE);
+
+        let mode = self.mode_label();
+        let mode_width = measure_text(mode, None, 20, 1.0).width;
+        draw_text(mode, screen_width() - mode_width - 30.0, screen_height() - 30.0, 20.0, LIGHTGRAY);
+
         if self.high_score > 0 {
             let high_score_text = format!("High Score: {}", self.high_score);
             let width = measure_text(&high_score_text, None, 30, 1.0).width;
@@ -383,6 +558,10 @@ impl Game {
             "",
         ];
 
+        let mode = self.mode_label();
+        let mode_width = measure_text(mode, None, 22, 1.0).width;
+        draw_text(mode, (screen_width() - mode_width) / 2.0, 290.0, 22.0, SKYBLUE);
+
         let mut y = 320.0;
         for instruction in &instructions {
             let width = measure_text(instruction, None, 20, 1.0).width;
@@ -467,10 +646,11 @@ impl Game {
 }
 
 fn window_conf() -> Conf {
+    let (window_width, window_height) = GameConfig::get().window_size();
     Conf {
         window_title: "Snake Game".to_owned(),
-        window_width: (GRID_SIZE as f32 * CELL_SIZE + 100.0) as i32,
-        window_height: (GRID_SIZE as f32 * CELL_SIZE + 200.0) as i32,
+        window_width,
+        window_height,
         window_resizable: false,
         ..Default::default()
     }
@@ -478,7 +658,7 @@ fn window_conf() -> Conf {
 
 #[macroquad::main(window_conf)]
 async fn main() {
-    let mut game = Game::new(GRID_SIZE);
+    let mut game = Game::new(GameConfig::get());
 
     loop {
         game.handle_input();
@@ -488,3 +668,147 @@ async fn main() {
         next_frame().await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(args: &[&str]) -> Result<GameConfig, clap::Error> {
+        let argv = std::iter::once("snake-game").chain(args.iter().copied());
+        Args::try_parse_from(argv).map(GameConfig::from)
+    }
+
+    #[test]
+    fn test_defaults() {
+        let config = config(&[]).unwrap();
+        assert_eq!(
+            config,
+            GameConfig { grid_size: 20, step_seconds: 0.15, wrap: false, obstacles: 0 }
+        );
+        assert_eq!(config.window_size(), (700, 800));
+    }
+
+    #[test]
+    fn test_args_map_to_config() {
+        let cfg = config(&["--grid-size", "40", "--speed", "0.05", "--wrap", "--obstacles", "12"])
+            .unwrap();
+        assert_eq!(
+            cfg,
+            GameConfig { grid_size: 40, step_seconds: 0.05, wrap: true, obstacles: 12 }
+        );
+        assert_eq!(cfg.window_size(), (1300, 1400));
+
+        // Small boards keep a usable window width
+        assert_eq!(config(&["--grid-size", "10"]).unwrap().window_size(), (600, 500));
+    }
+
+    #[test]
+    fn test_out_of_range_args_rejected() {
+        for args in [
+            &["--grid-size", "9"][..],
+            &["--grid-size", "61"],
+            &["--speed", "0"],
+            &["--speed", "5"],
+            &["--speed", "fast"],
+        ] {
+            assert!(config(args).is_err(), "{args:?}");
+        }
+    }
+
+    fn playing_game(wrap: bool) -> Game {
+        let mut game =
+            Game::new(GameConfig { grid_size: 10, step_seconds: 0.1, wrap, obstacles: 0 });
+        game.state = GameState::Playing;
+        game.snake = Snake::new(Position::new(9, 4));
+        game.food = Position::new(0, 0);
+        game
+    }
+
+    #[test]
+    fn test_wrap_mode_moves_head_to_opposite_edge() {
+        let mut game = playing_game(true);
+        game.update(1.0);
+        assert_eq!(game.state, GameState::Playing);
+        assert_eq!(game.snake.head(), Position::new(0, 4));
+
+        let mut game = playing_game(false);
+        game.update(1.0);
+        assert_eq!(game.state, GameState::GameOver);
+    }
+
+    #[test]
+    fn test_wrap_mode_still_ends_on_self_collision() {
+        let mut game = playing_game(true);
+        // Head at (9,4) moving right wraps onto (0,4), which the body occupies
+        game.snake.body = vec![
+            Position::new(9, 4),
+            Position::new(9, 5),
+            Position::new(0, 5),
+            Position::new(0, 4),
+            Position::new(0, 3),
+        ];
+        game.update(1.0);
+        assert_eq!(game.state, GameState::GameOver);
+    }
+
+    #[test]
+    fn test_step_interval_speeds_up_with_score() {
+        assert_eq!(level_for_score(0), 1);
+        assert_eq!(level_for_score(40), 1);
+        assert_eq!(level_for_score(50), 2);
+        assert_eq!(level_for_score(230), 5);
+
+        assert_eq!(step_interval(0.15, 0), 0.15);
+        assert_eq!(step_interval(0.15, 40), 0.15);
+        assert!((step_interval(0.15, 50) - 0.135).abs() < 1e-9);
+        assert!(step_interval(0.15, 100) < step_interval(0.15, 50));
+
+        // Floors at MIN_STEP_SECONDS, or at the base if that is already faster
+        assert_eq!(step_interval(0.15, 10_000), MIN_STEP_SECONDS);
+        assert_eq!(step_interval(0.02, 10_000), 0.02);
+    }
+
+    #[test]
+    fn test_obstacles_avoid_snake_and_food() {
+        let mut game = Game::new(GameConfig {
+            grid_size: 10,
+            step_seconds: 0.1,
+            wrap: false,
+            obstacles: 20,
+        });
+        for _ in 0..50 {
+            game.snake = Snake::new(Position::new(5, 5));
+            game.snake.body.extend([Position::new(4, 5), Position::new(3, 5)]);
+            game.place_obstacles();
+            game.spawn_food();
+
+            assert_eq!(game.obstacles.len(), 20);
+            let unique: std::collections::HashSet<_> =
+                game.obstacles.iter().map(|p| (p.x, p.y)).collect();
+            assert_eq!(unique.len(), 20);
+            for pos in &game.obstacles {
+                assert!(!game.snake.contains(*pos), "{pos:?} on snake");
+                assert!((0..10).contains(&pos.x) && (0..10).contains(&pos.y));
+                // Free lane to the right of the head at (5,5)
+                assert!(!(pos.y == 5 && (6..=8).contains(&pos.x)), "{pos:?} in lane");
+            }
+            assert!(!game.obstacles.contains(&game.food));
+        }
+
+        // Never more than a quarter of the board
+        game.obstacle_count = 1000;
+        game.place_obstacles();
+        assert_eq!(game.obstacles.len(), 25);
+    }
+
+    #[test]
+    fn test_obstacle_collision_ends_game() {
+        for wrap in [false, true] {
+            let mut game = playing_game(wrap);
+            game.snake = Snake::new(Position::new(4, 4));
+            game.obstacles = vec![Position::new(5, 4)];
+            game.update(1.0);
+            assert_eq!(game.state, GameState::GameOver, "wrap={wrap}");
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! Serialization of an execution's event log for export (CSV or JSON).
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+
+/// One event as written to an export file.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedEvent {
+    pub timestamp: Option<DateTime<Utc>>,
+    pub event_type: String,
+    pub summary: String,
+    pub data: serde_json::Value,
+}
+
+impl ExportedEvent {
+    /// Build an export row, deriving the summary from `data`.
+    pub fn new(
+        timestamp: Option<DateTime<Utc>>,
+        event_type: impl Into<String>,
+        data: serde_json::Value,
+    ) -> Self {
+        let event_type = event_type.into();
+        let summary = event_summary(&event_type, &data);
+        Self {
+            timestamp,
+            event_type,
+            summary,
+            data,
+        }
+    }
+}
+
+/// Export file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv",
+            ExportFormat::Json => "application/json",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            other => bail!("Unknown export format '{}'", other),
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.extension())
+    }
+}
+
+/// Serialize `events` in the given format.
+pub fn export_events(events: &[ExportedEvent], format: ExportFormat) -> Result<String> {
+    match format {
+        ExportFormat::Csv => Ok(events_to_csv(events)),
+        ExportFormat::Json => Ok(serde_json::to_string_pretty(events)?),
+    }
+}
+
+/// CSV with `timestamp,event_type,summary` columns (RFC 4180 quoting,
+/// CRLF line endings). Events without a timestamp get an empty cell.
+pub fn events_to_csv(events: &[ExportedEvent]) -> String {
+    let mut out = String::from("timestamp,event_type,summary\r\n");
+    for event in events {
+        let timestamp = event
+            .timestamp
+            .map(|t| t.to_rfc3339_opts(SecondsFormat::Millis, true))
+            .unwrap_or_default();
+        let row = [timestamp.as_str(), event.event_type.as_str(), event.summary.as_str()]
+            .map(csv_field)
+            .join(",");
+        out.push_str(&row);
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// Quote a field if it contains a delimiter, quote or line break.
+///
+/// Cells a spreadsheet would evaluate as a formula (leading `=`, `+`, `-`,
+/// `@`, tab or carriage return) get a `'` prefix so they open as text.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// One-line description of an event, picked from its most telling field.
+pub fn event_summary(event_type: &str, data: &serde_json::Value) -> String {
+    let text = |key: &str| data.get(key).and_then(|v| v.as_str()).unwrap_or_default();
+    let int = |key: &str| data.get(key).and_then(|v| v.as_i64()).unwrap_or_default();
+    let score = |key: &str| data.get(key).and_then(|v| v.as_f64()).unwrap_or_default();
+
+    match event_type {
+        "tool_invoked" => format!("{}: {}", text("tool_name"), text("summary")),
+        "file_changed" => format!(
+            "{} (+{}/-{})",
+            text("path"),
+            int("lines_added"),
+            int("lines_removed")
+        ),
+        "iteration_started" => format!("iteration {}", int("iteration")),
+        "iteration_completed" => {
+            format!("iteration {} (score {:.1})", int("iteration"), score("score"))
+        }
+        "test_result" => format!("{} passed, {} failed", int("passed"), int("failed")),
+        "score_updated" => format!(
+            "{:.1} -> {:.1}: {}",
+            score("old_score"),
+            score("new_score"),
+            text("reason")
+        ),
+        "state_changed" => format!("{} -> {}", text("old_state"), text("new_state")),
+        "subagent_spawned" => text("task_summary").to_string(),
+        "subagent_completed" => text("result_summary").to_string(),
+        "artifact_written" | "decision_made" => text("title").to_string(),
+        "log_message" | "error" => text("message").to_string(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    #[test]
+    fn test_csv_is_well_formed() {
+        let at = Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap();
+        let events = vec![
+            ExportedEvent::new(
+                Some(at),
+                "tool_invoked",
+                json!({"tool_name": "Bash", "summary": "echo \"a, b\""}),
+            ),
+            ExportedEvent::new(None, "log_message", json!({"message": "line one\nline two"})),
+            ExportedEvent::new(
+                Some(at),
+                "state_changed",
+                json!({"old_state": "running", "new_state": "completed"}),
+            ),
+        ];
+
+        let csv = events_to_csv(&events);
+        assert_eq!(
+            csv,
+            "timestamp,event_type,summary\r\n\
+             2024-05-01T12:30:00.000Z,tool_invoked,\"Bash: echo \"\"a, b\"\"\"\r\n\
+             ,log_message,\"line one\nline two\"\r\n\
+             2024-05-01T12:30:00.000Z,state_changed,running -> completed\r\n"
+        );
+    }
+
+    #[test]
+    fn test_csv_neutralizes_formula_cells() {
+        let events = vec![
+            ExportedEvent::new(None, "log_message", json!({"message": "=HYPERLINK(\"x\")"})),
+            ExportedEvent::new(None, "@SUM(A1)", json!({})),
+        ];
+        let csv = events_to_csv(&events);
+        let rows: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(rows[1], ",log_message,\"'=HYPERLINK(\"\"x\"\")\"");
+        assert!(rows[2].starts_with(",'@SUM(A1),"), "{csv}");
+
+        assert_eq!(csv_field("-1"), "'-1");
+        assert_eq!(csv_field("+cmd"), "'+cmd");
+        assert_eq!(csv_field("a-b"), "a-b");
+    }
+
+    #[test]
+    fn test_csv_empty_has_header() {
+        assert_eq!(events_to_csv(&[]), "timestamp,event_type,summary\r\n");
+    }
+
+    #[test]
+    fn test_json_export_round_trips() {
+        let events = vec![ExportedEvent::new(None, "error", json!({"message": "boom"}))];
+        let out = export_events(&events, ExportFormat::Json).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(parsed[0]["event_type"], "error");
+        assert_eq!(parsed[0]["summary"], "boom");
+        assert_eq!(parsed[0]["timestamp"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("CSV".parse::<ExportFormat>().unwrap(), ExportFormat::Csv);
+        assert_eq!("json".parse::<ExportFormat>().unwrap(), ExportFormat::Json);
+        assert!("xml".parse::<ExportFormat>().is_err());
+    }
+}
@@ -1,12 +1,14 @@
 //! Inventory scanner — parses agents, commands, skills, and modes from the project tree.
 
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use anyhow::{Context, Result};
 use gray_matter::engine::YAML;
 use gray_matter::Matter;
 
-use crate::types::{Inventory, InventoryItem, InventoryKind};
+use crate::types::{Inventory, InventoryError, InventoryItem, InventoryKind};
 
 /// Frontmatter from agent/trait/extension `.md` files.
 #[derive(Debug, serde::Deserialize)]
@@ -49,69 +51,281 @@ struct CommandsIndex {
 /// Skill frontmatter from `.claude/skills/*/SKILL.md`.
 #[derive(Debug, serde::Deserialize)]
 struct SkillFrontmatter {
-    name: String,
+    #[serde(default)]
+    name: Option<String>,
     #[serde(default)]
     description: String,
 }
 
 /// Scan the entire project tree and return a complete inventory.
 pub fn scan_all(project_root: &Path) -> Result<Inventory> {
-    let mut items = Vec::new();
+    InventoryScanner::new().scan(project_root, true)
+}
 
-    // Agents (core/)
-    scan_agent_dir(project_root, "agents/core", InventoryKind::Agent, &mut items)?;
+/// Parsed items (or the parse error) of one source file, valid while its
+/// mtime and size match.
+#[derive(Debug, Clone)]
+struct CachedFile {
+    modified: SystemTime,
+    len: u64,
+    result: std::result::Result<Vec<InventoryItem>, String>,
+}
 
-    // Traits (traits/)
-    scan_agent_dir(project_root, "agents/traits", InventoryKind::Trait, &mut items)?;
+/// Paths skipped by [`InventoryScanner`] unless overridden with
+/// [`InventoryScanner::with_excludes`].
+pub const DEFAULT_EXCLUDES: &[&str] = &[".git", "node_modules", "target", "vendor"];
+
+/// Inventory scanner that remembers parsed files between scans and only
+/// reparses those whose modified time or size changed.
+#[derive(Debug)]
+pub struct InventoryScanner {
+    cache: HashMap<PathBuf, CachedFile>,
+    reparsed: Vec<PathBuf>,
+    excludes: Vec<glob::Pattern>,
+}
 
-    // Extensions (extensions/)
-    scan_agent_dir(project_root, "agents/extensions", InventoryKind::Extension, &mut items)?;
+impl Default for InventoryScanner {
+    fn default() -> Self {
+        Self {
+            cache: HashMap::new(),
+            reparsed: Vec::new(),
+            excludes: DEFAULT_EXCLUDES
+                .iter()
+                .map(|p| glob::Pattern::new(p).expect("default exclude is a valid glob"))
+                .collect(),
+        }
+    }
+}
 
-    // Commands
-    scan_commands(project_root, &mut items)?;
+impl InventoryScanner {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-    // Skills
-    scan_skills(project_root, &mut items)?;
+    /// Replace the exclude list. A pattern without `/` matches any single
+    /// directory or file name (`vendor`); one with `/` matches the path
+    /// relative to the project root (`agents/extensions/*`).
+    pub fn with_excludes<I, S>(mut self, patterns: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.excludes = patterns
+            .into_iter()
+            .map(|p| {
+                glob::Pattern::new(p.as_ref())
+                    .with_context(|| format!("Invalid exclude pattern: {}", p.as_ref()))
+            })
+            .collect::<Result<_>>()?;
+        self.cache.clear();
+        Ok(self)
+    }
 
-    // Modes
-    scan_modes(project_root, &mut items)?;
+    /// Whether `path`, or any directory between it and `project_root`, is excluded.
+    fn is_excluded(&self, project_root: &Path, path: &Path) -> bool {
+        let relative = path.strip_prefix(project_root).unwrap_or(path);
+        relative
+            .ancestors()
+            .filter(|p| !p.as_os_str().is_empty())
+            .any(|prefix| self.matches_exclude(prefix))
+    }
 
-    Ok(Inventory { items })
-}
+    /// Whether a project-relative path itself matches an exclude pattern.
+    fn matches_exclude(&self, relative: &Path) -> bool {
+        let name = relative.file_name().map(Path::new);
+        self.excludes.iter().any(|pattern| {
+            let by_name =
+                !pattern.as_str().contains('/') && name.is_some_and(|n| pattern.matches_path(n));
+            by_name || pattern.matches_path(relative)
+        })
+    }
 
-/// Parse all `.md` files in a directory that have YAML frontmatter.
-fn scan_agent_dir(
-    project_root: &Path,
-    relative_dir: &str,
-    kind: InventoryKind,
-    out: &mut Vec<InventoryItem>,
-) -> Result<()> {
-    let dir = project_root.join(relative_dir);
-    if !dir.is_dir() {
-        return Ok(());
+    /// Entries of `dir` (sorted) minus excluded ones. Callers check `dir`
+    /// itself, so only each entry is matched, and nothing below an excluded
+    /// entry is ever read.
+    fn included_entries(&self, project_root: &Path, dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+        for entry in
+            std::fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?
+        {
+            let path = entry
+                .with_context(|| format!("Failed to read {}", dir.display()))?
+                .path();
+            if !self.matches_exclude(path.strip_prefix(project_root).unwrap_or(&path)) {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// Files parsed (rather than served from cache) by the last scan.
+    pub fn last_reparsed(&self) -> &[PathBuf] {
+        &self.reparsed
+    }
+
+    /// Scan the project tree, reusing cached entries for unchanged files.
+    /// `force_refresh` reparses every file. Files that fail to parse are
+    /// reported in `Inventory::errors` without affecting the others.
+    pub fn scan(&mut self, project_root: &Path, force_refresh: bool) -> Result<Inventory> {
+        self.reparsed.clear();
+        let mut seen = HashSet::new();
+        let mut inventory = Inventory::default();
+
+        // Agents, traits, and extensions
+        let kinds = [
+            ("agents/core", InventoryKind::Agent),
+            ("agents/traits", InventoryKind::Trait),
+            ("agents/extensions", InventoryKind::Extension),
+        ];
+        for (relative_dir, kind) in kinds {
+            for path in self.agent_files(project_root, relative_dir)? {
+                let result = self.cached(&path, force_refresh, &mut seen, |path| {
+                    Ok(parse_agent_md(path, &kind, project_root)?.into_iter().collect())
+                });
+                record(&mut inventory, project_root, &path, result);
+            }
+        }
+
+        // Commands
+        let index_path = project_root.join("commands/index.yaml");
+        if index_path.is_file() {
+            let result = self.cached(&index_path, force_refresh, &mut seen, parse_commands);
+            record(&mut inventory, project_root, &index_path, result);
+        }
+
+        // Skills
+        for path in self.skill_files(project_root)? {
+            let result = self.cached(&path, force_refresh, &mut seen, |path| {
+                Ok(vec![parse_skill_md(path, project_root)?])
+            });
+            record(&mut inventory, project_root, &path, result);
+        }
+
+        // Modes
+        let config_path = project_root.join("config/superclaud.yaml");
+        if config_path.is_file() {
+            let result = self.cached(&config_path, force_refresh, &mut seen, parse_modes);
+            record(&mut inventory, project_root, &config_path, result);
+        }
+
+        // Forget files that were deleted since the last scan
+        self.cache.retain(|path, _| seen.contains(path));
+
+        Ok(inventory)
+    }
+
+    /// All `.md` files in an agent directory, minus excluded paths.
+    fn agent_files(&self, project_root: &Path, relative_dir: &str) -> Result<Vec<PathBuf>> {
+        let dir = project_root.join(relative_dir);
+        if !dir.is_dir() || self.is_excluded(project_root, &dir) {
+            return Ok(Vec::new());
+        }
+
+        Ok(self
+            .included_entries(project_root, &dir)?
+            .into_iter()
+            .filter(|path| path.extension().is_some_and(|ext| ext == "md") && path.is_file())
+            .collect())
     }
 
-    let pattern = format!("{}/*.md", dir.display());
-    for entry in glob::glob(&pattern).context("glob pattern error")? {
-        let path = entry.context("glob entry error")?;
-        if let Some(item) = parse_agent_md(&path, &kind, project_root) {
-            out.push(item);
+    /// `.claude/skills/*/SKILL.md` (skipping DEPRECATED/ and excluded paths).
+    fn skill_files(&self, project_root: &Path) -> Result<Vec<PathBuf>> {
+        let skills_dir = project_root.join(".claude/skills");
+        if !skills_dir.is_dir() || self.is_excluded(project_root, &skills_dir) {
+            return Ok(Vec::new());
         }
+
+        let mut files = Vec::new();
+        for skill_dir in self.included_entries(project_root, &skills_dir)? {
+            // Skip DEPRECATED
+            if !skill_dir.is_dir() || skill_dir.file_name().is_some_and(|n| n == "DEPRECATED") {
+                continue;
+            }
+            let path = skill_dir.join("SKILL.md");
+            if path.is_file()
+                && !self.matches_exclude(path.strip_prefix(project_root).unwrap_or(&path))
+            {
+                files.push(path);
+            }
+        }
+
+        Ok(files)
     }
 
-    Ok(())
+    fn cached(
+        &mut self,
+        path: &Path,
+        force_refresh: bool,
+        seen: &mut HashSet<PathBuf>,
+        parse: impl FnOnce(&Path) -> Result<Vec<InventoryItem>>,
+    ) -> std::result::Result<Vec<InventoryItem>, String> {
+        seen.insert(path.to_path_buf());
+        let metadata = std::fs::metadata(path).ok();
+        let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+        let len = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
+
+        if !force_refresh {
+            if let (Some(cached), Some(modified)) = (self.cache.get(path), modified) {
+                if cached.modified == modified && cached.len == len {
+                    return cached.result.clone();
+                }
+            }
+        }
+
+        let result = parse(path).map_err(|e| format!("{:#}", e));
+        self.reparsed.push(path.to_path_buf());
+        match modified {
+            Some(modified) => {
+                self.cache.insert(
+                    path.to_path_buf(),
+                    CachedFile { modified, len, result: result.clone() },
+                );
+            }
+            None => {
+                self.cache.remove(path);
+            }
+        }
+        result
+    }
 }
 
+/// Add a file's items to the inventory, or its parse error.
+fn record(
+    inventory: &mut Inventory,
+    project_root: &Path,
+    path: &Path,
+    result: std::result::Result<Vec<InventoryItem>, String>,
+) {
+    match result {
+        Ok(items) => inventory.items.extend(items),
+        Err(message) => inventory.errors.push(InventoryError {
+            path: path
+                .strip_prefix(project_root)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .to_string(),
+            message,
+        }),
+    }
+}
+
+/// Parse an agent definition. Markdown files without a frontmatter block
+/// (e.g. READMEs) are not agents and yield `None`.
 fn parse_agent_md(
     path: &Path,
     kind: &InventoryKind,
     project_root: &Path,
-) -> Option<InventoryItem> {
-    let content = std::fs::read_to_string(path).ok()?;
+) -> Result<Option<InventoryItem>> {
+    let content = std::fs::read_to_string(path).context("Failed to read file")?;
     let matter = Matter::<YAML>::new();
     let parsed = matter.parse(&content);
+    if parsed.matter.trim().is_empty() {
+        return Ok(None);
+    }
 
-    let fm: AgentFrontmatter = parsed.data?.deserialize().ok()?;
+    let fm: AgentFrontmatter =
+        serde_yaml::from_str(&parsed.matter).context("Invalid YAML frontmatter")?;
 
     let source_file = path
         .strip_prefix(project_root)
@@ -119,7 +333,7 @@ fn parse_agent_md(
         .to_string_lossy()
         .to_string();
 
-    Some(InventoryItem {
+    Ok(Some(InventoryItem {
         name: fm.name,
         kind: kind.clone(),
         description: fm.description,
@@ -129,23 +343,20 @@ fn parse_agent_md(
         aliases: Vec::new(),
         flags: Vec::new(),
         source_file,
-    })
+    }))
 }
 
 /// Parse `commands/index.yaml`.
-fn scan_commands(project_root: &Path, out: &mut Vec<InventoryItem>) -> Result<()> {
-    let index_path = project_root.join("commands/index.yaml");
-    if !index_path.is_file() {
-        return Ok(());
-    }
-
-    let content = std::fs::read_to_string(&index_path)
+fn parse_commands(index_path: &Path) -> Result<Vec<InventoryItem>> {
+    let content = std::fs::read_to_string(index_path)
         .context("Failed to read commands/index.yaml")?;
     let index: CommandsIndex =
         serde_yaml::from_str(&content).context("Failed to parse commands/index.yaml")?;
 
-    for cmd in index.commands {
-        out.push(InventoryItem {
+    Ok(index
+        .commands
+        .into_iter()
+        .map(|cmd| InventoryItem {
             name: cmd.name,
             kind: InventoryKind::Command,
             description: cmd.description,
@@ -155,86 +366,59 @@ fn scan_commands(project_root: &Path, out: &mut Vec<InventoryItem>) -> Result<()
             aliases: cmd.aliases,
             flags: cmd.flags,
             source_file: format!("commands/{}", cmd.file),
-        });
-    }
-
-    Ok(())
+        })
+        .collect())
 }
 
-/// Scan `.claude/skills/*/SKILL.md` (skipping DEPRECATED/).
-fn scan_skills(project_root: &Path, out: &mut Vec<InventoryItem>) -> Result<()> {
-    let skills_dir = project_root.join(".claude/skills");
-    if !skills_dir.is_dir() {
-        return Ok(());
-    }
+/// Parse a skill. Frontmatter is optional; the directory name stands in
+/// for a missing `name`.
+fn parse_skill_md(path: &Path, project_root: &Path) -> Result<InventoryItem> {
+    let content = std::fs::read_to_string(path).context("Failed to read file")?;
 
-    let pattern = format!("{}/*/SKILL.md", skills_dir.display());
     let matter = Matter::<YAML>::new();
+    let parsed = matter.parse(&content);
+    let fm: Option<SkillFrontmatter> = if parsed.matter.trim().is_empty() {
+        None
+    } else {
+        Some(serde_yaml::from_str(&parsed.matter).context("Invalid YAML frontmatter")?)
+    };
 
-    for entry in glob::glob(&pattern).context("glob pattern error")? {
-        let path = entry.context("glob entry error")?;
+    let dir_name = path
+        .parent()
+        .and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
 
-        // Skip DEPRECATED
-        if path
-            .parent()
-            .and_then(|p| p.file_name())
-            .map(|n| n == "DEPRECATED")
-            .unwrap_or(false)
-        {
-            continue;
-        }
+    let name = fm.as_ref().and_then(|f| f.name.clone()).unwrap_or_else(|| dir_name.clone());
+    let description = fm.map(|f| f.description).unwrap_or_default();
 
-        let content = match std::fs::read_to_string(&path) {
-            Ok(c) => c,
-            Err(_) => continue,
-        };
-
-        let parsed = matter.parse(&content);
-        let fm: Option<SkillFrontmatter> = parsed.data.and_then(|d| d.deserialize().ok());
-
-        let dir_name = path
-            .parent()
-            .and_then(|p| p.file_name())
-            .map(|n| n.to_string_lossy().to_string())
-            .unwrap_or_default();
-
-        let name = fm.as_ref().map(|f| f.name.clone()).unwrap_or_else(|| dir_name.clone());
-        let description = fm.map(|f| f.description).unwrap_or_default();
-
-        let source_file = path
-            .strip_prefix(project_root)
-            .unwrap_or(&path)
-            .to_string_lossy()
-            .to_string();
-
-        out.push(InventoryItem {
-            name,
-            kind: InventoryKind::Skill,
-            description,
-            category: "skill".to_string(),
-            triggers: Vec::new(),
-            tools: Vec::new(),
-            aliases: Vec::new(),
-            flags: Vec::new(),
-            source_file,
-        });
-    }
+    let source_file = path
+        .strip_prefix(project_root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string();
 
-    Ok(())
+    Ok(InventoryItem {
+        name,
+        kind: InventoryKind::Skill,
+        description,
+        category: "skill".to_string(),
+        triggers: Vec::new(),
+        tools: Vec::new(),
+        aliases: Vec::new(),
+        flags: Vec::new(),
+        source_file,
+    })
 }
 
 /// Extract modes from `config/superclaud.yaml`.
-fn scan_modes(project_root: &Path, out: &mut Vec<InventoryItem>) -> Result<()> {
-    let config_path = project_root.join("config/superclaud.yaml");
-    if !config_path.is_file() {
-        return Ok(());
-    }
-
-    let content = std::fs::read_to_string(&config_path)
+fn parse_modes(config_path: &Path) -> Result<Vec<InventoryItem>> {
+    let content = std::fs::read_to_string(config_path)
         .context("Failed to read config/superclaud.yaml")?;
     let value: serde_yaml::Value =
         serde_yaml::from_str(&content).context("Failed to parse config/superclaud.yaml")?;
 
+    let mut out = Vec::new();
     if let Some(modes) = value.get("modes").and_then(|m| m.get("available")) {
         if let Some(seq) = modes.as_sequence() {
             for mode in seq {
@@ -255,7 +439,7 @@ fn scan_modes(project_root: &Path, out: &mut Vec<InventoryItem>) -> Result<()> {
         }
     }
 
-    Ok(())
+    Ok(out)
 }
 
 #[cfg(test)]
@@ -389,4 +573,88 @@ modes:
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].name, "analyze");
     }
+
+    #[test]
+    fn test_incremental_scan_reparses_only_changed_files() {
+        let tmp = setup_test_project();
+        let mut scanner = InventoryScanner::new();
+
+        let first = scanner.scan(tmp.path(), false).unwrap();
+        assert_eq!(scanner.last_reparsed().len(), 5);
+
+        let unchanged = scanner.scan(tmp.path(), false).unwrap();
+        assert!(scanner.last_reparsed().is_empty());
+        assert_eq!(unchanged.items.len(), first.items.len());
+
+        // Touch one file
+        let agent = tmp.path().join("agents/core/test-agent.md");
+        let later = fs::metadata(&agent).unwrap().modified().unwrap()
+            + std::time::Duration::from_secs(10);
+        fs::File::options().write(true).open(&agent).unwrap().set_modified(later).unwrap();
+
+        let rescanned = scanner.scan(tmp.path(), false).unwrap();
+        assert_eq!(scanner.last_reparsed(), [agent]);
+        assert_eq!(rescanned.agents()[0].name, "test-agent");
+        assert_eq!(rescanned.items.len(), first.items.len());
+
+        scanner.scan(tmp.path(), true).unwrap();
+        assert_eq!(scanner.last_reparsed().len(), 5);
+    }
+
+    #[test]
+    fn test_excluded_directories_are_skipped() {
+        let tmp = setup_test_project();
+        let root = tmp.path();
+        for dir in [".claude/skills/vendor", ".claude/skills/node_modules"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+            fs::write(root.join(dir).join("SKILL.md"), "---\nname: ignored\n---\n").unwrap();
+        }
+
+        let mut scanner = InventoryScanner::new();
+        let inventory = scanner.scan(root, false).unwrap();
+        assert_eq!(inventory.skills().len(), 1);
+        assert!(scanner.last_reparsed().iter().all(|p| {
+            let p = p.to_string_lossy();
+            !p.contains("vendor") && !p.contains("node_modules")
+        }));
+
+        let mut scanner = InventoryScanner::new().with_excludes(["agents/traits"]).unwrap();
+        let inventory = scanner.scan(root, false).unwrap();
+        assert!(inventory.traits().is_empty());
+        assert_eq!(inventory.agents().len(), 1);
+        // Overriding the defaults brings the vendored skills back
+        assert_eq!(inventory.skills().len(), 3);
+
+        // Path patterns prune matching skill directories
+        let mut scanner = InventoryScanner::new()
+            .with_excludes([".claude/skills/v*", ".claude/skills/node_modules"])
+            .unwrap();
+        assert_eq!(scanner.scan(root, false).unwrap().skills().len(), 1);
+
+        assert!(InventoryScanner::new().with_excludes(["[unclosed"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors_reported_alongside_entries() {
+        let tmp = setup_test_project();
+        fs::write(
+            tmp.path().join("agents/core/broken.md"),
+            "---\nname: broken\ntriggers: [unclosed\n---\n\n# Broken\n",
+        )
+        .unwrap();
+        fs::write(tmp.path().join("agents/core/README.md"), "# Agents\n").unwrap();
+
+        let mut scanner = InventoryScanner::new();
+        let inventory = scanner.scan(tmp.path(), false).unwrap();
+        assert_eq!(inventory.agents().len(), 1);
+        assert_eq!(inventory.agents()[0].name, "test-agent");
+        assert_eq!(inventory.errors.len(), 1);
+        assert_eq!(inventory.errors[0].path, "agents/core/broken.md");
+        assert!(inventory.errors[0].message.contains("Invalid YAML frontmatter"));
+
+        // Served from cache, the error is still reported
+        let cached = scanner.scan(tmp.path(), false).unwrap();
+        assert!(scanner.last_reparsed().is_empty());
+        assert_eq!(cached.errors, inventory.errors);
+    }
 }
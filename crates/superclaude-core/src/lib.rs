@@ -2,6 +2,7 @@
 
 #[cfg(feature = "glob")]
 pub mod config;
+pub mod export;
 #[cfg(feature = "glob")]
 pub mod inventory;
 #[cfg(feature = "glob")]
@@ -1,33 +1,99 @@
 //! Reader for historical metrics from `.superclaude_metrics/` JSONL files.
+//!
+//! Besides the current `events.jsonl` / `metrics.jsonl`, rotated siblings
+//! such as `events.jsonl.1`, `events-2024-01.jsonl` and gzipped
+//! `events.jsonl.2.gz` are read too, oldest first.
 
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 use anyhow::{Context, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use flate2::read::MultiGzDecoder;
+use serde::{Deserialize, Serialize};
 
 use crate::types::MetricEvent;
 
-/// Read all events from `.superclaude_metrics/events.jsonl`.
+/// Read all events from `.superclaude_metrics/events.jsonl` and its rotations.
 pub fn read_events(project_root: &Path) -> Result<Vec<MetricEvent>> {
-    let events_file = project_root.join(".superclaude_metrics/events.jsonl");
-    if !events_file.exists() {
-        return Ok(Vec::new());
-    }
+    read_rotated(&project_root.join(".superclaude_metrics"), "events")
+}
+
+/// Read all metrics from `.superclaude_metrics/metrics.jsonl` and its rotations.
+pub fn read_metrics(project_root: &Path) -> Result<Vec<MetricEvent>> {
+    read_rotated(&project_root.join(".superclaude_metrics"), "metrics")
+}
 
-    let file = File::open(&events_file)
-        .context(format!("Failed to open {}", events_file.display()))?;
-    let reader = BufReader::new(file);
+/// Files named `{stem}*.jsonl*` in `dir`, oldest modification time first.
+/// The current `{stem}.jsonl` sorts last on ties since it is still being
+/// appended to.
+fn rotated_files(dir: &Path, stem: &str) -> Result<Vec<PathBuf>> {
+    let pattern = dir.join(format!("{}*.jsonl*", glob::Pattern::escape(stem)));
+    let current = dir.join(format!("{}.jsonl", stem));
 
-    let mut events = Vec::new();
-    for line in reader.lines() {
-        let line = line?;
-        if line.trim().is_empty() {
+    let mut files: Vec<(SystemTime, bool, PathBuf)> = Vec::new();
+    for path in glob::glob(&pattern.to_string_lossy())?.flatten() {
+        // `events-2024-01.jsonl` is a rotation, `events_summary.jsonl` is not
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        let suffix = &name[stem.len()..];
+        if !path.is_file() || !(suffix.starts_with('.') || suffix.starts_with('-')) {
             continue;
         }
-        match serde_json::from_str::<MetricEvent>(&line) {
-            Ok(event) => events.push(event),
-            Err(_) => {
-                // Skip malformed lines
+        let modified = path
+            .metadata()
+            .and_then(|m| m.modified())
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        files.push((modified, path == current, path));
+    }
+
+    files.sort();
+    Ok(files.into_iter().map(|(_, _, path)| path).collect())
+}
+
+/// Identity used to drop events repeated across rotations: the event's
+/// own `event_id`/`id`/`sequence` when present. Events without one are
+/// never treated as duplicates.
+fn dedup_key(event: &MetricEvent) -> Option<String> {
+    ["event_id", "id", "sequence"].iter().find_map(|field| {
+        let value = event.data.get(field).filter(|v| !v.is_null())?;
+        Some(format!("{}:{}:{}", event.execution_id, field, value))
+    })
+}
+
+fn open_jsonl(path: &Path) -> Result<Box<dyn BufRead>> {
+    let file = File::open(path).context(format!("Failed to open {}", path.display()))?;
+    let reader: Box<dyn Read> = if path.extension().is_some_and(|e| e == "gz") {
+        Box::new(MultiGzDecoder::new(file))
+    } else {
+        Box::new(file)
+    };
+    Ok(Box::new(BufReader::new(reader)))
+}
+
+fn read_rotated(dir: &Path, stem: &str) -> Result<Vec<MetricEvent>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut seen = HashSet::new();
+    let mut events = Vec::new();
+    for path in rotated_files(dir, stem)? {
+        for line in open_jsonl(&path)?.lines() {
+            let line = line.context(format!("Failed to read {}", path.display()))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<MetricEvent>(&line) {
+                Ok(event) => {
+                    if dedup_key(&event).is_none_or(|key| seen.insert(key)) {
+                        events.push(event);
+                    }
+                }
+                Err(_) => {
+                    // Skip malformed lines
+                }
             }
         }
     }
@@ -35,32 +101,93 @@ pub fn read_events(project_root: &Path) -> Result<Vec<MetricEvent>> {
     Ok(events)
 }
 
-/// Read all metrics from `.superclaude_metrics/metrics.jsonl`.
-pub fn read_metrics(project_root: &Path) -> Result<Vec<MetricEvent>> {
-    let metrics_file = project_root.join(".superclaude_metrics/metrics.jsonl");
-    if !metrics_file.exists() {
-        return Ok(Vec::new());
+/// Where a [`MetricsCursor`] stopped reading one file.
+#[derive(Debug, Clone, Copy, Default)]
+struct FileOffset {
+    offset: u64,
+    /// Inode of the file the offset belongs to, so a rotated-in file with
+    /// the same name starts over from the beginning.
+    inode: Option<u64>,
+}
+
+/// Incremental reader remembering how far each JSONL file has been read.
+///
+/// Each call returns only the events appended since the previous call.
+/// If a file shrinks or is replaced (rotation), it is re-read from the
+/// start. A trailing line without a newline is left for the next call, as
+/// the writer may still be appending to it.
+#[derive(Debug, Default)]
+pub struct MetricsCursor {
+    offsets: HashMap<PathBuf, FileOffset>,
+}
+
+#[cfg(unix)]
+fn inode(metadata: &std::fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.ino())
+}
+
+#[cfg(not(unix))]
+fn inode(_metadata: &std::fs::Metadata) -> Option<u64> {
+    None
+}
+
+impl MetricsCursor {
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    let file = File::open(&metrics_file)
-        .context(format!("Failed to open {}", metrics_file.display()))?;
-    let reader = BufReader::new(file);
+    /// New events from `.superclaude_metrics/events.jsonl`.
+    pub fn read_new_events(&mut self, project_root: &Path) -> Result<Vec<MetricEvent>> {
+        self.read_new(&project_root.join(".superclaude_metrics/events.jsonl"))
+    }
 
-    let mut metrics = Vec::new();
-    for line in reader.lines() {
-        let line = line?;
-        if line.trim().is_empty() {
-            continue;
+    /// New metrics from `.superclaude_metrics/metrics.jsonl`.
+    pub fn read_new_metrics(&mut self, project_root: &Path) -> Result<Vec<MetricEvent>> {
+        self.read_new(&project_root.join(".superclaude_metrics/metrics.jsonl"))
+    }
+
+    /// Events appended to `path` since the last call for that path.
+    pub fn read_new(&mut self, path: &Path) -> Result<Vec<MetricEvent>> {
+        if !path.exists() {
+            self.offsets.remove(path);
+            return Ok(Vec::new());
+        }
+
+        let mut file = File::open(path).context(format!("Failed to open {}", path.display()))?;
+        let metadata = file.metadata()?;
+        let inode = inode(&metadata);
+        let mut start = self.offsets.get(path).copied().unwrap_or_default();
+        if metadata.len() < start.offset || start.inode != inode {
+            start = FileOffset { offset: 0, inode };
         }
-        match serde_json::from_str::<MetricEvent>(&line) {
-            Ok(event) => metrics.push(event),
-            Err(_) => {
-                // Skip malformed lines
+        file.seek(SeekFrom::Start(start.offset))?;
+
+        let mut reader = BufReader::new(file);
+        let mut offset = start.offset;
+        let mut events = Vec::new();
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = reader
+                .read_line(&mut line)
+                .context(format!("Failed to read {}", path.display()))?;
+            if read == 0 || !line.ends_with('\n') {
+                break;
+            }
+            offset += read as u64;
+            if line.trim().is_empty() {
+                continue;
+            }
+            // Skip malformed lines
+            if let Ok(event) = serde_json::from_str::<MetricEvent>(&line) {
+                events.push(event);
             }
         }
-    }
 
-    Ok(metrics)
+        self.offsets.insert(path.to_path_buf(), FileOffset { offset, inode });
+        Ok(events)
+    }
 }
 
 /// Read events for a specific execution ID.
@@ -72,6 +199,143 @@ pub fn read_events_for_execution(project_root: &Path, execution_id: &str) -> Res
         .collect())
 }
 
+/// Totals for the events whose timestamp falls in `[start, start + bucket)`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricsBucket {
+    pub start: DateTime<Utc>,
+    pub event_count: u32,
+    /// Distinct execution ids seen in the bucket
+    pub executions: u32,
+    pub cost_usd: f64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Number of `file_change` events
+    pub file_changes: u32,
+    pub tests_passed: u32,
+    pub tests_failed: u32,
+    /// `tests_passed / (tests_passed + tests_failed)`, `None` without test results
+    pub test_pass_rate: Option<f64>,
+}
+
+impl MetricsBucket {
+    fn empty(start: DateTime<Utc>) -> Self {
+        Self {
+            start,
+            event_count: 0,
+            executions: 0,
+            cost_usd: 0.0,
+            input_tokens: 0,
+            output_tokens: 0,
+            file_changes: 0,
+            tests_passed: 0,
+            tests_failed: 0,
+            test_pass_rate: None,
+        }
+    }
+}
+
+/// Numeric field of an event, also looked up under a nested `usage` object
+/// the way claude result messages report tokens.
+fn number(event: &MetricEvent, keys: &[&str]) -> Option<f64> {
+    keys.iter().find_map(|key| {
+        event
+            .data
+            .get(key)
+            .or_else(|| event.data.get("usage").and_then(|u| u.get(key)))
+            .and_then(|v| v.as_f64())
+    })
+}
+
+/// Most buckets [`aggregate`] returns; wider spans get wider buckets.
+pub const MAX_BUCKETS: i64 = 1000;
+
+/// Bucket `events` by timestamp into windows of `bucket`, aligned to the
+/// Unix epoch. Buckets are sorted ascending, with empty buckets filling the
+/// gaps between the first and last event. If that would take more than
+/// [`MAX_BUCKETS`], the width is raised to the smallest multiple of
+/// `bucket` that fits. Events without a parseable RFC 3339 timestamp are
+/// skipped.
+///
+/// Both the metrics hooks' event names and the daemon's (`file_changed`,
+/// `iteration_completed`) are understood.
+pub fn aggregate(events: &[MetricEvent], bucket: Duration) -> Vec<MetricsBucket> {
+    let requested = bucket.as_secs().min(i64::MAX as u64) as i64;
+    if requested == 0 {
+        return Vec::new();
+    }
+
+    let timed: Vec<(i64, &MetricEvent)> = events
+        .iter()
+        .filter_map(|event| {
+            let timestamp = DateTime::parse_from_rfc3339(event.timestamp.as_deref()?).ok()?;
+            Some((timestamp.timestamp(), event))
+        })
+        .collect();
+    let (Some(min), Some(max)) = (
+        timed.iter().map(|(t, _)| *t).min(),
+        timed.iter().map(|(t, _)| *t).max(),
+    ) else {
+        return Vec::new();
+    };
+    // Realigning to a wider grid can add one bucket, so aim one below the cap
+    let span_buckets = (max.div_euclid(requested) - min.div_euclid(requested)) as i128 + 1;
+    let factor = if span_buckets > MAX_BUCKETS as i128 {
+        (span_buckets + MAX_BUCKETS as i128 - 2) / (MAX_BUCKETS as i128 - 1)
+    } else {
+        1
+    };
+    let width = requested.saturating_mul(factor as i64);
+
+    let mut buckets: BTreeMap<i64, (MetricsBucket, HashSet<&str>)> = BTreeMap::new();
+    for (timestamp, event) in timed {
+        let start = timestamp.div_euclid(width) * width;
+        let (totals, executions) = buckets.entry(start).or_insert_with(|| {
+            let start = Utc.timestamp_opt(start, 0).unwrap();
+            (MetricsBucket::empty(start), HashSet::new())
+        });
+
+        totals.event_count += 1;
+        if !event.execution_id.is_empty() {
+            executions.insert(&event.execution_id);
+        }
+        totals.cost_usd += number(event, &["cost_usd", "total_cost_usd"]).unwrap_or(0.0);
+        totals.input_tokens += number(event, &["input_tokens"]).unwrap_or(0.0) as u64;
+        totals.output_tokens += number(event, &["output_tokens"]).unwrap_or(0.0) as u64;
+        match event.event_type.as_str() {
+            "file_change" => totals.file_changes += 1,
+            // The daemon also reports reads as file events
+            "file_changed"
+                if event.data.get("action").and_then(|a| a.as_str()) != Some("FILE_ACTION_READ") =>
+            {
+                totals.file_changes += 1
+            }
+            "test_result" => {
+                totals.tests_passed += number(event, &["passed"]).unwrap_or(0.0) as u32;
+                totals.tests_failed += number(event, &["failed"]).unwrap_or(0.0) as u32;
+            }
+            _ => {}
+        }
+    }
+
+    let (Some(&first), Some(&last)) = (buckets.keys().next(), buckets.keys().next_back()) else {
+        return Vec::new();
+    };
+    (first..=last)
+        .step_by(width as usize)
+        .map(|start| match buckets.remove(&start) {
+            Some((mut totals, executions)) => {
+                totals.executions = executions.len() as u32;
+                let tests = totals.tests_passed + totals.tests_failed;
+                if tests > 0 {
+                    totals.test_pass_rate = Some(totals.tests_passed as f64 / tests as f64);
+                }
+                totals
+            }
+            None => MetricsBucket::empty(Utc.timestamp_opt(start, 0).unwrap()),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -130,4 +394,176 @@ mod tests {
         assert_eq!(events[0].event_type, "test");
         assert_eq!(events[1].event_type, "test3");
     }
+
+    #[test]
+    fn test_read_rotated_events() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+        use std::time::{Duration, SystemTime};
+
+        let tmp = TempDir::new().unwrap();
+        let metrics_dir = tmp.path().join(".superclaude_metrics");
+        fs::create_dir(&metrics_dir).unwrap();
+
+        let oldest = metrics_dir.join("events-2024-01.jsonl.gz");
+        let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+        gz.write_all(
+            br#"{"event_type":"a","execution_id":"exec-1","event_id":"e1"}
+{"event_type":"b","execution_id":"exec-1","event_id":"e2"}
+"#,
+        )
+        .unwrap();
+        fs::write(&oldest, gz.finish().unwrap()).unwrap();
+
+        // Overlaps the previous rotation by one event
+        let middle = metrics_dir.join("events.jsonl.1");
+        fs::write(
+            &middle,
+            r#"{"event_type":"b","execution_id":"exec-1","event_id":"e2"}
+{"event_type":"c","execution_id":"exec-1","event_id":"e3"}
+"#,
+        )
+        .unwrap();
+
+        let current = metrics_dir.join("events.jsonl");
+        fs::write(
+            &current,
+            r#"{"event_type":"d","execution_id":"exec-1","event_id":"e4"}
+{"event_type":"d","execution_id":"exec-2"}
+{"event_type":"d","execution_id":"exec-2"}
+"#,
+        )
+        .unwrap();
+
+        // Name order differs from age order, so sorting must use mtime
+        let base = SystemTime::now() - Duration::from_secs(3600);
+        for (i, path) in [&oldest, &middle, &current].iter().enumerate() {
+            File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(base + Duration::from_secs(60 * i as u64))
+                .unwrap();
+        }
+        fs::write(metrics_dir.join("metrics.jsonl"), "").unwrap();
+
+        let events = read_events(tmp.path()).unwrap();
+        let types: Vec<_> = events.iter().map(|e| e.event_type.as_str()).collect();
+        assert_eq!(types, vec!["a", "b", "c", "d", "d", "d"]);
+        assert_eq!(read_events_for_execution(tmp.path(), "exec-2").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_cursor_returns_only_appended_events() {
+        use std::io::Write;
+
+        let tmp = TempDir::new().unwrap();
+        let metrics_dir = tmp.path().join(".superclaude_metrics");
+        fs::create_dir(&metrics_dir).unwrap();
+        let events_file = metrics_dir.join("events.jsonl");
+        fs::write(
+            &events_file,
+            r#"{"event_type":"first","execution_id":"exec-1"}
+{"event_type":"second","execution_id":"exec-1"}
+"#,
+        )
+        .unwrap();
+
+        let mut cursor = MetricsCursor::new();
+        assert_eq!(cursor.read_new_events(tmp.path()).unwrap().len(), 2);
+        assert!(cursor.read_new_events(tmp.path()).unwrap().is_empty());
+
+        // The unterminated line is only returned once it is complete
+        let mut file = File::options().append(true).open(&events_file).unwrap();
+        writeln!(file, r#"{{"event_type":"third","execution_id":"exec-1"}}"#).unwrap();
+        write!(file, r#"{{"event_type":"fou"#).unwrap();
+        let events = cursor.read_new_events(tmp.path()).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, "third");
+
+        writeln!(file, r#"rth","execution_id":"exec-1"}}"#).unwrap();
+        let events = cursor.read_new_events(tmp.path()).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, "fourth");
+
+        // Truncation starts over from the beginning
+        fs::write(&events_file, "{\"event_type\":\"fresh\"}\n").unwrap();
+        let events = cursor.read_new_events(tmp.path()).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, "fresh");
+    }
+
+    #[test]
+    fn test_aggregate_hourly_buckets() {
+        let event = |json: &str| serde_json::from_str::<MetricEvent>(json).unwrap();
+        let events = vec![
+            event(r#"{"event_type":"file_change","timestamp":"2024-05-01T10:05:00Z","execution_id":"exec-1","path":"a.rs"}"#),
+            event(r#"{"event_type":"result","timestamp":"2024-05-01T10:30:00+00:00","execution_id":"exec-1","total_cost_usd":0.25,"usage":{"input_tokens":100,"output_tokens":40}}"#),
+            event(r#"{"event_type":"test_result","timestamp":"2024-05-01T10:45:00Z","execution_id":"exec-2","passed":3,"failed":1}"#),
+            event(r#"{"event_type":"result","timestamp":"2024-05-01T12:10:00Z","execution_id":"exec-3","cost_usd":0.5,"input_tokens":10,"output_tokens":5}"#),
+            event(r#"{"event_type":"file_change","execution_id":"exec-3"}"#),
+        ];
+
+        let buckets = aggregate(&events, Duration::from_secs(3600));
+        assert_eq!(buckets.len(), 3);
+
+        let first = &buckets[0];
+        assert_eq!(first.start.to_rfc3339(), "2024-05-01T10:00:00+00:00");
+        assert_eq!(first.event_count, 3);
+        assert_eq!(first.executions, 2);
+        assert_eq!(first.cost_usd, 0.25);
+        assert_eq!((first.input_tokens, first.output_tokens), (100, 40));
+        assert_eq!(first.file_changes, 1);
+        assert_eq!(first.test_pass_rate, Some(0.75));
+
+        // Gap hour is zero-filled
+        assert_eq!(buckets[1], MetricsBucket::empty(first.start + chrono::Duration::hours(1)));
+
+        let last = &buckets[2];
+        assert_eq!(last.executions, 1);
+        assert_eq!(last.cost_usd, 0.5);
+        assert_eq!((last.input_tokens, last.output_tokens), (10, 5));
+        assert_eq!(last.test_pass_rate, None);
+    }
+
+    #[test]
+    fn test_aggregate_daemon_events() {
+        let event = |json: &str| serde_json::from_str::<MetricEvent>(json).unwrap();
+        let events = vec![
+            event(r#"{"execution_id":"e1","event_type":"file_changed","timestamp":"2024-05-01T10:01:00+00:00","path":"a.rs","action":"FILE_ACTION_EDIT"}"#),
+            event(r#"{"execution_id":"e1","event_type":"file_changed","timestamp":"2024-05-01T10:01:30+00:00","path":"b.rs","action":"FILE_ACTION_READ"}"#),
+            event(r#"{"execution_id":"e1","event_type":"test_result","timestamp":"2024-05-01T10:02:00+00:00","framework":"cargo","passed":4,"failed":0}"#),
+            event(r#"{"execution_id":"e1","event_type":"iteration_completed","timestamp":"2024-05-01T10:03:00+00:00","total_cost_usd":0.3,"input_tokens":50,"output_tokens":20}"#),
+        ];
+
+        let buckets = aggregate(&events, Duration::from_secs(3600));
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets[0].file_changes, 1);
+        assert_eq!(buckets[0].tests_passed, 4);
+        assert_eq!(buckets[0].cost_usd, 0.3);
+        assert_eq!((buckets[0].input_tokens, buckets[0].output_tokens), (50, 20));
+    }
+
+    #[test]
+    fn test_aggregate_caps_bucket_count() {
+        let event = |timestamp: &str| {
+            serde_json::from_str::<MetricEvent>(&format!(
+                r#"{{"event_type":"result","timestamp":"{timestamp}","cost_usd":1.0}}"#
+            ))
+            .unwrap()
+        };
+        // Ten years at one-minute buckets would be ~5.3M buckets
+        let events = vec![event("2014-01-01T00:00:00Z"), event("2024-01-01T00:00:00Z")];
+
+        let buckets = aggregate(&events, Duration::from_secs(60));
+        assert!(buckets.len() as i64 <= MAX_BUCKETS, "{} buckets", buckets.len());
+        assert!(buckets.len() > 1);
+        let width = (buckets[1].start - buckets[0].start).num_seconds();
+        assert_eq!(width % 60, 0, "width stays a multiple of the request");
+        assert_eq!(buckets.iter().map(|b| b.cost_usd).sum::<f64>(), 2.0);
+
+        // Extreme requested widths don't overflow
+        assert_eq!(aggregate(&events, Duration::from_secs(u64::MAX)).len(), 1);
+    }
 }
@@ -48,10 +48,21 @@ pub struct InventoryItem {
     pub source_file: String,
 }
 
+/// A source file the inventory scanner could not parse.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InventoryError {
+    /// Path relative to project root.
+    pub path: String,
+    pub message: String,
+}
+
 /// Complete inventory of all SuperClaude features.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Inventory {
     pub items: Vec<InventoryItem>,
+    /// Files that failed to parse; their entries are missing from `items`.
+    #[serde(default)]
+    pub errors: Vec<InventoryError>,
 }
 
 impl Inventory {
@@ -30,6 +30,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 timeout_seconds: 600.0,
                 pal_review_enabled: false,
                 min_improvement: 0.0,
+                env_allowlist: None,
+                max_repeated_tool_calls: 5,
+                stop_on_loop: true,
+                max_cost_usd: 0.0,
+                metrics_dir: String::new(),
+                max_event_history: 0,
             }),
         })
         .await?
@@ -43,6 +49,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .stream_events(StreamEventsRequest {
             execution_id: eid.clone(),
             include_history: true,
+            event_types: vec![],
+            ..Default::default()
         })
         .await?
         .into_inner();
@@ -0,0 +1,106 @@
+//! Optional bearer-token authentication for the TCP listener
+//!
+//! When `SUPERCLAUDE_AUTH_TOKEN` is set, every TCP call must carry an
+//! `authorization: Bearer <token>` metadata header. The Unix socket is
+//! not intercepted: filesystem permissions already gate access to it.
+
+use std::sync::Arc;
+
+use tonic::service::Interceptor;
+use tonic::{Request, Status};
+
+/// Shared secret required on the TCP listener; unset or empty disables auth
+pub const AUTH_TOKEN_ENV: &str = "SUPERCLAUDE_AUTH_TOKEN";
+
+/// Interceptor checking the `authorization` header against a shared secret.
+#[derive(Clone)]
+pub struct BearerAuth {
+    token: Option<Arc<str>>,
+}
+
+impl BearerAuth {
+    /// Require `token` on every call; `None` or an empty token lets all calls through.
+    pub fn new(token: Option<String>) -> Self {
+        Self {
+            token: token
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .map(Into::into),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(std::env::var(AUTH_TOKEN_ENV).ok())
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.token.is_some()
+    }
+}
+
+/// Compare without short-circuiting so response timing doesn't leak how
+/// much of the token matched.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+impl Interceptor for BearerAuth {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        let Some(expected) = &self.token else {
+            return Ok(request);
+        };
+
+        let provided = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .map(str::trim);
+
+        match provided {
+            Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => Ok(request),
+            Some(_) => Err(Status::unauthenticated("Invalid bearer token")),
+            None => Err(Status::unauthenticated("Missing bearer token")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request_with(header: Option<&str>) -> Request<()> {
+        let mut request = Request::new(());
+        if let Some(value) = header {
+            request
+                .metadata_mut()
+                .insert("authorization", value.parse().unwrap());
+        }
+        request
+    }
+
+    #[test]
+    fn test_accepts_matching_token() {
+        let mut auth = BearerAuth::new(Some("s3cret".to_string()));
+        assert!(auth.enabled());
+        assert!(auth.call(request_with(Some("Bearer s3cret"))).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_wrong_or_missing_token() {
+        let mut auth = BearerAuth::new(Some("s3cret".to_string()));
+        for header in [None, Some("Bearer nope"), Some("s3cret"), Some("Basic s3cret")] {
+            let err = auth.call(request_with(header)).unwrap_err();
+            assert_eq!(err.code(), tonic::Code::Unauthenticated, "{header:?}");
+        }
+    }
+
+    #[test]
+    fn test_disabled_without_token() {
+        for token in [None, Some(String::new()), Some("  ".to_string())] {
+            let mut auth = BearerAuth::new(token);
+            assert!(!auth.enabled());
+            assert!(auth.call(request_with(None)).is_ok());
+        }
+    }
+}
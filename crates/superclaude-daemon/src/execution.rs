@@ -1,7 +1,7 @@
 //! Execution management - spawns and monitors claude CLI processes
 
-use std::collections::{HashMap, VecDeque};
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::{Arc, LazyLock};
 
@@ -13,12 +13,17 @@ use regex::Regex;
 use serde::Deserialize;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::Command;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, OwnedSemaphorePermit, Semaphore};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+use crate::history::{self, ExecutionSnapshot};
 use crate::metrics_watcher::MetricsWatcher;
 use superclaude_proto::*;
+use superclaude_runtime::obsidian::{
+    extract_decisions_from_evidence, ObsidianArtifactWriter, ObsidianConfigService,
+};
+use superclaude_runtime::ToolInvocation;
 
 // Compiled regex patterns for test output parsing
 static PYTEST_RE: LazyLock<Regex> = LazyLock::new(|| {
@@ -29,9 +34,50 @@ static CARGO_TEST_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"test result:\s*(?:ok|FAILED)\.\s+(\d+) passed;\s+(\d+) failed;\s+(\d+) ignored")
         .unwrap()
 });
+// coverage.py: "TOTAL    120    18    85%"
+static COVERAGE_PY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^TOTAL\s+(?:\d+\s+)+(\d+(?:\.\d+)?)%").unwrap()
+});
+// tarpaulin: "85.00% coverage, 120/141 lines covered"
+static TARPAULIN_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(\d+(?:\.\d+)?)% coverage").unwrap()
+});
+
+/// Events retained in history when `ExecutionConfig.max_event_history` and
+/// `SUPERCLAUDE_MAX_EVENT_HISTORY` are unset.
+const DEFAULT_MAX_EVENT_HISTORY: usize = 5_000;
+/// Smallest accepted history cap; lower values are raised to this.
+const MIN_EVENT_HISTORY: usize = 100;
+/// Largest accepted history cap, so a caller cannot ask for unbounded
+/// per-execution memory; higher values are lowered to this.
+const MAX_EVENT_HISTORY: usize = 100_000;
+/// Overrides the history cap when `ExecutionConfig.max_event_history` is 0.
+const MAX_EVENT_HISTORY_ENV: &str = "SUPERCLAUDE_MAX_EVENT_HISTORY";
+
+/// Number of recent tool-call hashes retained for loop detection.
+const TOOL_CALL_WINDOW: usize = 32;
+
+/// Overrides the metrics directory when `ExecutionConfig.metrics_dir` is unset.
+const METRICS_DIR_ENV: &str = "SUPERCLAUDE_METRICS_DIR";
+/// Metrics directory relative to the project root when nothing is configured.
+const DEFAULT_METRICS_DIR: &str = ".superclaude_metrics";
+
+/// How long `send_input` waits on a stdin pipe the child isn't draining.
+const STDIN_WRITE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// One `--input-format stream-json` user message, newline-terminated.
+fn user_message_line(text: &str) -> String {
+    let mut line = serde_json::json!({
+        "type": "user",
+        "message": { "role": "user", "content": [{ "type": "text", "text": text }] },
+    })
+    .to_string();
+    line.push('\n');
+    line
+}
 
-/// Maximum number of events retained in history to prevent unbounded memory growth.
-const MAX_EVENT_HISTORY: usize = 5_000;
+/// Fraction of `max_cost_usd` at which a budget warning is emitted.
+const COST_WARNING_FRACTION: f64 = 0.8;
 
 // ---------------------------------------------------------------------------
 // Claude CLI stream-json deserialization types
@@ -60,6 +106,32 @@ struct StreamJsonEvent {
     /// Present on type="user" for tool results
     #[serde(default)]
     tool_use_result: Option<serde_json::Value>,
+    /// Present on messages emitted from inside a subagent (Task tool)
+    #[serde(default)]
+    parent_tool_use_id: Option<String>,
+    /// Present on type="stream_event" (`--include-partial-messages`)
+    #[serde(default)]
+    event: Option<PartialMessageEvent>,
+}
+
+/// Raw API streaming event carried by a `stream_event` line. Only the
+/// content-block events that build tool inputs are decoded.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum PartialMessageEvent {
+    ContentBlockStart {
+        index: u64,
+        content_block: serde_json::Value,
+    },
+    ContentBlockDelta {
+        index: u64,
+        delta: serde_json::Value,
+    },
+    ContentBlockStop {
+        index: u64,
+    },
+    #[serde(other)]
+    Other,
 }
 
 #[derive(Debug, Deserialize)]
@@ -67,8 +139,10 @@ struct StreamMessage {
     #[serde(default)]
     content: Vec<ContentBlock>,
     #[serde(default)]
-    #[allow(dead_code)]
     usage: Option<UsageInfo>,
+    /// Model that produced the message; falls back to the configured model
+    #[serde(default)]
+    model: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -101,6 +175,58 @@ struct UsageInfo {
     input_tokens: u64,
     #[serde(default)]
     output_tokens: u64,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<u64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<u64>,
+}
+
+impl UsageInfo {
+    /// Estimated USD cost on `model`, or `None` for unpriced models.
+    fn estimate_cost(&self, model: &str) -> Option<f64> {
+        let tokens = |n: u64| u32::try_from(n).unwrap_or(u32::MAX);
+        let usage = superclaude_runtime::api::Usage {
+            input_tokens: tokens(self.input_tokens),
+            output_tokens: tokens(self.output_tokens),
+            cache_creation_input_tokens: self.cache_creation_input_tokens.map(tokens),
+            cache_read_input_tokens: self.cache_read_input_tokens.map(tokens),
+        };
+        superclaude_runtime::estimate_cost(model, &usage)
+    }
+}
+
+/// Tokens and cost reported by a finished `Task` subagent in its
+/// `tool_use_result` payload.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct SubagentUsage {
+    input_tokens: u64,
+    output_tokens: u64,
+    cost_usd: f64,
+}
+
+impl SubagentUsage {
+    /// Extract usage from a Task result. Accepts both the CLI's camelCase
+    /// keys and snake_case. Returns `None` if nothing was reported.
+    fn from_result(result: &serde_json::Value) -> Option<Self> {
+        let usage = result.get("usage");
+        let tokens = |key: &str| {
+            usage
+                .and_then(|u| u.get(key))
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0)
+        };
+        let cost_usd = ["totalCostUsd", "total_cost_usd", "cost_usd"]
+            .iter()
+            .find_map(|key| result.get(*key).and_then(|v| v.as_f64()))
+            .unwrap_or(0.0);
+
+        let parsed = Self {
+            input_tokens: tokens("input_tokens"),
+            output_tokens: tokens("output_tokens"),
+            cost_usd,
+        };
+        (parsed != Self::default()).then_some(parsed)
+    }
 }
 
 /// Tracks a pending tool use for correlation with its result.
@@ -109,6 +235,128 @@ struct PendingToolUse {
     tool_input: String,
     node_id: String,
     parent_node_id: String,
+    depth: i32,
+}
+
+/// A streamed `tool_use` block whose input has not fully arrived yet.
+#[derive(Debug)]
+struct PartialToolUse {
+    id: String,
+    name: String,
+    parent_node_id: String,
+    json: String,
+}
+
+/// Tool-use input ready for `handle_tool_use`: (id, name, input, parent).
+type AssembledToolUse = (String, String, serde_json::Value, String);
+
+/// Reassembles tool inputs from `stream_event` lines (emitted with
+/// `--include-partial-messages`), mirroring the runtime's
+/// `StreamStateMachine`.
+///
+/// `content_block_start` opens a buffer keyed by subagent scope and block
+/// index, `input_json_delta` fragments are appended, and the tool is
+/// released at `content_block_stop`. The CLI repeats every block in a full
+/// `assistant` message, whose input is always complete (`{}` included);
+/// each tool id is released once, by whichever path arrives first.
+#[derive(Debug, Default)]
+struct ToolInputAssembler {
+    /// Open blocks keyed by (parent_tool_use_id, block index)
+    open: HashMap<(Option<String>, u64), PartialToolUse>,
+    /// Tools already handed to `handle_tool_use`, until their result arrives
+    released: HashSet<String>,
+}
+
+impl ToolInputAssembler {
+    /// `content_block_start` for a `tool_use` block.
+    fn start(&mut self, scope: Option<&str>, index: u64, id: &str, name: &str, parent_node_id: &str) {
+        let block = PartialToolUse {
+            id: id.to_string(),
+            name: name.to_string(),
+            parent_node_id: parent_node_id.to_string(),
+            json: String::new(),
+        };
+        if self.open.insert((scope.map(String::from), index), block).is_some() {
+            warn!(index, "Content block started twice; keeping the latest");
+        }
+    }
+
+    /// `content_block_delta` carrying an `input_json_delta`.
+    fn delta(&mut self, scope: Option<&str>, index: u64, partial_json: &str) {
+        match self.open.get_mut(&(scope.map(String::from), index)) {
+            Some(block) => block.json.push_str(partial_json),
+            None => debug!(index, "Input delta for a block that is not an open tool_use"),
+        }
+    }
+
+    /// `content_block_stop`. Returns the tool unless its id was already
+    /// released or its input does not parse; the full `assistant` message
+    /// then supplies it instead.
+    fn stop(&mut self, scope: Option<&str>, index: u64) -> Option<AssembledToolUse> {
+        let block = self.open.remove(&(scope.map(String::from), index))?;
+        let input = if block.json.trim().is_empty() {
+            serde_json::json!({})
+        } else {
+            match serde_json::from_str(&block.json) {
+                Ok(input) => input,
+                Err(e) => {
+                    warn!(tool_use_id = %block.id, error = %e, "Streamed tool input did not parse");
+                    return None;
+                }
+            }
+        };
+        self.release(&block.id)
+            .then_some((block.id, block.name, input, block.parent_node_id))
+    }
+
+    /// Mark a tool id as released. Returns false if it already was.
+    fn release(&mut self, id: &str) -> bool {
+        self.released.insert(id.to_string())
+    }
+
+    /// The tool's result arrived: drop its bookkeeping. A block still open
+    /// at this point lost its stop and is released with what arrived.
+    fn finish(&mut self, id: &str) -> Option<AssembledToolUse> {
+        let key = self.open.iter().find(|(_, block)| block.id == id).map(|(key, _)| key.clone());
+        let unfinished = key.and_then(|key| self.open.remove(&key));
+        let released = self.released.remove(id);
+        let block = unfinished.filter(|_| !released)?;
+        Some(Self::incomplete(block))
+    }
+
+    /// Release every block still open, e.g. when the run ends.
+    fn finish_all(&mut self) -> Vec<AssembledToolUse> {
+        let mut blocks: Vec<PartialToolUse> = self
+            .open
+            .drain()
+            .map(|(_, block)| block)
+            .filter(|block| !self.released.contains(&block.id))
+            .collect();
+        blocks.sort_by(|a, b| a.id.cmp(&b.id));
+        self.released.clear();
+        blocks.into_iter().map(Self::incomplete).collect()
+    }
+
+    fn incomplete(block: PartialToolUse) -> AssembledToolUse {
+        let input = if block.json.trim().is_empty() {
+            serde_json::json!({})
+        } else {
+            serde_json::from_str(&block.json).unwrap_or_else(|_| {
+                warn!(tool_use_id = %block.id, "Tool input ended incomplete");
+                serde_json::json!({ "error": "failed to parse tool input", "partial_input": block.json })
+            })
+        };
+        (block.id, block.name, input, block.parent_node_id)
+    }
+}
+
+/// Concurrency slot an execution runs in, when the daemon limits
+/// simultaneous executions.
+pub enum ExecutionSlot {
+    /// A slot was free; it is held until the execution finishes.
+    Acquired(OwnedSemaphorePermit),
+    /// No slot was free; the execution stays Pending until one is released.
+    Queued(Arc<Semaphore>),
 }
 
 /// Represents a running or completed execution
@@ -117,6 +365,9 @@ pub struct Execution {
     pub task: String,
     pub project_root: String,
     pub config: ExecutionConfig,
+    pub slot: Option<ExecutionSlot>,
+    /// CLI binary to run; `None` looks up `claude` in PATH.
+    pub claude_path: Option<PathBuf>,
 }
 
 /// Handle to a running execution
@@ -130,6 +381,7 @@ struct ExecutionInner {
     task: String,
     project_root: String,
     config: ExecutionConfig,
+    claude_path: Option<PathBuf>,
 
     // State
     state: RwLock<ExecutionState>,
@@ -137,17 +389,47 @@ struct ExecutionInner {
     current_score: RwLock<f32>,
     started_at: chrono::DateTime<Utc>,
     ended_at: RwLock<Option<chrono::DateTime<Utc>>>,
+    /// Set while paused; the open interval is folded into `paused_total` on resume.
+    paused_at: RwLock<Option<chrono::DateTime<Utc>>>,
+    paused_total: RwLock<chrono::Duration>,
     termination_reason: RwLock<Option<String>>,
 
     // Evidence tracking
     evidence: RwLock<EvidenceSummary>,
+    /// Latest coverage percentage reported alongside test output
+    coverage_percent: RwLock<Option<f32>>,
 
     // Telemetry tracking
     total_cost_usd: RwLock<f64>,
+    /// Cost reported by Task subagents, added on top of the CLI's own total.
+    subagent_cost_usd: RwLock<f64>,
+    /// Main-agent cost estimated from per-message usage; replaced by the
+    /// CLI's reported total once the result arrives.
+    estimated_cost_usd: RwLock<f64>,
     total_input_tokens: RwLock<u64>,
     total_output_tokens: RwLock<u64>,
     pending_tool_uses: RwLock<HashMap<String, PendingToolUse>>,
+    /// Untruncated output of recent results cut in ToolInvoked, by
+    /// tool_use_id, oldest first.
+    full_tool_outputs: RwLock<VecDeque<(String, String)>>,
+    /// Tool uses whose input is still arriving in fragments.
+    tool_inputs: RwLock<ToolInputAssembler>,
+    /// Daemon state dir whose index lists snapshot directories
+    state_dir: Option<PathBuf>,
+    /// Whether this execution's snapshot directory has been indexed
+    snapshot_dir_indexed: std::sync::atomic::AtomicBool,
+    /// Tree depth of every emitted node, keyed by node_id, so children can
+    /// be placed one level below their parent.
+    node_depths: RwLock<HashMap<String, i32>>,
     run_instructions: RwLock<Option<RunInstructions>>,
+    /// Hashes of the most recent `(tool_name, tool_input)` pairs, bounded to
+    /// `TOOL_CALL_WINDOW`, used to detect repeated identical calls.
+    recent_tool_calls: RwLock<VecDeque<u64>>,
+    /// Set when the daemon terminates the run itself (e.g. loop detected);
+    /// the state and termination reason are then kept as set.
+    terminated: RwLock<bool>,
+    /// Whether the cost budget warning has been emitted.
+    cost_warned: RwLock<bool>,
 
     // JSONL persistence
     jsonl_writer: RwLock<Option<std::io::BufWriter<std::fs::File>>>,
@@ -155,6 +437,15 @@ struct ExecutionInner {
     // Event streaming
     event_tx: broadcast::Sender<AgentEvent>,
     event_history: RwLock<VecDeque<AgentEvent>>,
+    /// Resolved cap on `event_history`.
+    max_event_history: usize,
+    /// Sequence number of the last emitted event
+    last_sequence: RwLock<u64>,
+
+    // Resource usage of the child process
+    peak_memory_bytes: RwLock<u64>,
+    stdout_bytes: RwLock<u64>,
+    stderr_bytes: RwLock<u64>,
 
     // Process management — stores the PID for lifecycle control (kill on stop).
     // The Child itself stays local to run_execution() for await-safe waiting.
@@ -176,9 +467,25 @@ impl Execution {
             task,
             project_root,
             config,
+            slot: None,
+            claude_path: None,
         }
     }
 
+    /// Run within a concurrency slot, released when the execution finishes.
+    pub fn with_slot(mut self, slot: ExecutionSlot) -> Self {
+        self.slot = Some(slot);
+        self
+    }
+
+    /// Run `path` instead of the `claude` found in PATH, so tests never
+    /// launch a real agent.
+    #[cfg(test)]
+    pub fn with_claude_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.claude_path = Some(path.into());
+        self
+    }
+
     pub async fn start(self) -> Result<ExecutionHandle> {
         let (event_tx, _) = broadcast::channel(1024);
 
@@ -187,21 +494,40 @@ impl Execution {
             task: self.task.clone(),
             project_root: self.project_root.clone(),
             config: self.config.clone(),
+            claude_path: self.claude_path.clone(),
             state: RwLock::new(ExecutionState::Pending),
             current_iteration: RwLock::new(0),
             current_score: RwLock::new(0.0),
             started_at: Utc::now(),
             ended_at: RwLock::new(None),
+            paused_at: RwLock::new(None),
+            paused_total: RwLock::new(chrono::Duration::zero()),
             termination_reason: RwLock::new(None),
             evidence: RwLock::new(EvidenceSummary::default()),
+            coverage_percent: RwLock::new(None),
             total_cost_usd: RwLock::new(0.0),
+            subagent_cost_usd: RwLock::new(0.0),
+            estimated_cost_usd: RwLock::new(0.0),
             total_input_tokens: RwLock::new(0),
             total_output_tokens: RwLock::new(0),
             pending_tool_uses: RwLock::new(HashMap::new()),
+            full_tool_outputs: RwLock::new(VecDeque::new()),
+            tool_inputs: RwLock::new(ToolInputAssembler::default()),
+            state_dir: history::state_dir(),
+            snapshot_dir_indexed: std::sync::atomic::AtomicBool::new(false),
+            node_depths: RwLock::new(HashMap::new()),
             run_instructions: RwLock::new(None),
+            recent_tool_calls: RwLock::new(VecDeque::new()),
+            terminated: RwLock::new(false),
+            cost_warned: RwLock::new(false),
             jsonl_writer: RwLock::new(None),
             event_tx: event_tx.clone(),
             event_history: RwLock::new(VecDeque::new()),
+            max_event_history: resolve_max_event_history(self.config.max_event_history),
+            last_sequence: RwLock::new(0),
+            peak_memory_bytes: RwLock::new(0),
+            stdout_bytes: RwLock::new(0),
+            stderr_bytes: RwLock::new(0),
             process_pid: RwLock::new(None),
             child_stdin: tokio::sync::RwLock::new(None),
             _metrics_watcher: RwLock::new(None),
@@ -213,12 +539,27 @@ impl Execution {
 
         // Spawn the execution in background
         let inner_clone = inner.clone();
+        let slot = self.slot;
         tokio::spawn(async move {
+            // Held for the lifetime of the execution
+            let _permit = match slot {
+                Some(ExecutionSlot::Acquired(permit)) => Some(permit),
+                Some(ExecutionSlot::Queued(slots)) => match inner_clone.wait_for_slot(slots).await {
+                    Some(permit) => Some(permit),
+                    // Stopped while queued
+                    None => return,
+                },
+                None => None,
+            };
+
             let inner_for_error = inner_clone.clone();
             if let Err(e) = inner_clone.run_execution().await {
                 error!(execution_id = %inner_for_error.id, error = %e, "Execution failed");
                 *inner_for_error.state.write() = ExecutionState::Failed;
                 *inner_for_error.termination_reason.write() = Some(e.to_string());
+                inner_for_error.ended_at.write().get_or_insert_with(Utc::now);
+                inner_for_error.write_run_summary();
+                inner_for_error.write_snapshot();
             }
         });
 
@@ -235,7 +576,249 @@ fn truncate_str(s: &str, max_chars: usize) -> String {
     }
 }
 
+/// Tool results longer than this many characters are cut in ToolInvoked.
+const MAX_TOOL_OUTPUT_CHARS: usize = 2000;
+
+/// Untruncated results kept for `get_tool_output`; older ones are dropped.
+const MAX_RETAINED_FULL_OUTPUTS: usize = 100;
+
+/// Content beyond this many bytes is not scanned when counting changed lines.
+const MAX_LINE_COUNT_BYTES: usize = 1024 * 1024;
+/// Edits whose line LCS table would exceed this many cells are counted as a
+/// full replacement instead of diffed.
+const MAX_DIFF_CELLS: usize = 1_000_000;
+
+/// External reference consulted by a web tool: the URL for `WebFetch`, or
+/// `search: <query>` for `WebSearch`.
+fn external_source(tool_name: &str, input: &serde_json::Value) -> Option<String> {
+    let field = |key| {
+        input
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    };
+    match tool_name {
+        "WebFetch" => field("url").map(str::to_string),
+        "WebSearch" => field("query").map(|q| format!("search: {q}")),
+        _ => None,
+    }
+}
+
+/// PAL consensus / thinkdeep calls, the only tools whose results become
+/// decisions (as `DecisionMade` events and Obsidian notes).
+fn is_decision_tool(tool_name: &str) -> bool {
+    let lower = tool_name.to_lowercase();
+    lower.contains("consensus") || lower.contains("thinkdeep")
+}
+
+/// Decision from a PAL consensus or thinkdeep result, parsed the way the
+/// Obsidian writer does. `None` for other tools or input without a question.
+fn decision_from_tool_result(
+    tool_name: &str,
+    tool_input: &str,
+    tool_output: &str,
+) -> Option<DecisionMade> {
+    let lower = tool_name.to_lowercase();
+    let question_keys = if lower.contains("consensus") {
+        ["question", "prompt"]
+    } else if lower.contains("thinkdeep") {
+        ["topic", "prompt"]
+    } else {
+        return None;
+    };
+
+    let input: serde_json::Value = serde_json::from_str(tool_input).ok()?;
+    let question = question_keys
+        .iter()
+        .find_map(|key| input.get(*key).and_then(|v| v.as_str()))?
+        .to_string();
+    let invocation = ToolInvocation {
+        tool_name: tool_name.to_string(),
+        tool_input: input,
+        tool_output: tool_output.to_string(),
+        timestamp: String::new(),
+    };
+    let record = extract_decisions_from_evidence(std::slice::from_ref(&invocation), "", "")
+        .into_iter()
+        .next()?;
+
+    Some(DecisionMade {
+        decision_type: record.decision_type,
+        title: record.title,
+        question,
+        summary: record.summary,
+        tool_use_id: String::new(),
+        node_id: String::new(),
+    })
+}
+
+/// Per-file `(path, lines_added, lines_removed)` for a `MultiEdit` input, in
+/// first-seen order. Edits target `file_path` unless they name their own;
+/// repeated paths are merged into one entry.
+fn multi_edit_changes(file_path: &str, input: &serde_json::Value) -> Vec<(String, i32, i32)> {
+    let mut changes: Vec<(String, i32, i32)> = Vec::new();
+    let edits = input.get("edits").and_then(|v| v.as_array());
+    for edit in edits.into_iter().flatten() {
+        let path = edit
+            .get("file_path")
+            .and_then(|v| v.as_str())
+            .unwrap_or(file_path);
+        if path.is_empty() {
+            continue;
+        }
+        let (added, removed) = diff_line_counts(
+            edit.get("old_string").and_then(|v| v.as_str()).unwrap_or(""),
+            edit.get("new_string").and_then(|v| v.as_str()).unwrap_or(""),
+        );
+        match changes.iter_mut().find(|(p, _, _)| p == path) {
+            Some((_, a, r)) => {
+                *a += added;
+                *r += removed;
+            }
+            None => changes.push((path.to_string(), added, removed)),
+        }
+    }
+    changes
+}
+
+/// Number of lines in `text`, scanning at most `MAX_LINE_COUNT_BYTES`.
+/// Binary content (containing NUL bytes) counts as zero lines.
+fn count_lines(text: &str) -> i32 {
+    let bytes = &text.as_bytes()[..text.len().min(MAX_LINE_COUNT_BYTES)];
+    if bytes.is_empty() || bytes.contains(&0) {
+        return 0;
+    }
+    let newlines = bytes.iter().filter(|&&b| b == b'\n').count();
+    let trailing = usize::from(!bytes.ends_with(b"\n"));
+    (newlines + trailing) as i32
+}
+
+/// Lines added and removed when replacing `old` with `new`, via the longest
+/// common subsequence of their lines.
+fn diff_line_counts(old: &str, new: &str) -> (i32, i32) {
+    let capped = |s: &str| {
+        let mut end = s.len().min(MAX_LINE_COUNT_BYTES);
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        s[..end].to_string()
+    };
+    let (old, new) = (capped(old), capped(new));
+    if old.contains('\0') || new.contains('\0') {
+        return (0, 0);
+    }
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let (n, m) = (old_lines.len(), new_lines.len());
+
+    if n.saturating_mul(m) > MAX_DIFF_CELLS {
+        return (m as i32, n as i32);
+    }
+
+    // Rolling-row LCS length
+    let mut prev = vec![0usize; m + 1];
+    let mut curr = vec![0usize; m + 1];
+    for old_line in &old_lines {
+        for (j, new_line) in new_lines.iter().enumerate() {
+            curr[j + 1] = if old_line == new_line {
+                prev[j] + 1
+            } else {
+                prev[j + 1].max(curr[j])
+            };
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    let common = prev[m];
+
+    ((m - common) as i32, (n - common) as i32)
+}
+
+/// Peak resident set size of `pid` from `/proc/<pid>/status` (VmHWM,
+/// falling back to VmRSS). `None` where /proc is unavailable.
+fn read_peak_rss_bytes(pid: u32) -> Option<u64> {
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    parse_rss_kb(&status, "VmHWM:")
+        .or_else(|| parse_rss_kb(&status, "VmRSS:"))
+        .map(|kb| kb * 1024)
+}
+
+fn parse_rss_kb(status: &str, key: &str) -> Option<u64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix(key))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|kb| kb.parse().ok())
+}
+
+/// Metrics directory for a project: `configured` if non-empty, else
+/// `$SUPERCLAUDE_METRICS_DIR`, else `<project_root>/.superclaude_metrics`.
+/// Relative paths resolve against the project root.
+pub fn resolve_metrics_dir(project_root: &Path, configured: &str) -> PathBuf {
+    resolve_metrics_dir_with(project_root, configured, std::env::var(METRICS_DIR_ENV).ok())
+}
+
+/// Event history cap: `configured` if non-zero, else
+/// `$SUPERCLAUDE_MAX_EVENT_HISTORY`, else 5000. Clamped to 100..=100000.
+fn resolve_max_event_history(configured: u32) -> usize {
+    resolve_max_event_history_with(configured, std::env::var(MAX_EVENT_HISTORY_ENV).ok())
+}
+
+fn resolve_max_event_history_with(configured: u32, env: Option<String>) -> usize {
+    let chosen = Some(configured as usize)
+        .filter(|&n| n > 0)
+        .or_else(|| env.and_then(|s| s.trim().parse::<usize>().ok()).filter(|&n| n > 0))
+        .unwrap_or(DEFAULT_MAX_EVENT_HISTORY);
+    if chosen < MIN_EVENT_HISTORY {
+        warn!(
+            requested = chosen,
+            minimum = MIN_EVENT_HISTORY,
+            "Event history cap too small, using minimum"
+        );
+    } else if chosen > MAX_EVENT_HISTORY {
+        warn!(
+            requested = chosen,
+            maximum = MAX_EVENT_HISTORY,
+            "Event history cap too large, using maximum"
+        );
+    }
+    chosen.clamp(MIN_EVENT_HISTORY, MAX_EVENT_HISTORY)
+}
+
+fn resolve_metrics_dir_with(project_root: &Path, configured: &str, env: Option<String>) -> PathBuf {
+    let chosen = Some(configured.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .or_else(|| env.map(|s| s.trim().to_string()).filter(|s| !s.is_empty()))
+        .unwrap_or_else(|| DEFAULT_METRICS_DIR.to_string());
+    project_root.join(chosen)
+}
+
 impl ExecutionInner {
+    /// Stay Pending until a concurrency slot frees up. Returns `None` if the
+    /// execution was stopped while queued.
+    async fn wait_for_slot(&self, slots: Arc<Semaphore>) -> Option<OwnedSemaphorePermit> {
+        info!(execution_id = %self.id, "Execution queued, waiting for a free slot");
+
+        self.emit_event(AgentEvent {
+            execution_id: self.id.clone(),
+            sequence: 0,
+            timestamp: Self::now_timestamp(),
+            event: Some(agent_event::Event::StateChanged(StateChanged {
+                old_state: ExecutionState::Pending as i32,
+                new_state: ExecutionState::Pending as i32,
+                reason: "Queued: waiting for a free execution slot".to_string(),
+            })),
+        });
+
+        let permit = slots.acquire_owned().await.ok()?;
+        if *self.state.read() == ExecutionState::Cancelled {
+            info!(execution_id = %self.id, "Execution stopped while queued");
+            self.ended_at.write().get_or_insert_with(Utc::now);
+            return None;
+        }
+        Some(permit)
+    }
+
     async fn run_execution(self: Arc<Self>) -> Result<()> {
         info!(execution_id = %self.id, task = %self.task, "Starting execution");
 
@@ -244,6 +827,7 @@ impl ExecutionInner {
         // Emit state change event
         self.emit_event(AgentEvent {
             execution_id: self.id.clone(),
+            sequence: 0,
             timestamp: Self::now_timestamp(),
             event: Some(agent_event::Event::StateChanged(StateChanged {
                 old_state: ExecutionState::Pending as i32,
@@ -253,28 +837,33 @@ impl ExecutionInner {
         });
 
         // Find claude CLI
-        let claude_path = which::which("claude")
-            .context("claude CLI not found in PATH")?;
+        let claude_path = match &self.claude_path {
+            Some(path) => path.clone(),
+            None => which::which("claude").context("claude CLI not found in PATH")?,
+        };
 
         // Build the command — use stream-json for structured output parsing
         let mut cmd = Command::new(&claude_path);
+        // The task and any send_input() messages go in over stdin as
+        // stream-json user messages; stdin is closed once the turn ends
         cmd.arg("--print")
             .arg("--verbose")
+            .arg("--input-format").arg("stream-json")
             .arg("--output-format").arg("stream-json")
             .arg("--permission-mode").arg("bypassPermissions")
             .arg("--no-session-persistence")
             .arg("--model").arg(&self.config.model)
-            .arg(&self.task)
             .current_dir(&self.project_root)
-            .stdin(Stdio::null())
+            .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
 
-        // Set environment for SuperClaude configuration
-        cmd.env("SUPERCLAUDE_EXECUTION_ID", &self.id);
-        cmd.env("SUPERCLAUDE_MAX_ITERATIONS", self.config.max_iterations.to_string());
-        cmd.env("SUPERCLAUDE_QUALITY_THRESHOLD", self.config.quality_threshold.to_string());
-        cmd.env("SUPERCLAUDE_MODEL", &self.config.model);
+        // Run claude in its own process group so stop/pause signals reach
+        // every descendant (bash commands, subagents), not just the CLI
+        #[cfg(unix)]
+        cmd.process_group(0);
+
+        self.apply_child_env(&mut cmd);
 
         info!(
             execution_id = %self.id,
@@ -291,50 +880,23 @@ impl ExecutionInner {
             *self.process_pid.write() = Some(pid);
         }
 
-        // Set up metrics watcher and JSONL writer for .superclaude_metrics/
-        let metrics_path = PathBuf::from(&self.project_root).join(".superclaude_metrics");
-        if metrics_path.exists() || std::fs::create_dir_all(&metrics_path).is_ok() {
-            // Initialize JSONL writer
-            let jsonl_path = metrics_path.join("events.jsonl");
-            match std::fs::OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(&jsonl_path)
-            {
-                Ok(file) => {
-                    *self.jsonl_writer.write() = Some(std::io::BufWriter::new(file));
-                }
-                Err(e) => {
-                    warn!(error = %e, "Failed to open JSONL writer");
-                }
-            }
-
-            match MetricsWatcher::new(
-                metrics_path,
-                self.id.clone(),
-                self.event_tx.clone(),
-            ) {
-                Ok(watcher) => {
-                    *self._metrics_watcher.write() = Some(watcher);
-                }
-                Err(e) => {
-                    warn!(error = %e, "Failed to start metrics watcher");
-                }
-            }
+        // Send the task, then keep stdin open so send_input() can steer
+        // the running execution until claude reports the turn's result
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(user_message_line(&self.task).as_bytes())
+                .await
+                .context("Failed to send task to claude stdin")?;
+            stdin.flush().await.context("Failed to send task to claude stdin")?;
+            *self.child_stdin.write().await = Some(stdin);
         }
 
+        self.open_metrics_outputs();
+
         // Read stdout for structured JSON progress events
         if let Some(stdout) = child.stdout.take() {
             let inner = self.clone();
-            tokio::spawn(async move {
-                let reader = BufReader::new(stdout);
-                let mut lines = reader.lines();
-
-                while let Ok(Some(line)) = lines.next_line().await {
-                    info!(execution_id = %inner.id, len = line.len(), "claude stdout line");
-                    inner.parse_stream_json_line(&line);
-                }
-            });
+            tokio::spawn(async move { inner.consume_stdout(stdout).await });
         }
 
         // Read stderr for errors — accumulate into buffer for failure reporting
@@ -358,6 +920,7 @@ impl ExecutionInner {
                     match line_result {
                         Ok(Ok(Some(line))) => {
                             debug!(execution_id = %inner.id, line = %line, "claude stderr");
+                            *inner.stderr_bytes.write() += line.len() as u64 + 1;
                             // Always buffer for failure reporting on process exit
                             stderr_buf.write().push(line.clone());
                             // Only batch lines that look like real errors for ErrorOccurred events
@@ -373,6 +936,7 @@ impl ExecutionInner {
                                 let msg = truncate_str(&batch.join("\n"), 1000);
                                 inner.emit_event(AgentEvent {
                                     execution_id: inner.id.clone(),
+                                    sequence: 0,
                                     timestamp: Self::now_timestamp(),
                                     event: Some(agent_event::Event::Error(ErrorOccurred {
                                         error_type: "stderr".to_string(),
@@ -395,6 +959,7 @@ impl ExecutionInner {
                         let msg = truncate_str(&batch.join("\n"), 1000);
                         inner.emit_event(AgentEvent {
                             execution_id: inner.id.clone(),
+                            sequence: 0,
                             timestamp: Self::now_timestamp(),
                             event: Some(agent_event::Event::Error(ErrorOccurred {
                                 error_type: "stderr".to_string(),
@@ -418,11 +983,17 @@ impl ExecutionInner {
                 let mut interval = tokio::time::interval(std::time::Duration::from_secs(5));
                 loop {
                     interval.tick().await;
-                    if *inner.state.read() != ExecutionState::Running {
-                        break;
+                    inner.sample_memory();
+                    inner.write_snapshot();
+                    match *inner.state.read() {
+                        ExecutionState::Running => {}
+                        // Stay alive but silent while paused
+                        ExecutionState::Paused => continue,
+                        _ => break,
                     }
                     inner.emit_event(AgentEvent {
                         execution_id: inner.id.clone(),
+                        sequence: 0,
                         timestamp: Self::now_timestamp(),
                         event: Some(agent_event::Event::LogMessage(LogMessage {
                             level: LogLevel::Debug as i32,
@@ -434,18 +1005,34 @@ impl ExecutionInner {
             })
         };
 
-        // Wait for completion
-        let exit_status = child.wait().await?;
+        // Wait for completion, bounded by the configured timeout
+        let exit_status = self.wait_with_timeout(&mut child).await?;
 
         // Stop the heartbeat
         heartbeat_handle.abort();
 
-        // Clear stored PID
+        // Clear stored PID and stdin pipe
         *self.process_pid.write() = None;
+        *self.child_stdin.write().await = None;
 
         // Update final state
         *self.ended_at.write() = Some(Utc::now());
 
+        let Some(exit_status) = exit_status else {
+            // Timed out: the process was killed in wait_with_timeout()
+            *self.state.write() = ExecutionState::Failed;
+            *self.termination_reason.write() = Some(format!(
+                "Execution timed out after {}s",
+                self.config.timeout_seconds
+            ));
+            return self.finish_execution().await;
+        };
+
+        // Terminated by the daemon: keep the recorded state and reason
+        if *self.terminated.read() {
+            return self.finish_execution().await;
+        }
+
         if exit_status.success() {
             *self.state.write() = ExecutionState::Completed;
             *self.termination_reason.write() = Some("Execution completed successfully".to_string());
@@ -468,15 +1055,117 @@ impl ExecutionInner {
             }
         }
 
+        self.finish_execution().await
+    }
+
+    /// Set up the child's environment: the inherited one (or only the
+    /// allowlisted vars when `env_allowlist` is configured) plus the
+    /// `SUPERCLAUDE_*` configuration vars.
+    fn apply_child_env(&self, cmd: &mut Command) {
+        if let Some(allowlist) = &self.config.env_allowlist {
+            cmd.env_clear();
+            for key in &allowlist.vars {
+                if let Ok(value) = std::env::var(key) {
+                    cmd.env(key, value);
+                }
+            }
+            debug!(execution_id = %self.id, vars = allowlist.vars.len(), "Applied env allowlist");
+        }
+
+        cmd.env("SUPERCLAUDE_EXECUTION_ID", &self.id);
+        cmd.env("SUPERCLAUDE_MAX_ITERATIONS", self.config.max_iterations.to_string());
+        cmd.env("SUPERCLAUDE_QUALITY_THRESHOLD", self.config.quality_threshold.to_string());
+        cmd.env("SUPERCLAUDE_MODEL", &self.config.model);
+    }
+
+    /// Wait for the child to exit. Returns `None` if `timeout_seconds`
+    /// of active (unpaused) time elapsed first, in which case the process has
+    /// been killed and reaped. A `timeout_seconds` of 0 means no limit.
+    async fn wait_with_timeout(
+        &self,
+        child: &mut tokio::process::Child,
+    ) -> Result<Option<std::process::ExitStatus>> {
+        let limit = std::time::Duration::try_from_secs_f32(self.config.timeout_seconds)
+            .ok()
+            .filter(|d| !d.is_zero());
+        let Some(limit) = limit else {
+            return Ok(Some(child.wait().await?));
+        };
+
+        // Time spent paused does not count toward the limit: each wake-up
+        // re-measures the active time and sleeps for whatever is left, so a
+        // paused execution just keeps re-arming the same remaining budget.
+        // `Child::wait` is cancel-safe, so re-polling it is fine.
+        loop {
+            let active = std::time::Duration::from_secs_f32(self.active_duration_seconds());
+            let remaining = limit.saturating_sub(active);
+            if remaining.is_zero() {
+                break;
+            }
+            if let Ok(status) = tokio::time::timeout(remaining, child.wait()).await {
+                return Ok(Some(status?));
+            }
+        }
+
+        warn!(
+            execution_id = %self.id,
+            timeout_seconds = self.config.timeout_seconds,
+            "Execution timed out, killing claude CLI"
+        );
+        self.kill_process(true);
+        #[cfg(not(unix))]
+        let _ = child.start_kill();
+        child.wait().await?;
+        Ok(None)
+    }
+
+    /// Kill the child process via its stored PID (SIGKILL when `force`,
+    /// SIGTERM otherwise).
+    fn kill_process(&self, force: bool) {
+        #[cfg(unix)]
+        {
+            self.signal_process(if force { libc::SIGKILL } else { libc::SIGTERM });
+            // A paused (SIGSTOPped) process only handles SIGTERM once continued
+            if !force {
+                self.signal_process(libc::SIGCONT);
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = force;
+    }
+
+    /// Send `signal` to the process group led by the stored PID, reaching
+    /// the child and all its descendants. No-op when no process is running.
+    #[cfg(unix)]
+    fn signal_process(&self, signal: libc::c_int) {
+        if let Some(pid) = *self.process_pid.read() {
+            // Safety: sending a signal to a known process group is safe
+            let ret = unsafe { libc::kill(-(pid as i32), signal) };
+            if ret != 0 {
+                let err = std::io::Error::last_os_error();
+                warn!(execution_id = %self.id, pid = pid, signal = signal, error = %err, "Failed to signal child process");
+            }
+        }
+    }
+
+    /// Flush output, emit the final StateChanged event and write the run
+    /// summary once the final state and termination reason are set.
+    async fn finish_execution(&self) -> Result<()> {
         // Flush JSONL writer
         if let Some(ref mut writer) = *self.jsonl_writer.write() {
             use std::io::Write;
             let _ = writer.flush();
         }
 
+        let completed = *self.state.read() == ExecutionState::Completed;
+        if completed {
+            self.sync_obsidian_decisions().await;
+        }
+
         // Emit completion event
         self.emit_event(AgentEvent {
             execution_id: self.id.clone(),
+            sequence: 0,
             timestamp: Self::now_timestamp(),
             event: Some(agent_event::Event::StateChanged(StateChanged {
                 old_state: ExecutionState::Running as i32,
@@ -491,75 +1180,413 @@ impl ExecutionInner {
             "Execution finished"
         );
 
+        self.write_run_summary();
+        self.write_snapshot();
+
         Ok(())
     }
 
-    // -----------------------------------------------------------------------
-    // Stream-JSON parsing
-    // -----------------------------------------------------------------------
+    /// Tool calls with their results, rebuilt from the retained
+    /// `ToolInvoked` result events.
+    fn tool_invocations(&self) -> Vec<ToolInvocation> {
+        self.event_history
+            .read()
+            .iter()
+            .filter_map(|event| match &event.event {
+                Some(agent_event::Event::ToolInvoked(tool)) if !tool.tool_output.is_empty() => {
+                    Some(ToolInvocation {
+                        tool_name: tool.tool_name.clone(),
+                        tool_input: serde_json::from_str(&tool.tool_input)
+                            .unwrap_or(serde_json::Value::Null),
+                        tool_output: tool.tool_output.clone(),
+                        timestamp: event
+                            .timestamp
+                            .as_ref()
+                            .and_then(|t| chrono::DateTime::from_timestamp(t.seconds, t.nanos as u32))
+                            .map(|t| t.to_rfc3339())
+                            .unwrap_or_default(),
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    }
 
-    fn parse_stream_json_line(&self, line: &str) {
-        let trimmed = line.trim();
-        if trimmed.is_empty() || !trimmed.starts_with('{') {
+    /// Write this run's decisions to the vault named in the project's
+    /// `.obsidian.yaml` when it syncs on task completion, emitting
+    /// `ArtifactWritten` for each. Failures are logged, never fatal.
+    async fn sync_obsidian_decisions(&self) {
+        let mut service = ObsidianConfigService::new(PathBuf::from(&self.project_root));
+        if !service.config_exists() {
             return;
         }
-
-        let event: StreamJsonEvent = match serde_json::from_str(trimmed) {
-            Ok(e) => e,
+        let config = match service.load_config() {
+            Ok(config) => config,
             Err(e) => {
-                warn!(error = %e, "Skipping non-JSON or unrecognised line");
+                warn!(execution_id = %self.id, error = %e, "Failed to load Obsidian config");
                 return;
             }
         };
+        if config.artifacts.sync_on != "task_completion" {
+            return;
+        }
 
-        match event.event_type.as_str() {
-            "system" => self.handle_system_event(&event),
-            "assistant" => self.handle_assistant_event(&event),
-            "user" => self.handle_user_event(&event),
-            "result" => self.handle_result_event(&event),
-            other => {
-                debug!(event_type = other, "Ignoring unknown stream-json event type");
+        let project_name = Path::new(&self.project_root)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let invocations: Vec<ToolInvocation> = self
+            .tool_invocations()
+            .into_iter()
+            .filter(|invocation| is_decision_tool(&invocation.tool_name))
+            .collect();
+        let decisions = extract_decisions_from_evidence(&invocations, &project_name, &self.id);
+        let vault = config.vault.path.clone();
+        let writer = ObsidianArtifactWriter::new(config);
+
+        for decision in decisions {
+            match writer.write_decision_async(&decision).await {
+                Ok(path) => self.emit_event(AgentEvent {
+                    execution_id: self.id.clone(),
+                    sequence: 0,
+                    timestamp: Self::now_timestamp(),
+                    event: Some(agent_event::Event::ArtifactWritten(ArtifactWritten {
+                        obsidian_path: path
+                            .strip_prefix(&vault)
+                            .unwrap_or(&path)
+                            .to_string_lossy()
+                            .to_string(),
+                        artifact_type: "decision".to_string(),
+                        title: decision.title.clone(),
+                    })),
+                }),
+                Err(e) => warn!(
+                    execution_id = %self.id,
+                    title = %decision.title,
+                    error = %e,
+                    "Failed to write Obsidian decision"
+                ),
             }
         }
     }
 
-    fn handle_system_event(&self, event: &StreamJsonEvent) {
-        if event.subtype.as_deref() == Some("init") {
-            self.emit_event(AgentEvent {
-                execution_id: self.id.clone(),
-                timestamp: Self::now_timestamp(),
-                event: Some(agent_event::Event::LogMessage(LogMessage {
-                    level: LogLevel::Info as i32,
-                    message: "Claude session initialised".to_string(),
-                    source: "claude-cli".to_string(),
-                })),
-            });
-        }
+    /// Machine-readable outcome of the run: status, final quality
+    /// assessment, run instructions, and evidence. CI steps read this
+    /// instead of scraping the event stream.
+    fn run_summary_json(&self) -> serde_json::Value {
+        let state = *self.state.read();
+        let score = *self.current_score.read();
+        let threshold = self.config.quality_threshold;
+        let evidence = self.evidence.read().clone();
+        let quality = self.compute_quality_breakdown();
+        let run_instructions = self.run_instructions.read().clone();
+
+        serde_json::json!({
+            "execution_id": self.id,
+            "status": {
+                "task": self.task,
+                "state": state.as_str_name(),
+                "termination_reason": self.termination_reason.read().clone().unwrap_or_default(),
+                "iterations": *self.current_iteration.read(),
+                "max_iterations": self.config.max_iterations,
+                "started_at": self.started_at.to_rfc3339(),
+                "ended_at": self.ended_at.read().map(|t| t.to_rfc3339()),
+                "duration_seconds": self.active_duration_seconds(),
+                "total_cost_usd": *self.total_cost_usd.read(),
+                "total_input_tokens": *self.total_input_tokens.read(),
+                "total_output_tokens": *self.total_output_tokens.read(),
+            },
+            "quality": {
+                "score": score,
+                "threshold": threshold,
+                "passed": state == ExecutionState::Completed && score >= threshold,
+                "dimensions": {
+                    "code_changes": quality.code_changes,
+                    "tests_run": quality.tests_run,
+                    "tests_pass": quality.tests_pass,
+                    "coverage": quality.coverage,
+                    "no_errors": quality.no_errors,
+                },
+                "breakdown": quality.breakdown.iter().map(|d| serde_json::json!({
+                    "name": d.name,
+                    "score": d.score,
+                    "max_score": d.max_score,
+                    "description": d.description,
+                })).collect::<Vec<_>>(),
+            },
+            "run_instructions": run_instructions.map(|ri| serde_json::json!({
+                "build_command": ri.build_command,
+                "run_command": ri.run_command,
+                "artifacts": ri.artifacts,
+                "notes": ri.notes,
+            })),
+            "evidence": {
+                "files_written": evidence.files_written,
+                "files_edited": evidence.files_edited,
+                "commands_run": evidence.commands_run,
+                "tests_run": evidence.tests_run,
+                "tests_passed": evidence.tests_passed,
+                "tests_failed": evidence.tests_failed,
+                "subagents_spawned": evidence.subagents_spawned,
+                "external_sources": evidence.external_sources,
+            },
+        })
     }
 
-    fn handle_assistant_event(&self, event: &StreamJsonEvent) {
-        let message = match &event.message {
-            Some(m) => m,
-            None => return,
-        };
+    /// Write `{metrics_dir}/{id}-summary.json`. Failures are logged,
+    /// never propagated: a missing artifact must not fail the run.
+    fn write_run_summary(&self) {
+        let dir = self.metrics_dir();
+        let path = dir.join(format!("{}-summary.json", self.id));
+        let result = std::fs::create_dir_all(&dir)
+            .and_then(|_| {
+                serde_json::to_string_pretty(&self.run_summary_json()).map_err(std::io::Error::from)
+            })
+            .and_then(|json| std::fs::write(&path, json));
 
-        // Accumulate token usage
-        if let Some(usage) = &message.usage {
-            *self.total_input_tokens.write() += usage.input_tokens;
-            *self.total_output_tokens.write() += usage.output_tokens;
+        match result {
+            Ok(()) => info!(execution_id = %self.id, path = %path.display(), "Wrote run summary"),
+            Err(e) => warn!(execution_id = %self.id, error = %e, "Failed to write run summary"),
         }
+    }
 
-        // Each assistant message counts as one turn
-        let iteration = {
-            let mut iter = self.current_iteration.write();
+    /// Resolved metrics directory for this execution.
+    fn metrics_dir(&self) -> PathBuf {
+        resolve_metrics_dir(Path::new(&self.project_root), &self.config.metrics_dir)
+    }
+
+    /// Set up the JSONL writer and metrics watcher in the metrics directory,
+    /// creating it if missing.
+    fn open_metrics_outputs(&self) {
+        let metrics_path = self.metrics_dir();
+        if let Err(e) = std::fs::create_dir_all(&metrics_path) {
+            warn!(path = %metrics_path.display(), error = %e, "Failed to create metrics directory");
+            return;
+        }
+        info!(execution_id = %self.id, path = %metrics_path.display(), "Using metrics directory");
+
+        // Initialize JSONL writer
+        let jsonl_path = metrics_path.join("events.jsonl");
+        match std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&jsonl_path)
+        {
+            Ok(file) => {
+                *self.jsonl_writer.write() = Some(std::io::BufWriter::new(file));
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to open JSONL writer");
+            }
+        }
+
+        match MetricsWatcher::new(
+            metrics_path,
+            self.id.clone(),
+            self.event_tx.clone(),
+        ) {
+            Ok(watcher) => {
+                *self._metrics_watcher.write() = Some(watcher);
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to start metrics watcher");
+            }
+        }
+    }
+
+    /// Parse stream-json lines from the claude CLI's stdout until EOF,
+    /// counting the bytes consumed.
+    async fn consume_stdout<R>(&self, stdout: R)
+    where
+        R: tokio::io::AsyncRead + Unpin,
+    {
+        let mut lines = BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            info!(execution_id = %self.id, len = line.len(), "claude stdout line");
+            *self.stdout_bytes.write() += line.len() as u64 + 1;
+            if self.parse_stream_json_line(&line) {
+                // End of turn: close stdin so claude exits once any queued
+                // input has been answered
+                *self.child_stdin.write().await = None;
+            }
+        }
+    }
+
+    /// Update `peak_memory_bytes` from the child's resident set size.
+    /// Leaves the value unchanged when sampling fails.
+    fn sample_memory(&self) {
+        let Some(pid) = *self.process_pid.read() else {
+            return;
+        };
+        if let Some(bytes) = read_peak_rss_bytes(pid) {
+            let mut peak = self.peak_memory_bytes.write();
+            *peak = (*peak).max(bytes);
+        }
+    }
+
+    /// Current status in persistable form.
+    fn snapshot(&self) -> ExecutionSnapshot {
+        ExecutionSnapshot {
+            execution_id: self.id.clone(),
+            task: self.task.clone(),
+            project_root: self.project_root.clone(),
+            state: self.state.read().as_str_name().to_string(),
+            current_iteration: *self.current_iteration.read(),
+            max_iterations: self.config.max_iterations,
+            current_score: *self.current_score.read(),
+            quality_threshold: self.config.quality_threshold,
+            termination_reason: self.termination_reason.read().clone().unwrap_or_default(),
+            started_at: self.started_at,
+            ended_at: *self.ended_at.read(),
+            duration_seconds: self.active_duration_seconds(),
+            total_cost_usd: *self.total_cost_usd.read(),
+            total_input_tokens: *self.total_input_tokens.read() as i64,
+            total_output_tokens: *self.total_output_tokens.read() as i64,
+            evidence: (&*self.evidence.read()).into(),
+            run_instructions: self.run_instructions.read().as_ref().map(Into::into),
+            pid: *self.process_pid.read(),
+        }
+    }
+
+    /// Persist the current status to `{metrics_dir}/executions/{id}.json`
+    /// so it survives a daemon restart. Failures are logged, never propagated.
+    fn write_snapshot(&self) {
+        let dir = history::snapshot_dir(&self.metrics_dir());
+        if let Err(e) = history::write_snapshot(&dir, &self.snapshot()) {
+            warn!(execution_id = %self.id, error = %e, "Failed to write execution snapshot");
+            return;
+        }
+        // Index the directory once so a restarted daemon finds it again
+        if !self.snapshot_dir_indexed.swap(true, std::sync::atomic::Ordering::Relaxed) {
+            if let Some(state_dir) = &self.state_dir {
+                if let Err(e) = history::register_snapshot_dir(state_dir, &dir) {
+                    warn!(execution_id = %self.id, error = %e, "Failed to index snapshot directory");
+                }
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Stream-JSON parsing
+    // -----------------------------------------------------------------------
+
+    /// Dispatch one stdout line. Returns true for the `result` event that
+    /// ends the turn.
+    fn parse_stream_json_line(&self, line: &str) -> bool {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || !trimmed.starts_with('{') {
+            return false;
+        }
+
+        let event: StreamJsonEvent = match serde_json::from_str(trimmed) {
+            Ok(e) => e,
+            Err(e) => {
+                warn!(error = %e, "Skipping non-JSON or unrecognised line");
+                return false;
+            }
+        };
+
+        match event.event_type.as_str() {
+            "system" => self.handle_system_event(&event),
+            "assistant" => self.handle_assistant_event(&event),
+            "user" => self.handle_user_event(&event),
+            "stream_event" => self.handle_partial_message_event(&event),
+            "result" => {
+                self.handle_result_event(&event);
+                return true;
+            }
+            other => {
+                debug!(event_type = other, "Ignoring unknown stream-json event type");
+            }
+        }
+        false
+    }
+
+    /// Node that tools called from this message hang off: the subagent's
+    /// node for messages produced inside a subagent, else `node_id`.
+    fn tool_parent_node(&self, parent_tool_use_id: Option<&str>, node_id: &str) -> String {
+        if let Some(parent_id) = parent_tool_use_id {
+            let subagent_node = format!("subagent-{}", parent_id);
+            if self.node_depths.read().contains_key(&subagent_node) {
+                return subagent_node;
+            }
+        }
+        node_id.to_string()
+    }
+
+    /// Feed `tool_use` content-block events to the input assembler and
+    /// handle each tool once its block stops.
+    fn handle_partial_message_event(&self, event: &StreamJsonEvent) {
+        let scope = event.parent_tool_use_id.as_deref();
+        match &event.event {
+            Some(PartialMessageEvent::ContentBlockStart { index, content_block })
+                if content_block.get("type").and_then(|t| t.as_str()) == Some("tool_use") =>
+            {
+                let field = |key| content_block.get(key).and_then(|v| v.as_str()).unwrap_or("");
+                let node_id = format!("iter-{}", *self.current_iteration.read());
+                let parent = self.tool_parent_node(scope, &node_id);
+                self.tool_inputs.write().start(scope, *index, field("id"), field("name"), &parent);
+            }
+            Some(PartialMessageEvent::ContentBlockDelta { index, delta })
+                if delta.get("type").and_then(|t| t.as_str()) == Some("input_json_delta") =>
+            {
+                let partial_json = delta.get("partial_json").and_then(|v| v.as_str()).unwrap_or("");
+                self.tool_inputs.write().delta(scope, *index, partial_json);
+            }
+            Some(PartialMessageEvent::ContentBlockStop { index }) => {
+                let assembled = self.tool_inputs.write().stop(scope, *index);
+                if let Some((id, name, input, parent)) = assembled {
+                    self.handle_tool_use(&id, &name, &input, &parent);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_system_event(&self, event: &StreamJsonEvent) {
+        if event.subtype.as_deref() == Some("init") {
+            self.emit_event(AgentEvent {
+                execution_id: self.id.clone(),
+                sequence: 0,
+                timestamp: Self::now_timestamp(),
+                event: Some(agent_event::Event::LogMessage(LogMessage {
+                    level: LogLevel::Info as i32,
+                    message: "Claude session initialised".to_string(),
+                    source: "claude-cli".to_string(),
+                })),
+            });
+        }
+    }
+
+    fn handle_assistant_event(&self, event: &StreamJsonEvent) {
+        let message = match &event.message {
+            Some(m) => m,
+            None => return,
+        };
+
+        // Accumulate token usage
+        if let Some(usage) = &message.usage {
+            *self.total_input_tokens.write() += usage.input_tokens;
+            *self.total_output_tokens.write() += usage.output_tokens;
+            // Subagent messages are costed from their Task result instead
+            if event.parent_tool_use_id.is_none() {
+                self.record_estimated_usage(message.model.as_deref(), usage);
+            }
+        }
+
+        // Each assistant message counts as one turn
+        let iteration = {
+            let mut iter = self.current_iteration.write();
             *iter += 1;
             *iter
         };
 
         let node_id = format!("iter-{}", iteration);
+        self.node_depths.write().insert(node_id.clone(), 0);
 
         self.emit_event(AgentEvent {
             execution_id: self.id.clone(),
+            sequence: 0,
             timestamp: Self::now_timestamp(),
             event: Some(agent_event::Event::IterationStarted(IterationStarted {
                 iteration,
@@ -568,15 +1595,21 @@ impl ExecutionInner {
             })),
         });
 
+        let tool_parent_id = self.tool_parent_node(event.parent_tool_use_id.as_deref(), &node_id);
+
         for block in &message.content {
             match block {
                 ContentBlock::ToolUse { id, name, input } => {
-                    self.handle_tool_use(id, name, input, &node_id);
+                    // Skip tools already released from their streamed deltas
+                    if self.tool_inputs.write().release(id) {
+                        self.handle_tool_use(id, name, input, &tool_parent_id);
+                    }
                 }
                 ContentBlock::Text { text } => {
                     let truncated = truncate_str(text, 200);
                     self.emit_event(AgentEvent {
                         execution_id: self.id.clone(),
+                        sequence: 0,
                         timestamp: Self::now_timestamp(),
                         event: Some(agent_event::Event::LogMessage(LogMessage {
                             level: LogLevel::Info as i32,
@@ -588,7 +1621,7 @@ impl ExecutionInner {
                 ContentBlock::ToolResult { tool_use_id, content } => {
                     // Correlate tool result with its invocation
                     if let Some(use_id) = tool_use_id {
-                        self.correlate_tool_result(use_id, content);
+                        self.correlate_tool_result(use_id, content, event.tool_use_result.as_ref());
                     }
                     // Extract text from inline tool results for test detection
                     if let Some(serde_json::Value::String(text)) = content {
@@ -616,6 +1649,7 @@ impl ExecutionInner {
 
             self.emit_event(AgentEvent {
                 execution_id: self.id.clone(),
+                sequence: 0,
                 timestamp: Self::now_timestamp(),
                 event: Some(agent_event::Event::ScoreUpdated(ScoreUpdated {
                     old_score,
@@ -627,6 +1661,26 @@ impl ExecutionInner {
         }
     }
 
+    /// Emit a `FileChanged` edit for `path` and record it in evidence.
+    fn record_edit(&self, path: String, lines_added: i32, lines_removed: i32, node_id: &str) {
+        self.emit_event(AgentEvent {
+            execution_id: self.id.clone(),
+            sequence: 0,
+            timestamp: Self::now_timestamp(),
+            event: Some(agent_event::Event::FileChanged(FileChanged {
+                path: path.clone(),
+                action: FileAction::Edit as i32,
+                lines_added,
+                lines_removed,
+                node_id: node_id.to_string(),
+            })),
+        });
+        let mut ev = self.evidence.write();
+        if !ev.files_edited.contains(&path) {
+            ev.files_edited.push(path);
+        }
+    }
+
     fn handle_tool_use(
         &self,
         id: &str,
@@ -635,8 +1689,11 @@ impl ExecutionInner {
         parent_node_id: &str,
     ) {
         let node_id = id.to_string();
+        let depth = self.node_depth(parent_node_id) + 1;
+        self.node_depths.write().insert(node_id.clone(), depth);
         let file_path = input
             .get("file_path")
+            .or_else(|| input.get("notebook_path"))
             .or_else(|| input.get("path"))
             .or_else(|| input.get("pattern"))
             .and_then(|v| v.as_str())
@@ -650,6 +1707,8 @@ impl ExecutionInner {
             } else {
                 "Bash".to_string()
             }
+        } else if let Some(source) = external_source(name, input) {
+            format!("{name}: {}", truncate_str(&source, 100))
         } else if file_path.is_empty() {
             format!("{name}")
         } else {
@@ -659,29 +1718,34 @@ impl ExecutionInner {
         // Serialize full input for telemetry
         let tool_input = serde_json::to_string(input).unwrap_or_default();
 
+        self.check_tool_loop(name, &tool_input);
+
         // Store pending tool use for correlation
         self.pending_tool_uses.write().insert(id.to_string(), PendingToolUse {
             tool_name: name.to_string(),
             tool_input: tool_input.clone(),
             node_id: node_id.clone(),
             parent_node_id: parent_node_id.to_string(),
+            depth,
         });
 
         // Emit ToolInvoked for every tool
         self.emit_event(AgentEvent {
             execution_id: self.id.clone(),
+            sequence: 0,
             timestamp: Self::now_timestamp(),
             event: Some(agent_event::Event::ToolInvoked(ToolInvoked {
                 tool_name: name.to_string(),
                 summary: summary.clone(),
                 blocked: false,
                 block_reason: String::new(),
-                depth: 1,
+                depth,
                 node_id: node_id.clone(),
                 parent_node_id: parent_node_id.to_string(),
                 tool_input,
                 tool_output: String::new(),
                 tool_use_id: id.to_string(),
+                tool_output_truncated: false,
             })),
         });
 
@@ -691,11 +1755,14 @@ impl ExecutionInner {
                 if !file_path.is_empty() {
                     self.emit_event(AgentEvent {
                         execution_id: self.id.clone(),
+                        sequence: 0,
                         timestamp: Self::now_timestamp(),
                         event: Some(agent_event::Event::FileChanged(FileChanged {
                             path: file_path.clone(),
                             action: FileAction::Write as i32,
-                            lines_added: 0,
+                            lines_added: count_lines(
+                                input.get("content").and_then(|v| v.as_str()).unwrap_or(""),
+                            ),
                             lines_removed: 0,
                             node_id: node_id.clone(),
                         })),
@@ -714,6 +1781,7 @@ impl ExecutionInner {
                                 .unwrap_or_default();
                             self.emit_event(AgentEvent {
                                 execution_id: self.id.clone(),
+                                sequence: 0,
                                 timestamp: Self::now_timestamp(),
                                 event: Some(agent_event::Event::ArtifactWritten(ArtifactWritten {
                                     obsidian_path: file_path.clone(),
@@ -732,27 +1800,31 @@ impl ExecutionInner {
             }
             "Edit" => {
                 if !file_path.is_empty() {
-                    self.emit_event(AgentEvent {
-                        execution_id: self.id.clone(),
-                        timestamp: Self::now_timestamp(),
-                        event: Some(agent_event::Event::FileChanged(FileChanged {
-                            path: file_path.clone(),
-                            action: FileAction::Edit as i32,
-                            lines_added: 0,
-                            lines_removed: 0,
-                            node_id: node_id.clone(),
-                        })),
-                    });
-                    let mut ev = self.evidence.write();
-                    if !ev.files_edited.contains(&file_path) {
-                        ev.files_edited.push(file_path);
-                    }
+                    let (lines_added, lines_removed) = diff_line_counts(
+                        input.get("old_string").and_then(|v| v.as_str()).unwrap_or(""),
+                        input.get("new_string").and_then(|v| v.as_str()).unwrap_or(""),
+                    );
+                    self.record_edit(file_path, lines_added, lines_removed, &node_id);
+                }
+            }
+            "MultiEdit" => {
+                for (path, lines_added, lines_removed) in multi_edit_changes(&file_path, input) {
+                    self.record_edit(path, lines_added, lines_removed, &node_id);
                 }
             }
+            "NotebookEdit" if !file_path.is_empty() => {
+                let source = input.get("new_source").and_then(|v| v.as_str()).unwrap_or("");
+                let lines_added = match input.get("edit_mode").and_then(|v| v.as_str()) {
+                    Some("delete") => 0,
+                    _ => count_lines(source),
+                };
+                self.record_edit(file_path, lines_added, 0, &node_id);
+            }
             "Read" | "Glob" | "Grep" => {
                 if !file_path.is_empty() {
                     self.emit_event(AgentEvent {
                         execution_id: self.id.clone(),
+                        sequence: 0,
                         timestamp: Self::now_timestamp(),
                         event: Some(agent_event::Event::FileChanged(FileChanged {
                             path: file_path,
@@ -767,6 +1839,14 @@ impl ExecutionInner {
             "Bash" => {
                 self.evidence.write().commands_run += 1;
             }
+            "WebFetch" | "WebSearch" => {
+                if let Some(source) = external_source(name, input) {
+                    let mut ev = self.evidence.write();
+                    if !ev.external_sources.contains(&source) {
+                        ev.external_sources.push(source);
+                    }
+                }
+            }
             "Task" => {
                 let subagent_type = input
                     .get("subagent_type")
@@ -779,69 +1859,178 @@ impl ExecutionInner {
                     .unwrap_or("")
                     .to_string();
                 self.evidence.write().subagents_spawned += 1;
+                let subagent_node_id = format!("subagent-{}", id);
+                self.node_depths.write().insert(subagent_node_id.clone(), depth);
                 self.emit_event(AgentEvent {
                     execution_id: self.id.clone(),
+                    sequence: 0,
                     timestamp: Self::now_timestamp(),
                     event: Some(agent_event::Event::SubagentSpawned(SubagentSpawned {
                         subagent_id: id.to_string(),
                         subagent_type,
                         task_summary: description,
-                        depth: 1,
-                        node_id: format!("subagent-{}", id),
+                        depth,
+                        node_id: subagent_node_id,
                         parent_node_id: parent_node_id.to_string(),
                     })),
                 });
             }
             _ => {
-                // Other tools (WebFetch, etc.) — already covered by ToolInvoked
+                // Other tools — already covered by ToolInvoked
+            }
+        }
+    }
+
+    /// Record a tool call and report a loop once the same call has been made
+    /// more than `max_repeated_tool_calls` times in a row.
+    fn check_tool_loop(&self, name: &str, tool_input: &str) {
+        use std::hash::{Hash, Hasher};
+
+        let limit = self.config.max_repeated_tool_calls;
+        if limit <= 0 || *self.terminated.read() {
+            return;
+        }
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        (name, tool_input).hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let streak = {
+            let mut recent = self.recent_tool_calls.write();
+            if recent.len() >= TOOL_CALL_WINDOW {
+                recent.pop_front();
             }
+            recent.push_back(hash);
+            recent.iter().rev().take_while(|&&h| h == hash).count()
+        };
+
+        // Fire once per streak, when it first exceeds the limit (or fills the window)
+        let threshold = (limit as usize + 1).min(TOOL_CALL_WINDOW);
+        if streak != threshold {
+            return;
+        }
+
+        let message = format!(
+            "Loop detected: {} called {} times in a row with identical input",
+            name, streak
+        );
+        warn!(execution_id = %self.id, tool = %name, repeats = streak, "Tool-call loop detected");
+        self.emit_event(AgentEvent {
+            execution_id: self.id.clone(),
+            sequence: 0,
+            timestamp: Self::now_timestamp(),
+            event: Some(agent_event::Event::LogMessage(LogMessage {
+                level: LogLevel::Warn as i32,
+                message: message.clone(),
+                source: "loop-detector".to_string(),
+            })),
+        });
+
+        if self.config.stop_on_loop {
+            self.terminate(message);
         }
     }
 
+    /// Stop the run from within the daemon: mark it Failed with `reason` and
+    /// signal the child to exit. run_execution() keeps this outcome.
+    fn terminate(&self, reason: String) {
+        if *self.terminated.read() {
+            return;
+        }
+        info!(execution_id = %self.id, reason = %reason, "Terminating execution");
+        *self.terminated.write() = true;
+        *self.state.write() = ExecutionState::Failed;
+        *self.termination_reason.write() = Some(reason);
+        self.kill_process(false);
+    }
+
+    /// Depth of a previously emitted node; unknown parents are treated as roots.
+    fn node_depth(&self, node_id: &str) -> i32 {
+        self.node_depths.read().get(node_id).copied().unwrap_or(0)
+    }
+
     /// Correlate a tool result with its pending invocation.
     fn correlate_tool_result(
         &self,
         tool_use_id: &str,
         content: &Option<serde_json::Value>,
+        tool_use_result: Option<&serde_json::Value>,
     ) {
+        // A result is the stop for any input still being assembled
+        let assembled = self.tool_inputs.write().finish(tool_use_id);
+        if let Some((id, name, input, parent)) = assembled {
+            self.handle_tool_use(&id, &name, &input, &parent);
+        }
+
         let pending = self.pending_tool_uses.write().remove(tool_use_id);
         if let Some(pending) = pending {
-            let tool_output = match content {
-                Some(serde_json::Value::String(s)) => truncate_str(s, 2000),
+            let full_output = match content {
+                Some(serde_json::Value::String(s)) => s.clone(),
                 Some(serde_json::Value::Array(arr)) => {
                     let texts: Vec<String> = arr.iter()
                         .filter_map(|item| item.get("text").and_then(|t| t.as_str()).map(String::from))
                         .collect();
-                    truncate_str(&texts.join("\n"), 2000)
+                    texts.join("\n")
                 }
                 _ => String::new(),
             };
+            let tool_output_truncated = full_output.chars().nth(MAX_TOOL_OUTPUT_CHARS).is_some();
+            let tool_output = truncate_str(&full_output, MAX_TOOL_OUTPUT_CHARS);
+            if tool_output_truncated {
+                let mut full = self.full_tool_outputs.write();
+                if full.len() == MAX_RETAINED_FULL_OUTPUTS {
+                    full.pop_front();
+                }
+                full.push_back((tool_use_id.to_string(), full_output));
+            }
 
             let is_task_tool = pending.tool_name == "Task";
+            let decision = decision_from_tool_result(
+                &pending.tool_name,
+                &pending.tool_input,
+                &tool_output,
+            );
 
             if !tool_output.is_empty() {
                 self.emit_event(AgentEvent {
                     execution_id: self.id.clone(),
+                    sequence: 0,
                     timestamp: Self::now_timestamp(),
                     event: Some(agent_event::Event::ToolInvoked(ToolInvoked {
                         tool_name: pending.tool_name,
                         summary: "(result)".to_string(),
                         blocked: false,
                         block_reason: String::new(),
-                        depth: 1,
+                        depth: pending.depth,
                         node_id: format!("{}-result", pending.node_id),
                         parent_node_id: pending.parent_node_id.clone(),
                         tool_input: pending.tool_input,
                         tool_output: tool_output.clone(),
                         tool_use_id: tool_use_id.to_string(),
+                        tool_output_truncated,
                     })),
                 });
             }
 
+            if let Some(mut decision) = decision {
+                decision.tool_use_id = tool_use_id.to_string();
+                decision.node_id = format!("{}-decision", pending.node_id);
+                self.emit_event(AgentEvent {
+                    execution_id: self.id.clone(),
+                    sequence: 0,
+                    timestamp: Self::now_timestamp(),
+                    event: Some(agent_event::Event::DecisionMade(decision)),
+                });
+            }
+
             // Emit SubagentCompleted when a Task tool result arrives
             if is_task_tool {
+                if let Some(usage) = tool_use_result.and_then(SubagentUsage::from_result) {
+                    self.record_subagent_usage(&pending.node_id, usage);
+                }
                 self.emit_event(AgentEvent {
                     execution_id: self.id.clone(),
+                    sequence: 0,
                     timestamp: Self::now_timestamp(),
                     event: Some(agent_event::Event::SubagentCompleted(SubagentCompleted {
                         subagent_id: pending.node_id.clone(),
@@ -854,6 +2043,29 @@ impl ExecutionInner {
         }
     }
 
+    /// Attribute a subagent's tokens and cost to this execution's totals.
+    fn record_subagent_usage(&self, subagent_id: &str, usage: SubagentUsage) {
+        *self.total_input_tokens.write() += usage.input_tokens;
+        *self.total_output_tokens.write() += usage.output_tokens;
+        *self.subagent_cost_usd.write() += usage.cost_usd;
+        *self.total_cost_usd.write() += usage.cost_usd;
+        self.check_cost_budget();
+
+        self.emit_event(AgentEvent {
+            execution_id: self.id.clone(),
+            sequence: 0,
+            timestamp: Self::now_timestamp(),
+            event: Some(agent_event::Event::LogMessage(LogMessage {
+                level: LogLevel::Info as i32,
+                message: format!(
+                    "Subagent {} used {} input / {} output tokens (${:.4})",
+                    subagent_id, usage.input_tokens, usage.output_tokens, usage.cost_usd
+                ),
+                source: "subagent-usage".to_string(),
+            })),
+        });
+    }
+
     fn handle_user_event(&self, event: &StreamJsonEvent) {
         // User events carry tool_use_result payloads
         if let Some(result) = &event.tool_use_result {
@@ -893,7 +2105,12 @@ impl ExecutionInner {
         // Also inspect the message content blocks if present
         if let Some(message) = &event.message {
             for block in &message.content {
-                if let ContentBlock::ToolResult { content, .. } = block {
+                if let ContentBlock::ToolResult { tool_use_id, content } = block {
+                    // Tool results (including Task subagent results) usually
+                    // arrive here rather than in assistant messages
+                    if let Some(use_id) = tool_use_id {
+                        self.correlate_tool_result(use_id, content, event.tool_use_result.as_ref());
+                    }
                     if let Some(serde_json::Value::String(text)) = content {
                         self.try_detect_test_results(text);
                     } else if let Some(serde_json::Value::Array(arr)) = content {
@@ -909,6 +2126,12 @@ impl ExecutionInner {
     }
 
     fn try_detect_test_results(&self, output: &str) {
+        // Coverage may be reported with or without a test summary
+        let coverage = Self::parse_coverage_percent(output);
+        if let Some(pct) = coverage {
+            *self.coverage_percent.write() = Some(pct);
+        }
+
         // pytest: "X passed, Y failed, Z skipped" or "X passed"
         if let Some((framework, passed, failed, skipped)) = Self::parse_pytest_summary(output)
             .or_else(|| Self::parse_cargo_test_summary(output))
@@ -921,13 +2144,14 @@ impl ExecutionInner {
 
             self.emit_event(AgentEvent {
                 execution_id: self.id.clone(),
+                sequence: 0,
                 timestamp: Self::now_timestamp(),
                 event: Some(agent_event::Event::TestResult(TestResult {
                     framework,
                     passed,
                     failed,
                     skipped,
-                    coverage_percent: 0.0,
+                    coverage_percent: coverage.unwrap_or(0.0),
                     failed_tests: vec![],
                     node_id: format!("test-{}", Uuid::new_v4()),
                 })),
@@ -944,6 +2168,16 @@ impl ExecutionInner {
         Some(("pytest".to_string(), passed, failed, skipped))
     }
 
+    /// Parse a coverage percentage from coverage.py's TOTAL row or
+    /// tarpaulin's "NN.NN% coverage" summary.
+    fn parse_coverage_percent(output: &str) -> Option<f32> {
+        let caps = COVERAGE_PY_RE
+            .captures(output)
+            .or_else(|| TARPAULIN_RE.captures(output))?;
+        let pct: f32 = caps.get(1)?.as_str().parse().ok()?;
+        Some(pct.clamp(0.0, 100.0))
+    }
+
     /// Parse cargo test summary: "test result: ok. X passed; Y failed; Z ignored"
     fn parse_cargo_test_summary(output: &str) -> Option<(String, i32, i32, i32)> {
         let caps = CARGO_TEST_RE.captures(output)?;
@@ -954,9 +2188,16 @@ impl ExecutionInner {
     }
 
     fn handle_result_event(&self, event: &StreamJsonEvent) {
+        // Tools still assembling at the end of the run never got a result
+        let unfinished = self.tool_inputs.write().finish_all();
+        for (id, name, input, parent) in unfinished {
+            self.handle_tool_use(&id, &name, &input, &parent);
+        }
+
         let num_turns = event.num_turns.unwrap_or(0);
         let is_error = event.is_error.unwrap_or(false);
-        let cost = event.total_cost_usd.unwrap_or(0.0);
+        // The CLI's total excludes Task subagents; add their recorded spend
+        let cost = event.total_cost_usd.unwrap_or(0.0) + *self.subagent_cost_usd.read();
         let duration_ms = event.duration_ms.unwrap_or(0.0);
 
         // Store cost
@@ -979,6 +2220,7 @@ impl ExecutionInner {
         if !truncated.is_empty() {
             self.emit_event(AgentEvent {
                 execution_id: self.id.clone(),
+                sequence: 0,
                 timestamp: Self::now_timestamp(),
                 event: Some(agent_event::Event::LogMessage(LogMessage {
                     level: if is_error { LogLevel::Error as i32 } else { LogLevel::Info as i32 },
@@ -1002,6 +2244,7 @@ impl ExecutionInner {
         let output_toks = *self.total_output_tokens.read();
         self.emit_event(AgentEvent {
             execution_id: self.id.clone(),
+            sequence: 0,
             timestamp: Self::now_timestamp(),
             event: Some(agent_event::Event::IterationCompleted(IterationCompleted {
                 iteration,
@@ -1033,6 +2276,7 @@ impl ExecutionInner {
         };
         self.emit_event(AgentEvent {
             execution_id: self.id.clone(),
+            sequence: 0,
             timestamp: Self::now_timestamp(),
             event: Some(agent_event::Event::ScoreUpdated(ScoreUpdated {
                 old_score,
@@ -1050,6 +2294,68 @@ impl ExecutionInner {
             is_error = is_error,
             "Execution result received"
         );
+
+        self.check_cost_budget();
+    }
+
+    /// Add a main-agent message's estimated cost to the running total so the
+    /// budget is enforced mid-run, not only when the CLI reports its result.
+    fn record_estimated_usage(&self, model: Option<&str>, usage: &UsageInfo) {
+        let model = model.unwrap_or(&self.config.model);
+        let Some(cost) = usage.estimate_cost(model) else {
+            debug!(execution_id = %self.id, model, "No pricing for model; cost not estimated");
+            return;
+        };
+        let estimated = {
+            let mut estimated = self.estimated_cost_usd.write();
+            *estimated += cost;
+            *estimated
+        };
+        *self.total_cost_usd.write() = estimated + *self.subagent_cost_usd.read();
+        self.check_cost_budget();
+    }
+
+    /// Compare spend against `max_cost_usd`: warn once at
+    /// `COST_WARNING_FRACTION` of the budget and stop the run once exceeded.
+    fn check_cost_budget(&self) {
+        let budget = self.config.max_cost_usd;
+        if budget <= 0.0 || *self.terminated.read() {
+            return;
+        }
+        let cost = *self.total_cost_usd.read();
+
+        if cost > budget {
+            let reason = format!("Cost budget exceeded: ${:.4} of ${:.2}", cost, budget);
+            warn!(execution_id = %self.id, cost_usd = cost, budget_usd = budget, "Cost budget exceeded");
+            self.emit_event(AgentEvent {
+                execution_id: self.id.clone(),
+                sequence: 0,
+                timestamp: Self::now_timestamp(),
+                event: Some(agent_event::Event::LogMessage(LogMessage {
+                    level: LogLevel::Error as i32,
+                    message: reason.clone(),
+                    source: "cost-budget".to_string(),
+                })),
+            });
+            self.terminate(reason);
+        } else if cost >= budget * COST_WARNING_FRACTION && !*self.cost_warned.read() {
+            *self.cost_warned.write() = true;
+            self.emit_event(AgentEvent {
+                execution_id: self.id.clone(),
+                sequence: 0,
+                timestamp: Self::now_timestamp(),
+                event: Some(agent_event::Event::LogMessage(LogMessage {
+                    level: LogLevel::Warn as i32,
+                    message: format!(
+                        "Cost ${:.4} has reached {:.0}% of the ${:.2} budget",
+                        cost,
+                        cost / budget * 100.0,
+                        budget
+                    ),
+                    source: "cost-budget".to_string(),
+                })),
+            });
+        }
     }
 
     /// Try to extract run instructions from result text.
@@ -1136,7 +2442,7 @@ impl ExecutionInner {
             code_changes: files_score / 50.0,
             tests_run: if ev.tests_run { 1.0 } else { 0.0 },
             tests_pass: if ev.tests_run && ev.tests_failed == 0 { 1.0 } else { 0.0 },
-            coverage: 0.0,
+            coverage: self.coverage_percent.read().map_or(0.0, |pct| pct / 100.0),
             no_errors: if !ev.tests_run || ev.tests_failed == 0 { 1.0 } else { 0.0 },
             breakdown: vec![
                 ScoreDimension {
@@ -1175,12 +2481,21 @@ impl ExecutionInner {
         }
     }
 
-    fn emit_event(&self, event: AgentEvent) {
+    fn emit_event(&self, mut event: AgentEvent) {
+        // Sequence, store and broadcast under the history lock so that
+        // subscribe_from() sees every event exactly once
+        let mut history = self.event_history.write();
+        event.sequence = {
+            let mut last = self.last_sequence.write();
+            *last += 1;
+            *last
+        };
+
         // Write to JSONL
         if let Some(ref mut writer) = *self.jsonl_writer.write() {
             use std::io::Write;
             if let Some(ref evt) = event.event {
-                let json_line = match evt {
+                let mut json_line = match evt {
                     agent_event::Event::ToolInvoked(e) => serde_json::json!({
                         "execution_id": event.execution_id,
                         "event_type": "tool_invoked",
@@ -1188,6 +2503,7 @@ impl ExecutionInner {
                         "summary": e.summary,
                         "tool_input": e.tool_input,
                         "tool_output": e.tool_output,
+                        "tool_output_truncated": e.tool_output_truncated,
                         "tool_use_id": e.tool_use_id,
                     }),
                     agent_event::Event::IterationCompleted(e) => serde_json::json!({
@@ -1199,23 +2515,43 @@ impl ExecutionInner {
                         "input_tokens": e.input_tokens,
                         "output_tokens": e.output_tokens,
                     }),
+                    agent_event::Event::FileChanged(e) => serde_json::json!({
+                        "execution_id": event.execution_id,
+                        "event_type": "file_changed",
+                        "path": e.path,
+                        "action": e.action().as_str_name(),
+                        "lines_added": e.lines_added,
+                        "lines_removed": e.lines_removed,
+                    }),
+                    agent_event::Event::TestResult(e) => serde_json::json!({
+                        "execution_id": event.execution_id,
+                        "event_type": "test_result",
+                        "framework": e.framework,
+                        "passed": e.passed,
+                        "failed": e.failed,
+                        "skipped": e.skipped,
+                    }),
                     _ => serde_json::json!({
                         "execution_id": event.execution_id,
                         "event_type": "other",
                     }),
                 };
+                // Timestamped so the dashboard can bucket history by time
+                let timestamp = event
+                    .timestamp
+                    .as_ref()
+                    .and_then(|t| chrono::DateTime::from_timestamp(t.seconds, t.nanos.max(0) as u32))
+                    .unwrap_or_else(Utc::now);
+                json_line["timestamp"] = serde_json::json!(timestamp.to_rfc3339());
                 let _ = writeln!(writer, "{}", json_line);
             }
         }
 
         // Store in history with bounded size
-        {
-            let mut history = self.event_history.write();
-            if history.len() >= MAX_EVENT_HISTORY {
-                history.pop_front();
-            }
-            history.push_back(event.clone());
+        while history.len() >= self.max_event_history {
+            history.pop_front();
         }
+        history.push_back(event.clone());
 
         // Broadcast to subscribers (ignore errors if no receivers)
         let _ = self.event_tx.send(event);
@@ -1228,6 +2564,17 @@ impl ExecutionInner {
             nanos: now.timestamp_subsec_nanos() as i32,
         })
     }
+
+    /// Wall-clock time since start minus any time spent paused.
+    fn active_duration_seconds(&self) -> f32 {
+        let end = self.ended_at.read().unwrap_or_else(Utc::now);
+        let mut paused = *self.paused_total.read();
+        if let Some(since) = *self.paused_at.read() {
+            paused += (end - since).max(chrono::Duration::zero());
+        }
+        let active = (end - self.started_at - paused).max(chrono::Duration::zero());
+        active.num_milliseconds() as f32 / 1000.0
+    }
 }
 
 impl ExecutionHandle {
@@ -1240,74 +2587,77 @@ impl ExecutionHandle {
         *self.inner.state.write() = ExecutionState::Cancelled;
         *self.inner.termination_reason.write() = Some("Stopped by user".to_string());
         // Kill the child process via stored PID
-        #[cfg(unix)]
-        if let Some(pid) = *self.inner.process_pid.read() {
-            let signal = if force { libc::SIGKILL } else { libc::SIGTERM };
-            // Safety: sending a signal to a known PID is safe
-            let ret = unsafe { libc::kill(pid as i32, signal) };
-            if ret != 0 {
-                let err = std::io::Error::last_os_error();
-                warn!(execution_id = %self.inner.id, pid = pid, error = %err, "Failed to kill child process");
-            }
-        }
+        self.inner.kill_process(force);
     }
 
     pub async fn pause(&self) {
         *self.inner.state.write() = ExecutionState::Paused;
+        self.inner.paused_at.write().get_or_insert_with(Utc::now);
+        // Suspend the child so it stops consuming CPU and tokens
+        #[cfg(unix)]
+        self.inner.signal_process(libc::SIGSTOP);
     }
 
     pub async fn resume(&self) {
         *self.inner.state.write() = ExecutionState::Running;
+        if let Some(since) = self.inner.paused_at.write().take() {
+            *self.inner.paused_total.write() += Utc::now() - since;
+        }
+        #[cfg(unix)]
+        self.inner.signal_process(libc::SIGCONT);
     }
 
-    /// Write input to the child process's stdin pipe.
+    /// Send input to the running claude process as a stream-json user
+    /// message. Fails once the turn has ended (stdin is closed then), if the
+    /// process has closed stdin, or if it stops draining it for
+    /// `STDIN_WRITE_TIMEOUT`.
     pub async fn send_input(&self, input: &str) -> Result<()> {
         let mut guard = self.inner.child_stdin.write().await;
-        if let Some(ref mut stdin) = *guard {
-            stdin.write_all(input.as_bytes()).await?;
-            stdin.write_all(b"\n").await?;
-            stdin.flush().await?;
-            Ok(())
-        } else {
+        let Some(ref mut stdin) = *guard else {
             anyhow::bail!("stdin pipe not available (process may have exited)")
+        };
+
+        let line = user_message_line(input);
+        let write = async {
+            stdin.write_all(line.as_bytes()).await?;
+            stdin.flush().await
+        };
+        match tokio::time::timeout(STDIN_WRITE_TIMEOUT, write).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) if e.kind() == std::io::ErrorKind::BrokenPipe => {
+                *guard = None;
+                anyhow::bail!("claude process closed its stdin; input not delivered")
+            }
+            Ok(Err(e)) => return Err(e).context("Failed to write to claude stdin"),
+            Err(_) => anyhow::bail!(
+                "claude process is not reading stdin (write timed out after {}s)",
+                STDIN_WRITE_TIMEOUT.as_secs()
+            ),
         }
+        drop(guard);
+
+        info!(execution_id = %self.inner.id, len = input.len(), "Sent user input");
+        self.inner.emit_event(AgentEvent {
+            execution_id: self.inner.id.clone(),
+            sequence: 0,
+            timestamp: ExecutionInner::now_timestamp(),
+            event: Some(agent_event::Event::LogMessage(LogMessage {
+                level: LogLevel::Info as i32,
+                message: truncate_str(input, 200),
+                source: "user-input".to_string(),
+            })),
+        });
+        Ok(())
     }
 
     pub async fn get_status(&self) -> ExecutionStatus {
-        ExecutionStatus {
-            execution_id: self.inner.id.clone(),
-            task: self.inner.task.clone(),
-            state: *self.inner.state.read() as i32,
-            current_iteration: *self.inner.current_iteration.read(),
-            max_iterations: self.inner.config.max_iterations,
-            current_score: *self.inner.current_score.read(),
-            quality_threshold: self.inner.config.quality_threshold,
-            termination_reason: self.inner.termination_reason.read().clone().unwrap_or_default(),
-            evidence: Some(self.inner.evidence.read().clone()),
-            started_at: Some(Timestamp {
-                seconds: self.inner.started_at.timestamp(),
-                nanos: self.inner.started_at.timestamp_subsec_nanos() as i32,
-            }),
-            ended_at: self.inner.ended_at.read().map(|dt| Timestamp {
-                seconds: dt.timestamp(),
-                nanos: dt.timestamp_subsec_nanos() as i32,
-            }),
-            total_cost_usd: *self.inner.total_cost_usd.read(),
-            total_input_tokens: *self.inner.total_input_tokens.read() as i64,
-            total_output_tokens: *self.inner.total_output_tokens.read() as i64,
-        }
-    }
-
-    pub fn to_summary(&self) -> ExecutionSummary {
-        let duration = {
-            let ended = self.inner.ended_at.read();
-            match *ended {
-                Some(end) => (end - self.inner.started_at).num_milliseconds() as f32 / 1000.0,
-                None => (Utc::now() - self.inner.started_at).num_milliseconds() as f32 / 1000.0,
-            }
-        };
-
-        ExecutionSummary {
+        self.get_status_sync()
+    }
+
+    pub fn to_summary(&self) -> ExecutionSummary {
+        let duration = self.inner.active_duration_seconds();
+
+        ExecutionSummary {
             execution_id: self.inner.id.clone(),
             task: self.inner.task.clone(),
             state: *self.inner.state.read() as i32,
@@ -1325,7 +2675,7 @@ impl ExecutionHandle {
 
     pub fn get_detail(&self) -> GetExecutionDetailResponse {
         let status = self.get_status_sync();
-        let events = self.inner.event_history.read().iter().cloned().collect();
+        let events = self.get_event_history();
         let run_instructions = self.inner.run_instructions.read().clone();
 
         GetExecutionDetailResponse {
@@ -1357,6 +2707,9 @@ impl ExecutionHandle {
             total_cost_usd: *self.inner.total_cost_usd.read(),
             total_input_tokens: *self.inner.total_input_tokens.read() as i64,
             total_output_tokens: *self.inner.total_output_tokens.read() as i64,
+            peak_memory_bytes: *self.inner.peak_memory_bytes.read(),
+            stdout_bytes: *self.inner.stdout_bytes.read(),
+            stderr_bytes: *self.inner.stderr_bytes.read(),
         }
     }
 
@@ -1367,6 +2720,40 @@ impl ExecutionHandle {
     pub fn get_event_history(&self) -> Vec<AgentEvent> {
         self.inner.event_history.read().iter().cloned().collect()
     }
+
+    /// Untruncated output of a tool result that ToolInvoked cut, while it
+    /// is still retained.
+    pub fn get_tool_output(&self, tool_use_id: &str) -> Option<String> {
+        self.inner
+            .full_tool_outputs
+            .read()
+            .iter()
+            .find(|(id, _)| id == tool_use_id)
+            .map(|(_, output)| output.clone())
+    }
+
+    /// Retained events with `sequence >= from_sequence` and a timestamp at or
+    /// after `from_timestamp`, plus a receiver for everything emitted after
+    /// them, with no gap or overlap between the two.
+    pub fn subscribe_from(
+        &self,
+        from_sequence: u64,
+        from_timestamp: Option<&Timestamp>,
+    ) -> (Vec<AgentEvent>, broadcast::Receiver<AgentEvent>) {
+        let history = self.inner.event_history.read();
+        let from_time = from_timestamp.map(|t| (t.seconds, t.nanos));
+        let replay = history
+            .iter()
+            .filter(|event| event.sequence >= from_sequence)
+            .filter(|event| match (from_time, &event.timestamp) {
+                (Some(from), Some(ts)) => (ts.seconds, ts.nanos) >= from,
+                _ => true,
+            })
+            .cloned()
+            .collect();
+        // Subscribing under the read lock: emit_event() can't interleave
+        (replay, self.inner.event_tx.subscribe())
+    }
 }
 
 #[cfg(test)]
@@ -1466,6 +2853,48 @@ mod tests {
         assert_eq!(ExecutionInner::parse_cargo_test_summary(output), None);
     }
 
+    // -- coverage tests --
+
+    #[test]
+    fn test_parse_coverage_py_total() {
+        let output = "Name      Stmts   Miss  Cover\n---\nsrc/a.py     100     15    85%\nTOTAL        120     18    85%\n";
+        assert_eq!(ExecutionInner::parse_coverage_percent(output), Some(85.0));
+    }
+
+    #[test]
+    fn test_parse_tarpaulin_coverage() {
+        let output = "|| Tested/Total Lines:\n72.50% coverage, 145/200 lines covered";
+        assert_eq!(ExecutionInner::parse_coverage_percent(output), Some(72.5));
+        assert_eq!(ExecutionInner::parse_coverage_percent("5 passed"), None);
+    }
+
+    #[test]
+    fn test_detect_tests_with_coverage() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        let output = "\
+            ---------- coverage: platform linux, python 3.12 ----------\n\
+            Name           Stmts   Miss  Cover\n\
+            src/app.py        40      4    90%\n\
+            TOTAL             40      4    90%\n\
+            ======== 12 passed, 1 skipped in 0.52s ========\n";
+
+        inner.try_detect_test_results(output);
+
+        let result = inner
+            .event_history
+            .read()
+            .iter()
+            .find_map(|event| match &event.event {
+                Some(agent_event::Event::TestResult(result)) => Some(result.clone()),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(result.framework, "pytest");
+        assert_eq!(result.passed, 12);
+        assert_eq!(result.coverage_percent, 90.0);
+        assert!((inner.compute_quality_breakdown().coverage - 0.9).abs() < 1e-6);
+    }
+
     // -- heuristic score tests --
 
     fn make_inner_with_evidence(evidence: EvidenceSummary) -> Arc<ExecutionInner> {
@@ -1481,28 +2910,1033 @@ mod tests {
                 timeout_seconds: 300.0,
                 pal_review_enabled: false,
                 min_improvement: 5.0,
+                env_allowlist: None,
+                max_repeated_tool_calls: 3,
+                stop_on_loop: false,
+                max_cost_usd: 0.0,
+                metrics_dir: String::new(),
+                max_event_history: 0,
             },
+            claude_path: None,
             state: RwLock::new(ExecutionState::Pending),
             current_iteration: RwLock::new(0),
             current_score: RwLock::new(0.0),
             started_at: Utc::now(),
             ended_at: RwLock::new(None),
+            paused_at: RwLock::new(None),
+            paused_total: RwLock::new(chrono::Duration::zero()),
             termination_reason: RwLock::new(None),
             evidence: RwLock::new(evidence),
+            coverage_percent: RwLock::new(None),
             total_cost_usd: RwLock::new(0.0),
+            subagent_cost_usd: RwLock::new(0.0),
+            estimated_cost_usd: RwLock::new(0.0),
             total_input_tokens: RwLock::new(0),
             total_output_tokens: RwLock::new(0),
             pending_tool_uses: RwLock::new(HashMap::new()),
+            full_tool_outputs: RwLock::new(VecDeque::new()),
+            tool_inputs: RwLock::new(ToolInputAssembler::default()),
+            state_dir: None,
+            snapshot_dir_indexed: std::sync::atomic::AtomicBool::new(false),
+            node_depths: RwLock::new(HashMap::new()),
             run_instructions: RwLock::new(None),
+            recent_tool_calls: RwLock::new(VecDeque::new()),
+            terminated: RwLock::new(false),
+            cost_warned: RwLock::new(false),
             jsonl_writer: RwLock::new(None),
             event_tx: tx,
             event_history: RwLock::new(VecDeque::new()),
+            max_event_history: DEFAULT_MAX_EVENT_HISTORY,
+            last_sequence: RwLock::new(0),
+            peak_memory_bytes: RwLock::new(0),
+            stdout_bytes: RwLock::new(0),
+            stderr_bytes: RwLock::new(0),
             process_pid: RwLock::new(None),
             child_stdin: tokio::sync::RwLock::new(None),
             _metrics_watcher: RwLock::new(None),
         })
     }
 
+    // -- line count tests --
+
+    #[test]
+    fn test_count_lines() {
+        assert_eq!(count_lines(""), 0);
+        assert_eq!(count_lines("one"), 1);
+        assert_eq!(count_lines("one\ntwo\nthree\n"), 3);
+        assert_eq!(count_lines("one\ntwo\nthree"), 3);
+        assert_eq!(count_lines("bin\0ary\n"), 0);
+    }
+
+    #[test]
+    fn test_count_lines_capped() {
+        let huge = "x\n".repeat(MAX_LINE_COUNT_BYTES);
+        assert_eq!(count_lines(&huge), (MAX_LINE_COUNT_BYTES / 2) as i32);
+    }
+
+    #[test]
+    fn test_diff_line_counts() {
+        let old = "fn main() {\n    println!(\"hi\");\n}\n";
+        let new = "fn main() {\n    let name = \"world\";\n    println!(\"hi {name}\");\n}\n";
+        assert_eq!(diff_line_counts(old, new), (2, 1));
+        assert_eq!(diff_line_counts("same\n", "same\n"), (0, 0));
+        assert_eq!(diff_line_counts("", "a\nb"), (2, 0));
+    }
+
+    fn file_changed_events(inner: &ExecutionInner) -> Vec<FileChanged> {
+        inner
+            .event_history
+            .read()
+            .iter()
+            .filter_map(|event| match &event.event {
+                Some(agent_event::Event::FileChanged(change)) => Some(change.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_write_and_edit_line_counts() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        inner.handle_tool_use(
+            "toolu_w",
+            "Write",
+            &serde_json::json!({"file_path": "src/a.rs", "content": "a\nb\nc\n"}),
+            "",
+        );
+        inner.handle_tool_use(
+            "toolu_e",
+            "Edit",
+            &serde_json::json!({
+                "file_path": "src/a.rs",
+                "old_string": "b\n",
+                "new_string": "b1\nb2\n",
+            }),
+            "",
+        );
+
+        let changes = file_changed_events(&inner);
+        assert_eq!(changes.len(), 2);
+        assert_eq!((changes[0].lines_added, changes[0].lines_removed), (3, 0));
+        assert_eq!((changes[1].lines_added, changes[1].lines_removed), (2, 1));
+    }
+
+    #[test]
+    fn test_multi_edit_emits_one_change_per_file() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        inner.handle_tool_use(
+            "toolu_m",
+            "MultiEdit",
+            &serde_json::json!({
+                "file_path": "src/a.rs",
+                "edits": [
+                    {"old_string": "a\n", "new_string": "a1\na2\n"},
+                    {"file_path": "src/b.rs", "old_string": "b\n", "new_string": ""},
+                    {"old_string": "c\n", "new_string": "c1\n"},
+                ],
+            }),
+            "",
+        );
+
+        let changes = file_changed_events(&inner);
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.path.as_str(), c.action, c.lines_added, c.lines_removed))
+            .collect();
+        let edit = FileAction::Edit as i32;
+        assert_eq!(summary, vec![("src/a.rs", edit, 3, 2), ("src/b.rs", edit, 0, 1)]);
+        assert_eq!(inner.evidence.read().files_edited, vec!["src/a.rs", "src/b.rs"]);
+    }
+
+    #[test]
+    fn test_multi_edit_without_edits_is_ignored() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        inner.handle_tool_use(
+            "toolu_m",
+            "MultiEdit",
+            &serde_json::json!({"file_path": "src/a.rs"}),
+            "",
+        );
+        assert!(file_changed_events(&inner).is_empty());
+        assert!(inner.evidence.read().files_edited.is_empty());
+    }
+
+    #[test]
+    fn test_notebook_edit_records_notebook() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        inner.handle_tool_use(
+            "toolu_n",
+            "NotebookEdit",
+            &serde_json::json!({
+                "notebook_path": "analysis.ipynb",
+                "cell_id": "c1",
+                "new_source": "import pandas\ndf.head()",
+            }),
+            "",
+        );
+
+        let changes = file_changed_events(&inner);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "analysis.ipynb");
+        assert_eq!(changes[0].action, FileAction::Edit as i32);
+        assert_eq!(changes[0].lines_added, 2);
+        assert_eq!(inner.evidence.read().files_edited, vec!["analysis.ipynb"]);
+    }
+
+    #[test]
+    fn test_external_source_from_web_tools() {
+        let fetch = serde_json::json!({"url": "https://docs.rs/tokio", "prompt": "summarize"});
+        assert_eq!(
+            external_source("WebFetch", &fetch).as_deref(),
+            Some("https://docs.rs/tokio")
+        );
+        let search = serde_json::json!({"query": "tonic tls config"});
+        assert_eq!(
+            external_source("WebSearch", &search).as_deref(),
+            Some("search: tonic tls config")
+        );
+        assert_eq!(external_source("WebFetch", &serde_json::json!({"url": " "})), None);
+        assert_eq!(external_source("Read", &fetch), None);
+    }
+
+    #[test]
+    fn test_web_fetch_recorded_as_external_source() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        let input = serde_json::json!({"url": "https://example.com/api", "prompt": "read"});
+        inner.handle_tool_use("toolu_w1", "WebFetch", &input, "");
+        inner.handle_tool_use("toolu_w2", "WebFetch", &input, "");
+        inner.handle_tool_use(
+            "toolu_s",
+            "WebSearch",
+            &serde_json::json!({"query": "rust csv quoting"}),
+            "",
+        );
+
+        assert_eq!(
+            inner.evidence.read().external_sources,
+            vec!["https://example.com/api", "search: rust csv quoting"]
+        );
+        let summary = inner.event_history.read().iter().find_map(|e| match &e.event {
+            Some(agent_event::Event::ToolInvoked(t)) => Some(t.summary.clone()),
+            _ => None,
+        });
+        assert_eq!(summary.as_deref(), Some("WebFetch: https://example.com/api"));
+    }
+
+    #[test]
+    fn test_consensus_result_emits_decision_made() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        inner.parse_stream_json_line(&assistant_tool_use(
+            "tu-c",
+            "mcp__pal__consensus",
+            serde_json::json!({"question": "Rust or Go for the daemon?"}),
+        ));
+        inner.correlate_tool_result(
+            "tu-c",
+            &Some(serde_json::json!("Consensus: Rust, for memory safety")),
+            None,
+        );
+
+        let decisions: Vec<DecisionMade> = inner
+            .event_history
+            .read()
+            .iter()
+            .filter_map(|event| match &event.event {
+                Some(agent_event::Event::DecisionMade(d)) => Some(d.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(decisions.len(), 1);
+        assert_eq!(decisions[0].decision_type, "consensus");
+        assert_eq!(decisions[0].question, "Rust or Go for the daemon?");
+        assert_eq!(decisions[0].summary, "Consensus: Rust, for memory safety");
+        assert_eq!(decisions[0].tool_use_id, "tu-c");
+    }
+
+    #[test]
+    fn test_decision_only_for_pal_decision_tools() {
+        let input = r#"{"question": "Which architecture?"}"#;
+        assert!(decision_from_tool_result("Bash", input, "output").is_none());
+        let thinkdeep = decision_from_tool_result(
+            "mcp__pal__thinkdeep",
+            r#"{"topic": "Caching strategy"}"#,
+            "Use an LRU",
+        )
+        .unwrap();
+        assert_eq!(thinkdeep.decision_type, "technical");
+        assert_eq!(thinkdeep.question, "Caching strategy");
+    }
+
+    fn tool_invoked_events(inner: &ExecutionInner) -> Vec<ToolInvoked> {
+        inner
+            .event_history
+            .read()
+            .iter()
+            .filter_map(|event| match &event.event {
+                Some(agent_event::Event::ToolInvoked(tool)) => Some(tool.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn assistant_tool_use(id: &str, name: &str, input: serde_json::Value) -> String {
+        serde_json::json!({
+            "type": "assistant",
+            "message": {"content": [{"type": "tool_use", "id": id, "name": name, "input": input}]},
+        })
+        .to_string()
+    }
+
+    fn stream_event(event: serde_json::Value) -> String {
+        serde_json::json!({"type": "stream_event", "event": event, "parent_tool_use_id": null}).to_string()
+    }
+
+    fn tool_block_start(index: u64, id: &str, name: &str) -> String {
+        stream_event(serde_json::json!({
+            "type": "content_block_start",
+            "index": index,
+            "content_block": {"type": "tool_use", "id": id, "name": name, "input": {}},
+        }))
+    }
+
+    fn input_delta(index: u64, partial_json: &str) -> String {
+        stream_event(serde_json::json!({
+            "type": "content_block_delta",
+            "index": index,
+            "delta": {"type": "input_json_delta", "partial_json": partial_json},
+        }))
+    }
+
+    fn block_stop(index: u64) -> String {
+        stream_event(serde_json::json!({"type": "content_block_stop", "index": index}))
+    }
+
+    #[test]
+    fn test_streamed_tool_input_released_at_block_stop() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        inner.parse_stream_json_line(&tool_block_start(1, "tu-1", "Bash"));
+        inner.parse_stream_json_line(&input_delta(1, "{\"command\": \"car"));
+        inner.parse_stream_json_line(&input_delta(1, "go test\"}"));
+        assert!(tool_invoked_events(&inner).is_empty());
+
+        inner.parse_stream_json_line(&block_stop(1));
+        let tools = tool_invoked_events(&inner);
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].tool_use_id, "tu-1");
+        assert_eq!(tools[0].summary, "Bash: cargo test");
+
+        // The CLI repeats the finished block in a full assistant message
+        inner.parse_stream_json_line(&assistant_tool_use(
+            "tu-1",
+            "Bash",
+            serde_json::json!({"command": "cargo test"}),
+        ));
+        assert_eq!(tool_invoked_events(&inner).len(), 1);
+        assert_eq!(inner.evidence.read().commands_run, 1);
+    }
+
+    #[test]
+    fn test_interleaved_streamed_blocks_keyed_by_index() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        inner.parse_stream_json_line(&tool_block_start(0, "tu-a", "Read"));
+        inner.parse_stream_json_line(&tool_block_start(1, "tu-b", "Bash"));
+        inner.parse_stream_json_line(&input_delta(1, "{\"command\": \"ls\"}"));
+        inner.parse_stream_json_line(&input_delta(0, "{\"file_path\": \"a.rs\"}"));
+        inner.parse_stream_json_line(&block_stop(1));
+        inner.parse_stream_json_line(&block_stop(0));
+
+        let tools = tool_invoked_events(&inner);
+        let ids: Vec<&str> = tools.iter().map(|t| t.tool_use_id.as_str()).collect();
+        assert_eq!(ids, ["tu-b", "tu-a"]);
+        let input: serde_json::Value = serde_json::from_str(&tools[1].tool_input).unwrap();
+        assert_eq!(input, serde_json::json!({"file_path": "a.rs"}));
+    }
+
+    #[test]
+    fn test_empty_object_input_is_complete() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        inner.parse_stream_json_line(&assistant_tool_use("tu-1", "TodoRead", serde_json::json!({})));
+        assert_eq!(tool_invoked_events(&inner).len(), 1);
+
+        // A streamed block with no deltas also has an empty, complete input
+        inner.parse_stream_json_line(&tool_block_start(0, "tu-2", "TodoRead"));
+        inner.parse_stream_json_line(&block_stop(0));
+        let tools = tool_invoked_events(&inner);
+        assert_eq!(tools.len(), 2);
+        assert_eq!(tools[1].tool_input, "{}");
+    }
+
+    #[test]
+    fn test_long_tool_output_flagged_truncated() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        inner.handle_tool_use("tu-1", "Read", &serde_json::json!({"file_path": "a.rs"}), "");
+        inner.handle_tool_use("tu-2", "Read", &serde_json::json!({"file_path": "b.rs"}), "");
+        inner.correlate_tool_result("tu-1", &Some(serde_json::json!("é".repeat(2001))), None);
+        inner.correlate_tool_result("tu-2", &Some(serde_json::json!("short")), None);
+
+        let results: Vec<ToolInvoked> = tool_invoked_events(&inner)
+            .into_iter()
+            .filter(|t| t.summary == "(result)")
+            .collect();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].tool_output_truncated);
+        assert_eq!(results[0].tool_output.chars().count(), MAX_TOOL_OUTPUT_CHARS + 1);
+        assert!(!results[1].tool_output_truncated);
+    }
+
+    #[test]
+    fn test_truncated_tool_output_retained_in_full() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        let handle = ExecutionHandle { inner: inner.clone() };
+        let long = "x".repeat(MAX_TOOL_OUTPUT_CHARS + 10);
+        inner.handle_tool_use("tu-1", "Read", &serde_json::json!({"file_path": "a.rs"}), "");
+        inner.handle_tool_use("tu-2", "Read", &serde_json::json!({"file_path": "b.rs"}), "");
+        inner.correlate_tool_result("tu-1", &Some(serde_json::json!(long)), None);
+        inner.correlate_tool_result("tu-2", &Some(serde_json::json!("short")), None);
+
+        assert_eq!(handle.get_tool_output("tu-1").as_deref(), Some(long.as_str()));
+        // Untruncated results are already complete in the event
+        assert_eq!(handle.get_tool_output("tu-2"), None);
+
+        // Only the most recent results are kept
+        for i in 0..MAX_RETAINED_FULL_OUTPUTS {
+            let id = format!("tu-more-{i}");
+            inner.handle_tool_use(&id, "Read", &serde_json::json!({"file_path": "c.rs"}), "");
+            inner.correlate_tool_result(&id, &Some(serde_json::json!(long)), None);
+        }
+        assert_eq!(handle.get_tool_output("tu-1"), None);
+        assert!(handle.get_tool_output("tu-more-0").is_some());
+    }
+
+    #[test]
+    fn test_unstopped_block_released_on_result() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        inner.parse_stream_json_line(&tool_block_start(0, "tu-2", "Read"));
+        inner.parse_stream_json_line(&input_delta(0, "{\"file_path\": \"src/"));
+        assert!(tool_invoked_events(&inner).is_empty());
+
+        inner.correlate_tool_result("tu-2", &Some(serde_json::json!("ok")), None);
+
+        // Skip the separate "(result)" event emitted for the "ok" output
+        let tools: Vec<_> = tool_invoked_events(&inner)
+            .into_iter()
+            .filter(|tool| tool.summary != "(result)")
+            .collect();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].tool_name, "Read");
+        let input: serde_json::Value = serde_json::from_str(&tools[0].tool_input).unwrap();
+        assert_eq!(input["partial_input"], "{\"file_path\": \"src/");
+    }
+
+    // -- loop detection tests --
+
+    fn loop_warnings(inner: &ExecutionInner) -> usize {
+        inner
+            .event_history
+            .read()
+            .iter()
+            .filter(|event| {
+                matches!(&event.event, Some(agent_event::Event::LogMessage(log)) if log.source == "loop-detector")
+            })
+            .count()
+    }
+
+    #[test]
+    fn test_loop_detected_at_threshold() {
+        // make_inner_with_evidence allows 3 repeats
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        let input = serde_json::json!({"file_path": "src/lib.rs"});
+
+        for i in 0..3 {
+            inner.handle_tool_use(&format!("toolu_{i}"), "Read", &input, "");
+        }
+        assert_eq!(loop_warnings(&inner), 0);
+
+        inner.handle_tool_use("toolu_3", "Read", &input, "");
+        assert_eq!(loop_warnings(&inner), 1);
+
+        // Fires once per streak
+        inner.handle_tool_use("toolu_4", "Read", &input, "");
+        assert_eq!(loop_warnings(&inner), 1);
+        // Not terminated unless stop_on_loop is set
+        assert!(!*inner.terminated.read());
+    }
+
+    #[test]
+    fn test_loop_streak_broken_by_different_call() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        let read = serde_json::json!({"file_path": "src/lib.rs"});
+        let other = serde_json::json!({"file_path": "src/main.rs"});
+
+        for i in 0..3 {
+            inner.handle_tool_use(&format!("toolu_a{i}"), "Read", &read, "");
+        }
+        inner.handle_tool_use("toolu_b", "Read", &other, "");
+        inner.handle_tool_use("toolu_c", "Read", &read, "");
+        assert_eq!(loop_warnings(&inner), 0);
+        assert!(inner.recent_tool_calls.read().len() <= TOOL_CALL_WINDOW);
+    }
+
+    #[test]
+    fn test_loop_terminates_when_configured() {
+        let mut inner = make_inner_with_evidence(EvidenceSummary::default());
+        Arc::get_mut(&mut inner).unwrap().config.stop_on_loop = true;
+        let input = serde_json::json!({"command": "ls"});
+
+        for i in 0..4 {
+            inner.handle_tool_use(&format!("toolu_{i}"), "Bash", &input, "");
+        }
+        assert!(*inner.terminated.read());
+        assert_eq!(*inner.state.read(), ExecutionState::Failed);
+        assert!(inner
+            .termination_reason
+            .read()
+            .as_deref()
+            .unwrap()
+            .starts_with("Loop detected"));
+    }
+
+    // -- cost budget tests --
+
+    fn budget_messages(inner: &ExecutionInner) -> Vec<LogMessage> {
+        inner
+            .event_history
+            .read()
+            .iter()
+            .filter_map(|event| match &event.event {
+                Some(agent_event::Event::LogMessage(log)) if log.source == "cost-budget" => {
+                    Some(log.clone())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cost_budget_warns_then_terminates() {
+        let mut inner = make_inner_with_evidence(EvidenceSummary::default());
+        Arc::get_mut(&mut inner).unwrap().config.max_cost_usd = 1.0;
+        let usage = |cost_usd| SubagentUsage { input_tokens: 10, output_tokens: 10, cost_usd };
+
+        inner.record_subagent_usage("a", usage(0.5));
+        assert!(budget_messages(&inner).is_empty());
+
+        inner.record_subagent_usage("b", usage(0.35));
+        inner.record_subagent_usage("c", usage(0.1));
+        let messages = budget_messages(&inner);
+        assert_eq!(messages.len(), 1, "warning is emitted once");
+        assert_eq!(messages[0].level, LogLevel::Warn as i32);
+        assert!(!*inner.terminated.read());
+
+        inner.record_subagent_usage("d", usage(0.2));
+        assert!(*inner.terminated.read());
+        assert_eq!(*inner.state.read(), ExecutionState::Failed);
+        assert!(inner
+            .termination_reason
+            .read()
+            .as_deref()
+            .unwrap()
+            .starts_with("Cost budget exceeded"));
+        assert_eq!(budget_messages(&inner).len(), 2);
+    }
+
+    #[test]
+    fn test_main_agent_usage_terminates_over_budget() {
+        let mut inner = make_inner_with_evidence(EvidenceSummary::default());
+        let config = &mut Arc::get_mut(&mut inner).unwrap().config;
+        config.max_cost_usd = 1.0;
+        config.model = "claude-sonnet-4-20250514".to_string();
+        // 100k input @ $3/Mtok + 20k output @ $15/Mtok = $0.60 per message
+        let message = r#"{"type":"assistant","message":{"content":[{"type":"text","text":"working"}],
+            "usage":{"input_tokens":100000,"output_tokens":20000}}}"#
+            .replace('\n', "");
+
+        inner.parse_stream_json_line(&message);
+        assert!((*inner.total_cost_usd.read() - 0.6).abs() < 1e-9);
+        assert!(!*inner.terminated.read());
+
+        inner.parse_stream_json_line(&message);
+        assert!(*inner.terminated.read(), "no result event was needed");
+        assert_eq!(*inner.state.read(), ExecutionState::Failed);
+        assert!(inner
+            .termination_reason
+            .read()
+            .as_deref()
+            .unwrap()
+            .starts_with("Cost budget exceeded"));
+    }
+
+    #[test]
+    fn test_subagent_messages_not_estimated() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        inner.parse_stream_json_line(
+            r#"{"type":"assistant","parent_tool_use_id":"task-1","message":{"model":"claude-sonnet-4","content":[],"usage":{"input_tokens":100000,"output_tokens":0}}}"#,
+        );
+        assert_eq!(*inner.total_cost_usd.read(), 0.0);
+
+        // Unpriced models leave the total to the CLI's result
+        inner.parse_stream_json_line(
+            r#"{"type":"assistant","message":{"model":"gpt-unknown","content":[],"usage":{"input_tokens":100000,"output_tokens":0}}}"#,
+        );
+        assert_eq!(*inner.total_cost_usd.read(), 0.0);
+    }
+
+    #[test]
+    fn test_cost_budget_zero_disables_cap() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        inner.record_subagent_usage(
+            "a",
+            SubagentUsage { input_tokens: 0, output_tokens: 0, cost_usd: 1_000.0 },
+        );
+        assert!(budget_messages(&inner).is_empty());
+        assert!(!*inner.terminated.read());
+    }
+
+    // -- interactive input tests --
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_send_input_reaches_child() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        // Stub that echoes stdin back on stdout
+        let mut child = Command::new("cat")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        *inner.child_stdin.write().await = child.stdin.take();
+        let handle = ExecutionHandle { inner: inner.clone() };
+
+        handle.send_input("also add tests").await.unwrap();
+
+        let mut stdout = BufReader::new(child.stdout.take().unwrap());
+        let mut line = String::new();
+        tokio::time::timeout(std::time::Duration::from_secs(5), stdout.read_line(&mut line))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(line, user_message_line("also add tests"));
+        let message: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(message["type"], "user");
+        assert_eq!(message["message"]["content"][0]["text"], "also add tests");
+        assert!(handle.get_event_history().iter().any(|event| matches!(
+            &event.event,
+            Some(agent_event::Event::LogMessage(log)) if log.source == "user-input"
+        )));
+
+        child.kill().await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_send_input_to_exited_child() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        let handle = ExecutionHandle { inner: inner.clone() };
+        assert!(handle.send_input("hello").await.is_err());
+
+        let mut child = Command::new("true").stdin(Stdio::piped()).spawn().unwrap();
+        *inner.child_stdin.write().await = child.stdin.take();
+        child.wait().await.unwrap();
+
+        let err = handle.send_input("hello").await.unwrap_err();
+        assert!(err.to_string().contains("closed its stdin"), "{err}");
+        assert!(inner.child_stdin.read().await.is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stdin_closed_when_turn_ends() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        let mut child = Command::new("cat")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .unwrap();
+        *inner.child_stdin.write().await = child.stdin.take();
+
+        let stdout = concat!(
+            r#"{"type":"system","subtype":"init"}"#,
+            "\n",
+            r#"{"type":"result","subtype":"success","total_cost_usd":0.01}"#,
+            "\n",
+        );
+        inner.consume_stdout(stdout.as_bytes()).await;
+
+        assert!(inner.child_stdin.read().await.is_none());
+        // cat sees EOF and exits on its own
+        let status = tokio::time::timeout(std::time::Duration::from_secs(5), child.wait())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(status.success());
+        let handle = ExecutionHandle { inner };
+        assert!(handle.send_input("too late").await.is_err());
+    }
+
+    // -- event resume tests --
+
+    fn log_event(inner: &ExecutionInner, message: &str) {
+        inner.emit_event(AgentEvent {
+            execution_id: inner.id.clone(),
+            sequence: 0,
+            timestamp: ExecutionInner::now_timestamp(),
+            event: Some(agent_event::Event::LogMessage(LogMessage {
+                level: LogLevel::Info as i32,
+                message: message.to_string(),
+                source: "test".to_string(),
+            })),
+        });
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_from_sequence_replays_tail() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        let handle = ExecutionHandle { inner: inner.clone() };
+        for i in 1..=4 {
+            log_event(&inner, &format!("event {i}"));
+        }
+        let sequences: Vec<u64> = handle.get_event_history().iter().map(|e| e.sequence).collect();
+        assert_eq!(sequences, vec![1, 2, 3, 4]);
+
+        // Client saw up to 2 before disconnecting
+        let (replay, mut live) = handle.subscribe_from(3, None);
+        assert_eq!(replay.iter().map(|e| e.sequence).collect::<Vec<_>>(), vec![3, 4]);
+
+        log_event(&inner, "event 5");
+        let next = live.recv().await.unwrap();
+        assert_eq!(next.sequence, 5);
+    }
+
+    #[test]
+    fn test_subscribe_from_timestamp() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        let handle = ExecutionHandle { inner: inner.clone() };
+        log_event(&inner, "old");
+        inner.event_history.write()[0].timestamp = Some(Timestamp { seconds: 100, nanos: 0 });
+        log_event(&inner, "new");
+
+        let (replay, _live) = handle.subscribe_from(0, Some(&Timestamp { seconds: 200, nanos: 0 }));
+        assert_eq!(replay.len(), 1);
+        assert_eq!(replay[0].sequence, 2);
+    }
+
+    #[test]
+    fn test_event_history_respects_configured_cap() {
+        let mut inner = make_inner_with_evidence(EvidenceSummary::default());
+        Arc::get_mut(&mut inner).unwrap().max_event_history = MIN_EVENT_HISTORY;
+        let handle = ExecutionHandle { inner: inner.clone() };
+
+        for i in 1..=250 {
+            log_event(&inner, &format!("event {i}"));
+            assert!(inner.event_history.read().len() <= MIN_EVENT_HISTORY);
+        }
+
+        let sequences: Vec<u64> = handle.get_event_history().iter().map(|e| e.sequence).collect();
+        assert_eq!(sequences, (151..=250).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn test_resolve_max_event_history() {
+        assert_eq!(resolve_max_event_history_with(0, None), DEFAULT_MAX_EVENT_HISTORY);
+        assert_eq!(resolve_max_event_history_with(0, Some("20000".to_string())), 20_000);
+        assert_eq!(
+            resolve_max_event_history_with(0, Some("lots".to_string())),
+            DEFAULT_MAX_EVENT_HISTORY
+        );
+        // The config field wins over the env var
+        assert_eq!(resolve_max_event_history_with(800, Some("20000".to_string())), 800);
+        assert_eq!(resolve_max_event_history_with(10, None), MIN_EVENT_HISTORY);
+        assert_eq!(resolve_max_event_history_with(u32::MAX, None), MAX_EVENT_HISTORY);
+        assert_eq!(
+            resolve_max_event_history_with(0, Some(usize::MAX.to_string())),
+            MAX_EVENT_HISTORY
+        );
+    }
+
+    // -- resource usage tests --
+
+    #[tokio::test]
+    async fn test_stdout_byte_counter() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        assert_eq!(*inner.stdout_bytes.read(), 0);
+
+        inner.consume_stdout(&b"{\"type\":\"system\"}\nnot json\n"[..]).await;
+        assert_eq!(*inner.stdout_bytes.read(), 27);
+
+        inner.consume_stdout(&b"more\n"[..]).await;
+        assert_eq!(*inner.stdout_bytes.read(), 32);
+        assert_eq!(ExecutionHandle { inner }.get_status_sync().stdout_bytes, 32);
+    }
+
+    #[test]
+    fn test_parse_rss_kb() {
+        let status = "Name:\tclaude\nVmHWM:\t  20480 kB\nVmRSS:\t  10240 kB\n";
+        assert_eq!(parse_rss_kb(status, "VmHWM:"), Some(20480));
+        assert_eq!(parse_rss_kb(status, "VmRSS:"), Some(10240));
+        assert_eq!(parse_rss_kb(status, "VmSwap:"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_sample_memory() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        inner.sample_memory();
+        assert_eq!(*inner.peak_memory_bytes.read(), 0, "no PID, no sample");
+
+        *inner.process_pid.write() = Some(std::process::id());
+        inner.sample_memory();
+        assert!(*inner.peak_memory_bytes.read() > 0);
+    }
+
+    // -- pause/resume tests --
+
+    /// Process state and consumed CPU ticks (utime + stime) from /proc.
+    #[cfg(target_os = "linux")]
+    fn proc_stat(pid: u32) -> (char, u64) {
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).unwrap();
+        // Fields after the parenthesised command name start at "state"
+        let rest = &stat[stat.rfind(')').unwrap() + 2..];
+        let fields: Vec<&str> = rest.split_whitespace().collect();
+        let state = fields[0].chars().next().unwrap();
+        let ticks = fields[11].parse::<u64>().unwrap() + fields[12].parse::<u64>().unwrap();
+        (state, ticks)
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_pause_stops_child_process() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg("while :; do :; done")
+            .process_group(0)
+            .spawn()
+            .unwrap();
+        let pid = child.id().unwrap();
+        *inner.process_pid.write() = Some(pid);
+        let handle = ExecutionHandle { inner: inner.clone() };
+
+        handle.pause().await;
+        assert_eq!(handle.state(), ExecutionState::Paused);
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        let (state, ticks_before) = proc_stat(pid);
+        assert_eq!(state, 'T', "child should be stopped");
+        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+        let (_, ticks_after) = proc_stat(pid);
+        assert_eq!(ticks_before, ticks_after, "paused child kept consuming CPU");
+
+        handle.resume().await;
+        assert_eq!(handle.state(), ExecutionState::Running);
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert_ne!(proc_stat(pid).0, 'T', "child should be running again");
+
+        child.kill().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_pause_without_process() {
+        let handle = ExecutionHandle { inner: make_inner_with_evidence(EvidenceSummary::default()) };
+        handle.pause().await;
+        assert_eq!(handle.state(), ExecutionState::Paused);
+        handle.resume().await;
+        assert_eq!(handle.state(), ExecutionState::Running);
+    }
+
+    // -- process group tests --
+
+    #[cfg(target_os = "linux")]
+    fn process_gone(pid: u32) -> bool {
+        // Exited-but-unreaped (zombie) processes count as gone
+        match std::fs::read_to_string(format!("/proc/{}/stat", pid)) {
+            Ok(_) => proc_stat(pid).0 == 'Z',
+            Err(_) => true,
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_stop_kills_process_tree() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        // The shell forks a grandchild and reports its PID
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg("sleep 30 & echo $!; wait")
+            .stdout(Stdio::piped())
+            .process_group(0)
+            .spawn()
+            .unwrap();
+        let mut stdout = BufReader::new(child.stdout.take().unwrap());
+        let mut line = String::new();
+        stdout.read_line(&mut line).await.unwrap();
+        let grandchild: u32 = line.trim().parse().unwrap();
+        *inner.process_pid.write() = child.id();
+        let handle = ExecutionHandle { inner: inner.clone() };
+
+        handle.stop(false).await;
+        child.wait().await.unwrap();
+
+        for _ in 0..100 {
+            if process_gone(grandchild) {
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        panic!("grandchild {} survived stop", grandchild);
+    }
+
+    // -- concurrency limit tests --
+
+    async fn wait_for_state_change(handle: &ExecutionHandle, from: ExecutionState) {
+        for _ in 0..100 {
+            if handle.state() != from {
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        panic!("execution stayed in {:?}", from);
+    }
+
+    #[tokio::test]
+    async fn test_queued_execution_waits_for_slot() {
+        const LIMIT: usize = 2;
+        let slots = Arc::new(Semaphore::new(LIMIT));
+        let config = make_inner_with_evidence(EvidenceSummary::default()).config.clone();
+
+        // N executions occupy every slot
+        let running: Vec<_> = (0..LIMIT)
+            .map(|_| slots.clone().try_acquire_owned().unwrap())
+            .collect();
+        assert!(slots.clone().try_acquire_owned().is_err());
+
+        // The N+1th is queued. Once it proceeds it must not launch a real
+        // claude, so it gets a missing binary and fails to spawn.
+        let project = tempfile::tempdir().unwrap();
+        let handle = Execution::new(
+            "queued".to_string(),
+            "task".to_string(),
+            project.path().to_string_lossy().to_string(),
+            config,
+        )
+        .with_slot(ExecutionSlot::Queued(slots.clone()))
+        .with_claude_path(project.path().join("no-such-claude"))
+        .start()
+        .await
+        .unwrap();
+
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert_eq!(handle.state(), ExecutionState::Pending);
+        let queued = handle.get_event_history().into_iter().any(|event| {
+            matches!(
+                event.event,
+                Some(agent_event::Event::StateChanged(ref change))
+                    if change.new_state == ExecutionState::Pending as i32
+                        && change.reason.starts_with("Queued")
+            )
+        });
+        assert!(queued, "expected a queued StateChanged event");
+
+        // Freeing a slot lets it proceed
+        drop(running);
+        wait_for_state_change(&handle, ExecutionState::Pending).await;
+    }
+
+    #[tokio::test]
+    async fn test_stopped_while_queued_never_runs() {
+        let slots = Arc::new(Semaphore::new(1));
+        let running = slots.clone().try_acquire_owned().unwrap();
+        let config = make_inner_with_evidence(EvidenceSummary::default()).config.clone();
+
+        let project = tempfile::tempdir().unwrap();
+        let handle = Execution::new(
+            "queued".to_string(),
+            "task".to_string(),
+            project.path().to_string_lossy().to_string(),
+            config,
+        )
+        .with_slot(ExecutionSlot::Queued(slots.clone()))
+        .with_claude_path(project.path().join("no-such-claude"))
+        .start()
+        .await
+        .unwrap();
+
+        handle.stop(false).await;
+        drop(running);
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert_eq!(handle.state(), ExecutionState::Cancelled);
+        // The slot is released again
+        assert_eq!(slots.available_permits(), 1);
+    }
+
+    // -- timeout tests --
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_wait_with_timeout_kills_process() {
+        let mut inner = make_inner_with_evidence(EvidenceSummary::default());
+        Arc::get_mut(&mut inner).unwrap().config.timeout_seconds = 1.0;
+
+        let mut child = Command::new("sleep").arg("30").process_group(0).spawn().unwrap();
+        *inner.process_pid.write() = child.id();
+
+        let started = std::time::Instant::now();
+        let status = inner.wait_with_timeout(&mut child).await.unwrap();
+        assert!(status.is_none(), "expected timeout, got {:?}", status);
+        assert!(started.elapsed() < std::time::Duration::from_secs(10));
+        // Process was killed and reaped
+        assert!(child.try_wait().unwrap().is_some());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_wait_with_timeout_excludes_paused_time() {
+        let mut inner = make_inner_with_evidence(EvidenceSummary::default());
+        Arc::get_mut(&mut inner).unwrap().config.timeout_seconds = 1.0;
+        // Paused since start: wall time passes but active time stays at zero.
+        *inner.paused_at.write() = Some(inner.started_at);
+
+        let mut child = Command::new("sleep").arg("2").process_group(0).spawn().unwrap();
+        *inner.process_pid.write() = child.id();
+
+        let status = inner.wait_with_timeout(&mut child).await.unwrap();
+        assert!(status.unwrap().success(), "paused time must not trigger the timeout");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_env_allowlist_restricts_child_environment() {
+        let mut inner = make_inner_with_evidence(EvidenceSummary::default());
+        Arc::get_mut(&mut inner).unwrap().config.env_allowlist = Some(EnvAllowlist {
+            vars: vec!["PATH".to_string(), "SUPERCLAUDE_TEST_UNSET_VAR".to_string()],
+        });
+
+        let mut cmd = Command::new("env");
+        inner.apply_child_env(&mut cmd);
+        let output = cmd.output().await.unwrap();
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let keys: Vec<&str> = stdout.lines().filter_map(|l| l.split_once('=')).map(|(k, _)| k).collect();
+
+        assert!(keys.contains(&"PATH"));
+        assert!(keys.contains(&"SUPERCLAUDE_EXECUTION_ID"));
+        assert!(!keys.contains(&"SUPERCLAUDE_TEST_UNSET_VAR"));
+        for key in &keys {
+            assert!(
+                *key == "PATH" || key.starts_with("SUPERCLAUDE_"),
+                "unexpected variable leaked to child: {key}"
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_wait_with_zero_timeout_is_unbounded() {
+        let mut inner = make_inner_with_evidence(EvidenceSummary::default());
+        Arc::get_mut(&mut inner).unwrap().config.timeout_seconds = 0.0;
+
+        let mut child = Command::new("true").spawn().unwrap();
+        let status = inner.wait_with_timeout(&mut child).await.unwrap();
+        assert!(status.unwrap().success());
+    }
+
     #[test]
     fn test_heuristic_score_no_evidence() {
         let inner = make_inner_with_evidence(EvidenceSummary::default());
@@ -1552,4 +3986,353 @@ mod tests {
         // files: 30 + min(10*5,20)=50, tests: 20, cmds: min(40,10)=10, completion: 20 → 100 capped
         assert_eq!(inner.compute_heuristic_score(), 100.0);
     }
+
+    // -- active duration tests --
+
+    #[test]
+    fn test_active_duration_excludes_paused_time() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        let started = inner.started_at;
+        *inner.ended_at.write() = Some(started + chrono::Duration::seconds(100));
+        *inner.paused_total.write() = chrono::Duration::seconds(30);
+        assert_eq!(inner.active_duration_seconds(), 70.0);
+    }
+
+    #[test]
+    fn test_active_duration_open_pause_interval() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        let started = inner.started_at;
+        *inner.paused_at.write() = Some(started + chrono::Duration::seconds(40));
+        *inner.ended_at.write() = Some(started + chrono::Duration::seconds(100));
+        // Ended while still paused: only the first 40s were active
+        assert_eq!(inner.active_duration_seconds(), 40.0);
+    }
+
+    // -- node depth tests --
+
+    #[test]
+    fn test_nested_subagent_depth() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        inner.node_depths.write().insert("iter-1".to_string(), 0);
+
+        let task_input = serde_json::json!({"subagent_type": "general-purpose"});
+        inner.handle_tool_use("tu-1", "Task", &task_input, "iter-1");
+        assert_eq!(inner.node_depth("tu-1"), 1);
+        assert_eq!(inner.node_depth("subagent-tu-1"), 1);
+
+        // A subagent spawning its own subagent nests one level deeper
+        inner.handle_tool_use("tu-2", "Task", &task_input, "subagent-tu-1");
+        assert_eq!(inner.node_depth("subagent-tu-2"), 2);
+
+        let read_input = serde_json::json!({"file_path": "src/lib.rs"});
+        inner.handle_tool_use("tu-3", "Read", &read_input, "subagent-tu-2");
+        assert_eq!(inner.node_depth("tu-3"), 3);
+    }
+
+    #[test]
+    fn test_run_summary_json() {
+        let evidence = EvidenceSummary {
+            files_written: vec!["src/main.rs".to_string()],
+            tests_run: true,
+            tests_passed: 4,
+            ..Default::default()
+        };
+        let inner = make_inner_with_evidence(evidence);
+        *inner.state.write() = ExecutionState::Completed;
+        *inner.current_score.write() = 85.0;
+        *inner.run_instructions.write() = Some(RunInstructions {
+            build_command: "cargo build".to_string(),
+            ..Default::default()
+        });
+
+        let summary = inner.run_summary_json();
+        assert_eq!(summary["execution_id"], "test-id");
+        assert_eq!(summary["status"]["state"], "EXECUTION_STATE_COMPLETED");
+        assert_eq!(summary["quality"]["passed"], true);
+        assert_eq!(summary["run_instructions"]["build_command"], "cargo build");
+        assert_eq!(summary["evidence"]["tests_passed"], 4);
+    }
+
+    #[test]
+    fn test_write_run_summary() {
+        let root = std::env::temp_dir().join(format!("sc-summary-{}", Uuid::new_v4()));
+        let mut inner = make_inner_with_evidence(EvidenceSummary::default());
+        Arc::get_mut(&mut inner).unwrap().project_root = root.to_string_lossy().to_string();
+        *inner.state.write() = ExecutionState::Failed;
+
+        inner.write_run_summary();
+
+        let path = root.join(".superclaude_metrics").join("test-id-summary.json");
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let _ = std::fs::remove_dir_all(&root);
+        assert_eq!(written["quality"]["passed"], false);
+        assert_eq!(written["status"]["state"], "EXECUTION_STATE_FAILED");
+    }
+
+    #[test]
+    fn test_write_snapshot_reloads() {
+        let root = std::env::temp_dir().join(format!("sc-snapshot-{}", Uuid::new_v4()));
+        let mut inner = make_inner_with_evidence(EvidenceSummary {
+            files_edited: vec!["src/lib.rs".to_string()],
+            ..Default::default()
+        });
+        {
+            let inner = Arc::get_mut(&mut inner).unwrap();
+            inner.project_root = root.join("project").to_string_lossy().to_string();
+            inner.state_dir = Some(root.join("state"));
+        }
+        *inner.state.write() = ExecutionState::Completed;
+        *inner.total_cost_usd.write() = 0.25;
+
+        inner.write_snapshot();
+        // Found through the daemon's index, not the daemon's own cwd
+        let loaded = history::load_indexed_snapshots(Some(&root.join("state")), &root.join("elsewhere"));
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(loaded.len(), 1);
+        let status = loaded[0].to_status();
+        assert_eq!(status.execution_id, "test-id");
+        assert_eq!(status.state, ExecutionState::Completed as i32);
+        assert_eq!(status.total_cost_usd, 0.25);
+        assert_eq!(status.evidence.unwrap().files_edited, vec!["src/lib.rs"]);
+    }
+
+    #[test]
+    fn test_resolve_metrics_dir() {
+        let root = Path::new("/work/project");
+        assert_eq!(
+            resolve_metrics_dir_with(root, "", None),
+            root.join(".superclaude_metrics")
+        );
+        assert_eq!(
+            resolve_metrics_dir_with(root, "", Some("/var/metrics".to_string())),
+            PathBuf::from("/var/metrics")
+        );
+        // The config field wins over the env var; relative paths join the root
+        assert_eq!(
+            resolve_metrics_dir_with(root, "out/metrics", Some("/var/metrics".to_string())),
+            root.join("out/metrics")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_completion_syncs_decisions_to_obsidian_vault() {
+        // Outside the system temp dir, which the vault writer rejects
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .and_then(Path::parent)
+            .unwrap()
+            .join("target/test-vaults")
+            .join(format!("sc-obsidian-{}", Uuid::new_v4()));
+        let project = root.join("project");
+        let vault = root.join("vault");
+        std::fs::create_dir_all(&project).unwrap();
+        std::fs::write(
+            project.join(".obsidian.yaml"),
+            format!(
+                "vault:\n  path: {}\nartifacts:\n  sync_on: task_completion\n  backlinks:\n    enabled: false\n",
+                vault.display()
+            ),
+        )
+        .unwrap();
+
+        let mut inner = make_inner_with_evidence(EvidenceSummary::default());
+        Arc::get_mut(&mut inner).unwrap().project_root = project.to_string_lossy().to_string();
+        inner.handle_tool_use(
+            "tu-c",
+            "mcp__pal__consensus",
+            &serde_json::json!({"question": "Which database should we use?"}),
+            "",
+        );
+        inner.correlate_tool_result("tu-c", &Some(serde_json::json!("Postgres")), None);
+        *inner.state.write() = ExecutionState::Completed;
+        inner.sync_obsidian_decisions().await;
+
+        let written: Vec<PathBuf> = std::fs::read_dir(vault.join("Claude/Decisions"))
+            .map(|dir| dir.filter_map(|e| e.ok()).map(|e| e.path()).collect())
+            .unwrap_or_default();
+        let artifacts: Vec<ArtifactWritten> = inner
+            .event_history
+            .read()
+            .iter()
+            .filter_map(|event| match &event.event {
+                Some(agent_event::Event::ArtifactWritten(a)) => Some(a.clone()),
+                _ => None,
+            })
+            .collect();
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(written.len(), 1, "decision files: {written:?}");
+        assert_eq!(artifacts.len(), 1);
+        assert_eq!(artifacts[0].artifact_type, "decision");
+        assert!(artifacts[0].obsidian_path.starts_with("Claude/Decisions/"));
+    }
+
+    #[tokio::test]
+    async fn test_obsidian_sync_ignores_non_decision_tools() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .and_then(Path::parent)
+            .unwrap()
+            .join("target/test-vaults")
+            .join(format!("sc-obsidian-{}", Uuid::new_v4()));
+        let project = root.join("project");
+        let vault = root.join("vault");
+        std::fs::create_dir_all(&project).unwrap();
+        std::fs::write(
+            project.join(".obsidian.yaml"),
+            format!(
+                "vault:\n  path: {}\nartifacts:\n  sync_on: task_completion\n",
+                vault.display()
+            ),
+        )
+        .unwrap();
+
+        let mut inner = make_inner_with_evidence(EvidenceSummary::default());
+        Arc::get_mut(&mut inner).unwrap().project_root = project.to_string_lossy().to_string();
+        inner.handle_tool_use(
+            "tu-r",
+            "Read",
+            &serde_json::json!({"file_path": "docs/architecture.md"}),
+            "",
+        );
+        inner.correlate_tool_result("tu-r", &Some(serde_json::json!("# Architecture")), None);
+        *inner.state.write() = ExecutionState::Completed;
+        inner.sync_obsidian_decisions().await;
+
+        let vault_created = vault.exists();
+        let _ = std::fs::remove_dir_all(&root);
+        assert!(!vault_created, "no decision should be written for a Read");
+        assert!(!inner.event_history.read().iter().any(|event| matches!(
+            event.event,
+            Some(agent_event::Event::ArtifactWritten(_))
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_obsidian_sync_skipped_without_config() {
+        let root = std::env::temp_dir().join(format!("sc-obsidian-{}", Uuid::new_v4()));
+        let mut inner = make_inner_with_evidence(EvidenceSummary::default());
+        Arc::get_mut(&mut inner).unwrap().project_root = root.to_string_lossy().to_string();
+        inner.sync_obsidian_decisions().await;
+        assert!(inner.event_history.read().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_events_written_to_overridden_metrics_dir() {
+        let root = std::env::temp_dir().join(format!("sc-metrics-{}", Uuid::new_v4()));
+        let custom = root.join("custom-metrics");
+        let mut inner = make_inner_with_evidence(EvidenceSummary::default());
+        {
+            let inner = Arc::get_mut(&mut inner).unwrap();
+            inner.project_root = root.join("project").to_string_lossy().to_string();
+            inner.config.metrics_dir = custom.to_string_lossy().to_string();
+        }
+
+        inner.open_metrics_outputs();
+        inner.handle_tool_use("toolu_1", "Read", &serde_json::json!({"file_path": "a.rs"}), "");
+        if let Some(ref mut writer) = *inner.jsonl_writer.write() {
+            use std::io::Write;
+            writer.flush().unwrap();
+        }
+        inner.write_run_summary();
+
+        let events = std::fs::read_to_string(custom.join("events.jsonl")).unwrap_or_default();
+        let summary_written = custom.join("test-id-summary.json").exists();
+        let default_used = root.join("project").join(".superclaude_metrics").exists();
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert!(events.contains("tool_invoked"), "events.jsonl: {events:?}");
+        assert!(summary_written);
+        assert!(!default_used);
+    }
+
+    // The metrics watcher started by open_metrics_outputs needs a runtime
+    #[tokio::test]
+    async fn test_events_jsonl_readable_by_metrics_summary() {
+        use superclaude_core::metrics_reader::aggregate;
+        use superclaude_core::types::MetricEvent;
+
+        let root = tempfile::tempdir().unwrap();
+        let mut inner = make_inner_with_evidence(EvidenceSummary::default());
+        Arc::get_mut(&mut inner).unwrap().project_root = root.path().to_string_lossy().to_string();
+
+        inner.open_metrics_outputs();
+        inner.handle_tool_use(
+            "toolu_1",
+            "Write",
+            &serde_json::json!({"file_path": "a.rs", "content": "fn main() {}\n"}),
+            "",
+        );
+        inner.handle_tool_use("toolu_2", "Read", &serde_json::json!({"file_path": "b.rs"}), "");
+        if let Some(ref mut writer) = *inner.jsonl_writer.write() {
+            use std::io::Write;
+            writer.flush().unwrap();
+        }
+        let jsonl = std::fs::read_to_string(root.path().join(".superclaude_metrics/events.jsonl"))
+            .unwrap_or_default();
+
+        let events: Vec<MetricEvent> = jsonl
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert!(!events.is_empty());
+        assert!(events.iter().all(|e| e.timestamp.is_some()), "{jsonl}");
+        let buckets = aggregate(&events, std::time::Duration::from_secs(3600));
+        assert_eq!(buckets.iter().map(|b| b.file_changes).sum::<u32>(), 1);
+    }
+
+    #[test]
+    fn test_subagent_usage_from_result() {
+        let result = serde_json::json!({
+            "status": "completed",
+            "totalCostUsd": 0.25,
+            "usage": {"input_tokens": 1200, "output_tokens": 300},
+        });
+        assert_eq!(
+            SubagentUsage::from_result(&result),
+            Some(SubagentUsage { input_tokens: 1200, output_tokens: 300, cost_usd: 0.25 })
+        );
+        assert_eq!(SubagentUsage::from_result(&serde_json::json!({"status": "completed"})), None);
+    }
+
+    #[test]
+    fn test_task_result_attributes_subagent_usage() {
+        let inner = make_inner_with_evidence(EvidenceSummary::default());
+        let mut rx = inner.event_tx.subscribe();
+        inner.node_depths.write().insert("iter-1".to_string(), 0);
+        *inner.total_input_tokens.write() = 100;
+
+        let task_input = serde_json::json!({"subagent_type": "general-purpose"});
+        inner.handle_tool_use("tu-1", "Task", &task_input, "iter-1");
+
+        let result = serde_json::json!({
+            "total_cost_usd": 0.5,
+            "usage": {"input_tokens": 40, "output_tokens": 10},
+        });
+        let content = Some(serde_json::json!("done"));
+        inner.correlate_tool_result("tu-1", &content, Some(&result));
+
+        assert_eq!(*inner.total_input_tokens.read(), 140);
+        assert_eq!(*inner.total_output_tokens.read(), 10);
+        assert_eq!(*inner.subagent_cost_usd.read(), 0.5);
+
+        let mut saw_usage_log = false;
+        while let Ok(event) = rx.try_recv() {
+            if let Some(agent_event::Event::LogMessage(log)) = event.event {
+                saw_usage_log |= log.source == "subagent-usage";
+            }
+        }
+        assert!(saw_usage_log);
+
+        // The CLI's final cost is reported on top of the subagent spend
+        let result_event: StreamJsonEvent = serde_json::from_value(serde_json::json!({
+            "type": "result",
+            "total_cost_usd": 1.0,
+        }))
+        .unwrap();
+        inner.handle_result_event(&result_event);
+        assert_eq!(*inner.total_cost_usd.read(), 1.5);
+    }
 }
@@ -0,0 +1,451 @@
+//! Execution snapshots persisted under `<metrics dir>/executions/`
+//!
+//! Each execution periodically writes its status to `{id}.json` so the
+//! daemon can serve past executions from `list_executions` and
+//! `get_execution_detail` after a restart.
+//!
+//! Metrics directories live under each project, so the daemon also keeps an
+//! index of every snapshot directory it has written to in its state dir
+//! (`$SUPERCLAUDE_STATE_DIR`, default `~/.superclaude/daemon`) and reloads
+//! all of them on startup.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use prost_types::Timestamp;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use superclaude_proto::*;
+
+/// Overrides the daemon state directory.
+const STATE_DIR_ENV: &str = "SUPERCLAUDE_STATE_DIR";
+/// File in the state directory listing known snapshot directories.
+const SNAPSHOT_INDEX_FILE: &str = "snapshot_dirs.json";
+
+/// Serializes read-modify-write of the snapshot index across executions.
+static INDEX_LOCK: parking_lot::Mutex<()> = parking_lot::Mutex::new(());
+
+/// Directory holding execution snapshots within a metrics directory.
+pub fn snapshot_dir(metrics_dir: &Path) -> PathBuf {
+    metrics_dir.join("executions")
+}
+
+/// Daemon state directory: `$SUPERCLAUDE_STATE_DIR`, else
+/// `$HOME/.superclaude/daemon`. `None` when neither is set.
+pub fn state_dir() -> Option<PathBuf> {
+    state_dir_with(std::env::var(STATE_DIR_ENV).ok(), std::env::var("HOME").ok())
+}
+
+fn state_dir_with(configured: Option<String>, home: Option<String>) -> Option<PathBuf> {
+    let non_empty = |s: String| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+    configured
+        .and_then(non_empty)
+        .map(PathBuf::from)
+        .or_else(|| home.and_then(non_empty).map(|h| Path::new(&h).join(".superclaude/daemon")))
+}
+
+/// Snapshot directories recorded in the index. Missing or unreadable
+/// indexes yield an empty list.
+pub fn indexed_snapshot_dirs(state_dir: &Path) -> Vec<PathBuf> {
+    let path = state_dir.join(SNAPSHOT_INDEX_FILE);
+    let Ok(json) = std::fs::read_to_string(&path) else {
+        return vec![];
+    };
+    serde_json::from_str(&json).unwrap_or_else(|e| {
+        warn!(path = %path.display(), error = %e, "Ignoring unreadable snapshot index");
+        vec![]
+    })
+}
+
+/// Add `dir` to the index in `state_dir` unless it is already listed.
+pub fn register_snapshot_dir(state_dir: &Path, dir: &Path) -> Result<()> {
+    let dir = std::path::absolute(dir).unwrap_or_else(|_| dir.to_path_buf());
+    let _guard = INDEX_LOCK.lock();
+    let mut dirs = indexed_snapshot_dirs(state_dir);
+    if dirs.contains(&dir) {
+        return Ok(());
+    }
+    dirs.push(dir);
+
+    std::fs::create_dir_all(state_dir)
+        .with_context(|| format!("Failed to create {}", state_dir.display()))?;
+    let path = state_dir.join(SNAPSHOT_INDEX_FILE);
+    let tmp = state_dir.join(format!(".{SNAPSHOT_INDEX_FILE}.tmp"));
+    std::fs::write(&tmp, serde_json::to_string_pretty(&dirs)?)?;
+    std::fs::rename(&tmp, &path)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// Load snapshots from every indexed directory plus `extra`, each
+/// directory and execution once.
+pub fn load_indexed_snapshots(state_dir: Option<&Path>, extra: &Path) -> Vec<ExecutionSnapshot> {
+    let mut dirs = state_dir.map(indexed_snapshot_dirs).unwrap_or_default();
+    dirs.push(std::path::absolute(extra).unwrap_or_else(|_| extra.to_path_buf()));
+
+    let mut seen_dirs = std::collections::HashSet::new();
+    let mut seen_ids = std::collections::HashSet::new();
+    let mut snapshots: Vec<ExecutionSnapshot> = dirs
+        .iter()
+        .filter(|dir| seen_dirs.insert(dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf())))
+        .flat_map(|dir| load_snapshots(dir))
+        .filter(|snapshot| seen_ids.insert(snapshot.execution_id.clone()))
+        .collect();
+    snapshots.sort_by_key(|s| s.started_at);
+    snapshots
+}
+
+/// Serializable status of one execution.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionSnapshot {
+    pub execution_id: String,
+    pub task: String,
+    pub project_root: String,
+    /// `ExecutionState::as_str_name()`, e.g. "EXECUTION_STATE_COMPLETED"
+    pub state: String,
+    pub current_iteration: i32,
+    pub max_iterations: i32,
+    pub current_score: f32,
+    pub quality_threshold: f32,
+    pub termination_reason: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub duration_seconds: f32,
+    pub total_cost_usd: f64,
+    pub total_input_tokens: i64,
+    pub total_output_tokens: i64,
+    pub evidence: SnapshotEvidence,
+    pub run_instructions: Option<SnapshotRunInstructions>,
+    /// PID of the claude process while it was running
+    pub pid: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotEvidence {
+    pub files_written: Vec<String>,
+    pub files_edited: Vec<String>,
+    pub commands_run: i32,
+    pub tests_run: bool,
+    pub tests_passed: i32,
+    pub tests_failed: i32,
+    pub subagents_spawned: i32,
+    #[serde(default)]
+    pub external_sources: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotRunInstructions {
+    pub build_command: String,
+    pub run_command: String,
+    pub artifacts: Vec<String>,
+    pub notes: String,
+}
+
+impl From<&EvidenceSummary> for SnapshotEvidence {
+    fn from(e: &EvidenceSummary) -> Self {
+        Self {
+            files_written: e.files_written.clone(),
+            files_edited: e.files_edited.clone(),
+            commands_run: e.commands_run,
+            tests_run: e.tests_run,
+            tests_passed: e.tests_passed,
+            tests_failed: e.tests_failed,
+            subagents_spawned: e.subagents_spawned,
+            external_sources: e.external_sources.clone(),
+        }
+    }
+}
+
+impl From<&SnapshotEvidence> for EvidenceSummary {
+    fn from(e: &SnapshotEvidence) -> Self {
+        Self {
+            files_written: e.files_written.clone(),
+            files_edited: e.files_edited.clone(),
+            commands_run: e.commands_run,
+            tests_run: e.tests_run,
+            tests_passed: e.tests_passed,
+            tests_failed: e.tests_failed,
+            subagents_spawned: e.subagents_spawned,
+            external_sources: e.external_sources.clone(),
+        }
+    }
+}
+
+impl From<&RunInstructions> for SnapshotRunInstructions {
+    fn from(ri: &RunInstructions) -> Self {
+        Self {
+            build_command: ri.build_command.clone(),
+            run_command: ri.run_command.clone(),
+            artifacts: ri.artifacts.clone(),
+            notes: ri.notes.clone(),
+        }
+    }
+}
+
+impl From<&SnapshotRunInstructions> for RunInstructions {
+    fn from(ri: &SnapshotRunInstructions) -> Self {
+        Self {
+            build_command: ri.build_command.clone(),
+            run_command: ri.run_command.clone(),
+            artifacts: ri.artifacts.clone(),
+            notes: ri.notes.clone(),
+        }
+    }
+}
+
+fn to_timestamp(dt: DateTime<Utc>) -> Timestamp {
+    Timestamp {
+        seconds: dt.timestamp(),
+        nanos: dt.timestamp_subsec_nanos() as i32,
+    }
+}
+
+impl ExecutionSnapshot {
+    pub fn execution_state(&self) -> ExecutionState {
+        ExecutionState::from_str_name(&self.state).unwrap_or(ExecutionState::Unspecified)
+    }
+
+    /// Whether the snapshot was taken while the execution was still live.
+    fn is_active(&self) -> bool {
+        matches!(
+            self.execution_state(),
+            ExecutionState::Pending | ExecutionState::Running | ExecutionState::Paused
+        )
+    }
+
+    pub fn to_status(&self) -> ExecutionStatus {
+        ExecutionStatus {
+            execution_id: self.execution_id.clone(),
+            task: self.task.clone(),
+            state: self.execution_state() as i32,
+            current_iteration: self.current_iteration,
+            max_iterations: self.max_iterations,
+            current_score: self.current_score,
+            quality_threshold: self.quality_threshold,
+            termination_reason: self.termination_reason.clone(),
+            evidence: Some((&self.evidence).into()),
+            started_at: Some(to_timestamp(self.started_at)),
+            ended_at: self.ended_at.map(to_timestamp),
+            total_cost_usd: self.total_cost_usd,
+            total_input_tokens: self.total_input_tokens,
+            total_output_tokens: self.total_output_tokens,
+            // Resource usage is only tracked for live processes
+            peak_memory_bytes: 0,
+            stdout_bytes: 0,
+            stderr_bytes: 0,
+        }
+    }
+
+    pub fn to_summary(&self) -> ExecutionSummary {
+        ExecutionSummary {
+            execution_id: self.execution_id.clone(),
+            task: self.task.clone(),
+            state: self.execution_state() as i32,
+            current_iteration: self.current_iteration,
+            current_score: self.current_score,
+            started_at: Some(to_timestamp(self.started_at)),
+            total_cost_usd: self.total_cost_usd,
+            duration_seconds: self.duration_seconds,
+            evidence: Some((&self.evidence).into()),
+        }
+    }
+
+    /// Detail view of a restored execution. The event stream is not
+    /// persisted, so `events` is empty.
+    pub fn to_detail(&self) -> GetExecutionDetailResponse {
+        GetExecutionDetailResponse {
+            status: Some(self.to_status()),
+            events: vec![],
+            run_instructions: self.run_instructions.as_ref().map(Into::into),
+        }
+    }
+}
+
+/// Write `{dir}/{id}.json` via a temp file and rename, so a crash never
+/// leaves a truncated snapshot behind.
+pub fn write_snapshot(dir: &Path, snapshot: &ExecutionSnapshot) -> Result<()> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(format!("{}.json", snapshot.execution_id));
+    let tmp = dir.join(format!(".{}.json.tmp", snapshot.execution_id));
+    std::fs::write(&tmp, serde_json::to_string_pretty(snapshot)?)?;
+    std::fs::rename(&tmp, &path)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// Whether a process with `pid` still exists.
+fn pid_alive(pid: u32) -> bool {
+    #[cfg(unix)]
+    {
+        // Safety: signal 0 only checks for existence and permission
+        let ret = unsafe { libc::kill(pid as i32, 0) };
+        ret == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = pid;
+        false
+    }
+}
+
+/// Load every snapshot in `dir`. Executions recorded as live are marked
+/// Failed: the restarted daemon no longer owns their process and cannot
+/// stream or stop it. Unreadable files are skipped with a warning.
+pub fn load_snapshots(dir: &Path) -> Vec<ExecutionSnapshot> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut snapshots = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let parsed = std::fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| Ok(serde_json::from_str::<ExecutionSnapshot>(&json)?));
+        let mut snapshot = match parsed {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "Skipping unreadable execution snapshot");
+                continue;
+            }
+        };
+
+        if snapshot.is_active() {
+            snapshot.state = ExecutionState::Failed.as_str_name().to_string();
+            snapshot.termination_reason = match snapshot.pid.filter(|&pid| pid_alive(pid)) {
+                Some(pid) => format!(
+                    "Interrupted: daemon restarted while the execution was running \
+                     (claude process {pid} may still be running)"
+                ),
+                None => "Interrupted: daemon restarted while the execution was running".to_string(),
+            };
+            snapshot.ended_at.get_or_insert_with(Utc::now);
+            snapshot.pid = None;
+        }
+        snapshots.push(snapshot);
+    }
+
+    snapshots.sort_by_key(|s| s.started_at);
+    info!(dir = %dir.display(), count = snapshots.len(), "Loaded execution snapshots");
+    snapshots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn sample_snapshot(id: &str, state: ExecutionState, pid: Option<u32>) -> ExecutionSnapshot {
+        ExecutionSnapshot {
+            execution_id: id.to_string(),
+            task: "add a feature".to_string(),
+            project_root: "/tmp".to_string(),
+            state: state.as_str_name().to_string(),
+            current_iteration: 2,
+            max_iterations: 3,
+            current_score: 82.5,
+            quality_threshold: 70.0,
+            termination_reason: String::new(),
+            started_at: Utc::now(),
+            ended_at: None,
+            duration_seconds: 12.0,
+            total_cost_usd: 0.42,
+            total_input_tokens: 1000,
+            total_output_tokens: 500,
+            evidence: SnapshotEvidence {
+                files_written: vec!["src/lib.rs".to_string()],
+                commands_run: 3,
+                ..Default::default()
+            },
+            run_instructions: None,
+            pid,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let dir = std::env::temp_dir().join(format!("sc-history-{}", Uuid::new_v4()));
+        let snapshot = sample_snapshot("done", ExecutionState::Completed, None);
+
+        write_snapshot(&dir, &snapshot).unwrap();
+        let loaded = load_snapshots(&dir);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(loaded, vec![snapshot]);
+        let summary = loaded[0].to_summary();
+        assert_eq!(summary.state, ExecutionState::Completed as i32);
+        assert_eq!(summary.evidence.unwrap().files_written, vec!["src/lib.rs"]);
+    }
+
+    #[test]
+    fn test_running_snapshots_marked_interrupted() {
+        let dir = std::env::temp_dir().join(format!("sc-history-{}", Uuid::new_v4()));
+        // PIDs are capped well below i32::MAX, so this one never exists
+        write_snapshot(&dir, &sample_snapshot("stale", ExecutionState::Running, Some(i32::MAX as u32)))
+            .unwrap();
+        write_snapshot(&dir, &sample_snapshot("live", ExecutionState::Running, Some(std::process::id())))
+            .unwrap();
+        std::fs::write(dir.join("garbage.json"), "not json").unwrap();
+
+        let loaded = load_snapshots(&dir);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(loaded.len(), 2);
+        let stale = loaded.iter().find(|s| s.execution_id == "stale").unwrap();
+        assert_eq!(stale.execution_state(), ExecutionState::Failed);
+        assert!(stale.ended_at.is_some());
+        assert!(stale.termination_reason.starts_with("Interrupted"));
+        // A surviving process is no longer controllable by this daemon
+        let live = loaded.iter().find(|s| s.execution_id == "live").unwrap();
+        assert_eq!(live.execution_state(), ExecutionState::Failed);
+        assert!(live.termination_reason.contains("may still be running"));
+        assert_eq!(live.pid, None);
+    }
+
+    #[test]
+    fn test_snapshots_reloaded_from_indexed_dirs() {
+        let root = std::env::temp_dir().join(format!("sc-history-{}", Uuid::new_v4()));
+        let state = root.join("state");
+        let project_a = root.join("a/.superclaude_metrics/executions");
+        let project_b = root.join("b/.superclaude_metrics/executions");
+        let cwd_dir = root.join("cwd/.superclaude_metrics/executions");
+        write_snapshot(&project_a, &sample_snapshot("a", ExecutionState::Completed, None)).unwrap();
+        write_snapshot(&project_b, &sample_snapshot("b", ExecutionState::Running, None)).unwrap();
+        write_snapshot(&cwd_dir, &sample_snapshot("c", ExecutionState::Completed, None)).unwrap();
+
+        register_snapshot_dir(&state, &project_a).unwrap();
+        register_snapshot_dir(&state, &project_b).unwrap();
+        register_snapshot_dir(&state, &project_a).unwrap();
+        assert_eq!(indexed_snapshot_dirs(&state).len(), 2);
+
+        let loaded = load_indexed_snapshots(Some(&state), &cwd_dir);
+        let mut ids: Vec<&str> = loaded.iter().map(|s| s.execution_id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, ["a", "b", "c"]);
+        let b = loaded.iter().find(|s| s.execution_id == "b").unwrap();
+        assert_eq!(b.execution_state(), ExecutionState::Failed);
+
+        // An indexed directory that is also the cwd fallback loads once
+        assert_eq!(load_indexed_snapshots(Some(&state), &project_a).len(), 2);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_state_dir_resolution() {
+        assert_eq!(
+            state_dir_with(Some("/srv/sc".into()), Some("/home/me".into())),
+            Some(PathBuf::from("/srv/sc"))
+        );
+        assert_eq!(
+            state_dir_with(Some("  ".into()), Some("/home/me".into())),
+            Some(PathBuf::from("/home/me/.superclaude/daemon"))
+        );
+        assert_eq!(state_dir_with(None, None), None);
+    }
+}
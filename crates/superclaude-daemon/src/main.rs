@@ -2,25 +2,32 @@
 //!
 //! This daemon:
 //! - Listens on Unix socket (/tmp/superclaude.sock) and TCP (127.0.0.1:50051)
+//! - Optionally requires a bearer token on TCP (SUPERCLAUDE_AUTH_TOKEN)
+//! - Optionally serves TCP over TLS (SUPERCLAUDE_TLS_CERT / SUPERCLAUDE_TLS_KEY)
 //! - Manages execution lifecycle (start/stop/pause/resume)
 //! - Spawns claude CLI processes for each execution
 //! - Watches .superclaude_metrics/ for real-time events
 //! - Streams events to connected Zed panels
 
+mod auth;
 mod execution;
+mod history;
 mod metrics_watcher;
 mod server;
+mod tls;
 
 use std::path::PathBuf;
 
 use anyhow::Result;
 use tokio::net::UnixListener;
 use tokio::signal::unix::{signal, SignalKind};
+use tonic::service::interceptor::InterceptedService;
 use tonic::transport::Server;
 use tracing::{info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use superclaude_proto::super_claude_service_server::SuperClaudeServiceServer;
+use crate::auth::BearerAuth;
 use crate::server::SuperClaudeService;
 
 const UNIX_SOCKET_PATH: &str = "/tmp/superclaude.sock";
@@ -75,13 +82,21 @@ async fn main() -> Result<()> {
             .map_err(|e| anyhow::anyhow!("Unix server error: {}", e))
     });
 
-    // Spawn TCP listener
-    let tcp_service = grpc_service;
+    // Spawn TCP listener, behind bearer-token auth when configured
+    let auth = BearerAuth::from_env();
+    if auth.enabled() {
+        info!("TCP listener requires a bearer token");
+    }
+    let tcp_service = InterceptedService::new(grpc_service, auth);
+    let mut tcp_server = Server::builder();
+    if let Some(tls) = tls::tls_config_from_env()? {
+        tcp_server = tcp_server.tls_config(tls)?;
+    }
     let tcp_handle = tokio::spawn(async move {
         let addr = TCP_ADDR.parse()?;
         info!("Listening on TCP: {}", TCP_ADDR);
 
-        Server::builder()
+        tcp_server
             .add_service(tcp_service)
             .serve(addr)
             .await
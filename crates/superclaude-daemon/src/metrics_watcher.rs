@@ -196,6 +196,7 @@ fn parse_metrics_event(line: &str, execution_id: &str) -> Option<AgentEvent> {
                 tool_input: value.get("tool_input").and_then(|v| v.as_str()).unwrap_or("").to_string(),
                 tool_output: value.get("tool_output").and_then(|v| v.as_str()).unwrap_or("").to_string(),
                 tool_use_id: value.get("tool_use_id").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                tool_output_truncated: value.get("tool_output_truncated").and_then(|v| v.as_bool()).unwrap_or(false),
             }))
         }
 
@@ -309,6 +310,7 @@ fn parse_metrics_event(line: &str, execution_id: &str) -> Option<AgentEvent> {
 
     event.map(|e| AgentEvent {
         execution_id: execution_id.to_string(),
+        sequence: 0,
         timestamp,
         event: Some(e),
     })
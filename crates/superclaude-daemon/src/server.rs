@@ -1,6 +1,7 @@
 //! gRPC server implementation for SuperClaude service
 
 use std::pin::Pin;
+use std::sync::Arc;
 
 use chrono::Utc;
 use dashmap::DashMap;
@@ -8,11 +9,13 @@ use futures::Stream;
 use prost_types::Timestamp;
 use tokio_stream::wrappers::BroadcastStream;
 use tokio_stream::StreamExt;
+use tokio::sync::Semaphore;
 use tonic::{Request, Response, Status};
 use tracing::{info, warn};
 use uuid::Uuid;
 
-use crate::execution::{Execution, ExecutionHandle};
+use crate::execution::{resolve_metrics_dir, Execution, ExecutionHandle, ExecutionSlot};
+use crate::history::{self, ExecutionSnapshot};
 use superclaude_proto::*;
 use superclaude_proto::super_claude_service_server::SuperClaudeService as SuperClaudeServiceTrait;
 
@@ -20,18 +23,33 @@ use superclaude_proto::super_claude_service_server::SuperClaudeService as SuperC
 const DEFAULT_MAX_ITERATIONS: i32 = 3;
 const DEFAULT_QUALITY_THRESHOLD: f32 = 70.0;
 const DEFAULT_TIMEOUT_SECONDS: f32 = 300.0;
+const DEFAULT_MAX_REPEATED_TOOL_CALLS: i32 = 5;
+
+/// Maximum simultaneous executions; unset or 0 means unlimited
+const MAX_CONCURRENT_ENV: &str = "SUPERCLAUDE_MAX_CONCURRENT_EXECUTIONS";
+/// Set to "0" or "false" to reject executions beyond the limit instead of queueing them
+const QUEUE_EXECUTIONS_ENV: &str = "SUPERCLAUDE_QUEUE_EXECUTIONS";
 
 /// The main service implementation
 pub struct SuperClaudeService {
     /// Active executions by ID
     executions: DashMap<String, ExecutionHandle>,
 
+    /// Executions restored from snapshots written before the last restart
+    history: DashMap<String, ExecutionSnapshot>,
+
     /// Default configuration
     default_config: parking_lot::RwLock<ExecutionConfig>,
 
     /// Obsidian configuration
     obsidian_config: parking_lot::RwLock<Option<ObsidianConfig>>,
 
+    /// Concurrency limit: (max simultaneous executions, slots). None when unlimited.
+    execution_slots: Option<(usize, Arc<Semaphore>)>,
+
+    /// Queue executions beyond the limit (Pending) instead of rejecting them
+    queue_when_full: bool,
+
     /// Daemon start time
     start_time: chrono::DateTime<Utc>,
 }
@@ -40,6 +58,7 @@ impl SuperClaudeService {
     pub fn new() -> Self {
         Self {
             executions: DashMap::new(),
+            history: DashMap::new(),
             default_config: parking_lot::RwLock::new(ExecutionConfig {
                 max_iterations: DEFAULT_MAX_ITERATIONS,
                 quality_threshold: DEFAULT_QUALITY_THRESHOLD,
@@ -47,10 +66,76 @@ impl SuperClaudeService {
                 timeout_seconds: DEFAULT_TIMEOUT_SECONDS,
                 pal_review_enabled: true,
                 min_improvement: 5.0,
+                env_allowlist: None,
+                max_repeated_tool_calls: DEFAULT_MAX_REPEATED_TOOL_CALLS,
+                stop_on_loop: false,
+                max_cost_usd: 0.0,
+                metrics_dir: String::new(),
+                max_event_history: 0,
             }),
             obsidian_config: parking_lot::RwLock::new(None),
+            execution_slots: None,
+            queue_when_full: true,
             start_time: Utc::now(),
         }
+        .with_concurrency_from_env()
+        .with_restored_history()
+    }
+
+    /// Reload execution snapshots from every snapshot directory in the
+    /// daemon's index, plus the metrics directory of its working directory.
+    fn with_restored_history(self) -> Self {
+        let cwd = std::env::current_dir().unwrap_or_default();
+        let cwd_dir = history::snapshot_dir(&resolve_metrics_dir(&cwd, ""));
+        let state_dir = history::state_dir();
+        for snapshot in history::load_indexed_snapshots(state_dir.as_deref(), &cwd_dir) {
+            self.history.insert(snapshot.execution_id.clone(), snapshot);
+        }
+        self
+    }
+
+    /// Limit simultaneous executions to `max` (0 means unlimited). Beyond the
+    /// limit, executions are queued or rejected per `queue_when_full`.
+    pub fn with_concurrency_limit(mut self, max: usize, queue_when_full: bool) -> Self {
+        self.execution_slots = (max > 0).then(|| (max, Arc::new(Semaphore::new(max))));
+        self.queue_when_full = queue_when_full;
+        self
+    }
+
+    fn with_concurrency_from_env(self) -> Self {
+        let max = match std::env::var(MAX_CONCURRENT_ENV) {
+            Ok(value) => value.trim().parse::<usize>().unwrap_or_else(|_| {
+                warn!(value = %value, "Invalid {}, ignoring", MAX_CONCURRENT_ENV);
+                0
+            }),
+            Err(_) => 0,
+        };
+        let queue_when_full = std::env::var(QUEUE_EXECUTIONS_ENV)
+            .map(|v| !matches!(v.trim().to_lowercase().as_str(), "0" | "false" | "no"))
+            .unwrap_or(true);
+        if max > 0 {
+            info!(max = max, queue = queue_when_full, "Limiting concurrent executions");
+        }
+        self.with_concurrency_limit(max, queue_when_full)
+    }
+
+    /// Claim a concurrency slot for a new execution. Returns the slot (None
+    /// when unlimited) and the execution's initial state.
+    #[allow(clippy::result_large_err, reason = "the Status is returned as the RPC error as-is")]
+    fn claim_slot(&self) -> Result<(Option<ExecutionSlot>, ExecutionState), Status> {
+        let Some((max, slots)) = &self.execution_slots else {
+            return Ok((None, ExecutionState::Running));
+        };
+        match slots.clone().try_acquire_owned() {
+            Ok(permit) => Ok((Some(ExecutionSlot::Acquired(permit)), ExecutionState::Running)),
+            Err(_) if self.queue_when_full => {
+                Ok((Some(ExecutionSlot::Queued(slots.clone())), ExecutionState::Pending))
+            }
+            Err(_) => Err(Status::resource_exhausted(format!(
+                "Daemon at capacity: {} executions already running",
+                max
+            ))),
+        }
     }
 
     fn now_timestamp() -> Option<Timestamp> {
@@ -62,6 +147,73 @@ impl SuperClaudeService {
     }
 }
 
+/// Snake-case name of an event's payload, matching the `AgentEvent` oneof
+/// field names. Used to filter `StreamEvents` by `event_types`.
+fn event_kind(event: &AgentEvent) -> &'static str {
+    use agent_event::Event;
+    match &event.event {
+        Some(Event::IterationStarted(_)) => "iteration_started",
+        Some(Event::IterationCompleted(_)) => "iteration_completed",
+        Some(Event::ToolInvoked(_)) => "tool_invoked",
+        Some(Event::FileChanged(_)) => "file_changed",
+        Some(Event::TestResult(_)) => "test_result",
+        Some(Event::ScoreUpdated(_)) => "score_updated",
+        Some(Event::StateChanged(_)) => "state_changed",
+        Some(Event::SubagentSpawned(_)) => "subagent_spawned",
+        Some(Event::SubagentCompleted(_)) => "subagent_completed",
+        Some(Event::ArtifactWritten(_)) => "artifact_written",
+        Some(Event::LogMessage(_)) => "log_message",
+        Some(Event::Error(_)) => "error",
+        Some(Event::DecisionMade(_)) => "decision_made",
+        None => "unknown",
+    }
+}
+
+/// Filter, sort (newest first) and page execution summaries for
+/// `list_executions`. Page tokens are offsets into the filtered list.
+#[allow(clippy::result_large_err, reason = "the Status is returned as the RPC error as-is")]
+fn paginate_executions(
+    mut summaries: Vec<ExecutionSummary>,
+    req: &ListExecutionsRequest,
+) -> Result<(Vec<ExecutionSummary>, String), Status> {
+    let state_filter = ExecutionState::try_from(req.state_filter)
+        .unwrap_or(ExecutionState::Unspecified);
+    summaries.retain(|summary| {
+        if state_filter != ExecutionState::Unspecified {
+            summary.state == state_filter as i32
+        } else {
+            req.include_completed
+                || summary.state == ExecutionState::Running as i32
+                || summary.state == ExecutionState::Pending as i32
+        }
+    });
+
+    let started = |s: &ExecutionSummary| s.started_at.as_ref().map(|t| (t.seconds, t.nanos));
+    summaries.sort_by(|a, b| {
+        started(b)
+            .cmp(&started(a))
+            .then_with(|| a.execution_id.cmp(&b.execution_id))
+    });
+
+    let offset = if req.page_token.is_empty() {
+        0
+    } else {
+        req.page_token
+            .parse::<usize>()
+            .map_err(|_| Status::invalid_argument(format!("Invalid page_token: {}", req.page_token)))?
+    };
+    let page_size = if req.page_size > 0 { req.page_size } else { req.limit.max(0) } as usize;
+
+    let page: Vec<ExecutionSummary> = summaries.iter().skip(offset).take(page_size).cloned().collect();
+    let next = offset + page.len();
+    let next_page_token = if !page.is_empty() && next < summaries.len() {
+        next.to_string()
+    } else {
+        String::new()
+    };
+    Ok((page, next_page_token))
+}
+
 #[tonic::async_trait]
 impl SuperClaudeServiceTrait for SuperClaudeService {
     // =========================================================================
@@ -84,13 +236,18 @@ impl SuperClaudeServiceTrait for SuperClaudeService {
         // Merge request config with defaults
         let config = req.config.unwrap_or_else(|| self.default_config.read().clone());
 
+        let (slot, state) = self.claim_slot()?;
+
         // Create execution
-        let execution = Execution::new(
+        let mut execution = Execution::new(
             execution_id.clone(),
             req.task,
             req.project_root,
             config,
         );
+        if let Some(slot) = slot {
+            execution = execution.with_slot(slot);
+        }
 
         let handle = execution.start().await.map_err(|e| {
             Status::internal(format!("Failed to start execution: {}", e))
@@ -100,7 +257,7 @@ impl SuperClaudeServiceTrait for SuperClaudeService {
 
         Ok(Response::new(StartExecutionResponse {
             execution_id,
-            state: ExecutionState::Running as i32,
+            state: state as i32,
             started_at: SuperClaudeService::now_timestamp(),
         }))
     }
@@ -182,6 +339,10 @@ impl SuperClaudeServiceTrait for SuperClaudeService {
             Ok(Response::new(GetStatusResponse {
                 status: Some(status),
             }))
+        } else if let Some(snapshot) = self.history.get(&req.execution_id) {
+            Ok(Response::new(GetStatusResponse {
+                status: Some(snapshot.to_status()),
+            }))
         } else {
             Err(Status::not_found(format!(
                 "Execution {} not found",
@@ -196,22 +357,27 @@ impl SuperClaudeServiceTrait for SuperClaudeService {
     ) -> Result<Response<ListExecutionsResponse>, Status> {
         let req = request.into_inner();
 
-        let executions: Vec<ExecutionSummary> = self
+        // Live executions plus restored history not shadowed by them
+        let mut summaries: Vec<ExecutionSummary> = self
             .executions
             .iter()
-            .filter(|entry| {
-                if req.include_completed {
-                    true
-                } else {
-                    let state = entry.value().state();
-                    state == ExecutionState::Running || state == ExecutionState::Pending
-                }
-            })
-            .take(req.limit as usize)
             .map(|entry| entry.value().to_summary())
             .collect();
+        let live: std::collections::HashSet<String> =
+            summaries.iter().map(|s| s.execution_id.clone()).collect();
+        summaries.extend(
+            self.history
+                .iter()
+                .filter(|entry| !live.contains(entry.key()))
+                .map(|entry| entry.value().to_summary()),
+        );
 
-        Ok(Response::new(ListExecutionsResponse { executions }))
+        let (executions, next_page_token) = paginate_executions(summaries, &req)?;
+
+        Ok(Response::new(ListExecutionsResponse {
+            executions,
+            next_page_token,
+        }))
     }
 
     type StreamEventsStream = Pin<Box<dyn Stream<Item = Result<AgentEvent, Status>> + Send>>;
@@ -223,21 +389,37 @@ impl SuperClaudeServiceTrait for SuperClaudeService {
         let req = request.into_inner();
 
         if let Some(handle) = self.executions.get(&req.execution_id) {
-            let receiver = handle.subscribe_events();
+            let resume = req.from_sequence > 0 || req.from_timestamp.is_some();
+            let (history, receiver) = if resume {
+                handle.subscribe_from(req.from_sequence, req.from_timestamp.as_ref())
+            } else if req.include_history {
+                handle.subscribe_from(0, None)
+            } else {
+                (vec![], handle.subscribe_events())
+            };
+            let event_types = req.event_types;
+            let wanted = move |event: &AgentEvent| {
+                event_types.is_empty() || event_types.iter().any(|t| t == event_kind(event))
+            };
 
             // Convert broadcast receiver to stream
+            let live_filter = wanted.clone();
             let stream = BroadcastStream::new(receiver)
                 .filter_map(|result| result.ok())
+                .filter(move |event| live_filter(event))
                 .map(Ok);
 
-            // If include_history, prepend historical events
-            if req.include_history {
-                let history = handle.get_event_history();
+            // Prepend replayed history (include_history or a resume offset)
+            if history.is_empty() {
+                Ok(Response::new(Box::pin(stream)))
+            } else {
+                let history: Vec<AgentEvent> = history
+                    .into_iter()
+                    .filter(|event| wanted(event))
+                    .collect();
                 let history_stream = tokio_stream::iter(history.into_iter().map(Ok));
                 let combined = history_stream.chain(stream);
                 Ok(Response::new(Box::pin(combined)))
-            } else {
-                Ok(Response::new(Box::pin(stream)))
             }
         } else {
             Err(Status::not_found(format!(
@@ -360,6 +542,8 @@ impl SuperClaudeServiceTrait for SuperClaudeService {
 
         if let Some(handle) = self.executions.get(&req.execution_id) {
             Ok(Response::new(handle.get_detail()))
+        } else if let Some(snapshot) = self.history.get(&req.execution_id) {
+            Ok(Response::new(snapshot.to_detail()))
         } else {
             Err(Status::not_found(format!(
                 "Execution {} not found",
@@ -368,6 +552,24 @@ impl SuperClaudeServiceTrait for SuperClaudeService {
         }
     }
 
+    async fn get_tool_output(
+        &self,
+        request: Request<GetToolOutputRequest>,
+    ) -> Result<Response<GetToolOutputResponse>, Status> {
+        let req = request.into_inner();
+
+        let handle = self.executions.get(&req.execution_id).ok_or_else(|| {
+            Status::not_found(format!("Execution {} not found", req.execution_id))
+        })?;
+        match handle.get_tool_output(&req.tool_use_id) {
+            Some(output) => Ok(Response::new(GetToolOutputResponse { output })),
+            None => Err(Status::not_found(format!(
+                "Full output of {} is no longer available",
+                req.tool_use_id
+            ))),
+        }
+    }
+
     // =========================================================================
     // Health Check
     // =========================================================================
@@ -392,3 +594,76 @@ impl SuperClaudeServiceTrait for SuperClaudeService {
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(id: &str, state: ExecutionState, started_secs: i64) -> ExecutionSummary {
+        ExecutionSummary {
+            execution_id: id.to_string(),
+            state: state as i32,
+            started_at: Some(Timestamp { seconds: started_secs, nanos: 0 }),
+            ..Default::default()
+        }
+    }
+
+    fn sample() -> Vec<ExecutionSummary> {
+        vec![
+            summary("a", ExecutionState::Completed, 100),
+            summary("b", ExecutionState::Failed, 200),
+            summary("c", ExecutionState::Running, 300),
+            summary("d", ExecutionState::Completed, 400),
+            summary("e", ExecutionState::Completed, 500),
+        ]
+    }
+
+    fn ids(page: &[ExecutionSummary]) -> Vec<&str> {
+        page.iter().map(|s| s.execution_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_state_filter() {
+        let req = ListExecutionsRequest {
+            limit: 10,
+            state_filter: ExecutionState::Completed as i32,
+            ..Default::default()
+        };
+        let (page, next) = paginate_executions(sample(), &req).unwrap();
+        assert_eq!(ids(&page), vec!["e", "d", "a"]);
+        assert!(next.is_empty());
+
+        // Without a filter, include_completed=false keeps only live executions
+        let req = ListExecutionsRequest { limit: 10, ..Default::default() };
+        let (page, _) = paginate_executions(sample(), &req).unwrap();
+        assert_eq!(ids(&page), vec!["c"]);
+    }
+
+    #[test]
+    fn test_paging_walks_pages_newest_first() {
+        let mut req = ListExecutionsRequest {
+            include_completed: true,
+            page_size: 3,
+            ..Default::default()
+        };
+        let (first, token) = paginate_executions(sample(), &req).unwrap();
+        assert_eq!(ids(&first), vec!["e", "d", "c"]);
+        assert_eq!(token, "3");
+
+        req.page_token = token;
+        let (second, token) = paginate_executions(sample(), &req).unwrap();
+        assert_eq!(ids(&second), vec!["b", "a"]);
+        assert!(token.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_page_token() {
+        let req = ListExecutionsRequest {
+            page_size: 2,
+            page_token: "bogus".to_string(),
+            ..Default::default()
+        };
+        let err = paginate_executions(sample(), &req).unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    }
+}
@@ -30,6 +30,7 @@ async fn main() -> anyhow::Result<()> {
             role: Role::User,
             content: vec![ContentBlock::Text {
                 text: "Explain what Rust is in one sentence.".to_string(),
+                cache_control: None,
             }],
         }],
         ..Default::default()
@@ -43,7 +44,7 @@ async fn main() -> anyhow::Result<()> {
         response.usage.output_tokens
     );
 
-    if let ContentBlock::Text { text } = &response.content[0] {
+    if let ContentBlock::Text { text, .. } = &response.content[0] {
         println!("\nResponse:\n{}", text);
     }
 
@@ -56,6 +57,7 @@ async fn main() -> anyhow::Result<()> {
             role: Role::User,
             content: vec![ContentBlock::Text {
                 text: "Count from 1 to 10, one number per line.".to_string(),
+                cache_control: None,
             }],
         }],
         ..Default::default()
@@ -100,15 +102,16 @@ async fn main() -> anyhow::Result<()> {
             role: Role::User,
             content: vec![ContentBlock::Text {
                 text: "What should I build?".to_string(),
+                cache_control: None,
             }],
         }],
-        system: Some("You are a creative software architect who suggests innovative project ideas.".to_string()),
+        system: Some("You are a creative software architect who suggests innovative project ideas.".into()),
         temperature: Some(0.9),
         ..Default::default()
     };
 
     let response = client.create_message(request).await?;
-    if let ContentBlock::Text { text } = &response.content[0] {
+    if let ContentBlock::Text { text, .. } = &response.content[0] {
         println!("Creative suggestion:\n{}", text);
     }
 
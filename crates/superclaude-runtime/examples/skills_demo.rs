@@ -15,7 +15,7 @@ fn main() -> anyhow::Result<()> {
     let feedback_dir = temp_dir.path().join("feedback");
 
     // Create a skill store
-    let mut store = SkillStore::new(Some(skills_dir), Some(feedback_dir))?;
+    let store = SkillStore::new(Some(skills_dir), Some(feedback_dir))?;
     println!("✓ Created SkillStore");
 
     // Create a sample skill
@@ -39,6 +39,7 @@ fn main() -> anyhow::Result<()> {
         applicability_conditions: vec!["Rust projects".to_string()],
         promoted: false,
         promotion_reason: String::new(),
+        last_applied_at: None,
     };
 
     // Save the skill
@@ -71,7 +72,7 @@ fn main() -> anyhow::Result<()> {
     println!("✓ Saved feedback for iteration {}", feedback.iteration);
 
     // Search for skills
-    let results = store.search_skills("demo", None, 50.0, false)?;
+    let results = store.search_skills("demo", None, 50.0, false, false)?;
     println!("✓ Found {} skills matching 'demo'", results.len());
 
     // Record skill application
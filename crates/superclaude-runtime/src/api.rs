@@ -53,9 +53,11 @@ while let Some(event) = stream.next().await {
 use anyhow::{Context, Result};
 use reqwest::{header, Client, StatusCode};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
 use std::time::Duration;
 use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, warn};
 
 // ============================================================================
@@ -70,12 +72,21 @@ pub enum Role {
     Assistant,
 }
 
+/// Prompt caching marker, serialized as `{"type": "ephemeral"}`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CacheControl {
+    Ephemeral,
+}
+
 /// Content block in a message
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ContentBlock {
     Text {
         text: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
     },
     Image {
         source: ImageSource,
@@ -90,17 +101,62 @@ pub enum ContentBlock {
         content: String,
         #[serde(skip_serializing_if = "Option::is_none")]
         is_error: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
+    },
+    /// Extended thinking output. Must be sent back unchanged (signature
+    /// included) in follow-up turns.
+    Thinking {
+        thinking: String,
+        signature: String,
     },
+    /// Thinking the API has encrypted; opaque, but must also be sent back
+    RedactedThinking {
+        data: String,
+    },
+}
+
+impl ContentBlock {
+    /// Plain text block without caching
+    pub fn text(text: impl Into<String>) -> Self {
+        ContentBlock::Text {
+            text: text.into(),
+            cache_control: None,
+        }
+    }
+
+    /// Mark this block as a cache breakpoint. No-op for block types that
+    /// don't carry `cache_control`.
+    pub fn with_cache_control(mut self) -> Self {
+        match &mut self {
+            ContentBlock::Text { cache_control, .. }
+            | ContentBlock::ToolResult { cache_control, .. } => {
+                *cache_control = Some(CacheControl::Ephemeral);
+            }
+            ContentBlock::Image { .. }
+            | ContentBlock::ToolUse { .. }
+            | ContentBlock::Thinking { .. }
+            | ContentBlock::RedactedThinking { .. } => {}
+        }
+        self
+    }
 }
 
 /// Image source (base64 or URL)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// `Base64` must carry the image's `media_type` (`image/jpeg`, `image/png`,
+/// `image/gif` or `image/webp`). `Url` has none: the API fetches the image
+/// and infers the type, so the URL must serve one of those formats.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ImageSource {
     Base64 {
         media_type: String,
         data: String,
     },
+    Url {
+        url: String,
+    },
 }
 
 /// Message in conversation
@@ -116,6 +172,28 @@ pub struct Tool {
     pub name: String,
     pub description: String,
     pub input_schema: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControl>,
+}
+
+/// System prompt: a plain string, or text blocks (needed for caching)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SystemPrompt {
+    Text(String),
+    Blocks(Vec<ContentBlock>),
+}
+
+impl From<String> for SystemPrompt {
+    fn from(text: String) -> Self {
+        SystemPrompt::Text(text)
+    }
+}
+
+impl From<&str> for SystemPrompt {
+    fn from(text: &str) -> Self {
+        SystemPrompt::Text(text.to_string())
+    }
 }
 
 /// Request to create a message
@@ -126,7 +204,7 @@ pub struct CreateMessageRequest {
     pub messages: Vec<Message>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub system: Option<String>,
+    pub system: Option<SystemPrompt>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f32>,
@@ -140,6 +218,9 @@ pub struct CreateMessageRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tools: Option<Vec<Tool>>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ToolChoice>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stream: Option<bool>,
 
@@ -148,6 +229,40 @@ pub struct CreateMessageRequest {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stop_sequences: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<ThinkingConfig>,
+}
+
+/// How Claude may use the provided tools
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ToolChoice {
+    /// Claude decides whether to call a tool (API default)
+    Auto,
+    /// Claude must call one of the tools
+    Any,
+    /// Claude must call this tool
+    Tool { name: String },
+    /// Claude must not call any tool
+    None,
+}
+
+/// Request rejected locally before being sent
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum RequestValidationError {
+    #[error("tool_choice requires a tool named '{name}', but no such tool was provided")]
+    UnknownTool { name: String },
+    #[error("tool_choice '{0}' requires at least one tool")]
+    NoTools(&'static str),
+}
+
+/// Extended thinking configuration, e.g. `{"type": "enabled", "budget_tokens": 2048}`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ThinkingConfig {
+    Enabled { budget_tokens: u32 },
+    Disabled,
 }
 
 impl Default for CreateMessageRequest {
@@ -161,18 +276,95 @@ impl Default for CreateMessageRequest {
             top_p: None,
             top_k: None,
             tools: None,
+            tool_choice: None,
             stream: None,
             metadata: None,
             stop_sequences: None,
+            thinking: None,
         }
     }
 }
 
-/// Usage statistics
+impl CreateMessageRequest {
+    /// Check the request for mistakes the API would reject
+    pub fn validate(&self) -> std::result::Result<(), RequestValidationError> {
+        let tools = self.tools.as_deref().unwrap_or_default();
+        match &self.tool_choice {
+            Some(ToolChoice::Any) if tools.is_empty() => Err(RequestValidationError::NoTools("any")),
+            Some(ToolChoice::Tool { name }) if !tools.iter().any(|t| &t.name == name) => {
+                Err(RequestValidationError::UnknownTool { name: name.clone() })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Enable extended thinking with the given token budget
+    ///
+    /// The API requires `budget_tokens` < `max_tokens`.
+    pub fn with_thinking(mut self, budget_tokens: u32) -> Self {
+        self.thinking = Some(ThinkingConfig::Enabled { budget_tokens });
+        self
+    }
+
+    /// Set the system prompt as a single cacheable text block
+    pub fn with_cached_system(mut self, text: impl Into<String>) -> Self {
+        self.system = Some(SystemPrompt::Blocks(vec![
+            ContentBlock::text(text).with_cache_control(),
+        ]));
+        self
+    }
+
+    /// Set the tools, marking the last one as a cache breakpoint so the
+    /// whole tool list is cached
+    pub fn with_cached_tools(mut self, mut tools: Vec<Tool>) -> Self {
+        if let Some(last) = tools.last_mut() {
+            last.cache_control = Some(CacheControl::Ephemeral);
+        }
+        self.tools = Some(tools);
+        self
+    }
+}
+
+/// Request body for `/v1/messages/count_tokens`: the subset of a message
+/// request that contributes to input tokens
+#[derive(Debug, Serialize)]
+struct CountTokensRequest<'a> {
+    model: &'a str,
+    messages: &'a [Message],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<&'a SystemPrompt>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<&'a [Tool]>,
+}
+
+impl<'a> From<&'a CreateMessageRequest> for CountTokensRequest<'a> {
+    fn from(request: &'a CreateMessageRequest) -> Self {
+        Self {
+            model: &request.model,
+            messages: &request.messages,
+            system: request.system.as_ref(),
+            tools: request.tools.as_deref(),
+        }
+    }
+}
+
+/// Response from `/v1/messages/count_tokens`
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CountTokensResponse {
+    pub input_tokens: u32,
+}
+
+/// Usage statistics
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Usage {
     pub input_tokens: u32,
     pub output_tokens: u32,
+    /// Tokens written to the prompt cache by this request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_creation_input_tokens: Option<u32>,
+    /// Tokens served from the prompt cache
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read_input_tokens: Option<u32>,
 }
 
 /// Stop reason
@@ -251,6 +443,15 @@ pub enum ContentBlockStartData {
         id: String,
         name: String,
     },
+    Thinking {
+        #[serde(default)]
+        thinking: String,
+        #[serde(default)]
+        signature: String,
+    },
+    RedactedThinking {
+        data: String,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -262,6 +463,12 @@ pub enum ContentDelta {
     InputJsonDelta {
         partial_json: String,
     },
+    ThinkingDelta {
+        thinking: String,
+    },
+    SignatureDelta {
+        signature: String,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -288,6 +495,185 @@ impl std::fmt::Display for ApiError {
 
 impl std::error::Error for ApiError {}
 
+/// Status code returned when the API is temporarily overloaded
+pub const STATUS_OVERLOADED: u16 = 529;
+
+/// Error body envelope: `{"type": "error", "error": {...}}`
+#[derive(Debug, Deserialize)]
+struct ApiErrorEnvelope {
+    error: ApiError,
+}
+
+/// Failure returned by the API, classified so callers can react without
+/// string matching
+///
+/// `AnthropicClient` methods return `anyhow::Result`; API failures can be
+/// recovered with `err.downcast_ref::<ApiClientError>()`.
+#[derive(Debug, thiserror::Error)]
+pub enum ApiClientError {
+    /// 401 / 403: missing or invalid API key, or no access
+    #[error("authentication failed [{status}]: {error}")]
+    AuthFailed { status: StatusCode, error: ApiError },
+
+    /// Other 4xx: the request itself was rejected
+    #[error("invalid request [{status}]: {error}")]
+    InvalidRequest { status: StatusCode, error: ApiError },
+
+    /// 429
+    #[error("rate limited: {error}")]
+    RateLimited {
+        error: ApiError,
+        retry_after: Option<Duration>,
+    },
+
+    /// 529 / `overloaded_error`
+    #[error("overloaded: {error}")]
+    Overloaded {
+        error: ApiError,
+        retry_after: Option<Duration>,
+    },
+
+    /// Other 5xx
+    #[error("server error [{status}]: {error}")]
+    ServerError {
+        status: StatusCode,
+        error: ApiError,
+        retry_after: Option<Duration>,
+    },
+
+    /// Success status, but the body didn't match the expected shape
+    #[error("failed to parse response JSON: {source}")]
+    Deserialization {
+        #[source]
+        source: serde_json::Error,
+        body: String,
+    },
+}
+
+impl ApiClientError {
+    /// Classify a non-success response. The body is parsed as an API error
+    /// envelope when possible; otherwise the raw text becomes the message.
+    pub fn from_status(status: StatusCode, headers: &header::HeaderMap, body: &str) -> Self {
+        let error = serde_json::from_str::<ApiErrorEnvelope>(body)
+            .map(|envelope| envelope.error)
+            .unwrap_or_else(|_| ApiError {
+                r#type: status
+                    .canonical_reason()
+                    .unwrap_or("unknown")
+                    .to_lowercase()
+                    .replace(' ', "_"),
+                message: body.to_string(),
+            });
+        let retry_after = retry_after_from_headers(headers);
+
+        if status.as_u16() == STATUS_OVERLOADED || error.r#type == "overloaded_error" {
+            return ApiClientError::Overloaded { error, retry_after };
+        }
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                ApiClientError::AuthFailed { status, error }
+            }
+            StatusCode::TOO_MANY_REQUESTS => ApiClientError::RateLimited { error, retry_after },
+            s if s.is_server_error() => ApiClientError::ServerError {
+                status,
+                error,
+                retry_after,
+            },
+            _ => ApiClientError::InvalidRequest { status, error },
+        }
+    }
+
+    async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.text().await.unwrap_or_default();
+        Self::from_status(status, &headers, &body)
+    }
+
+    /// The parsed API error, if the failure came from an error response
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            ApiClientError::AuthFailed { error, .. }
+            | ApiClientError::InvalidRequest { error, .. }
+            | ApiClientError::RateLimited { error, .. }
+            | ApiClientError::Overloaded { error, .. }
+            | ApiClientError::ServerError { error, .. } => Some(error),
+            ApiClientError::Deserialization { .. } => None,
+        }
+    }
+
+    /// How long the server asked us to wait before retrying
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ApiClientError::RateLimited { retry_after, .. }
+            | ApiClientError::Overloaded { retry_after, .. }
+            | ApiClientError::ServerError { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Rate limits, overload and other 5xx responses are transient
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ApiClientError::RateLimited { .. }
+                | ApiClientError::Overloaded { .. }
+                | ApiClientError::ServerError { .. }
+        )
+    }
+}
+
+/// Read the server's requested wait from `retry-after` (seconds or HTTP
+/// date), falling back to the latest `anthropic-ratelimit-*-reset` timestamp
+fn retry_after_from_headers(headers: &header::HeaderMap) -> Option<Duration> {
+    let now = chrono::Utc::now();
+    let until = |at: chrono::DateTime<chrono::Utc>| (at - now).to_std().unwrap_or(Duration::ZERO);
+
+    if let Some(value) = headers
+        .get(header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+    {
+        if let Ok(seconds) = value.parse::<f64>() {
+            if seconds.is_finite() && seconds >= 0.0 {
+                return Some(Duration::try_from_secs_f64(seconds).unwrap_or(Duration::MAX));
+            }
+        }
+        if let Ok(at) = chrono::DateTime::parse_from_rfc2822(value) {
+            return Some(until(at.with_timezone(&chrono::Utc)));
+        }
+    }
+
+    headers
+        .iter()
+        .filter(|(name, _)| {
+            let name = name.as_str();
+            name.starts_with("anthropic-ratelimit-") && name.ends_with("-reset")
+        })
+        .filter_map(|(_, value)| value.to_str().ok())
+        .filter_map(|value| chrono::DateTime::parse_from_rfc3339(value.trim()).ok())
+        .map(|at| until(at.with_timezone(&chrono::Utc)))
+        .max()
+}
+
+/// Whether the request failed because the model is overloaded (529)
+fn is_overloaded(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<ApiClientError>(),
+        Some(ApiClientError::Overloaded { .. })
+    )
+}
+
+/// Delay before the next attempt: the server's hint when it gave one,
+/// capped at `max` so a huge or hostile header cannot stall the client,
+/// otherwise the computed backoff
+fn retry_delay(err: &anyhow::Error, backoff: Duration, max: Duration) -> Duration {
+    err.downcast_ref::<ApiClientError>()
+        .and_then(ApiClientError::retry_after)
+        .map(|hint| hint.min(max))
+        .unwrap_or(backoff)
+}
+
 // ============================================================================
 // Anthropic Client
 // ============================================================================
@@ -312,6 +698,94 @@ impl Default for RetryConfig {
     }
 }
 
+/// Default API endpoint
+pub const DEFAULT_API_BASE: &str = "https://api.anthropic.com";
+
+/// Default `anthropic-version` header
+pub const DEFAULT_API_VERSION: &str = "2023-06-01";
+
+/// Default overall request timeout (covers long generations)
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Builder for `AnthropicClient` with HTTP tuning knobs
+#[derive(Debug, Clone)]
+pub struct AnthropicClientBuilder {
+    api_key: String,
+    api_base: String,
+    api_version: String,
+    connect_timeout: Option<Duration>,
+    request_timeout: Duration,
+    pool_max_idle_per_host: Option<usize>,
+    retry_config: RetryConfig,
+}
+
+impl AnthropicClientBuilder {
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            api_base: DEFAULT_API_BASE.to_string(),
+            api_version: DEFAULT_API_VERSION.to_string(),
+            connect_timeout: None,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            pool_max_idle_per_host: None,
+            retry_config: RetryConfig::default(),
+        }
+    }
+
+    pub fn api_base(mut self, api_base: impl Into<String>) -> Self {
+        self.api_base = api_base.into();
+        self
+    }
+
+    pub fn api_version(mut self, api_version: impl Into<String>) -> Self {
+        self.api_version = api_version.into();
+        self
+    }
+
+    /// Timeout for establishing the TCP/TLS connection only
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout for the whole request, including reading the response
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Maximum idle connections kept open per host
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    pub fn retry_config(mut self, config: RetryConfig) -> Self {
+        self.retry_config = config;
+        self
+    }
+
+    pub fn build(self) -> Result<AnthropicClient> {
+        let mut http = Client::builder().timeout(self.request_timeout);
+        if let Some(timeout) = self.connect_timeout {
+            http = http.connect_timeout(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            http = http.pool_max_idle_per_host(max);
+        }
+        let http_client = http.build().context("Failed to create HTTP client")?;
+
+        Ok(AnthropicClient {
+            api_key: self.api_key,
+            api_base: self.api_base,
+            api_version: self.api_version,
+            http_client,
+            retry_config: self.retry_config,
+            model_fallbacks: Vec::new(),
+        })
+    }
+}
+
 /// Anthropic API client
 pub struct AnthropicClient {
     api_key: String,
@@ -319,6 +793,8 @@ pub struct AnthropicClient {
     api_version: String,
     http_client: Client,
     retry_config: RetryConfig,
+    /// Models to try, in order, when the requested model stays overloaded
+    model_fallbacks: Vec<String>,
 }
 
 impl AnthropicClient {
@@ -332,29 +808,26 @@ impl AnthropicClient {
         let api_key = env::var("ANTHROPIC_API_KEY")
             .context("ANTHROPIC_API_KEY environment variable not set")?;
 
-        let api_base = env::var("ANTHROPIC_API_BASE")
-            .unwrap_or_else(|_| "https://api.anthropic.com".to_string());
+        let api_base =
+            env::var("ANTHROPIC_API_BASE").unwrap_or_else(|_| DEFAULT_API_BASE.to_string());
 
         let api_version =
-            env::var("ANTHROPIC_API_VERSION").unwrap_or_else(|_| "2023-06-01".to_string());
+            env::var("ANTHROPIC_API_VERSION").unwrap_or_else(|_| DEFAULT_API_VERSION.to_string());
 
         Self::new(api_key, api_base, api_version)
     }
 
     /// Create a new client with explicit configuration
     pub fn new(api_key: String, api_base: String, api_version: String) -> Result<Self> {
-        let http_client = Client::builder()
-            .timeout(Duration::from_secs(300)) // 5 minutes
+        Self::builder(api_key)
+            .api_base(api_base)
+            .api_version(api_version)
             .build()
-            .context("Failed to create HTTP client")?;
+    }
 
-        Ok(Self {
-            api_key,
-            api_base,
-            api_version,
-            http_client,
-            retry_config: RetryConfig::default(),
-        })
+    /// Start building a client with custom timeouts and pooling
+    pub fn builder(api_key: impl Into<String>) -> AnthropicClientBuilder {
+        AnthropicClientBuilder::new(api_key)
     }
 
     /// Set custom retry configuration
@@ -363,58 +836,150 @@ impl AnthropicClient {
         self
     }
 
+    /// Set the model fallback chain
+    ///
+    /// When a request's model is still overloaded (529) after retries, the
+    /// request is re-sent with the next model in this list. The requested
+    /// model is always tried first; listing it here is harmless.
+    pub fn with_model_fallbacks(mut self, models: Vec<impl Into<String>>) -> Self {
+        self.model_fallbacks = models.into_iter().map(Into::into).collect();
+        self
+    }
+
     /// Create a message (non-streaming)
+    ///
+    /// `model` on the response names the model that actually served the
+    /// request, which may be a fallback.
     pub async fn create_message(
         &self,
         mut request: CreateMessageRequest,
     ) -> Result<CreateMessageResponse> {
+        request.validate()?;
         request.stream = Some(false);
 
-        self.retry_request(|| async {
-            let url = format!("{}/v1/messages", self.api_base);
-            let response = self
-                .http_client
-                .post(&url)
-                .header(header::CONTENT_TYPE, "application/json")
-                .header("x-api-key", &self.api_key)
-                .header("anthropic-version", &self.api_version)
-                .json(&request)
-                .send()
-                .await
-                .context("Failed to send request")?;
-
-            self.handle_response(response).await
+        self.with_model_fallbacks_for(request, |request| async move {
+            self.retry_request(|| async {
+                let url = format!("{}/v1/messages", self.api_base);
+                let response = self
+                    .http_client
+                    .post(&url)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .header("x-api-key", &self.api_key)
+                    .header("anthropic-version", &self.api_version)
+                    .json(&request)
+                    .send()
+                    .await
+                    .context("Failed to send request")?;
+
+                self.handle_response(response).await
+            })
+            .await
         })
         .await
     }
 
+    /// Count the input tokens a request would consume, without sending it
+    ///
+    /// Useful for trimming context before dispatch so the prompt stays
+    /// under the model's context window.
+    pub async fn count_tokens(&self, request: &CreateMessageRequest) -> Result<u32> {
+        let body = CountTokensRequest::from(request);
+
+        let response: CountTokensResponse = self
+            .retry_request(|| async {
+                let url = format!("{}/v1/messages/count_tokens", self.api_base);
+                let response = self
+                    .http_client
+                    .post(&url)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .header("x-api-key", &self.api_key)
+                    .header("anthropic-version", &self.api_version)
+                    .json(&body)
+                    .send()
+                    .await
+                    .context("Failed to send count_tokens request")?;
+
+                self.handle_response(response).await
+            })
+            .await?;
+
+        debug!("count_tokens: {} input tokens", response.input_tokens);
+        Ok(response.input_tokens)
+    }
+
     /// Create a message with streaming
     pub async fn create_message_stream(
         &self,
         mut request: CreateMessageRequest,
     ) -> Result<MessageStream> {
+        request.validate()?;
         request.stream = Some(true);
 
-        let url = format!("{}/v1/messages", self.api_base);
-        let response = self
-            .http_client
-            .post(&url)
-            .header(header::CONTENT_TYPE, "application/json")
-            .header(header::ACCEPT, "text/event-stream")
-            .header("x-api-key", &self.api_key)
-            .header("anthropic-version", &self.api_version)
-            .json(&request)
-            .send()
+        self.with_model_fallbacks_for(request, |request| async move {
+            self.retry_request(|| async {
+                let url = format!("{}/v1/messages", self.api_base);
+                let response = self
+                    .http_client
+                    .post(&url)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .header(header::ACCEPT, "text/event-stream")
+                    .header("x-api-key", &self.api_key)
+                    .header("anthropic-version", &self.api_version)
+                    .json(&request)
+                    .send()
+                    .await
+                    .context("Failed to send streaming request")?;
+
+                if !response.status().is_success() {
+                    return Err(ApiClientError::from_response(response).await.into());
+                }
+
+                Ok(MessageStream::new(response, request.model.clone()))
+            })
             .await
-            .context("Failed to send streaming request")?;
+        })
+        .await
+    }
+
+    /// Requested model followed by the configured fallbacks, deduplicated
+    fn model_chain(&self, primary: &str) -> Vec<String> {
+        let mut chain = vec![primary.to_string()];
+        for model in &self.model_fallbacks {
+            if !chain.contains(model) {
+                chain.push(model.clone());
+            }
+        }
+        chain
+    }
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            anyhow::bail!("API request failed [{}]: {}", status, error_text);
+    /// Run `f` against each model in the chain until one isn't overloaded
+    async fn with_model_fallbacks_for<F, Fut, T>(
+        &self,
+        mut request: CreateMessageRequest,
+        f: F,
+    ) -> Result<T>
+    where
+        F: Fn(CreateMessageRequest) -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let chain = self.model_chain(&request.model);
+        let mut models = chain.iter().peekable();
+
+        while let Some(model) = models.next() {
+            request.model = model.clone();
+            match f(request.clone()).await {
+                Err(err) if is_overloaded(&err) => match models.peek() {
+                    Some(next) => warn!(
+                        "Model {} still overloaded after retries, falling back to {}: {}",
+                        model, next, err
+                    ),
+                    None => return Err(err),
+                },
+                result => return result,
+            }
         }
 
-        Ok(MessageStream::new(response))
+        anyhow::bail!("No model to send request to")
     }
 
     /// Handle non-streaming response
@@ -426,10 +991,10 @@ impl AnthropicClient {
 
         if status.is_success() {
             let body = response.text().await.context("Failed to read response body")?;
-            serde_json::from_str(&body).context("Failed to parse response JSON")
+            serde_json::from_str(&body)
+                .map_err(|source| ApiClientError::Deserialization { source, body }.into())
         } else {
-            let error_text = response.text().await.unwrap_or_default();
-            anyhow::bail!("API request failed [{}]: {}", status, error_text)
+            Err(ApiClientError::from_response(response).await.into())
         }
     }
 
@@ -453,29 +1018,42 @@ impl AnthropicClient {
                     }
 
                     // Check if error is retryable
-                    let should_retry = err
-                        .downcast_ref::<reqwest::Error>()
-                        .map(|e| {
-                            e.is_timeout()
-                                || e.status()
-                                    .map(|s| {
-                                        s == StatusCode::TOO_MANY_REQUESTS
-                                            || s.is_server_error()
-                                    })
-                                    .unwrap_or(false)
-                        })
-                        .unwrap_or(false);
+                    let should_retry = if let Some(e) = err.downcast_ref::<ApiClientError>() {
+                        e.is_retryable()
+                    } else {
+                        err.downcast_ref::<reqwest::Error>()
+                            .map(|e| {
+                                e.is_timeout()
+                                    || e.status()
+                                        .map(|s| {
+                                            s == StatusCode::TOO_MANY_REQUESTS
+                                                || s.as_u16() == STATUS_OVERLOADED
+                                                || s.is_server_error()
+                                        })
+                                        .unwrap_or(false)
+                            })
+                            .unwrap_or(false)
+                    };
 
                     if !should_retry {
                         return Err(err);
                     }
 
+                    let wait = retry_delay(
+                        &err,
+                        Duration::from_millis(delay_ms),
+                        Duration::from_millis(self.retry_config.max_delay_ms),
+                    );
+
                     warn!(
                         "Request failed (attempt {}/{}), retrying in {}ms: {}",
-                        attempt, self.retry_config.max_retries, delay_ms, err
+                        attempt,
+                        self.retry_config.max_retries,
+                        wait.as_millis(),
+                        err
                     );
 
-                    sleep(Duration::from_millis(delay_ms)).await;
+                    sleep(wait).await;
 
                     // Exponential backoff
                     delay_ms = ((delay_ms as f64) * self.retry_config.backoff_multiplier) as u64;
@@ -497,21 +1075,68 @@ use futures::stream::{Stream, StreamExt};
 pub struct MessageStream {
     stream: std::pin::Pin<Box<dyn Stream<Item = Result<eventsource_stream::Event, eventsource_stream::EventStreamError<reqwest::Error>>> + Send>>,
     state_machine: StreamStateMachine,
+    model: String,
+    cancel_token: Option<CancellationToken>,
 }
 
 impl MessageStream {
-    fn new(response: reqwest::Response) -> Self {
+    fn new(response: reqwest::Response, model: String) -> Self {
         let stream = Box::pin(response.bytes_stream().eventsource());
         Self {
             stream,
             state_machine: StreamStateMachine::default(),
+            model,
+            cancel_token: None,
         }
     }
 
+    /// Stop the stream when `token` is cancelled: `next()` then returns
+    /// `None` and the HTTP body is closed
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel_token = Some(token);
+        self
+    }
+
+    /// Abort the stream, closing the connection, and return whatever
+    /// message content was received so far
+    pub fn cancel(mut self) -> CreateMessageResponse {
+        self.close();
+        let mut message = self.state_machine.into_partial_message();
+        if message.model.is_empty() {
+            message.model = self.model;
+        }
+        message
+    }
+
+    /// Drop the underlying response body so the connection is released
+    fn close(&mut self) {
+        self.stream = Box::pin(futures::stream::empty());
+        self.cancel_token = None;
+    }
+
+    /// Model this stream was requested from (may be a fallback)
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
     /// Get the next event from the stream
     pub async fn next(&mut self) -> Option<Result<StreamEvent>> {
         loop {
-            match self.stream.next().await {
+            let next = match self.cancel_token.clone() {
+                Some(token) => tokio::select! {
+                    biased;
+                    _ = token.cancelled() => None,
+                    item = self.stream.next() => Some(item),
+                },
+                None => Some(self.stream.next().await),
+            };
+            let Some(item) = next else {
+                debug!("Message stream cancelled");
+                self.close();
+                return None;
+            };
+
+            match item {
                 Some(Ok(event)) => {
                     // Parse SSE event
                     match event.event.as_str() {
@@ -570,8 +1195,9 @@ struct StreamStateMachine {
     message_id: Option<String>,
     model: Option<String>,
     role: Option<Role>,
-    content_blocks: Vec<StreamContentBlock>,
-    current_index: Option<usize>,
+    /// Blocks keyed by their stream `index`, so deltas for blocks that are
+    /// open at the same time (parallel tool use) never mix
+    content_blocks: BTreeMap<usize, StreamContentBlock>,
     usage: Option<Usage>,
     stop_reason: Option<StopReason>,
     stop_sequence: Option<String>,
@@ -581,6 +1207,8 @@ struct StreamStateMachine {
 enum StreamContentBlock {
     Text { text: String },
     ToolUse { id: String, name: String, input: String },
+    Thinking { thinking: String, signature: String },
+    RedactedThinking { data: String },
 }
 
 impl StreamStateMachine {
@@ -593,44 +1221,57 @@ impl StreamStateMachine {
                 self.usage = Some(message.usage.clone());
             }
             StreamEvent::ContentBlockStart { index, content_block } => {
-                self.current_index = Some(*index);
-                match content_block {
-                    ContentBlockStartData::Text { text } => {
-                        self.content_blocks.push(StreamContentBlock::Text {
-                            text: text.clone(),
-                        });
+                let block = match content_block {
+                    ContentBlockStartData::Text { text } => StreamContentBlock::Text {
+                        text: text.clone(),
+                    },
+                    ContentBlockStartData::ToolUse { id, name } => StreamContentBlock::ToolUse {
+                        id: id.clone(),
+                        name: name.clone(),
+                        input: String::new(),
+                    },
+                    ContentBlockStartData::Thinking { thinking, signature } => {
+                        StreamContentBlock::Thinking {
+                            thinking: thinking.clone(),
+                            signature: signature.clone(),
+                        }
                     }
-                    ContentBlockStartData::ToolUse { id, name } => {
-                        self.content_blocks.push(StreamContentBlock::ToolUse {
-                            id: id.clone(),
-                            name: name.clone(),
-                            input: String::new(),
-                        });
+                    ContentBlockStartData::RedactedThinking { data } => {
+                        StreamContentBlock::RedactedThinking { data: data.clone() }
                     }
+                };
+                if self.content_blocks.insert(*index, block).is_some() {
+                    warn!("Content block {} started twice; keeping the latest", index);
                 }
             }
             StreamEvent::ContentBlockDelta { index, delta } => {
-                if let Some(block) = self.content_blocks.get_mut(*index) {
-                    match (block, delta) {
-                        (StreamContentBlock::Text { text }, ContentDelta::TextDelta { text: delta_text }) => {
-                            text.push_str(delta_text);
-                        }
-                        (StreamContentBlock::ToolUse { input, .. }, ContentDelta::InputJsonDelta { partial_json }) => {
-                            input.push_str(partial_json);
-                        }
-                        _ => {
-                            warn!("Mismatched delta type for content block");
-                        }
+                let Some(block) = self.content_blocks.get_mut(index) else {
+                    warn!("Delta for unknown content block {}", index);
+                    return Ok(());
+                };
+                match (block, delta) {
+                    (StreamContentBlock::Text { text }, ContentDelta::TextDelta { text: delta_text }) => {
+                        text.push_str(delta_text);
+                    }
+                    (StreamContentBlock::ToolUse { input, .. }, ContentDelta::InputJsonDelta { partial_json }) => {
+                        input.push_str(partial_json);
+                    }
+                    (StreamContentBlock::Thinking { thinking, .. }, ContentDelta::ThinkingDelta { thinking: delta_thinking }) => {
+                        thinking.push_str(delta_thinking);
+                    }
+                    (StreamContentBlock::Thinking { signature, .. }, ContentDelta::SignatureDelta { signature: delta_signature }) => {
+                        signature.push_str(delta_signature);
+                    }
+                    _ => {
+                        warn!("Mismatched delta type for content block {}", index);
                     }
                 }
             }
-            StreamEvent::ContentBlockStop { index: _ } => {
-                self.current_index = None;
-            }
+            StreamEvent::ContentBlockStop { index: _ } => {}
             StreamEvent::MessageDelta { delta, usage } => {
                 self.stop_reason = delta.stop_reason.clone();
                 self.stop_sequence = delta.stop_sequence.clone();
-                // Update usage with output tokens
+                // Update usage with output tokens (cache counts come from message_start)
                 if let Some(existing_usage) = &mut self.usage {
                     existing_usage.output_tokens = usage.output_tokens;
                 }
@@ -653,11 +1294,38 @@ impl StreamStateMachine {
         let role = self.role.context("Missing role")?;
         let usage = self.usage.context("Missing usage")?;
 
-        let content: Vec<ContentBlock> = self
-            .content_blocks
-            .into_iter()
+        Ok(CreateMessageResponse {
+            id: message_id,
+            r#type: "message".to_string(),
+            role,
+            content: Self::finish_blocks(self.content_blocks),
+            model,
+            stop_reason: self.stop_reason,
+            stop_sequence: self.stop_sequence,
+            usage,
+        })
+    }
+
+    /// Whatever has been accumulated so far, for streams that ended early.
+    /// Missing message metadata is left empty.
+    fn into_partial_message(self) -> CreateMessageResponse {
+        CreateMessageResponse {
+            id: self.message_id.unwrap_or_default(),
+            r#type: "message".to_string(),
+            role: self.role.unwrap_or(Role::Assistant),
+            content: Self::finish_blocks(self.content_blocks),
+            model: self.model.unwrap_or_default(),
+            stop_reason: self.stop_reason,
+            stop_sequence: self.stop_sequence,
+            usage: self.usage.unwrap_or_default(),
+        }
+    }
+
+    fn finish_blocks(blocks: BTreeMap<usize, StreamContentBlock>) -> Vec<ContentBlock> {
+        blocks
+            .into_values()
             .map(|block| match block {
-                StreamContentBlock::Text { text } => ContentBlock::Text { text },
+                StreamContentBlock::Text { text } => ContentBlock::text(text),
                 StreamContentBlock::ToolUse { id, name, input } => {
                     let parsed_input: serde_json::Value =
                         serde_json::from_str(&input).unwrap_or_else(|_| {
@@ -669,19 +1337,14 @@ impl StreamStateMachine {
                         input: parsed_input,
                     }
                 }
+                StreamContentBlock::Thinking { thinking, signature } => {
+                    ContentBlock::Thinking { thinking, signature }
+                }
+                StreamContentBlock::RedactedThinking { data } => {
+                    ContentBlock::RedactedThinking { data }
+                }
             })
-            .collect();
-
-        Ok(CreateMessageResponse {
-            id: message_id,
-            r#type: "message".to_string(),
-            role,
-            content,
-            model,
-            stop_reason: self.stop_reason,
-            stop_sequence: self.stop_sequence,
-            usage,
-        })
+            .collect()
     }
 }
 
@@ -761,6 +1424,133 @@ impl SubprocessBridge {
 
         serde_json::from_str(&response_line).context("Failed to parse subprocess response")
     }
+
+    /// Create a message via subprocess, streaming events as they arrive
+    ///
+    /// The request is sent with `stream: true`; the subprocess must answer
+    /// with one JSON `StreamEvent` per line on stdout. A non-zero exit is
+    /// reported as a final error carrying the subprocess's stderr.
+    pub async fn create_message_stream(
+        &self,
+        mut request: CreateMessageRequest,
+    ) -> Result<BridgeStream> {
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+        use tokio::process::Command;
+
+        request.stream = Some(true);
+
+        let mut child = Command::new(&self.python_path)
+            .args(&self.args)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .context("Failed to spawn Python subprocess")?;
+
+        let mut stdin = child.stdin.take().context("Failed to open stdin")?;
+        let stdout = child.stdout.take().context("Failed to open stdout")?;
+        let mut stderr = child.stderr.take().context("Failed to open stderr")?;
+
+        let request_json = serde_json::to_string(&request)?;
+        stdin
+            .write_all(request_json.as_bytes())
+            .await
+            .context("Failed to write to stdin")?;
+        stdin
+            .write_all(b"\n")
+            .await
+            .context("Failed to write newline")?;
+        drop(stdin);
+
+        // Drain stderr concurrently so a chatty subprocess can't block
+        let stderr_task = tokio::spawn(async move {
+            let mut buf = String::new();
+            let _ = stderr.read_to_string(&mut buf).await;
+            buf
+        });
+
+        let (tx, rx) = tokio::sync::mpsc::channel(64);
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stdout).lines();
+            loop {
+                let line = match lines.next_line().await {
+                    Ok(Some(line)) => line,
+                    Ok(None) => break,
+                    Err(e) => {
+                        let _ = tx
+                            .send(Err(anyhow::anyhow!("Failed to read subprocess output: {}", e)))
+                            .await;
+                        break;
+                    }
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<StreamEvent>(&line) {
+                    Ok(event) => {
+                        if tx.send(Ok(event)).await.is_err() {
+                            // Receiver dropped; kill_on_drop ends the subprocess
+                            return;
+                        }
+                    }
+                    Err(e) => warn!("Failed to parse subprocess stream event: {}", e),
+                }
+            }
+
+            let stderr = stderr_task.await.unwrap_or_default();
+            match child.wait().await {
+                Ok(status) if status.success() => {}
+                Ok(status) => {
+                    let _ = tx
+                        .send(Err(anyhow::anyhow!(
+                            "Subprocess exited with error: {}: {}",
+                            status,
+                            stderr.trim()
+                        )))
+                        .await;
+                }
+                Err(e) => {
+                    let _ = tx
+                        .send(Err(anyhow::anyhow!("Failed to wait for child: {}", e)))
+                        .await;
+                }
+            }
+        });
+
+        Ok(BridgeStream {
+            rx,
+            state_machine: StreamStateMachine::default(),
+        })
+    }
+}
+
+/// Streaming response from `SubprocessBridge`
+pub struct BridgeStream {
+    rx: tokio::sync::mpsc::Receiver<Result<StreamEvent>>,
+    state_machine: StreamStateMachine,
+}
+
+impl BridgeStream {
+    /// Get the next event from the subprocess
+    pub async fn next(&mut self) -> Option<Result<StreamEvent>> {
+        let item = self.rx.recv().await?;
+        if let Ok(event) = &item {
+            if let Err(e) = self.state_machine.process(event) {
+                warn!("State machine error: {}", e);
+            }
+        }
+        Some(item)
+    }
+
+    /// Collect the full message from the stream
+    pub async fn collect_message(mut self) -> Result<CreateMessageResponse> {
+        while let Some(event) = self.next().await {
+            event?;
+        }
+
+        self.state_machine.into_message()
+    }
 }
 
 #[cfg(test)]
@@ -769,26 +1559,388 @@ mod tests {
 
     #[test]
     fn test_content_block_serialization() {
-        let block = ContentBlock::Text {
-            text: "Hello".to_string(),
-        };
+        let block = ContentBlock::text("Hello");
         let json = serde_json::to_string(&block).unwrap();
         assert!(json.contains(r#""type":"text"#));
         assert!(json.contains(r#""text":"Hello"#));
     }
 
+    #[test]
+    fn test_image_source_round_trip() {
+        let base64 = ContentBlock::Image {
+            source: ImageSource::Base64 {
+                media_type: "image/png".to_string(),
+                data: "iVBORw0KGgo=".to_string(),
+            },
+        };
+        let json = serde_json::to_string(&base64).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"image","source":{"type":"base64","media_type":"image/png","data":"iVBORw0KGgo="}}"#
+        );
+        let parsed: ContentBlock = serde_json::from_str(&json).unwrap();
+        assert!(matches!(parsed, ContentBlock::Image { source: ImageSource::Base64 { .. } }));
+
+        let url = ContentBlock::Image {
+            source: ImageSource::Url {
+                url: "https://example.com/diagram.png".to_string(),
+            },
+        };
+        let json = serde_json::to_string(&url).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"image","source":{"type":"url","url":"https://example.com/diagram.png"}}"#
+        );
+        match serde_json::from_str::<ContentBlock>(&json).unwrap() {
+            ContentBlock::Image { source } => assert_eq!(
+                source,
+                ImageSource::Url {
+                    url: "https://example.com/diagram.png".to_string()
+                }
+            ),
+            other => panic!("expected image block, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_message_serialization() {
         let msg = Message {
             role: Role::User,
-            content: vec![ContentBlock::Text {
-                text: "Test".to_string(),
-            }],
+            content: vec![ContentBlock::text("Test")],
         };
         let json = serde_json::to_string(&msg).unwrap();
         assert!(json.contains(r#""role":"user"#));
     }
 
+    #[test]
+    fn test_uncached_request_has_no_cache_control() {
+        let req = CreateMessageRequest {
+            system: Some("Be brief.".into()),
+            messages: vec![Message {
+                role: Role::User,
+                content: vec![ContentBlock::text("Hi")],
+            }],
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&req).unwrap();
+        assert!(!json.contains("cache_control"));
+        assert!(json.contains(r#""system":"Be brief.""#));
+    }
+
+    #[test]
+    fn test_cached_request_round_trip() {
+        let tools = vec![
+            Tool {
+                name: "read".to_string(),
+                description: "Read a file".to_string(),
+                input_schema: serde_json::json!({"type": "object"}),
+                cache_control: None,
+            },
+            Tool {
+                name: "write".to_string(),
+                description: "Write a file".to_string(),
+                input_schema: serde_json::json!({"type": "object"}),
+                cache_control: None,
+            },
+        ];
+        let req = CreateMessageRequest::default()
+            .with_cached_system("Large system prompt")
+            .with_cached_tools(tools);
+
+        let value = serde_json::to_value(&req).unwrap();
+        assert_eq!(value["system"][0]["type"], "text");
+        assert_eq!(value["system"][0]["cache_control"]["type"], "ephemeral");
+        assert!(value["tools"][0].get("cache_control").is_none());
+        assert_eq!(value["tools"][1]["cache_control"]["type"], "ephemeral");
+
+        let parsed: CreateMessageRequest = serde_json::from_value(value).unwrap();
+        match parsed.system {
+            Some(SystemPrompt::Blocks(blocks)) => assert!(matches!(
+                blocks[0],
+                ContentBlock::Text { cache_control: Some(CacheControl::Ephemeral), .. }
+            )),
+            other => panic!("expected system blocks, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_usage_cache_fields() {
+        let usage: Usage = serde_json::from_str(
+            r#"{"input_tokens": 12, "output_tokens": 3, "cache_read_input_tokens": 2048}"#,
+        )
+        .unwrap();
+        assert_eq!(usage.cache_read_input_tokens, Some(2048));
+        assert_eq!(usage.cache_creation_input_tokens, None);
+    }
+
+    #[test]
+    fn test_count_tokens_request_omits_generation_fields() {
+        let req = CreateMessageRequest {
+            system: Some("sys".into()),
+            temperature: Some(0.5),
+            messages: vec![Message {
+                role: Role::User,
+                content: vec![ContentBlock::text("Hi")],
+            }],
+            ..Default::default()
+        };
+        let value = serde_json::to_value(CountTokensRequest::from(&req)).unwrap();
+        assert_eq!(value["model"], req.model);
+        assert_eq!(value["system"], "sys");
+        assert!(value.get("max_tokens").is_none());
+        assert!(value.get("temperature").is_none());
+        assert!(value.get("tools").is_none());
+    }
+
+    #[test]
+    fn test_retry_after_seconds_overrides_backoff() {
+        let mut headers = header::HeaderMap::new();
+        headers.insert(header::RETRY_AFTER, "2".parse().unwrap());
+        let err: anyhow::Error =
+            ApiClientError::from_status(StatusCode::TOO_MANY_REQUESTS, &headers, "").into();
+
+        assert_eq!(
+            retry_delay(&err, Duration::from_millis(500), Duration::from_secs(10)),
+            Duration::from_secs(2)
+        );
+    }
+
+    #[test]
+    fn test_retry_after_clamped_to_max_delay() {
+        let max = Duration::from_millis(RetryConfig::default().max_delay_ms);
+        for value in ["3600", "1e300"] {
+            let mut headers = header::HeaderMap::new();
+            headers.insert(header::RETRY_AFTER, value.parse().unwrap());
+            let err: anyhow::Error =
+                ApiClientError::from_status(StatusCode::TOO_MANY_REQUESTS, &headers, "").into();
+            assert_eq!(retry_delay(&err, Duration::from_millis(500), max), max, "{value}");
+        }
+
+        let mut headers = header::HeaderMap::new();
+        let far = chrono::Utc::now() + chrono::Duration::days(365);
+        headers.insert("anthropic-ratelimit-tokens-reset", far.to_rfc3339().parse().unwrap());
+        let err: anyhow::Error =
+            ApiClientError::from_status(StatusCode::TOO_MANY_REQUESTS, &headers, "").into();
+        assert_eq!(retry_delay(&err, Duration::from_millis(500), max), max);
+    }
+
+    #[test]
+    fn test_retry_after_http_date_and_ratelimit_reset() {
+        let mut headers = header::HeaderMap::new();
+        let at = chrono::Utc::now() + chrono::Duration::seconds(30);
+        headers.insert(header::RETRY_AFTER, at.to_rfc2822().parse().unwrap());
+        let wait = retry_after_from_headers(&headers).unwrap();
+        assert!(wait > Duration::from_secs(25) && wait <= Duration::from_secs(30));
+
+        let mut headers = header::HeaderMap::new();
+        let soon = chrono::Utc::now() + chrono::Duration::seconds(5);
+        let later = chrono::Utc::now() + chrono::Duration::seconds(20);
+        headers.insert("anthropic-ratelimit-requests-reset", soon.to_rfc3339().parse().unwrap());
+        headers.insert("anthropic-ratelimit-tokens-reset", later.to_rfc3339().parse().unwrap());
+        let wait = retry_after_from_headers(&headers).unwrap();
+        assert!(wait > Duration::from_secs(15) && wait <= Duration::from_secs(20));
+    }
+
+    #[test]
+    fn test_retry_delay_without_hint_uses_backoff() {
+        let err: anyhow::Error = ApiClientError::from_status(
+            StatusCode::from_u16(STATUS_OVERLOADED).unwrap(),
+            &header::HeaderMap::new(),
+            r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#,
+        )
+        .into();
+
+        assert!(err.downcast_ref::<ApiClientError>().unwrap().is_retryable());
+        assert_eq!(
+            retry_delay(&err, Duration::from_millis(500), Duration::from_secs(10)),
+            Duration::from_millis(500)
+        );
+    }
+
+    #[test]
+    fn test_client_errors_not_retryable() {
+        let err = ApiClientError::from_status(StatusCode::BAD_REQUEST, &header::HeaderMap::new(), "");
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn test_api_client_error_classification() {
+        let headers = header::HeaderMap::new();
+        let body = |kind: &str| {
+            format!(r#"{{"type":"error","error":{{"type":"{kind}","message":"details"}}}}"#)
+        };
+
+        let err = ApiClientError::from_status(
+            StatusCode::UNAUTHORIZED,
+            &headers,
+            &body("authentication_error"),
+        );
+        assert!(matches!(err, ApiClientError::AuthFailed { .. }));
+        assert_eq!(err.api_error().unwrap().r#type, "authentication_error");
+
+        let err = ApiClientError::from_status(
+            StatusCode::BAD_REQUEST,
+            &headers,
+            &body("invalid_request_error"),
+        );
+        match &err {
+            ApiClientError::InvalidRequest { status, error } => {
+                assert_eq!(*status, StatusCode::BAD_REQUEST);
+                assert_eq!(error.r#type, "invalid_request_error");
+                assert_eq!(error.message, "details");
+            }
+            other => panic!("expected InvalidRequest, got {other:?}"),
+        }
+
+        let err = ApiClientError::from_status(
+            StatusCode::TOO_MANY_REQUESTS,
+            &headers,
+            &body("rate_limit_error"),
+        );
+        assert!(matches!(err, ApiClientError::RateLimited { .. }));
+
+        let err = ApiClientError::from_status(
+            StatusCode::from_u16(STATUS_OVERLOADED).unwrap(),
+            &headers,
+            &body("overloaded_error"),
+        );
+        assert!(matches!(err, ApiClientError::Overloaded { .. }));
+
+        let err = ApiClientError::from_status(StatusCode::BAD_GATEWAY, &headers, "<html>bad gateway</html>");
+        match &err {
+            ApiClientError::ServerError { status, error, .. } => {
+                assert_eq!(*status, StatusCode::BAD_GATEWAY);
+                assert_eq!(error.r#type, "bad_gateway");
+                assert_eq!(error.message, "<html>bad gateway</html>");
+            }
+            other => panic!("expected ServerError, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_model_chain_dedupes_primary() {
+        let client = AnthropicClient::new(String::new(), String::new(), String::new())
+            .unwrap()
+            .with_model_fallbacks(vec!["claude-sonnet-4", "claude-3-5-haiku"]);
+
+        assert_eq!(
+            client.model_chain("claude-sonnet-4"),
+            vec!["claude-sonnet-4", "claude-3-5-haiku"]
+        );
+        assert_eq!(
+            client.model_chain("claude-opus-4"),
+            vec!["claude-opus-4", "claude-sonnet-4", "claude-3-5-haiku"]
+        );
+    }
+
+    fn tool(name: &str) -> Tool {
+        Tool {
+            name: name.to_string(),
+            description: String::new(),
+            input_schema: serde_json::json!({"type": "object"}),
+            cache_control: None,
+        }
+    }
+
+    #[test]
+    fn test_tool_choice_serialization() {
+        let cases = [
+            (ToolChoice::Auto, serde_json::json!({"type": "auto"})),
+            (ToolChoice::Any, serde_json::json!({"type": "any"})),
+            (
+                ToolChoice::Tool {
+                    name: "read_file".to_string(),
+                },
+                serde_json::json!({"type": "tool", "name": "read_file"}),
+            ),
+            (ToolChoice::None, serde_json::json!({"type": "none"})),
+        ];
+        for (choice, expected) in cases {
+            assert_eq!(serde_json::to_value(&choice).unwrap(), expected);
+            assert_eq!(serde_json::from_value::<ToolChoice>(expected).unwrap(), choice);
+        }
+
+        let value = serde_json::to_value(CreateMessageRequest::default()).unwrap();
+        assert!(value.get("tool_choice").is_none());
+    }
+
+    #[test]
+    fn test_tool_choice_validation() {
+        let mut req = CreateMessageRequest {
+            tools: Some(vec![tool("read_file")]),
+            tool_choice: Some(ToolChoice::Tool {
+                name: "read_file".to_string(),
+            }),
+            ..Default::default()
+        };
+        assert!(req.validate().is_ok());
+
+        req.tool_choice = Some(ToolChoice::Tool {
+            name: "write_file".to_string(),
+        });
+        assert_eq!(
+            req.validate(),
+            Err(RequestValidationError::UnknownTool {
+                name: "write_file".to_string()
+            })
+        );
+
+        req.tools = None;
+        req.tool_choice = Some(ToolChoice::Any);
+        assert_eq!(req.validate(), Err(RequestValidationError::NoTools("any")));
+
+        req.tool_choice = Some(ToolChoice::None);
+        assert!(req.validate().is_ok());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_subprocess_bridge_stream() {
+        let script = r#"read request
+case "$request" in *'"stream":true'*) ;; *) echo "not a stream request" >&2; exit 2;; esac
+cat <<'JSON'
+{"type":"message_start","message":{"id":"msg_b","type":"message","role":"assistant","content":[],"model":"claude-sonnet-4-20250514","usage":{"input_tokens":3,"output_tokens":0}}}
+{"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}
+{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi from "}}
+
+{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Python"}}
+{"type":"content_block_stop","index":0}
+{"type":"message_delta","delta":{"stop_reason":"end_turn","stop_sequence":null},"usage":{"input_tokens":3,"output_tokens":2}}
+{"type":"message_stop"}
+JSON
+"#;
+        let bridge = SubprocessBridge::new("sh", vec!["-c", script]);
+        let stream = bridge
+            .create_message_stream(CreateMessageRequest::default())
+            .await
+            .unwrap();
+
+        let msg = stream.collect_message().await.unwrap();
+        assert_eq!(msg.id, "msg_b");
+        assert_eq!(msg.usage.output_tokens, 2);
+        assert!(matches!(&msg.content[0], ContentBlock::Text { text, .. } if text == "Hi from Python"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_subprocess_bridge_stream_reports_exit_and_stderr() {
+        let script = r#"read request
+echo '{"type":"ping"}'
+echo "anthropic.APIConnectionError: boom" >&2
+exit 3
+"#;
+        let bridge = SubprocessBridge::new("sh", vec!["-c", script]);
+        let mut stream = bridge
+            .create_message_stream(CreateMessageRequest::default())
+            .await
+            .unwrap();
+
+        assert!(matches!(stream.next().await, Some(Ok(StreamEvent::Ping))));
+        let err = stream.next().await.unwrap().unwrap_err().to_string();
+        assert!(err.contains("APIConnectionError: boom"), "{err}");
+        assert!(stream.next().await.is_none());
+    }
+
     #[test]
     fn test_create_message_request_default() {
         let req = CreateMessageRequest::default();
@@ -796,6 +1948,125 @@ mod tests {
         assert_eq!(req.max_tokens, 4096);
     }
 
+    #[test]
+    fn test_stream_state_machine_interleaved_tool_use() {
+        let mut sm = StreamStateMachine::default();
+        let events = [
+            r#"{"type":"message_start","message":{"id":"msg_par","type":"message","role":"assistant","content":[],"model":"claude-sonnet-4-20250514","usage":{"input_tokens":30,"output_tokens":0}}}"#,
+            r#"{"type":"content_block_start","index":0,"content_block":{"type":"tool_use","id":"toolu_a","name":"read_file"}}"#,
+            r#"{"type":"content_block_start","index":1,"content_block":{"type":"tool_use","id":"toolu_b","name":"run_command"}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"input_json_delta","partial_json":"{\"path\":"}}"#,
+            r#"{"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"{\"cmd\":\"cargo"}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"input_json_delta","partial_json":"\"src/lib.rs\"}"}}"#,
+            r#"{"type":"content_block_stop","index":0}"#,
+            r#"{"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":" test\"}"}}"#,
+            r#"{"type":"content_block_stop","index":1}"#,
+            r#"{"type":"message_delta","delta":{"stop_reason":"tool_use","stop_sequence":null},"usage":{"input_tokens":30,"output_tokens":40}}"#,
+            r#"{"type":"message_stop"}"#,
+        ];
+        for raw in events {
+            sm.process(&serde_json::from_str(raw).unwrap()).unwrap();
+        }
+
+        let msg = sm.into_message().unwrap();
+        assert_eq!(msg.content.len(), 2);
+        match (&msg.content[0], &msg.content[1]) {
+            (
+                ContentBlock::ToolUse { id: id_a, input: input_a, .. },
+                ContentBlock::ToolUse { id: id_b, input: input_b, .. },
+            ) => {
+                assert_eq!(id_a, "toolu_a");
+                assert_eq!(input_a, &serde_json::json!({"path": "src/lib.rs"}));
+                assert_eq!(id_b, "toolu_b");
+                assert_eq!(input_b, &serde_json::json!({"cmd": "cargo test"}));
+            }
+            other => panic!("expected two tool_use blocks, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_into_partial_message() {
+        let mut sm = StreamStateMachine::default();
+        let events = [
+            r#"{"type":"message_start","message":{"id":"msg_p","type":"message","role":"assistant","content":[],"model":"claude-sonnet-4-20250514","usage":{"input_tokens":7,"output_tokens":0}}}"#,
+            r#"{"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Partial ans"}}"#,
+        ];
+        for raw in events {
+            sm.process(&serde_json::from_str(raw).unwrap()).unwrap();
+        }
+
+        let msg = sm.into_partial_message();
+        assert_eq!(msg.id, "msg_p");
+        assert!(msg.stop_reason.is_none());
+        assert_eq!(msg.usage.input_tokens, 7);
+        assert!(matches!(&msg.content[0], ContentBlock::Text { text, .. } if text == "Partial ans"));
+
+        // Nothing received at all still yields an (empty) message
+        let empty = StreamStateMachine::default().into_partial_message();
+        assert!(empty.id.is_empty());
+        assert!(empty.content.is_empty());
+    }
+
+    #[test]
+    fn test_thinking_serialization() {
+        let req = CreateMessageRequest::default().with_thinking(2048);
+        let value = serde_json::to_value(&req).unwrap();
+        assert_eq!(
+            value["thinking"],
+            serde_json::json!({"type": "enabled", "budget_tokens": 2048})
+        );
+        assert!(serde_json::to_value(CreateMessageRequest::default())
+            .unwrap()
+            .get("thinking")
+            .is_none());
+
+        // Thinking blocks round-trip verbatim for follow-up turns
+        let block = ContentBlock::Thinking {
+            thinking: "Let me plan.".to_string(),
+            signature: "sig==".to_string(),
+        };
+        let json = serde_json::to_string(&block).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"thinking","thinking":"Let me plan.","signature":"sig=="}"#
+        );
+        let parsed: ContentBlock = serde_json::from_str(&json).unwrap();
+        assert!(matches!(parsed, ContentBlock::Thinking { ref signature, .. } if signature == "sig=="));
+    }
+
+    #[test]
+    fn test_stream_state_machine_thinking() {
+        let mut sm = StreamStateMachine::default();
+        let events = [
+            r#"{"type":"message_start","message":{"id":"msg_t","type":"message","role":"assistant","content":[],"model":"claude-sonnet-4-20250514","usage":{"input_tokens":20,"output_tokens":0}}}"#,
+            r#"{"type":"content_block_start","index":0,"content_block":{"type":"thinking","thinking":""}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"thinking_delta","thinking":"First, "}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"thinking_delta","thinking":"check the inputs."}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"signature_delta","signature":"EqQBCgIYAh=="}}"#,
+            r#"{"type":"content_block_stop","index":0}"#,
+            r#"{"type":"content_block_start","index":1,"content_block":{"type":"text","text":""}}"#,
+            r#"{"type":"content_block_delta","index":1,"delta":{"type":"text_delta","text":"Done."}}"#,
+            r#"{"type":"content_block_stop","index":1}"#,
+            r#"{"type":"message_stop"}"#,
+        ];
+        for raw in events {
+            let event: StreamEvent = serde_json::from_str(raw).unwrap();
+            sm.process(&event).unwrap();
+        }
+
+        let msg = sm.into_message().unwrap();
+        assert_eq!(msg.content.len(), 2);
+        match &msg.content[0] {
+            ContentBlock::Thinking { thinking, signature } => {
+                assert_eq!(thinking, "First, check the inputs.");
+                assert_eq!(signature, "EqQBCgIYAh==");
+            }
+            other => panic!("expected thinking block, got {other:?}"),
+        }
+        assert!(matches!(&msg.content[1], ContentBlock::Text { text, .. } if text == "Done."));
+    }
+
     #[tokio::test]
     async fn test_stream_state_machine() {
         let mut sm = StreamStateMachine::default();
@@ -811,6 +2082,8 @@ mod tests {
                 usage: Usage {
                     input_tokens: 10,
                     output_tokens: 0,
+                    cache_creation_input_tokens: None,
+                    cache_read_input_tokens: None,
                 },
             },
         })
@@ -855,6 +2128,8 @@ mod tests {
             usage: Usage {
                 input_tokens: 10,
                 output_tokens: 5,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
             },
         })
         .unwrap();
@@ -863,7 +2138,7 @@ mod tests {
         let msg = sm.into_message().unwrap();
         assert_eq!(msg.id, "msg_123");
         assert_eq!(msg.content.len(), 1);
-        if let ContentBlock::Text { text } = &msg.content[0] {
+        if let ContentBlock::Text { text, .. } = &msg.content[0] {
             assert_eq!(text, "Hello world");
         } else {
             panic!("Expected text block");
@@ -5,7 +5,10 @@
 use chrono::{DateTime, Utc};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Record of a file modification.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -36,6 +39,42 @@ impl FileChange {
     }
 }
 
+/// Hex SHA-256 of file content, as stored in `FileChange::content_hash`.
+pub fn content_hash(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+/// Files larger than this are recorded without a content hash.
+pub const MAX_HASHED_FILE_BYTES: u64 = 8 * 1024 * 1024;
+
+/// [`content_hash`] of the file at `path`, streamed in chunks. `None` if
+/// it cannot be read or is larger than [`MAX_HASHED_FILE_BYTES`].
+pub async fn file_content_hash(path: &Path) -> Option<String> {
+    use tokio::io::AsyncReadExt;
+
+    let file = tokio::fs::File::open(path).await.ok()?;
+    if file.metadata().await.ok()?.len() > MAX_HASHED_FILE_BYTES {
+        return None;
+    }
+    // The file may grow after the size check
+    let mut reader = file.take(MAX_HASHED_FILE_BYTES + 1);
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut total = 0u64;
+    loop {
+        let n = reader.read(&mut buf).await.ok()?;
+        if n == 0 {
+            break;
+        }
+        total += n as u64;
+        if total > MAX_HASHED_FILE_BYTES {
+            return None;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Some(format!("{:x}", hasher.finalize()))
+}
+
 /// Record of a command execution.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CommandResult {
@@ -69,6 +108,52 @@ impl CommandResult {
     }
 }
 
+/// Outcome of a build command.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BuildResult {
+    pub command: String,
+    pub success: bool,
+    pub duration_ms: u64,
+}
+
+impl BuildResult {
+    pub fn new(command: String, success: bool) -> Self {
+        Self {
+            command,
+            success,
+            duration_ms: 0,
+        }
+    }
+
+    pub fn with_duration(mut self, duration_ms: u64) -> Self {
+        self.duration_ms = duration_ms;
+        self
+    }
+}
+
+/// Warning and error counts from a linter run.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LintResult {
+    pub tool: String, // "clippy", "eslint", "ruff"
+    pub warnings: u32,
+    pub errors: u32,
+}
+
+impl LintResult {
+    pub fn new(tool: String) -> Self {
+        Self {
+            tool,
+            warnings: 0,
+            errors: 0,
+        }
+    }
+
+    /// Total findings of either kind.
+    pub fn issues(&self) -> u32 {
+        self.warnings + self.errors
+    }
+}
+
 /// Parsed test execution results.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TestResult {
@@ -95,8 +180,467 @@ impl TestResult {
     }
 }
 
+/// Outcome of a subagent (Task tool) run.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SubagentResult {
+    pub subagent_type: String,
+    pub task_summary: String,
+    pub success: bool,
+    pub result_summary: String,
+    pub files_modified: Vec<String>,
+    pub duration_seconds: f64,
+}
+
+impl SubagentResult {
+    pub fn new(subagent_type: String, task_summary: String) -> Self {
+        Self {
+            subagent_type,
+            task_summary,
+            success: true,
+            result_summary: String::new(),
+            files_modified: Vec::new(),
+            duration_seconds: 0.0,
+        }
+    }
+
+    /// Build from the daemon's SubagentSpawned/SubagentCompleted event pair.
+    pub fn from_events(
+        spawned: &superclaude_proto::SubagentSpawned,
+        completed: &superclaude_proto::SubagentCompleted,
+    ) -> Self {
+        Self::new(spawned.subagent_type.clone(), spawned.task_summary.clone())
+            .with_outcome(completed.success, completed.result_summary.clone())
+    }
+
+    pub fn with_outcome(mut self, success: bool, result_summary: String) -> Self {
+        self.success = success;
+        self.result_summary = result_summary;
+        self
+    }
+
+    pub fn with_files(mut self, files_modified: Vec<String>) -> Self {
+        self.files_modified = files_modified;
+        self
+    }
+
+    pub fn with_duration(mut self, duration_seconds: f64) -> Self {
+        self.duration_seconds = duration_seconds;
+        self
+    }
+}
+
+/// Parses test runner output into a [`TestResult`].
+///
+/// Implement this to teach [`EvidenceCollector`] about a new framework and
+/// add it with [`EvidenceCollector::register_parser`].
+pub trait TestParser: std::fmt::Debug + Send + Sync {
+    /// Whether this parser recognizes the command or its output.
+    fn detect(&self, command: &str, output: &str) -> bool;
+
+    /// Extract counts from output this parser detected.
+    fn parse(&self, output: &str) -> TestResult;
+}
+
+/// pytest summary lines, plus coverage percentage if present.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PytestParser;
+
+impl TestParser for PytestParser {
+    fn detect(&self, command: &str, output: &str) -> bool {
+        command.contains("pytest") || output.to_lowercase().contains("pytest")
+    }
+
+    fn parse(&self, output: &str) -> TestResult {
+        let mut result = TestResult::new("pytest".to_string());
+
+        // Match patterns like "5 passed, 2 failed, 1 skipped"
+        if let Ok(re) = Regex::new(r"(\d+)\s+passed") {
+            if let Some(caps) = re.captures(output) {
+                if let Some(val) = caps.get(1) {
+                    result.passed = val.as_str().parse().unwrap_or(0);
+                }
+            }
+        }
+
+        if let Ok(re) = Regex::new(r"(\d+)\s+failed") {
+            if let Some(caps) = re.captures(output) {
+                if let Some(val) = caps.get(1) {
+                    result.failed = val.as_str().parse().unwrap_or(0);
+                }
+            }
+        }
+
+        if let Ok(re) = Regex::new(r"(\d+)\s+skipped") {
+            if let Some(caps) = re.captures(output) {
+                if let Some(val) = caps.get(1) {
+                    result.skipped = val.as_str().parse().unwrap_or(0);
+                }
+            }
+        }
+
+        if let Ok(re) = Regex::new(r"(\d+)\s+error") {
+            if let Some(caps) = re.captures(output) {
+                if let Some(val) = caps.get(1) {
+                    result.errors = val.as_str().parse().unwrap_or(0);
+                }
+            }
+        }
+
+        // Parse coverage if present
+        if let Ok(re) = Regex::new(r"(\d+(?:\.\d+)?)\s*%") {
+            if let Some(caps) = re.captures(output) {
+                if let Some(val) = caps.get(1) {
+                    result.coverage = val.as_str().parse().unwrap_or(0.0);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// Jest / `npm test` summary lines.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JestParser;
+
+impl TestParser for JestParser {
+    fn detect(&self, command: &str, output: &str) -> bool {
+        command.contains("jest")
+            || command.contains("npm test")
+            || output.to_lowercase().contains("tests passed")
+    }
+
+    fn parse(&self, output: &str) -> TestResult {
+        let mut result = TestResult::new("jest".to_string());
+
+        // Match patterns like "Tests: 5 passed, 2 failed, 7 total"
+        if let Ok(re) = Regex::new(r"(\d+)\s+passed") {
+            if let Some(caps) = re.captures(output) {
+                if let Some(val) = caps.get(1) {
+                    result.passed = val.as_str().parse().unwrap_or(0);
+                }
+            }
+        }
+
+        if let Ok(re) = Regex::new(r"(\d+)\s+failed") {
+            if let Some(caps) = re.captures(output) {
+                if let Some(val) = caps.get(1) {
+                    result.failed = val.as_str().parse().unwrap_or(0);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// Vitest `Test Files` / `Tests` summary lines.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VitestParser;
+
+impl VitestParser {
+    /// Counts from a summary line such as `Tests  2 failed | 12 passed (14)`.
+    fn summary_counts(output: &str, label: &str) -> Option<(u32, u32, u32)> {
+        let line_re = Regex::new(&format!(r"(?m)^\s*{}\s+(.*)$", label)).ok()?;
+        let line = line_re.captures(output)?.get(1)?.as_str();
+
+        let count_re = Regex::new(r"(\d+)\s+(passed|failed|skipped|todo)").ok()?;
+        let (mut passed, mut failed, mut skipped) = (0, 0, 0);
+        for caps in count_re.captures_iter(line) {
+            let n: u32 = caps[1].parse().unwrap_or(0);
+            match &caps[2] {
+                "passed" => passed += n,
+                "failed" => failed += n,
+                _ => skipped += n,
+            }
+        }
+        Some((passed, failed, skipped))
+    }
+}
+
+impl TestParser for VitestParser {
+    fn detect(&self, command: &str, output: &str) -> bool {
+        command.contains("vitest")
+            || output.contains("Test Files")
+            || Regex::new(r"(?m)^\s*Tests\s+\d+\s+(?:passed|failed|skipped)")
+                .map(|re| re.is_match(output))
+                .unwrap_or(false)
+    }
+
+    fn parse(&self, output: &str) -> TestResult {
+        let mut result = TestResult::new("vitest".to_string());
+
+        if let Some((passed, failed, skipped)) = Self::summary_counts(output, "Tests") {
+            result.passed = passed;
+            result.failed = failed;
+            result.skipped = skipped;
+        }
+
+        // A file that fails to load contributes no test counts, so report
+        // failed files beyond the failed tests as errors.
+        if let Some((files_passed, files_failed, _)) = Self::summary_counts(output, "Test Files") {
+            if result.passed + result.failed == 0 {
+                result.passed = files_passed;
+            }
+            result.errors = files_failed.saturating_sub(result.failed);
+        }
+
+        result
+    }
+}
+
+/// RSpec `N examples, N failures, N pending` summary line.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RspecParser;
+
+impl TestParser for RspecParser {
+    fn detect(&self, command: &str, output: &str) -> bool {
+        command.contains("rspec")
+            || Regex::new(r"\d+\s+examples?,\s+\d+\s+failures?")
+                .map(|re| re.is_match(output))
+                .unwrap_or(false)
+    }
+
+    fn parse(&self, output: &str) -> TestResult {
+        let mut result = TestResult::new("rspec".to_string());
+
+        // Match "5 examples, 1 failure, 2 pending"
+        if let Ok(re) = Regex::new(r"(\d+)\s+examples?,\s+(\d+)\s+failures?(?:,\s+(\d+)\s+pending)?") {
+            if let Some(caps) = re.captures(output) {
+                let count = |i: usize| {
+                    caps.get(i)
+                        .and_then(|m| m.as_str().parse::<u32>().ok())
+                        .unwrap_or(0)
+                };
+                let (examples, failed, pending) = (count(1), count(2), count(3));
+                result.failed = failed;
+                result.skipped = pending;
+                result.passed = examples.saturating_sub(failed + pending);
+            }
+        }
+
+        // Errors raised outside of examples (e.g. a file failing to load)
+        if let Ok(re) = Regex::new(r"(\d+)\s+errors?\s+occurred\s+outside\s+of\s+examples") {
+            if let Some(caps) = re.captures(output) {
+                result.errors = caps[1].parse().unwrap_or(0);
+            }
+        }
+
+        result
+    }
+}
+
+/// Mocha `passing` / `failing` / `pending` summary lines.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MochaParser;
+
+impl TestParser for MochaParser {
+    fn detect(&self, command: &str, output: &str) -> bool {
+        command.contains("mocha")
+            || Regex::new(r"(?m)^\s*\d+\s+passing\b")
+                .map(|re| re.is_match(output))
+                .unwrap_or(false)
+    }
+
+    fn parse(&self, output: &str) -> TestResult {
+        let mut result = TestResult::new("mocha".to_string());
+
+        // Match "12 passing (40ms)", "2 failing", "1 pending"
+        if let Ok(re) = Regex::new(r"(?m)^\s*(\d+)\s+(passing|failing|pending)\b") {
+            for caps in re.captures_iter(output) {
+                let n = caps[1].parse().unwrap_or(0);
+                match &caps[2] {
+                    "passing" => result.passed = n,
+                    "failing" => result.failed = n,
+                    _ => result.skipped = n,
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// `cargo test` result lines.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CargoTestParser;
+
+impl TestParser for CargoTestParser {
+    fn detect(&self, command: &str, _output: &str) -> bool {
+        command.contains("cargo test")
+    }
+
+    fn parse(&self, output: &str) -> TestResult {
+        let mut result = TestResult::new("cargo".to_string());
+
+        // Match "test result: ok. X passed; Y failed"
+        if let Ok(re) = Regex::new(r"(\d+)\s+passed.*?(\d+)\s+failed") {
+            if let Some(caps) = re.captures(output) {
+                if let Some(passed) = caps.get(1) {
+                    result.passed = passed.as_str().parse().unwrap_or(0);
+                }
+                if let Some(failed) = caps.get(2) {
+                    result.failed = failed.as_str().parse().unwrap_or(0);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// `go test` per-package ok/FAIL lines.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GoTestParser;
+
+impl TestParser for GoTestParser {
+    fn detect(&self, command: &str, _output: &str) -> bool {
+        command.contains("go test")
+    }
+
+    fn parse(&self, output: &str) -> TestResult {
+        let mut result = TestResult::new("go".to_string());
+
+        // Go tests output "ok" for passed, "FAIL" for failed
+        // Use (?m) for multiline mode so ^ matches start of each line
+        if let Ok(re) = Regex::new(r"(?m)^ok\s+") {
+            result.passed = re.find_iter(output).count() as u32;
+        }
+
+        if let Ok(re) = Regex::new(r"(?m)^FAIL\s+") {
+            result.failed = re.find_iter(output).count() as u32;
+        }
+
+        result
+    }
+}
+
+/// Built-in parsers in detection order. Vitest and Mocha must come before
+/// Jest so `npm test` running them is not read as jest, and Cargo must come
+/// before Go because "cargo test" contains "go test".
+fn default_test_parsers() -> Vec<Arc<dyn TestParser>> {
+    vec![
+        Arc::new(PytestParser),
+        Arc::new(VitestParser),
+        Arc::new(MochaParser),
+        Arc::new(RspecParser),
+        Arc::new(JestParser),
+        Arc::new(CargoTestParser),
+        Arc::new(GoTestParser),
+    ]
+}
+
+/// Numeric attribute from an XML start tag's attribute text.
+fn xml_attr<T: std::str::FromStr>(attrs: &str, name: &str) -> Option<T> {
+    let re = Regex::new(&format!(r#"\b{}\s*=\s*(?:"([^"]*)"|'([^']*)')"#, name)).ok()?;
+    let caps = re.captures(attrs)?;
+    caps.get(1).or_else(|| caps.get(2))?.as_str().trim().parse().ok()
+}
+
+/// Sum every `<testsuite>` in a JUnit XML report. Counts missing from a
+/// suite's attributes are taken from its `<testcase>` children instead.
+/// Returns `None` when the document has no suites.
+fn parse_junit_xml(xml: &str) -> Option<TestResult> {
+    let suite_re = Regex::new(r"(?s)<testsuite\b([^>]*?)(?:/>|>(.*?)</testsuite>)").ok()?;
+    let count = |body: &str, tag: &str| {
+        Regex::new(&format!(r"<{}\b", tag))
+            .map(|re| re.find_iter(body).count() as u32)
+            .unwrap_or(0)
+    };
+
+    let mut result = TestResult::new("junit".to_string());
+    let mut found = false;
+    for caps in suite_re.captures_iter(xml) {
+        found = true;
+        let attrs = caps.get(1).map_or("", |m| m.as_str());
+        let body = caps.get(2).map_or("", |m| m.as_str());
+
+        let tests = xml_attr(attrs, "tests").unwrap_or_else(|| count(body, "testcase"));
+        let failures = xml_attr(attrs, "failures").unwrap_or_else(|| count(body, "failure"));
+        let errors = xml_attr(attrs, "errors").unwrap_or_else(|| count(body, "error"));
+        let skipped = xml_attr(attrs, "skipped")
+            .or_else(|| xml_attr(attrs, "disabled"))
+            .unwrap_or_else(|| count(body, "skipped"));
+
+        result.passed += tests.saturating_sub(failures + errors + skipped);
+        result.failed += failures;
+        result.errors += errors;
+        result.skipped += skipped;
+        result.duration_seconds += xml_attr::<f64>(attrs, "time").unwrap_or(0.0);
+    }
+
+    found.then_some(result)
+}
+
+/// Build invocations: `cargo build`, `tsc`, `go build`, and `npm run build`
+/// or its yarn/pnpm equivalents.
+static BUILD_COMMAND: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
+    Regex::new(r"\bcargo\s+build\b|\btsc\b|\bgo\s+build\b|\b(?:npm|pnpm|yarn)\s+(?:run\s+)?build\b")
+        .expect("valid regex")
+});
+
+/// Compiler diagnostics that mean a build failed even when the exit code
+/// wasn't captured (hooks report 0). Go errors are matched by their
+/// `file.go:line:col:` prefix rather than the `# package` header, which
+/// would also match any markdown heading in the output.
+static BUILD_FAILURE: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
+    Regex::new(
+        r"(?mi)^error(?:\[E\d+\])?:|error TS\d+:|could not compile|^npm err!|build failed|^\S+\.go:\d+:\d+: ",
+    )
+    .expect("valid regex")
+});
+
+/// Parse linter output for the `cargo clippy`, `eslint` and `ruff`
+/// commands. Returns `None` for any other command.
+fn parse_lint_output(command: &str, output: &str) -> Option<LintResult> {
+    if command.contains("cargo clippy") {
+        // Count individual diagnostics, skipping the per-crate summaries
+        // ("generated 3 warnings", "could not compile", "aborting due to").
+        let mut result = LintResult::new("clippy".to_string());
+        for line in output.lines() {
+            if line.starts_with("warning:") {
+                if !line.contains(" generated ") && !line.contains("build failed") {
+                    result.warnings += 1;
+                }
+            } else if (line.starts_with("error:") || line.starts_with("error["))
+                && !line.contains("could not compile")
+                && !line.contains("aborting due to")
+            {
+                result.errors += 1;
+            }
+        }
+        return Some(result);
+    }
+
+    if command.contains("eslint") {
+        // Match "✖ 12 problems (3 errors, 9 warnings)"
+        let mut result = LintResult::new("eslint".to_string());
+        if let Ok(re) = Regex::new(r"(\d+)\s+errors?,\s+(\d+)\s+warnings?") {
+            if let Some(caps) = re.captures(output) {
+                result.errors = caps[1].parse().unwrap_or(0);
+                result.warnings = caps[2].parse().unwrap_or(0);
+            }
+        }
+        return Some(result);
+    }
+
+    if command.contains("ruff") && !command.contains("ruff format") {
+        // ruff reports every finding as an "error", but they are lint
+        // violations, so count them as warnings ("Found 7 errors.")
+        let mut result = LintResult::new("ruff".to_string());
+        if let Ok(re) = Regex::new(r"Found\s+(\d+)\s+errors?") {
+            if let Some(caps) = re.captures(output) {
+                result.warnings = caps[1].parse().unwrap_or(0);
+            }
+        }
+        return Some(result);
+    }
+
+    None
+}
+
 /// Tool invocation record for debugging.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ToolInvocation {
     pub tool_name: String,
     pub tool_input: serde_json::Value,
@@ -104,6 +648,39 @@ pub struct ToolInvocation {
     pub timestamp: String,
 }
 
+/// Append `path` unless it is already listed.
+fn push_unique(paths: &mut Vec<String>, path: &str) {
+    if !paths.iter().any(|p| p == path) {
+        paths.push(path.to_string());
+    }
+}
+
+/// Append the items of `other` not already in `items`.
+fn extend_unique<T: Clone + PartialEq>(items: &mut Vec<T>, other: &[T]) {
+    for item in other {
+        if !items.contains(item) {
+            items.push(item.clone());
+        }
+    }
+}
+
+/// Errors from saving or loading an evidence JSON file.
+#[derive(Debug, thiserror::Error)]
+pub enum EvidenceFileError {
+    #[error("Evidence file not found: {path:?}")]
+    NotFound { path: PathBuf },
+
+    #[error("Failed to access evidence file {path:?}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Invalid evidence JSON: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
 /// Collects evidence from SDK hooks during query() execution.
 ///
 /// This is passed to hook callbacks which populate it as tools execute.
@@ -117,7 +694,8 @@ pub struct ToolInvocation {
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvidenceCollector {
-    // File tracking
+    // File tracking: distinct paths in first-seen order; every operation
+    // is kept in `file_changes`
     pub files_written: Vec<String>,
     pub files_edited: Vec<String>,
     pub files_read: Vec<String>,
@@ -130,9 +708,17 @@ pub struct EvidenceCollector {
     pub tests_run: bool,
     pub test_results: Vec<TestResult>,
 
+    // Build tracking
+    #[serde(default)]
+    pub builds: Vec<BuildResult>,
+
+    // Lint tracking
+    #[serde(default)]
+    pub lint_results: Vec<LintResult>,
+
     // Subagent tracking
     pub subagents_spawned: u32,
-    pub subagent_results: Vec<serde_json::Value>,
+    pub subagent_results: Vec<SubagentResult>,
 
     // Session info
     pub session_id: String,
@@ -143,6 +729,10 @@ pub struct EvidenceCollector {
 
     // Raw tool invocations (for debugging)
     pub tool_invocations: Vec<ToolInvocation>,
+
+    // Test output parsers, tried in order
+    #[serde(skip, default = "default_test_parsers")]
+    test_parsers: Vec<Arc<dyn TestParser>>,
 }
 
 impl Default for EvidenceCollector {
@@ -162,12 +752,15 @@ impl EvidenceCollector {
             commands_run: Vec::new(),
             tests_run: false,
             test_results: Vec::new(),
+            builds: Vec::new(),
+            lint_results: Vec::new(),
             subagents_spawned: 0,
             subagent_results: Vec::new(),
             session_id: String::new(),
             start_time: Utc::now(),
             end_time: None,
             tool_invocations: Vec::new(),
+            test_parsers: default_test_parsers(),
         }
     }
 
@@ -180,6 +773,8 @@ impl EvidenceCollector {
         self.commands_run.clear();
         self.tests_run = false;
         self.test_results.clear();
+        self.builds.clear();
+        self.lint_results.clear();
         self.subagents_spawned = 0;
         self.subagent_results.clear();
         self.tool_invocations.clear();
@@ -187,7 +782,7 @@ impl EvidenceCollector {
 
     /// Record a file write operation.
     pub fn record_file_write(&mut self, path: String, lines_changed: usize) {
-        self.files_written.push(path.clone());
+        push_unique(&mut self.files_written, &path);
         self.file_changes.push(
             FileChange::new(path, "write".to_string()).with_lines(lines_changed)
         );
@@ -195,7 +790,7 @@ impl EvidenceCollector {
 
     /// Record a file edit operation.
     pub fn record_file_edit(&mut self, path: String, lines_changed: usize) {
-        self.files_edited.push(path.clone());
+        push_unique(&mut self.files_edited, &path);
         self.file_changes.push(
             FileChange::new(path, "edit".to_string()).with_lines(lines_changed)
         );
@@ -203,10 +798,64 @@ impl EvidenceCollector {
 
     /// Record a file read operation.
     pub fn record_file_read(&mut self, path: String) {
-        self.files_read.push(path.clone());
+        push_unique(&mut self.files_read, &path);
         self.file_changes.push(FileChange::new(path, "read".to_string()));
     }
 
+    /// Record a file write, hashing the content written.
+    pub fn record_file_write_with_content(&mut self, path: String, lines_changed: usize, content: &[u8]) {
+        self.record_file_write(path, lines_changed);
+        self.set_last_hash(content_hash(content));
+    }
+
+    /// Record a file edit, hashing the file's content after the edit.
+    pub fn record_file_edit_with_content(&mut self, path: String, lines_changed: usize, content: &[u8]) {
+        self.record_file_edit_with_hash(path, lines_changed, content_hash(content));
+    }
+
+    /// Record a file edit with an already computed [`content_hash`] of the
+    /// file after the edit.
+    pub fn record_file_edit_with_hash(&mut self, path: String, lines_changed: usize, hash: String) {
+        self.record_file_edit(path, lines_changed);
+        self.set_last_hash(hash);
+    }
+
+    /// Record a file read, hashing the content seen.
+    pub fn record_file_read_with_content(&mut self, path: String, content: &[u8]) {
+        self.record_file_read_with_hash(path, content_hash(content));
+    }
+
+    /// Record a file read with an already computed [`content_hash`].
+    pub fn record_file_read_with_hash(&mut self, path: String, hash: String) {
+        self.record_file_read(path);
+        self.set_last_hash(hash);
+    }
+
+    fn set_last_hash(&mut self, hash: String) {
+        if let Some(change) = self.file_changes.last_mut() {
+            change.content_hash = hash;
+        }
+    }
+
+    /// Writes and edits that actually changed a file. A change whose hash
+    /// matches the last known hash of its path (from a read or an earlier
+    /// change) is a no-op and dropped; unhashed changes are kept.
+    pub fn changed_files_excluding_noops(&self) -> Vec<&FileChange> {
+        let mut last_hash: HashMap<&str, &str> = HashMap::new();
+        let mut changed = Vec::new();
+        for change in &self.file_changes {
+            let hash = change.content_hash.as_str();
+            let is_noop = !hash.is_empty() && last_hash.get(change.path.as_str()) == Some(&hash);
+            if change.action != "read" && !is_noop {
+                changed.push(change);
+            }
+            if !hash.is_empty() {
+                last_hash.insert(&change.path, hash);
+            }
+        }
+        changed
+    }
+
     /// Record a command execution.
     pub fn record_command(&mut self, command: String, output: String, exit_code: i32, duration_ms: u64) {
         let cmd_result = CommandResult::new(command.clone(), output.clone())
@@ -220,167 +869,157 @@ impl EvidenceCollector {
             self.tests_run = true;
             self.test_results.push(test_result);
         }
-    }
 
-    /// Record raw tool invocation for debugging.
-    pub fn record_tool_invocation(
-        &mut self,
-        tool_name: String,
-        tool_input: serde_json::Value,
-        tool_output: String,
-    ) {
-        // Truncate large outputs
-        let truncated_output = if tool_output.len() > 1000 {
-            format!("{}...", &tool_output[..1000])
-        } else {
-            tool_output
-        };
-
-        self.tool_invocations.push(ToolInvocation {
-            tool_name,
-            tool_input,
-            tool_output: truncated_output,
-            timestamp: Utc::now().to_rfc3339(),
-        });
-    }
-
-    /// Parse test framework output to extract pass/fail counts.
-    fn parse_test_output(&self, command: &str, output: &str) -> Option<TestResult> {
-        let output_lower = output.to_lowercase();
-
-        // Detect pytest
-        if command.contains("pytest") || output_lower.contains("pytest") {
-            return Some(self.parse_pytest_output(output));
+        if let Some(lint_result) = parse_lint_output(&command, &output) {
+            self.lint_results.push(lint_result);
         }
 
-        // Detect Jest/npm test
-        if command.contains("jest")
-            || command.contains("npm test")
-            || output_lower.contains("tests passed")
-        {
-            return Some(self.parse_jest_output(output));
+        if BUILD_COMMAND.is_match(&command) {
+            let success = exit_code == 0 && !BUILD_FAILURE.is_match(&output);
+            self.record_build(BuildResult::new(command, success).with_duration(duration_ms));
         }
+    }
 
-        // Detect Cargo tests (check BEFORE Go tests because "cargo test" contains "go test")
-        if command.contains("cargo test") {
-            return Some(self.parse_cargo_test_output(output));
-        }
+    /// Record a build outcome.
+    pub fn record_build(&mut self, build: BuildResult) {
+        self.builds.push(build);
+    }
 
-        // Detect Go tests
-        if command.contains("go test") {
-            return Some(self.parse_go_test_output(output));
+    /// Most recent result for each linter, so a re-run after fixes
+    /// supersedes the earlier findings.
+    pub fn latest_lint_results(&self) -> Vec<&LintResult> {
+        let mut latest: Vec<&LintResult> = Vec::new();
+        for result in self.lint_results.iter().rev() {
+            if !latest.iter().any(|r| r.tool == result.tool) {
+                latest.push(result);
+            }
         }
+        latest
+    }
 
-        None
+    /// Total lint warnings across the latest run of each linter.
+    pub fn total_lint_warnings(&self) -> u32 {
+        self.latest_lint_results().iter().map(|r| r.warnings).sum()
     }
 
-    /// Parse pytest output format.
-    fn parse_pytest_output(&self, output: &str) -> TestResult {
-        let mut result = TestResult::new("pytest".to_string());
+    /// Total lint errors across the latest run of each linter.
+    pub fn total_lint_errors(&self) -> u32 {
+        self.latest_lint_results().iter().map(|r| r.errors).sum()
+    }
 
-        // Match patterns like "5 passed, 2 failed, 1 skipped"
-        if let Ok(re) = Regex::new(r"(\d+)\s+passed") {
-            if let Some(caps) = re.captures(output) {
-                if let Some(val) = caps.get(1) {
-                    result.passed = val.as_str().parse().unwrap_or(0);
-                }
-            }
-        }
+    /// Outcome of the most recent build, if any ran.
+    pub fn last_build_succeeded(&self) -> Option<bool> {
+        self.builds.last().map(|b| b.success)
+    }
 
-        if let Ok(re) = Regex::new(r"(\d+)\s+failed") {
-            if let Some(caps) = re.captures(output) {
-                if let Some(val) = caps.get(1) {
-                    result.failed = val.as_str().parse().unwrap_or(0);
-                }
-            }
+    /// Record test outcomes from a JUnit XML report. Documents without any
+    /// `<testsuite>` are ignored.
+    pub fn record_junit_report(&mut self, xml: &str) {
+        if let Some(test_result) = parse_junit_xml(xml) {
+            self.tests_run = true;
+            self.test_results.push(test_result);
         }
+    }
 
-        if let Ok(re) = Regex::new(r"(\d+)\s+skipped") {
-            if let Some(caps) = re.captures(output) {
-                if let Some(val) = caps.get(1) {
-                    result.skipped = val.as_str().parse().unwrap_or(0);
-                }
+    /// Set line coverage from lcov tracefile data, summing `LF:` (lines
+    /// found) and `LH:` (lines hit) across all records. The percentage is
+    /// stored on the most recent test result, or on a new `"lcov"` result if
+    /// no tests have been recorded. Data without any instrumented lines is
+    /// ignored.
+    pub fn parse_lcov(&mut self, lcov: &str) {
+        let (mut found, mut hit) = (0u64, 0u64);
+        for line in lcov.lines() {
+            let line = line.trim();
+            if let Some(n) = line.strip_prefix("LF:") {
+                found += n.trim().parse::<u64>().unwrap_or(0);
+            } else if let Some(n) = line.strip_prefix("LH:") {
+                hit += n.trim().parse::<u64>().unwrap_or(0);
             }
         }
-
-        if let Ok(re) = Regex::new(r"(\d+)\s+error") {
-            if let Some(caps) = re.captures(output) {
-                if let Some(val) = caps.get(1) {
-                    result.errors = val.as_str().parse().unwrap_or(0);
-                }
-            }
+        if found == 0 {
+            return;
         }
 
-        // Parse coverage if present
-        if let Ok(re) = Regex::new(r"(\d+(?:\.\d+)?)\s*%") {
-            if let Some(caps) = re.captures(output) {
-                if let Some(val) = caps.get(1) {
-                    result.coverage = val.as_str().parse().unwrap_or(0.0);
-                }
+        let coverage = hit as f64 / found as f64 * 100.0;
+        match self.test_results.last_mut() {
+            Some(result) => result.coverage = coverage,
+            None => {
+                let mut result = TestResult::new("lcov".to_string());
+                result.coverage = coverage;
+                self.test_results.push(result);
             }
         }
-
-        result
     }
 
-    /// Parse Jest output format.
-    fn parse_jest_output(&self, output: &str) -> TestResult {
-        let mut result = TestResult::new("jest".to_string());
-
-        // Match patterns like "Tests: 5 passed, 2 failed, 7 total"
-        if let Ok(re) = Regex::new(r"(\d+)\s+passed") {
-            if let Some(caps) = re.captures(output) {
-                if let Some(val) = caps.get(1) {
-                    result.passed = val.as_str().parse().unwrap_or(0);
-                }
-            }
-        }
+    /// Record a completed subagent run.
+    pub fn record_subagent_result(&mut self, result: SubagentResult) {
+        self.subagents_spawned += 1;
+        self.subagent_results.push(result);
+    }
 
-        if let Ok(re) = Regex::new(r"(\d+)\s+failed") {
-            if let Some(caps) = re.captures(output) {
-                if let Some(val) = caps.get(1) {
-                    result.failed = val.as_str().parse().unwrap_or(0);
-                }
-            }
-        }
+    /// Record raw tool invocation for debugging.
+    pub fn record_tool_invocation(
+        &mut self,
+        tool_name: String,
+        tool_input: serde_json::Value,
+        tool_output: String,
+    ) {
+        // Truncate large outputs
+        let truncated_output = if tool_output.len() > 1000 {
+            format!("{}...", &tool_output[..1000])
+        } else {
+            tool_output
+        };
 
-        result
+        self.tool_invocations.push(ToolInvocation {
+            tool_name,
+            tool_input,
+            tool_output: truncated_output,
+            timestamp: Utc::now().to_rfc3339(),
+        });
     }
 
-    /// Parse Go test output format.
-    fn parse_go_test_output(&self, output: &str) -> TestResult {
-        let mut result = TestResult::new("go".to_string());
+    /// Add a test output parser. Registered parsers are tried before the
+    /// built-ins, so they can also take over detection from them.
+    pub fn register_parser(&mut self, parser: Box<dyn TestParser>) {
+        self.test_parsers.insert(0, Arc::from(parser));
+    }
 
-        // Go tests output "ok" for passed, "FAIL" for failed
-        // Use (?m) for multiline mode so ^ matches start of each line
-        if let Ok(re) = Regex::new(r"(?m)^ok\s+") {
-            result.passed = re.find_iter(output).count() as u32;
-        }
+    /// Parse test framework output with the first parser that detects it.
+    fn parse_test_output(&self, command: &str, output: &str) -> Option<TestResult> {
+        self.test_parsers
+            .iter()
+            .find(|parser| parser.detect(command, output))
+            .map(|parser| parser.parse(output))
+    }
 
-        if let Ok(re) = Regex::new(r"(?m)^FAIL\s+") {
-            result.failed = re.find_iter(output).count() as u32;
+    /// Absorb evidence from another session, e.g. a subagent's. File lists
+    /// are unioned so no path is counted twice; commands, test, build and
+    /// lint results are added; records already present are not repeated.
+    pub fn merge(&mut self, other: &EvidenceCollector) {
+        for path in &other.files_written {
+            push_unique(&mut self.files_written, path);
         }
+        for path in &other.files_edited {
+            push_unique(&mut self.files_edited, path);
+        }
+        for path in &other.files_read {
+            push_unique(&mut self.files_read, path);
+        }
+        extend_unique(&mut self.file_changes, &other.file_changes);
 
-        result
-    }
-
-    /// Parse Cargo test output format.
-    fn parse_cargo_test_output(&self, output: &str) -> TestResult {
-        let mut result = TestResult::new("cargo".to_string());
+        self.commands_run.extend(other.commands_run.iter().cloned());
+        self.tests_run |= other.tests_run;
+        self.test_results.extend(other.test_results.iter().cloned());
+        self.builds.extend(other.builds.iter().cloned());
+        self.lint_results.extend(other.lint_results.iter().cloned());
 
-        // Match "test result: ok. X passed; Y failed"
-        if let Ok(re) = Regex::new(r"(\d+)\s+passed.*?(\d+)\s+failed") {
-            if let Some(caps) = re.captures(output) {
-                if let Some(passed) = caps.get(1) {
-                    result.passed = passed.as_str().parse().unwrap_or(0);
-                }
-                if let Some(failed) = caps.get(2) {
-                    result.failed = failed.as_str().parse().unwrap_or(0);
-                }
-            }
-        }
+        self.subagents_spawned += other.subagents_spawned;
+        extend_unique(&mut self.subagent_results, &other.subagent_results);
+        extend_unique(&mut self.tool_invocations, &other.tool_invocations);
 
-        result
+        self.start_time = self.start_time.min(other.start_time);
+        self.end_time = self.end_time.max(other.end_time);
     }
 
     /// Total unique files written or edited.
@@ -409,6 +1048,39 @@ impl EvidenceCollector {
         self.total_tests_failed() == 0 && self.total_tests_passed() > 0
     }
 
+    /// Fraction of recorded subagent runs that succeeded (0.0 when none ran).
+    pub fn subagent_success_rate(&self) -> f64 {
+        if self.subagent_results.is_empty() {
+            return 0.0;
+        }
+        let succeeded = self.subagent_results.iter().filter(|r| r.success).count();
+        succeeded as f64 / self.subagent_results.len() as f64
+    }
+
+    /// Write the complete evidence as JSON, for reloading with
+    /// [`load_json`](Self::load_json). Timestamps keep second precision.
+    pub fn save_json(&self, path: &Path) -> Result<(), EvidenceFileError> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json).map_err(|source| EvidenceFileError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Load evidence written by [`save_json`](Self::save_json).
+    pub fn load_json(path: &Path) -> Result<Self, EvidenceFileError> {
+        let json = std::fs::read_to_string(path).map_err(|source| match source.kind() {
+            std::io::ErrorKind::NotFound => EvidenceFileError::NotFound {
+                path: path.to_path_buf(),
+            },
+            _ => EvidenceFileError::Io {
+                path: path.to_path_buf(),
+                source,
+            },
+        })?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
     /// Serialize evidence to dictionary for logging/metrics.
     pub fn to_dict(&self) -> serde_json::Value {
         serde_json::json!({
@@ -421,7 +1093,12 @@ impl EvidenceCollector {
             "tests_passed": self.total_tests_passed(),
             "tests_failed": self.total_tests_failed(),
             "all_tests_passing": self.all_tests_passing(),
+            "builds_run": self.builds.len(),
+            "last_build_succeeded": self.last_build_succeeded(),
+            "lint_warnings": self.total_lint_warnings(),
+            "lint_errors": self.total_lint_errors(),
             "subagents_spawned": self.subagents_spawned,
+            "subagent_success_rate": self.subagent_success_rate(),
             "session_id": self.session_id,
             "start_time": self.start_time.to_rfc3339(),
             "end_time": self.end_time.map(|t| t.to_rfc3339()),
@@ -474,6 +1151,134 @@ mod tests {
         assert_eq!(evidence.file_changes[0].action, "read");
     }
 
+    #[test]
+    fn test_repeated_file_operations_listed_once() {
+        let mut evidence = EvidenceCollector::new();
+        evidence.record_file_write("src/a.py".to_string(), 5);
+        evidence.record_file_write("src/b.py".to_string(), 3);
+        evidence.record_file_write("src/a.py".to_string(), 7);
+        evidence.record_file_edit("src/a.py".to_string(), 1);
+        evidence.record_file_edit("src/a.py".to_string(), 2);
+
+        assert_eq!(evidence.files_written, vec!["src/a.py", "src/b.py"]);
+        assert_eq!(evidence.files_edited, vec!["src/a.py"]);
+        assert_eq!(evidence.total_files_modified(), 2);
+        // The full history is kept for timelines
+        assert_eq!(evidence.file_changes.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_file_content_hash_streams_and_caps_size() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let small = dir.path().join("small.rs");
+        std::fs::write(&small, b"fn main() {}\n").unwrap();
+        assert_eq!(file_content_hash(&small).await, Some(content_hash(b"fn main() {}\n")));
+
+        let large = dir.path().join("large.bin");
+        let file = std::fs::File::create(&large).unwrap();
+        file.set_len(MAX_HASHED_FILE_BYTES + 1).unwrap();
+        assert_eq!(file_content_hash(&large).await, None);
+
+        assert_eq!(file_content_hash(&dir.path().join("missing.rs")).await, None);
+    }
+
+    #[test]
+    fn test_content_hash_detects_noop_writes() {
+        assert_eq!(content_hash(b"fn main() {}\n"), content_hash(b"fn main() {}\n"));
+        assert_ne!(content_hash(b"fn main() {}\n"), content_hash(b"fn main() { }\n"));
+
+        let mut evidence = EvidenceCollector::new();
+        evidence.record_file_read_with_content("a.rs".to_string(), b"old");
+        evidence.record_file_write_with_content("a.rs".to_string(), 1, b"old");
+        evidence.record_file_edit_with_content("a.rs".to_string(), 1, b"new");
+        evidence.record_file_edit_with_content("a.rs".to_string(), 1, b"new");
+        evidence.record_file_write("b.rs".to_string(), 3);
+
+        assert_eq!(evidence.file_changes[1].content_hash, content_hash(b"old"));
+        let changed: Vec<(&str, &str)> = evidence
+            .changed_files_excluding_noops()
+            .iter()
+            .map(|c| (c.path.as_str(), c.action.as_str()))
+            .collect();
+        // The rewrite of unchanged content and the repeated edit are no-ops
+        assert_eq!(changed, vec![("a.rs", "edit"), ("b.rs", "write")]);
+    }
+
+    #[test]
+    fn test_merge_subagent_evidence() {
+        let mut parent = EvidenceCollector::new();
+        parent.record_file_write("src/lib.rs".to_string(), 10);
+        parent.record_command("cargo build".to_string(), String::new(), 0, 100);
+        parent.subagents_spawned = 1;
+
+        let mut child = EvidenceCollector::new();
+        child.record_file_edit("src/lib.rs".to_string(), 2);
+        child.record_file_write("src/lib.rs".to_string(), 3);
+        child.record_file_write("src/util.rs".to_string(), 20);
+        child.record_command("cargo test".to_string(), String::new(), 0, 200);
+        child.tests_run = true;
+        let mut tests = TestResult::new("cargo".to_string());
+        tests.passed = 4;
+        tests.failed = 1;
+        child.test_results.push(tests);
+
+        parent.merge(&child);
+
+        // src/lib.rs was touched by both sessions but is one file
+        assert_eq!(parent.files_written, vec!["src/lib.rs", "src/util.rs"]);
+        assert_eq!(parent.files_edited, vec!["src/lib.rs"]);
+        assert_eq!(parent.total_files_modified(), 2);
+        assert_eq!(parent.file_changes.len(), 4);
+        assert_eq!(parent.commands_run.len(), 2);
+        assert!(parent.tests_run);
+        assert_eq!(parent.total_tests_passed(), 4);
+        assert_eq!(parent.total_tests_failed(), 1);
+        assert_eq!(parent.subagents_spawned, 1);
+    }
+
+    #[test]
+    fn test_json_file_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("evidence.json");
+
+        let mut evidence = EvidenceCollector::new();
+        evidence.session_id = "session-1".to_string();
+        evidence.record_file_write_with_content("src/main.rs".to_string(), 12, b"fn main() {}");
+        evidence.record_file_edit("src/lib.rs".to_string(), 3);
+        evidence.record_command("cargo build".to_string(), "Finished".to_string(), 0, 900);
+        evidence.tool_invocations.push(ToolInvocation {
+            tool_name: "Read".to_string(),
+            tool_input: serde_json::json!({"file_path": "src/lib.rs"}),
+            tool_output: "contents".to_string(),
+            timestamp: "2024-05-01T12:00:00Z".to_string(),
+        });
+        evidence.end_time = Some(Utc::now());
+
+        evidence.save_json(&path).unwrap();
+        let loaded = EvidenceCollector::load_json(&path).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(&evidence).unwrap()
+        );
+        assert_eq!(loaded.file_changes.len(), 2);
+        assert_eq!(loaded.file_changes[0].content_hash, content_hash(b"fn main() {}"));
+        assert_eq!(loaded.tool_invocations, evidence.tool_invocations);
+        assert_eq!(loaded.builds, evidence.builds);
+        // Parsers are not serialized but come back with their defaults
+        assert!(loaded
+            .parse_test_output("pytest tests/", "===== 3 passed in 0.1s =====")
+            .is_some());
+    }
+
+    #[test]
+    fn test_load_json_missing_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let err = EvidenceCollector::load_json(&dir.path().join("absent.json")).unwrap_err();
+        assert!(matches!(err, EvidenceFileError::NotFound { .. }));
+        assert!(err.to_string().contains("absent.json"));
+    }
+
     #[test]
     fn test_total_files_modified() {
         let mut evidence = EvidenceCollector::new();
@@ -608,6 +1413,109 @@ mod tests {
         assert_eq!(result.failed, 5);
     }
 
+    #[test]
+    fn test_parse_vitest_passed() {
+        let mut evidence = EvidenceCollector::new();
+        evidence.record_command(
+            "npx vitest run".to_string(),
+            " Test Files  4 passed (4)\n      Tests  14 passed (14)\n   Duration  1.20s".to_string(),
+            0,
+            0,
+        );
+
+        assert!(evidence.tests_run);
+        let result = &evidence.test_results[0];
+        assert_eq!(result.framework, "vitest");
+        assert_eq!(result.passed, 14);
+        assert_eq!(result.failed, 0);
+        assert_eq!(result.errors, 0);
+    }
+
+    #[test]
+    fn test_parse_vitest_mixed() {
+        let mut evidence = EvidenceCollector::new();
+        evidence.record_command(
+            "npm test".to_string(),
+            " Test Files  2 failed | 3 passed (5)\n      Tests  12 passed | 1 failed | 2 skipped (15)"
+                .to_string(),
+            1,
+            0,
+        );
+
+        let result = &evidence.test_results[0];
+        assert_eq!(result.framework, "vitest");
+        assert_eq!(result.passed, 12);
+        assert_eq!(result.failed, 1);
+        assert_eq!(result.skipped, 2);
+        // One failed file had no failing tests (e.g. an import error)
+        assert_eq!(result.errors, 1);
+    }
+
+    #[test]
+    fn test_parse_rspec_passed() {
+        let mut evidence = EvidenceCollector::new();
+        evidence.record_command(
+            "bundle exec rspec".to_string(),
+            "Finished in 0.5 seconds\n8 examples, 0 failures".to_string(),
+            0,
+            0,
+        );
+
+        let result = &evidence.test_results[0];
+        assert_eq!(result.framework, "rspec");
+        assert_eq!(result.passed, 8);
+        assert_eq!(result.failed, 0);
+    }
+
+    #[test]
+    fn test_parse_rspec_mixed() {
+        let mut evidence = EvidenceCollector::new();
+        evidence.record_command(
+            "rake spec".to_string(),
+            "5 examples, 1 failure, 2 pending".to_string(),
+            1,
+            0,
+        );
+
+        let result = &evidence.test_results[0];
+        assert_eq!(result.framework, "rspec");
+        assert_eq!(result.passed, 2);
+        assert_eq!(result.failed, 1);
+        assert_eq!(result.skipped, 2);
+    }
+
+    #[test]
+    fn test_parse_mocha_passed() {
+        let mut evidence = EvidenceCollector::new();
+        evidence.record_command(
+            "npx mocha".to_string(),
+            "  12 passing (40ms)".to_string(),
+            0,
+            0,
+        );
+
+        let result = &evidence.test_results[0];
+        assert_eq!(result.framework, "mocha");
+        assert_eq!(result.passed, 12);
+    }
+
+    #[test]
+    fn test_parse_mocha_mixed() {
+        let mut evidence = EvidenceCollector::new();
+        evidence.record_command(
+            "npm test".to_string(),
+            "  12 passing (1s)\n  1 pending\n  2 failing\n\n  1) api returns 200".to_string(),
+            1,
+            0,
+        );
+
+        let result = &evidence.test_results[0];
+        assert_eq!(result.framework, "mocha");
+        assert_eq!(result.passed, 12);
+        assert_eq!(result.failed, 2);
+        assert_eq!(result.skipped, 1);
+    }
+
     #[test]
     fn test_parse_go_test_ok() {
         let mut evidence = EvidenceCollector::new();
@@ -657,6 +1565,209 @@ mod tests {
         assert_eq!(result.failed, 0);
     }
 
+    #[test]
+    fn test_record_junit_report() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="ci" tests="9" failures="2" errors="1">
+  <testsuite name="api" tests="5" failures="1" errors="0" skipped="1" time="1.5">
+    <testcase classname="api" name="create" time="0.2"/>
+    <testcase classname="api" name="update" time="0.3">
+      <failure message="expected 200">assertion failed</failure>
+    </testcase>
+    <testcase classname="api" name="delete" time="0.1"><skipped/></testcase>
+    <testcase classname="api" name="list" time="0.4"/>
+    <testcase classname="api" name="get" time="0.5"/>
+  </testsuite>
+  <testsuite name="db">
+    <testcase classname="db" name="connect"/>
+    <testcase classname="db" name="migrate"><error message="timeout"/></testcase>
+    <testcase classname="db" name="query"><failure/></testcase>
+    <testcase classname="db" name="close"/>
+  </testsuite>
+  <testsuite name="empty" tests="0"/>
+</testsuites>"#;
+
+        let mut evidence = EvidenceCollector::new();
+        evidence.record_junit_report(xml);
+
+        assert!(evidence.tests_run);
+        let result = &evidence.test_results[0];
+        assert_eq!(result.framework, "junit");
+        // api: 5 - 1 failed - 1 skipped; db (no attributes): 4 - 1 - 1
+        assert_eq!(result.passed, 5);
+        assert_eq!(result.failed, 2);
+        assert_eq!(result.errors, 1);
+        assert_eq!(result.skipped, 1);
+        assert!((result.duration_seconds - 1.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_record_junit_report_without_suites() {
+        let mut evidence = EvidenceCollector::new();
+        evidence.record_junit_report("<testsuites/>");
+        assert!(!evidence.tests_run);
+        assert!(evidence.test_results.is_empty());
+    }
+
+    #[test]
+    fn test_parse_lcov() {
+        let lcov = "TN:\nSF:src/lib.rs\nDA:1,1\nDA:2,0\nLF:40\nLH:30\nend_of_record\n\
+                    SF:src/main.rs\nLF:10\nLH:5\nend_of_record\n";
+
+        let mut evidence = EvidenceCollector::new();
+        evidence.record_command("pytest".to_string(), "8 passed\nTOTAL 99%".to_string(), 0, 0);
+        evidence.parse_lcov(lcov);
+
+        // 35 of 50 lines hit, replacing the percentage scraped from stdout
+        assert_eq!(evidence.test_results.len(), 1);
+        assert!((evidence.test_results[0].coverage - 70.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_lcov_without_test_results() {
+        let mut evidence = EvidenceCollector::new();
+        evidence.parse_lcov("SF:a.ts\nLF:4\nLH:1\nend_of_record\n");
+        assert_eq!(evidence.test_results[0].framework, "lcov");
+        assert!((evidence.test_results[0].coverage - 25.0).abs() < 1e-9);
+
+        evidence.parse_lcov("SF:b.ts\nend_of_record\n");
+        assert_eq!(evidence.test_results.len(), 1);
+    }
+
+    #[test]
+    fn test_record_build_detection() {
+        let mut evidence = EvidenceCollector::new();
+        evidence.record_command("cargo build --release".to_string(), "Finished".to_string(), 0, 900);
+        assert_eq!(evidence.builds.len(), 1);
+        assert!(evidence.builds[0].success);
+        assert_eq!(evidence.builds[0].duration_ms, 900);
+
+        // Hooks report exit code 0, so failures are also read from output
+        evidence.record_command(
+            "npx tsc --noEmit".to_string(),
+            "src/app.ts(3,7): error TS2322: Type 'string' is not assignable".to_string(),
+            0,
+            0,
+        );
+        evidence.record_command("go build ./...".to_string(), String::new(), 2, 0);
+        evidence.record_command("npm run build".to_string(), "built in 1.2s".to_string(), 0, 0);
+        evidence.record_command("ls -la".to_string(), "error: nothing".to_string(), 0, 0);
+
+        let outcomes: Vec<bool> = evidence.builds.iter().map(|b| b.success).collect();
+        assert_eq!(outcomes, vec![true, false, false, true]);
+        assert_eq!(evidence.last_build_succeeded(), Some(true));
+    }
+
+    #[test]
+    fn test_build_failure_ignores_markdown_headings() {
+        let mut evidence = EvidenceCollector::new();
+        evidence.record_command(
+            "npm run build".to_string(),
+            "# Changelog\n\nbuilt in 1.2s".to_string(),
+            0,
+            0,
+        );
+        evidence.record_command(
+            "go build ./...".to_string(),
+            "# example.com/app\n./main.go:12:5: undefined: foo".to_string(),
+            0,
+            0,
+        );
+
+        let outcomes: Vec<bool> = evidence.builds.iter().map(|b| b.success).collect();
+        assert_eq!(outcomes, vec![true, false]);
+    }
+
+    #[test]
+    fn test_parse_clippy_output() {
+        let output = "warning: unused variable: `x`\n --> src/lib.rs:3:9\n\
+                      warning: needless borrow\n --> src/lib.rs:8:5\n\
+                      error: this comparison is always false\n --> src/lib.rs:12:8\n\
+                      warning: `demo` (lib) generated 2 warnings\n\
+                      error: could not compile `demo` due to 1 previous error";
+        let mut evidence = EvidenceCollector::new();
+        evidence.record_command("cargo clippy --all-targets".to_string(), output.to_string(), 101, 0);
+
+        let result = &evidence.lint_results[0];
+        assert_eq!(result.tool, "clippy");
+        assert_eq!(result.warnings, 2);
+        assert_eq!(result.errors, 1);
+    }
+
+    #[test]
+    fn test_parse_eslint_and_ruff_output() {
+        let mut evidence = EvidenceCollector::new();
+        evidence.record_command(
+            "npx eslint src".to_string(),
+            "\u{2716} 12 problems (3 errors, 9 warnings)".to_string(),
+            1,
+            0,
+        );
+        evidence.record_command("ruff check .".to_string(), "Found 7 errors.".to_string(), 1, 0);
+
+        assert_eq!(evidence.total_lint_warnings(), 16);
+        assert_eq!(evidence.total_lint_errors(), 3);
+    }
+
+    #[test]
+    fn test_latest_lint_run_supersedes_earlier() {
+        let mut evidence = EvidenceCollector::new();
+        evidence.record_command("ruff check .".to_string(), "Found 7 errors.".to_string(), 1, 0);
+        evidence.record_command("ruff check .".to_string(), "All checks passed!".to_string(), 0, 0);
+
+        assert_eq!(evidence.lint_results.len(), 2);
+        assert_eq!(evidence.total_lint_warnings(), 0);
+    }
+
+    #[derive(Debug)]
+    struct TapParser;
+
+    impl TestParser for TapParser {
+        fn detect(&self, command: &str, _output: &str) -> bool {
+            command.contains("tap")
+        }
+
+        fn parse(&self, output: &str) -> TestResult {
+            let mut result = TestResult::new("tap".to_string());
+            if let Some(caps) = Regex::new(r"# pass\s+(\d+)").unwrap().captures(output) {
+                result.passed = caps[1].parse().unwrap_or(0);
+            }
+            result
+        }
+    }
+
+    #[test]
+    fn test_register_custom_parser() {
+        let mut evidence = EvidenceCollector::new();
+        evidence.record_command("tap test/".to_string(), "# pass  7".to_string(), 0, 0);
+        assert!(!evidence.tests_run);
+
+        evidence.register_parser(Box::new(TapParser));
+        evidence.record_command("tap test/".to_string(), "# pass  7".to_string(), 0, 0);
+
+        assert!(evidence.tests_run);
+        assert_eq!(evidence.test_results[0].framework, "tap");
+        assert_eq!(evidence.test_results[0].passed, 7);
+    }
+
+    #[test]
+    fn test_cargo_detected_before_go() {
+        assert!(GoTestParser.detect("cargo test", ""));
+        let evidence = EvidenceCollector::new();
+        let result = evidence
+            .parse_test_output("cargo test", "test result: ok. 3 passed; 0 failed; 0 ignored")
+            .unwrap();
+        assert_eq!(result.framework, "cargo");
+    }
+
+    #[test]
+    fn test_deserialized_collector_keeps_builtin_parsers() {
+        let json = serde_json::to_string(&EvidenceCollector::new()).unwrap();
+        let mut evidence: EvidenceCollector = serde_json::from_str(&json).unwrap();
+        evidence.record_command("pytest".to_string(), "4 passed".to_string(), 0, 0);
+        assert_eq!(evidence.total_tests_passed(), 4);
+    }
+
     #[test]
     fn test_total_tests_passed() {
         let mut evidence = EvidenceCollector::new();
@@ -697,6 +1808,46 @@ mod tests {
         assert!(!evidence.all_tests_passing());
     }
 
+    #[test]
+    fn test_subagent_success_rate() {
+        let mut evidence = EvidenceCollector::new();
+        assert_eq!(evidence.subagent_success_rate(), 0.0);
+
+        evidence.record_subagent_result(SubagentResult::new("Explore".to_string(), "find tests".to_string()));
+        evidence.record_subagent_result(
+            SubagentResult::new("Plan".to_string(), "draft plan".to_string())
+                .with_outcome(false, "timed out".to_string()),
+        );
+
+        assert_eq!(evidence.subagents_spawned, 2);
+        assert_eq!(evidence.subagent_success_rate(), 0.5);
+    }
+
+    #[test]
+    fn test_subagent_result_from_events() {
+        let spawned = superclaude_proto::SubagentSpawned {
+            subagent_id: "tu-1".to_string(),
+            subagent_type: "Explore".to_string(),
+            task_summary: "Find all test files".to_string(),
+            depth: 1,
+            node_id: "subagent-tu-1".to_string(),
+            parent_node_id: "iter-1".to_string(),
+        };
+        let completed = superclaude_proto::SubagentCompleted {
+            subagent_id: "tu-1".to_string(),
+            success: true,
+            result_summary: "Found 15 test files".to_string(),
+            node_id: "subagent-tu-1".to_string(),
+        };
+
+        let result = SubagentResult::from_events(&spawned, &completed).with_duration(4.5);
+        assert_eq!(result.subagent_type, "Explore");
+        assert_eq!(result.task_summary, "Find all test files");
+        assert!(result.success);
+        assert_eq!(result.result_summary, "Found 15 test files");
+        assert_eq!(result.duration_seconds, 4.5);
+    }
+
     #[test]
     fn test_to_dict() {
         let mut evidence = EvidenceCollector::new();
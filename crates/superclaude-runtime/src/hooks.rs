@@ -17,7 +17,7 @@
 //!
 //! Based on: SuperClaude/Orchestrator/hooks.py
 
-use crate::evidence::EvidenceCollector;
+use crate::evidence::{file_content_hash, EvidenceCollector, SubagentResult};
 use crate::safety::SafetyValidator;
 use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
@@ -46,6 +46,9 @@ pub struct HookInput {
     pub session_id: String,
     #[serde(default)]
     pub stop_hook_active: bool,
+    /// Session working directory; relative tool paths resolve against it.
+    #[serde(default)]
+    pub cwd: String,
 }
 
 /// Output returned by hook callbacks
@@ -199,6 +202,75 @@ pub fn create_safety_hooks() -> HookConfig {
     config
 }
 
+/// A `Task` tool call awaiting its result.
+#[derive(Debug)]
+struct TaskStart {
+    subagent_type: String,
+    description: String,
+    started: std::time::Instant,
+    /// `file_changes` length when the subagent started; `usize::MAX` if
+    /// the start was not seen
+    file_changes_before: usize,
+}
+
+impl TaskStart {
+    fn new(tool_input: &HashMap<String, Value>, file_changes_before: usize) -> Self {
+        let field = |key: &str| {
+            tool_input
+                .get(key)
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string()
+        };
+        let subagent_type = match field("subagent_type") {
+            t if t.is_empty() => "unknown".to_string(),
+            t => t,
+        };
+        Self {
+            subagent_type,
+            description: field("description"),
+            started: std::time::Instant::now(),
+            file_changes_before,
+        }
+    }
+
+    /// Build the subagent's result from its Task response. Files written or
+    /// edited since the start are attributed to it; with subagents running
+    /// in parallel, each is credited with every change made meanwhile.
+    fn finish(self, evidence: &EvidenceCollector, tool_response: &Value) -> SubagentResult {
+        let result_summary = match tool_response {
+            Value::String(text) => text.clone(),
+            other => other
+                .get("content")
+                .and_then(|c| c.as_array())
+                .map(|blocks| {
+                    blocks
+                        .iter()
+                        .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                })
+                .unwrap_or_default(),
+        };
+        let success = !tool_response
+            .get("is_error")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let mut files_modified: Vec<String> = Vec::new();
+        for change in evidence.file_changes.iter().skip(self.file_changes_before) {
+            if change.action != "read" && !files_modified.contains(&change.path) {
+                files_modified.push(change.path.clone());
+            }
+        }
+
+        SubagentResult::new(self.subagent_type, self.description)
+            .with_outcome(success, result_summary)
+            .with_files(files_modified)
+            .with_duration(self.started.elapsed().as_secs_f64())
+    }
+}
+
 /// Create evidence collection hooks.
 pub fn create_evidence_hooks(evidence: Arc<Mutex<EvidenceCollector>>) -> HookConfig {
     let mut config = HookConfig::new();
@@ -222,6 +294,19 @@ pub fn create_evidence_hooks(evidence: Arc<Mutex<EvidenceCollector>>) -> HookCon
                 return HookOutput::allow();
             }
 
+            // Hash of the file's post-tool state; skipped for large files
+            let on_disk_hash = match input.tool_name.as_str() {
+                "Edit" | "Read" => {
+                    let path = Path::new(file_path);
+                    if path.is_relative() && !input.cwd.is_empty() {
+                        file_content_hash(&Path::new(&input.cwd).join(path)).await
+                    } else {
+                        file_content_hash(path).await
+                    }
+                }
+                _ => None,
+            };
+
             let mut ev = evidence.lock().unwrap();
 
             match input.tool_name.as_str() {
@@ -232,7 +317,7 @@ pub fn create_evidence_hooks(evidence: Arc<Mutex<EvidenceCollector>>) -> HookCon
                         .and_then(|v| v.as_str())
                         .unwrap_or("");
                     let lines = content.matches('\n').count() + 1;
-                    ev.record_file_write(file_path.to_string(), lines);
+                    ev.record_file_write_with_content(file_path.to_string(), lines, content.as_bytes());
                     debug!("Recorded file write: {} ({} lines)", file_path, lines);
                 }
                 "Edit" => {
@@ -250,11 +335,17 @@ pub fn create_evidence_hooks(evidence: Arc<Mutex<EvidenceCollector>>) -> HookCon
                         (new_str.matches('\n').count() as i32 - old_str.matches('\n').count() as i32)
                             .unsigned_abs() as usize
                             + 1;
-                    ev.record_file_edit(file_path.to_string(), lines);
+                    match on_disk_hash {
+                        Some(hash) => ev.record_file_edit_with_hash(file_path.to_string(), lines, hash),
+                        None => ev.record_file_edit(file_path.to_string(), lines),
+                    }
                     debug!("Recorded file edit: {} ({} lines)", file_path, lines);
                 }
                 "Read" => {
-                    ev.record_file_read(file_path.to_string());
+                    match on_disk_hash {
+                        Some(hash) => ev.record_file_read_with_hash(file_path.to_string(), hash),
+                        None => ev.record_file_read(file_path.to_string()),
+                    }
                     debug!("Recorded file read: {}", file_path);
                 }
                 _ => {}
@@ -328,26 +419,68 @@ pub fn create_evidence_hooks(evidence: Arc<Mutex<EvidenceCollector>>) -> HookCon
         })
     });
 
-    // Hook 5: SubagentStop hook - track subagent completions
+    // Hook 5: Task start - remember what each subagent was asked to do
+    let task_starts: Arc<Mutex<HashMap<String, TaskStart>>> = Arc::default();
     let evidence_clone = Arc::clone(&evidence);
-    let on_subagent_stop: HookCallback = Box::new(move |input, tool_use_id, _context| {
+    let starts_clone = Arc::clone(&task_starts);
+    let on_task_start: HookCallback = Box::new(move |input, tool_use_id, _context| {
         let evidence = Arc::clone(&evidence_clone);
+        let starts = Arc::clone(&starts_clone);
         Box::pin(async move {
-            if input.hook_event_name != "SubagentStop" {
+            let Some(tool_use_id) = tool_use_id.filter(|_| input.hook_event_name == "PreToolUse")
+            else {
+                return HookOutput::allow();
+            };
+
+            let file_changes_before = evidence.lock().unwrap().file_changes.len();
+            starts.lock().unwrap().insert(
+                tool_use_id,
+                TaskStart::new(&input.tool_input, file_changes_before),
+            );
+
+            HookOutput::allow()
+        })
+    });
+
+    // Hook 6: Task result - record the subagent's outcome against its start
+    let evidence_clone = Arc::clone(&evidence);
+    let starts_clone = Arc::clone(&task_starts);
+    let on_task_result: HookCallback = Box::new(move |input, tool_use_id, _context| {
+        let evidence = Arc::clone(&evidence_clone);
+        let starts = Arc::clone(&starts_clone);
+        Box::pin(async move {
+            if input.hook_event_name != "PostToolUse" {
                 return HookOutput::allow();
             }
 
+            let start = tool_use_id
+                .as_ref()
+                .and_then(|id| starts.lock().unwrap().remove(id))
+                .unwrap_or_else(|| TaskStart::new(&input.tool_input, usize::MAX));
+
             let mut ev = evidence.lock().unwrap();
-            ev.subagents_spawned += 1;
-            ev.subagent_results.push(json!({
-                "tool_use_id": tool_use_id,
-                "stop_hook_active": input.stop_hook_active,
-            }));
+            let result = start.finish(&ev, &input.tool_response);
+            ev.record_subagent_result(result);
+            debug!("Recorded subagent completion: {:?}", tool_use_id);
 
             HookOutput::allow()
         })
     });
 
+    // Hook 7: SubagentStop - the result itself arrives with the Task's
+    // PostToolUse, which carries the tool id needed to correlate it
+    let starts_clone = Arc::clone(&task_starts);
+    let on_subagent_stop: HookCallback = Box::new(move |input, _tool_use_id, _context| {
+        let starts = Arc::clone(&starts_clone);
+        Box::pin(async move {
+            if input.hook_event_name == "SubagentStop" {
+                let running = starts.lock().unwrap().len();
+                debug!(running, "Subagent stopped for session: {}", input.session_id);
+            }
+            HookOutput::allow()
+        })
+    });
+
     // Register PostToolUse hooks
     config.post_tool_use.push(HookMatcher {
         matcher: Some("Write|Edit|Read".to_string()),
@@ -364,6 +497,17 @@ pub fn create_evidence_hooks(evidence: Arc<Mutex<EvidenceCollector>>) -> HookCon
         hooks: vec![track_all_tools],
     });
 
+    // Register Task start/result hooks
+    config.pre_tool_use.push(HookMatcher {
+        matcher: Some("Task".to_string()),
+        hooks: vec![on_task_start],
+    });
+
+    config.post_tool_use.push(HookMatcher {
+        matcher: Some("Task".to_string()),
+        hooks: vec![on_task_result],
+    });
+
     // Register Stop/SubagentStop hooks
     config.stop.push(on_stop);
     config.subagent_stop.push(on_subagent_stop);
@@ -474,6 +618,7 @@ mod tests {
             tool_response: Value::Null,
             session_id: String::new(),
             stop_hook_active: false,
+            cwd: String::new(),
         };
 
         let result = bash_matcher.hooks[0](input, None, HashMap::new()).await;
@@ -504,6 +649,7 @@ mod tests {
             tool_response: Value::Null,
             session_id: String::new(),
             stop_hook_active: false,
+            cwd: String::new(),
         };
 
         let result = bash_matcher.hooks[0](input, None, HashMap::new()).await;
@@ -534,6 +680,7 @@ mod tests {
             tool_response: Value::Null,
             session_id: String::new(),
             stop_hook_active: false,
+            cwd: String::new(),
         };
 
         write_matcher.hooks[0](input, None, HashMap::new()).await;
@@ -544,6 +691,35 @@ mod tests {
         assert_eq!(ev.file_changes[0].lines_changed, 3);
     }
 
+    #[tokio::test]
+    async fn test_evidence_hooks_hash_relative_read_against_session_cwd() {
+        let cwd = tempfile::TempDir::new().unwrap();
+        std::fs::write(cwd.path().join("notes.md"), "# Notes\n").unwrap();
+
+        let evidence = Arc::new(Mutex::new(EvidenceCollector::new()));
+        let config = create_evidence_hooks(Arc::clone(&evidence));
+        let matcher = config
+            .post_tool_use
+            .iter()
+            .find(|m| m.matcher.as_deref() == Some("Write|Edit|Read"))
+            .unwrap();
+
+        let input = HookInput {
+            hook_event_name: "PostToolUse".to_string(),
+            tool_name: "Read".to_string(),
+            tool_input: HashMap::from([("file_path".to_string(), json!("notes.md"))]),
+            tool_response: Value::Null,
+            session_id: String::new(),
+            stop_hook_active: false,
+            cwd: cwd.path().to_string_lossy().to_string(),
+        };
+        matcher.hooks[0](input, None, HashMap::new()).await;
+
+        let ev = evidence.lock().unwrap();
+        assert_eq!(ev.files_read, vec!["notes.md"]);
+        assert_eq!(ev.file_changes[0].content_hash, crate::evidence::content_hash(b"# Notes\n"));
+    }
+
     #[tokio::test]
     async fn test_merge_hooks() {
         let config1 = create_safety_hooks();
@@ -557,4 +733,66 @@ mod tests {
         assert!(merged.stop.len() >= 1);
         assert!(merged.subagent_stop.len() >= 1);
     }
+
+    fn task_hook_input(event: &str, tool_response: Value) -> HookInput {
+        HookInput {
+            hook_event_name: event.to_string(),
+            tool_name: "Task".to_string(),
+            tool_input: HashMap::from([
+                ("subagent_type".to_string(), json!("code-reviewer")),
+                ("description".to_string(), json!("Review the parser")),
+            ]),
+            tool_response,
+            session_id: String::new(),
+            stop_hook_active: false,
+            cwd: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_task_hooks_record_correlated_subagent_result() {
+        let evidence = Arc::new(Mutex::new(EvidenceCollector::new()));
+        evidence.lock().unwrap().record_file_write("before.rs".to_string(), 1);
+        let config = create_evidence_hooks(Arc::clone(&evidence));
+        let task_hook = |hooks: &[HookMatcher]| {
+            hooks
+                .iter()
+                .position(|m| m.matcher.as_deref() == Some("Task"))
+                .unwrap()
+        };
+        let start = &config.pre_tool_use[task_hook(&config.pre_tool_use)].hooks[0];
+        let result = &config.post_tool_use[task_hook(&config.post_tool_use)].hooks[0];
+
+        start(task_hook_input("PreToolUse", Value::Null), Some("tu-1".into()), HashMap::new()).await;
+        {
+            let mut ev = evidence.lock().unwrap();
+            ev.record_file_edit("src/parser.rs".to_string(), 4);
+            ev.record_file_read("src/lib.rs".to_string());
+            ev.record_file_edit("src/parser.rs".to_string(), 2);
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        let response = json!({"content": [{"type": "text", "text": "Found 2 issues"}]});
+        result(task_hook_input("PostToolUse", response), Some("tu-1".into()), HashMap::new()).await;
+
+        let ev = evidence.lock().unwrap();
+        assert_eq!(ev.subagents_spawned, 1);
+        let subagent = &ev.subagent_results[0];
+        assert_eq!(subagent.subagent_type, "code-reviewer");
+        assert_eq!(subagent.task_summary, "Review the parser");
+        assert_eq!(subagent.result_summary, "Found 2 issues");
+        assert!(subagent.success);
+        assert_eq!(subagent.files_modified, vec!["src/parser.rs".to_string()]);
+        assert!(subagent.duration_seconds > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_subagent_stop_does_not_record_result() {
+        let evidence = Arc::new(Mutex::new(EvidenceCollector::new()));
+        let config = create_evidence_hooks(Arc::clone(&evidence));
+
+        config.subagent_stop[0](task_hook_input("SubagentStop", Value::Null), None, HashMap::new())
+            .await;
+
+        assert_eq!(evidence.lock().unwrap().subagents_spawned, 0);
+    }
 }
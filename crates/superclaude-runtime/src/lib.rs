@@ -13,14 +13,26 @@ pub mod registry;
 pub mod obsidian;
 pub mod selector;
 pub mod loop_runner;
+pub mod pricing;
 
 // Re-export commonly used types
 pub use events::{EventsTracker, FileAction, LogLevel, QualityDimensions};
-pub use evidence::{CommandResult, EvidenceCollector, FileChange, TestResult, ToolInvocation};
-pub use safety::{DangerousPattern, PatternCategory, SafetyValidator, ValidationError};
+pub use evidence::{
+    BuildResult, CargoTestParser, CommandResult, EvidenceCollector, EvidenceFileError, FileChange,
+    GoTestParser, JestParser, LintResult, MochaParser, PytestParser, RspecParser, SubagentResult,
+    TestParser, TestResult, ToolInvocation, VitestParser,
+};
+pub use safety::{
+    AuditRecord, DangerousPattern, PatternCategory, SafetyConfig, SafetyConfigError,
+    SafetyValidator, ValidationError,
+};
 pub use hooks::{
     create_evidence_hooks, create_logging_hooks, create_safety_hooks, create_sdk_hooks,
     merge_hooks, HookCallback, HookConfig, HookInput, HookMatcher, HookOutput,
 };
-pub use quality::{assess_quality, QualityAssessment, QualityBand, QualityConfig};
+pub use quality::{
+    assess_quality, BandThresholds, QualityAssessment, QualityBand, QualityConfig,
+    QualityConfigError, QualityTrend, TrendDirection,
+};
+pub use pricing::{estimate_cost, estimate_cost_with, ModelPricing};
 pub use skills::{LearnedSkill, learn_from_session, retrieve_skills_for_task};
@@ -558,9 +558,7 @@ async fn execute_with_api(
         max_tokens: 4096,
         messages: vec![Message {
             role: Role::User,
-            content: vec![ContentBlock::Text {
-                text: prompt.to_string(),
-            }],
+            content: vec![ContentBlock::text(prompt)],
         }],
         system: None,
         metadata: None,
@@ -570,6 +568,8 @@ async fn execute_with_api(
         top_k: None,
         top_p: None,
         tools: None, // No tools for now
+        tool_choice: None,
+        thinking: None,
     };
 
     // Execute with timeout
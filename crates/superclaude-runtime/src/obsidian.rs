@@ -10,11 +10,13 @@ use serde_yaml;
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::fs;
+use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
 use walkdir::WalkDir;
 
 use crate::evidence::ToolInvocation;
+use crate::safety::SafetyValidator;
 
 // ============================================================================
 // Configuration Structures
@@ -134,12 +136,19 @@ pub struct ArtifactConfig {
     pub output_paths: HashMap<String, String>,
     #[serde(default)]
     pub backlinks: BacklinksConfig,
+    /// Handling of a decision whose slug and summary match an existing file
+    #[serde(default = "default_dedup")]
+    pub dedup: String, // "off", "skip", "update"
 }
 
 fn default_sync_on() -> String {
     "task_completion".to_string()
 }
 
+fn default_dedup() -> String {
+    "off".to_string()
+}
+
 fn default_artifact_types() -> Vec<String> {
     vec!["decisions".to_string()]
 }
@@ -157,6 +166,7 @@ impl Default for ArtifactConfig {
             types: default_artifact_types(),
             output_paths: default_output_paths(),
             backlinks: BacklinksConfig::default(),
+            dedup: default_dedup(),
         }
     }
 }
@@ -167,6 +177,10 @@ pub struct NoteConfig {
     pub format: String, // "rich", "minimal"
     #[serde(default = "default_frontmatter_include")]
     pub frontmatter_include: Vec<String>,
+    /// `{{variable}}` template for decision notes, relative to the vault.
+    /// Unset uses the built-in layout.
+    #[serde(default)]
+    pub template: Option<PathBuf>,
 }
 
 fn default_note_format() -> String {
@@ -191,6 +205,7 @@ impl Default for NoteConfig {
         Self {
             format: default_note_format(),
             frontmatter_include: default_frontmatter_include(),
+            template: None,
         }
     }
 }
@@ -305,6 +320,95 @@ impl DecisionRecord {
 
         format!("{}-{}-{}.md", date_str, slug, short_hash)
     }
+
+    /// Short SHA256 of the trimmed summary, used to spot duplicates
+    pub fn summary_hash(&self) -> String {
+        let hash = format!("{:x}", Sha256::digest(self.summary.trim().as_bytes()));
+        hash[..16].to_string()
+    }
+}
+
+/// Substitute `{{name}}` placeholders from `vars`. Fails listing every
+/// placeholder without a value; an unclosed `{{` is left as text.
+pub fn render_template(template: &str, vars: &HashMap<&str, String>) -> Result<String, String> {
+    let mut out = String::with_capacity(template.len());
+    let mut unknown: Vec<&str> = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let name = rest[start + 2..start + 2 + len].trim();
+        match vars.get(name) {
+            Some(value) => out.push_str(value),
+            None => {
+                if !unknown.contains(&name) {
+                    unknown.push(name);
+                }
+            }
+        }
+        rest = &rest[start + 2 + len + 2..];
+    }
+    out.push_str(rest);
+
+    if unknown.is_empty() {
+        Ok(out)
+    } else {
+        Err(format!("Unknown template variable(s): {}", unknown.join(", ")))
+    }
+}
+
+/// Resolve `.` and `..` components without touching the filesystem.
+fn normalize_lexically(path: &Path) -> PathBuf {
+    use std::path::Component;
+
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Canonicalize the deepest existing ancestor of `path` and re-append the
+/// missing tail, so symlinks anywhere in the existing part are resolved.
+fn canonicalize_existing(path: &Path) -> std::io::Result<PathBuf> {
+    let path = if path.is_absolute() {
+        normalize_lexically(path)
+    } else {
+        normalize_lexically(&std::env::current_dir()?.join(path))
+    };
+    let mut existing = path.as_path();
+    loop {
+        match existing.canonicalize() {
+            Ok(canonical) => {
+                let tail = path.strip_prefix(existing).unwrap_or(Path::new(""));
+                return Ok(canonical.join(tail));
+            }
+            Err(e) => match existing.parent() {
+                Some(parent) => existing = parent,
+                None => return Err(e),
+            },
+        }
+    }
+}
+
+/// Slug part of a `to_filename` name (`{date}-{slug}-{hash}.md`)
+fn decision_file_slug(path: &Path) -> Option<&str> {
+    let stem = path.file_stem()?.to_str()?;
+    stem.get(11..stem.len().checked_sub(7)?)
+}
+
+fn frontmatter_summary_hash(raw: &str) -> Option<String> {
+    let (frontmatter, _) = ObsidianVaultService::split_frontmatter(raw);
+    frontmatter.get("summary_hash")?.as_str().map(String::from)
 }
 
 // ============================================================================
@@ -313,11 +417,15 @@ impl DecisionRecord {
 
 pub struct ObsidianArtifactWriter {
     config: ObsidianConfig,
+    validator: SafetyValidator,
 }
 
 impl ObsidianArtifactWriter {
     pub fn new(config: ObsidianConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            validator: SafetyValidator::new(),
+        }
     }
 
     pub fn from_config_file(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
@@ -325,36 +433,42 @@ impl ObsidianArtifactWriter {
         Ok(Self::new(config))
     }
 
-    /// Write a decision record to the Obsidian vault
+    /// Write a decision record to the Obsidian vault.
+    ///
+    /// Blocking file I/O: from async code use
+    /// [`write_decision_async`](Self::write_decision_async) instead.
     pub fn write_decision(&self, decision: &DecisionRecord) -> Result<PathBuf, Box<dyn std::error::Error>> {
-        // Check if decisions are enabled
-        if !self.config.artifacts.types.contains(&"decisions".to_string()) {
+        let Some(output_rel) = self.decisions_dir() else {
             debug!("Decision artifacts disabled in config");
             return Err("Decision artifacts disabled".into());
-        }
-
-        // Get output path
-        let output_rel = self.config.artifacts.output_paths
-            .get("decisions")
-            .cloned()
-            .unwrap_or_else(|| "Claude/Decisions/".to_string());
-
-        let output_dir = self.config.vault.path.join(&output_rel);
+        };
+        let output_dir = self.vault_target(&output_rel)?;
 
         // Ensure output directory exists
         fs::create_dir_all(&output_dir)?;
 
+        let duplicate = self.find_duplicate_decision(&output_dir, decision);
+        if let Some(existing) = duplicate.as_ref().filter(|_| self.config.artifacts.dedup == "skip") {
+            info!("Skipped duplicate decision artifact: {}", existing.display());
+            return Ok(existing.clone());
+        }
+
         // Generate content
-        let filename = decision.to_filename();
-        let file_path = output_dir.join(&filename);
-        let content = self.generate_decision_content(decision);
+        let file_path = duplicate
+            .clone()
+            .unwrap_or_else(|| output_dir.join(decision.to_filename()));
+        let template = match self.template_path() {
+            Some(path) => Some(fs::read_to_string(path)?),
+            None => None,
+        };
+        let content = self.generate_decision_content(decision, template.as_deref())?;
 
         // Write file
         fs::write(&file_path, content)?;
-        info!("Wrote decision artifact: {}", filename);
+        info!("Wrote decision artifact: {}", file_path.display());
 
-        // Inject backlinks if enabled
-        if self.config.artifacts.backlinks.enabled {
+        // Inject backlinks if enabled; an updated file is already linked
+        if self.config.artifacts.backlinks.enabled && duplicate.is_none() {
             if let Err(e) = self.inject_backlinks(decision, &file_path) {
                 warn!("Failed to inject backlinks: {}", e);
             }
@@ -363,15 +477,185 @@ impl ObsidianArtifactWriter {
         Ok(file_path)
     }
 
-    /// Generate markdown content for a decision
-    fn generate_decision_content(&self, decision: &DecisionRecord) -> String {
+    /// Async variant of [`write_decision`](Self::write_decision) for use on a
+    /// Tokio runtime; all file I/O goes through `tokio::fs`.
+    pub async fn write_decision_async(
+        &self,
+        decision: &DecisionRecord,
+    ) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(output_rel) = self.decisions_dir() else {
+            debug!("Decision artifacts disabled in config");
+            return Err("Decision artifacts disabled".into());
+        };
+        let output_dir = self.vault_target(&output_rel)?;
+
+        tokio::fs::create_dir_all(&output_dir).await?;
+
+        let duplicate = self.find_duplicate_decision_async(&output_dir, decision).await;
+        if let Some(existing) = duplicate.as_ref().filter(|_| self.config.artifacts.dedup == "skip") {
+            info!("Skipped duplicate decision artifact: {}", existing.display());
+            return Ok(existing.clone());
+        }
+
+        let file_path = duplicate
+            .clone()
+            .unwrap_or_else(|| output_dir.join(decision.to_filename()));
+        let template = match self.template_path() {
+            Some(path) => Some(tokio::fs::read_to_string(path).await?),
+            None => None,
+        };
+        let content = self.generate_decision_content(decision, template.as_deref())?;
+        tokio::fs::write(&file_path, content).await?;
+        info!("Wrote decision artifact: {}", file_path.display());
+
+        if self.config.artifacts.backlinks.enabled && duplicate.is_none() {
+            if let Err(e) = self.inject_backlinks_async(decision, &file_path).await {
+                warn!("Failed to inject backlinks: {}", e);
+            }
+        }
+
+        Ok(file_path)
+    }
+
+    fn dedup_enabled(&self) -> bool {
+        matches!(self.config.artifacts.dedup.as_str(), "skip" | "update")
+    }
+
+    /// Existing decision in `dir` with the same slug and summary hash.
+    /// Always `None` when dedup is off.
+    fn find_duplicate_decision(&self, dir: &Path, decision: &DecisionRecord) -> Option<PathBuf> {
+        if !self.dedup_enabled() {
+            return None;
+        }
+        let slug = decision.to_slug();
+        let hash = decision.summary_hash();
+
+        let mut candidates: Vec<PathBuf> = fs::read_dir(dir)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| decision_file_slug(path) == Some(slug.as_str()))
+            .collect();
+        candidates.sort();
+        candidates.into_iter().find(|path| {
+            fs::read_to_string(path)
+                .map(|raw| frontmatter_summary_hash(&raw).as_deref() == Some(hash.as_str()))
+                .unwrap_or(false)
+        })
+    }
+
+    async fn find_duplicate_decision_async(
+        &self,
+        dir: &Path,
+        decision: &DecisionRecord,
+    ) -> Option<PathBuf> {
+        if !self.dedup_enabled() {
+            return None;
+        }
+        let slug = decision.to_slug();
+        let hash = decision.summary_hash();
+
+        let mut candidates = Vec::new();
+        let mut entries = tokio::fs::read_dir(dir).await.ok()?;
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if decision_file_slug(&path) == Some(slug.as_str()) {
+                candidates.push(path);
+            }
+        }
+        candidates.sort();
+        for path in candidates {
+            if let Ok(raw) = tokio::fs::read_to_string(&path).await {
+                if frontmatter_summary_hash(&raw).as_deref() == Some(hash.as_str()) {
+                    return Some(path);
+                }
+            }
+        }
+        None
+    }
+
+    /// Output directory for decisions relative to the vault, or `None` if
+    /// they are disabled.
+    fn decisions_dir(&self) -> Option<PathBuf> {
+        if !self.config.artifacts.types.contains(&"decisions".to_string()) {
+            return None;
+        }
+        let output_rel = self.config.artifacts.output_paths
+            .get("decisions")
+            .cloned()
+            .unwrap_or_else(|| "Claude/Decisions/".to_string());
+        Some(PathBuf::from(output_rel))
+    }
+
+    /// Resolve a vault-relative write target, rejecting paths that land
+    /// outside the vault (lexically or through a symlink) or whose resolved
+    /// location fails `SafetyValidator::validate_path`.
+    fn vault_target(&self, relative: &Path) -> Result<PathBuf, String> {
+        let vault = &self.config.vault.path;
+        let resolved = vault.join(relative);
+        let outside = |target: &Path| {
+            format!(
+                "Refusing to write outside the Obsidian vault {}: {}",
+                vault.display(),
+                target.display()
+            )
+        };
+        if !normalize_lexically(&resolved).starts_with(normalize_lexically(vault)) {
+            return Err(outside(&resolved));
+        }
+
+        let unresolvable = |e: std::io::Error| {
+            format!("Refusing to write to Obsidian path {}: {}", resolved.display(), e)
+        };
+        let canonical_vault = canonicalize_existing(vault).map_err(unresolvable)?;
+        let canonical = canonicalize_existing(&resolved).map_err(unresolvable)?;
+        if !canonical.starts_with(&canonical_vault) {
+            return Err(outside(&canonical));
+        }
+        self.validator
+            .validate_path(&canonical)
+            .map_err(|e| format!("Refusing to write to Obsidian path: {}", e))?;
+        Ok(resolved)
+    }
+
+    /// Configured note template, resolved against the vault
+    fn template_path(&self) -> Option<PathBuf> {
+        self.config.notes.template.as_ref().map(|t| self.config.vault.path.join(t))
+    }
+
+    /// Generate markdown content for a decision, from `template` when given
+    fn generate_decision_content(
+        &self,
+        decision: &DecisionRecord,
+        template: Option<&str>,
+    ) -> Result<String, String> {
+        let frontmatter = format!(
+            "---\n{}\n---",
+            serde_yaml::to_string(&self.build_frontmatter(decision)).unwrap().trim()
+        );
+
+        let Some(template) = template else {
+            return Ok(self.default_decision_content(decision, frontmatter));
+        };
+
+        let vars = HashMap::from([
+            ("title", decision.title.clone()),
+            ("summary", decision.summary.clone()),
+            ("context", decision.context.clone()),
+            ("rationale", decision.rationale.clone()),
+            ("decision_type", decision.decision_type.clone()),
+            ("frontmatter", frontmatter),
+            ("date", decision.created.format("%Y-%m-%d").to_string()),
+        ]);
+        render_template(template, &vars)
+    }
+
+    /// Built-in decision note layout
+    fn default_decision_content(&self, decision: &DecisionRecord, frontmatter: String) -> String {
         let mut lines = Vec::new();
 
         // Frontmatter
-        let frontmatter = self.build_frontmatter(decision);
-        lines.push("---".to_string());
-        lines.push(serde_yaml::to_string(&frontmatter).unwrap().trim().to_string());
-        lines.push("---".to_string());
+        lines.push(frontmatter);
         lines.push(String::new());
 
         // Title
@@ -466,6 +750,11 @@ impl ObsidianArtifactWriter {
             frontmatter.insert("related".to_string(), serde_json::json!(related));
         }
 
+        // Needed to recognize this decision when it comes up again
+        if self.dedup_enabled() {
+            frontmatter.insert("summary_hash".to_string(), serde_json::json!(decision.summary_hash()));
+        }
+
         // Add any extra metadata
         for (key, value) in &decision.metadata {
             if !frontmatter.contains_key(key) {
@@ -490,7 +779,13 @@ impl ObsidianArtifactWriter {
         let date_str = decision.created.format("%Y-%m-%d").to_string();
 
         for note_path in &decision.source_notes {
-            let full_path = vault_path.join(note_path);
+            let full_path = match self.vault_target(Path::new(note_path)) {
+                Ok(path) => path,
+                Err(e) => {
+                    warn!("Skipping backlink for {}: {}", note_path, e);
+                    continue;
+                }
+            };
             if !full_path.exists() {
                 debug!("Source note not found: {}", note_path);
                 continue;
@@ -505,6 +800,45 @@ impl ObsidianArtifactWriter {
         Ok(())
     }
 
+    async fn inject_backlinks_async(
+        &self,
+        decision: &DecisionRecord,
+        decision_path: &Path,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if decision.source_notes.is_empty() {
+            return Ok(());
+        }
+
+        let vault_path = &self.config.vault.path;
+        let section_header = &self.config.artifacts.backlinks.section;
+        let decision_relative = decision_path.strip_prefix(vault_path)?
+            .to_string_lossy()
+            .to_string();
+        let date_str = decision.created.format("%Y-%m-%d").to_string();
+        let backlink = format!("- [[{}|{}]] - {}", decision_relative, decision.title, date_str);
+
+        for note_path in &decision.source_notes {
+            let full_path = match self.vault_target(Path::new(note_path)) {
+                Ok(path) => path,
+                Err(e) => {
+                    warn!("Skipping backlink for {}: {}", note_path, e);
+                    continue;
+                }
+            };
+            if !tokio::fs::try_exists(&full_path).await.unwrap_or(false) {
+                debug!("Source note not found: {}", note_path);
+                continue;
+            }
+
+            match inject_backlink_into_file_async(&full_path, &backlink, section_header).await {
+                Ok(_) => debug!("Injected backlink into {}", note_path),
+                Err(e) => warn!("Failed to inject backlink into {}: {}", note_path, e),
+            }
+        }
+
+        Ok(())
+    }
+
     fn inject_backlink_into_file(
         &self,
         file_path: &Path,
@@ -513,40 +847,41 @@ impl ObsidianArtifactWriter {
         date_str: &str,
         section_header: &str,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let content = fs::read_to_string(file_path)?;
         let backlink = format!("- [[{}|{}]] - {}", decision_path, decision_title, date_str);
 
-        let new_content = if content.contains(section_header) {
-            // Section exists, append to it
-            let lines: Vec<&str> = content.lines().collect();
-            let mut new_lines = Vec::new();
-            let mut found_section = false;
-            let mut added = false;
-
-            for line in lines {
-                new_lines.push(line.to_string());
-                if line.trim() == section_header {
-                    found_section = true;
-                } else if found_section && !added && !line.starts_with("- ") {
-                    // Found end of list, insert before this line
-                    new_lines.insert(new_lines.len() - 1, backlink.clone());
-                    added = true;
-                    found_section = false;
+        // Scan line by line rather than loading the whole note
+        let mut scan = BacklinkScan::default();
+        let mut reader = BufReader::new(fs::File::open(file_path)?);
+        let mut line = String::new();
+        while reader.read_line(&mut line)? > 0 {
+            scan.feed(&line, section_header);
+            line.clear();
+        }
+
+        match scan.placement() {
+            BacklinkPlacement::InsertBefore(at) => {
+                // Mid-file insert: stream through a temp file, then swap it in
+                let tmp_path = backlink_temp_path(file_path);
+                let mut reader = BufReader::new(fs::File::open(file_path)?);
+                let mut writer = BufWriter::new(fs::File::create(&tmp_path)?);
+                let mut line_no = 0;
+                while reader.read_line(&mut line)? > 0 {
+                    if line_no == at {
+                        writeln!(writer, "{}", backlink)?;
+                    }
+                    writer.write_all(line.as_bytes())?;
+                    line.clear();
+                    line_no += 1;
                 }
+                writer.flush()?;
+                drop(writer);
+                fs::rename(&tmp_path, file_path)?;
             }
-
-            if !added {
-                // Add at end of file
-                new_lines.push(backlink);
+            placement => {
+                let text = scan.append_text(placement, &backlink, section_header);
+                fs::OpenOptions::new().append(true).open(file_path)?.write_all(text.as_bytes())?;
             }
-
-            new_lines.join("\n")
-        } else {
-            // Add new section at end
-            format!("{}\n\n{}\n\n{}\n", content.trim_end(), section_header, backlink)
-        };
-
-        fs::write(file_path, new_content)?;
+        }
         Ok(())
     }
 
@@ -561,6 +896,116 @@ impl ObsidianArtifactWriter {
     }
 }
 
+/// Where a backlink goes in an existing note.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BacklinkPlacement {
+    /// No backlinks section yet; append one at the end.
+    NewSection,
+    /// The section's list runs to the end of the file; append to it.
+    Append,
+    /// Insert before this zero-based line, which ends the section's list.
+    InsertBefore(usize),
+}
+
+/// Line-by-line scan of a note for its backlinks section, so large notes
+/// are never held in memory.
+#[derive(Debug, Default)]
+struct BacklinkScan {
+    lines: usize,
+    in_section: bool,
+    found_section: bool,
+    insert_at: Option<usize>,
+    ends_with_newline: bool,
+}
+
+impl BacklinkScan {
+    /// Feed the next raw line, including its line ending.
+    fn feed(&mut self, line: &str, section_header: &str) {
+        self.ends_with_newline = line.ends_with('\n');
+        if self.insert_at.is_none() {
+            let text = line.trim_end_matches(['\n', '\r']);
+            if text.trim() == section_header {
+                self.in_section = true;
+                self.found_section = true;
+            } else if self.in_section && !text.starts_with("- ") {
+                // First line past the backlink list
+                self.insert_at = Some(self.lines);
+            }
+        }
+        self.lines += 1;
+    }
+
+    fn placement(&self) -> BacklinkPlacement {
+        match (self.found_section, self.insert_at) {
+            (false, _) => BacklinkPlacement::NewSection,
+            (true, Some(line)) => BacklinkPlacement::InsertBefore(line),
+            (true, None) => BacklinkPlacement::Append,
+        }
+    }
+
+    /// Text appended to the note for `NewSection` and `Append`.
+    fn append_text(&self, placement: BacklinkPlacement, backlink: &str, section_header: &str) -> String {
+        let separator = if self.lines == 0 || self.ends_with_newline { "" } else { "\n" };
+        match placement {
+            BacklinkPlacement::NewSection => {
+                format!("{}\n{}\n\n{}\n", separator, section_header, backlink)
+            }
+            _ => format!("{}{}\n", separator, backlink),
+        }
+    }
+}
+
+/// Sibling temp file used while rewriting a note.
+fn backlink_temp_path(file_path: &Path) -> PathBuf {
+    let mut name = file_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".superclaude-tmp");
+    file_path.with_file_name(name)
+}
+
+/// Async counterpart of `ObsidianArtifactWriter::inject_backlink_into_file`.
+async fn inject_backlink_into_file_async(
+    file_path: &Path,
+    backlink: &str,
+    section_header: &str,
+) -> std::io::Result<()> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+
+    let mut scan = BacklinkScan::default();
+    let mut reader = tokio::io::BufReader::new(tokio::fs::File::open(file_path).await?);
+    let mut line = String::new();
+    while reader.read_line(&mut line).await? > 0 {
+        scan.feed(&line, section_header);
+        line.clear();
+    }
+
+    match scan.placement() {
+        BacklinkPlacement::InsertBefore(at) => {
+            let tmp_path = backlink_temp_path(file_path);
+            let mut reader = tokio::io::BufReader::new(tokio::fs::File::open(file_path).await?);
+            let mut writer = tokio::io::BufWriter::new(tokio::fs::File::create(&tmp_path).await?);
+            let mut line_no = 0;
+            while reader.read_line(&mut line).await? > 0 {
+                if line_no == at {
+                    writer.write_all(format!("{}\n", backlink).as_bytes()).await?;
+                }
+                writer.write_all(line.as_bytes()).await?;
+                line.clear();
+                line_no += 1;
+            }
+            writer.flush().await?;
+            drop(writer);
+            tokio::fs::rename(&tmp_path, file_path).await?;
+        }
+        placement => {
+            let text = scan.append_text(placement, backlink, section_header);
+            let mut file = tokio::fs::OpenOptions::new().append(true).open(file_path).await?;
+            file.write_all(text.as_bytes()).await?;
+            file.flush().await?;
+        }
+    }
+    Ok(())
+}
+
 // ============================================================================
 // Decision Extraction from Evidence
 // ============================================================================
@@ -599,6 +1044,15 @@ pub fn extract_decisions_from_evidence(
     decisions
 }
 
+/// The first `max_chars` characters of `s`; never splits a multi-byte
+/// character.
+fn truncate_chars(s: &str, max_chars: usize) -> &str {
+    match s.char_indices().nth(max_chars) {
+        Some((idx, _)) => &s[..idx],
+        None => s,
+    }
+}
+
 fn parse_consensus_decision(
     invocation: &ToolInvocation,
     project_name: &str,
@@ -611,16 +1065,11 @@ fn parse_consensus_decision(
         .or_else(|| tool_input.get("prompt"))
         .and_then(|v| v.as_str())?;
 
-    let summary = if invocation.tool_output.len() > 500 {
-        &invocation.tool_output[..500]
-    } else {
-        &invocation.tool_output
-    };
+    let summary = truncate_chars(&invocation.tool_output, 500);
 
-    let title = if question.len() > 100 {
-        format!("Consensus: {}...", &question[..100])
-    } else {
-        format!("Consensus: {}", question)
+    let title = match truncate_chars(question, 100) {
+        short if short.len() < question.len() => format!("Consensus: {}...", short),
+        _ => format!("Consensus: {}", question),
     };
 
     Some(DecisionRecord {
@@ -648,16 +1097,11 @@ fn parse_thinkdeep_decision(
         .or_else(|| tool_input.get("prompt"))
         .and_then(|v| v.as_str())?;
 
-    let summary = if invocation.tool_output.len() > 500 {
-        &invocation.tool_output[..500]
-    } else {
-        &invocation.tool_output
-    };
+    let summary = truncate_chars(&invocation.tool_output, 500);
 
-    let title = if topic.len() > 100 {
-        format!("Analysis: {}...", &topic[..100])
-    } else {
-        format!("Analysis: {}", topic)
+    let title = match truncate_chars(topic, 100) {
+        short if short.len() < topic.len() => format!("Analysis: {}...", short),
+        _ => format!("Analysis: {}", topic),
     };
 
     Some(DecisionRecord {
@@ -685,21 +1129,16 @@ fn parse_architecture_decision(
     let mut title = "Architecture Decision".to_string();
     for key in &["topic", "question", "prompt", "command"] {
         if let Some(value) = tool_input.get(*key).and_then(|v| v.as_str()) {
-            let truncated = if value.len() > 80 {
-                format!("{}...", &value[..80])
-            } else {
-                value.to_string()
+            let truncated = match truncate_chars(value, 80) {
+                short if short.len() < value.len() => format!("{}...", short),
+                _ => value.to_string(),
             };
             title = format!("Architecture: {}", truncated);
             break;
         }
     }
 
-    let summary = if invocation.tool_output.len() > 500 {
-        &invocation.tool_output[..500]
-    } else {
-        &invocation.tool_output
-    };
+    let summary = truncate_chars(&invocation.tool_output, 500);
 
     Some(DecisionRecord {
         title,
@@ -1241,6 +1680,254 @@ fn capitalize_first(s: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tempfile::TempDir;
+
+    /// Scratch vault under the workspace `target/`, since `validate_path`
+    /// rejects write targets in the system temp directory.
+    fn temp_vault() -> TempDir {
+        let scratch = normalize_lexically(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("../../target/test-vaults"),
+        );
+        fs::create_dir_all(&scratch).unwrap();
+        tempfile::Builder::new().prefix("vault").tempdir_in(scratch).unwrap()
+    }
+
+    fn writer_for(vault: &Path) -> ObsidianArtifactWriter {
+        let mut config = ObsidianConfig::default();
+        config.vault.path = vault.to_path_buf();
+        ObsidianArtifactWriter::new(config)
+    }
+
+    #[tokio::test]
+    async fn test_write_decision_async_appends_backlink() {
+        let vault = temp_vault();
+        fs::create_dir_all(vault.path().join("Knowledge")).unwrap();
+        let note = vault.path().join("Knowledge/db.md");
+        fs::write(&note, "# Databases\n\nNotes here.\n").unwrap();
+
+        let mut decision = DecisionRecord::new(
+            "Use Postgres".to_string(),
+            "Summary".to_string(),
+            "consensus".to_string(),
+            "Context".to_string(),
+            "Rationale".to_string(),
+        );
+        decision.source_notes = vec!["Knowledge/db.md".to_string()];
+
+        let path = writer_for(vault.path()).write_decision_async(&decision).await.unwrap();
+        assert!(path.starts_with(vault.path().join("Claude/Decisions")));
+        assert!(fs::read_to_string(&path).unwrap().contains("# Use Postgres"));
+
+        let relative = path.strip_prefix(vault.path()).unwrap().to_string_lossy().to_string();
+        assert_eq!(
+            fs::read_to_string(&note).unwrap(),
+            format!(
+                "# Databases\n\nNotes here.\n\n## Claude References\n\n- [[{}|Use Postgres]] - {}\n",
+                relative,
+                decision.created.format("%Y-%m-%d")
+            )
+        );
+    }
+
+    #[test]
+    fn test_duplicate_decision_written_once() {
+        let vault = temp_vault();
+        let mut config = ObsidianConfig::default();
+        config.vault.path = vault.path().to_path_buf();
+        config.artifacts.dedup = "skip".to_string();
+        let writer = ObsidianArtifactWriter::new(config);
+
+        let make = |created: &str| {
+            let mut decision = DecisionRecord::new(
+                "Use Postgres".to_string(),
+                "Postgres fits the workload".to_string(),
+                "consensus".to_string(),
+                "Context".to_string(),
+                "Rationale".to_string(),
+            );
+            decision.created = DateTime::parse_from_rfc3339(created).unwrap().with_timezone(&Utc);
+            decision
+        };
+
+        let first = writer.write_decision(&make("2024-01-15T10:30:00Z")).unwrap();
+        let second = writer.write_decision(&make("2024-01-16T09:00:00Z")).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(fs::read_dir(first.parent().unwrap()).unwrap().count(), 1);
+
+        // A different summary is a different decision
+        let mut changed = make("2024-01-16T09:00:00Z");
+        changed.summary = "SQLite is enough".to_string();
+        writer.write_decision(&changed).unwrap();
+        assert_eq!(fs::read_dir(first.parent().unwrap()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_duplicate_decision_updated_in_place() {
+        let vault = temp_vault();
+        let mut config = ObsidianConfig::default();
+        config.vault.path = vault.path().to_path_buf();
+        config.artifacts.dedup = "update".to_string();
+        let writer = ObsidianArtifactWriter::new(config);
+
+        let mut decision = DecisionRecord::new(
+            "Use Postgres".to_string(),
+            "Postgres fits the workload".to_string(),
+            "consensus".to_string(),
+            "Context".to_string(),
+            "First rationale".to_string(),
+        );
+        let first = writer.write_decision(&decision).unwrap();
+        decision.created += chrono::Duration::days(1);
+        decision.rationale = "Second rationale".to_string();
+        let second = writer.write_decision(&decision).unwrap();
+
+        assert_eq!(first, second);
+        assert!(fs::read_to_string(&second).unwrap().contains("Second rationale"));
+    }
+
+    #[test]
+    fn test_custom_decision_template() {
+        let vault = temp_vault();
+        fs::write(
+            vault.path().join("decision.tmpl.md"),
+            "{{frontmatter}}\n\n> [!tip] {{ title }} ({{date}})\n\n{{summary}}\n\nWhy: {{rationale}}\n",
+        )
+        .unwrap();
+        let mut config = ObsidianConfig::default();
+        config.vault.path = vault.path().to_path_buf();
+        config.artifacts.backlinks.enabled = false;
+        config.notes.template = Some(PathBuf::from("decision.tmpl.md"));
+        config.notes.frontmatter_include = vec!["title".to_string()];
+
+        let mut decision = DecisionRecord::new(
+            "Use Postgres".to_string(),
+            "Postgres fits the workload".to_string(),
+            "consensus".to_string(),
+            "Context".to_string(),
+            "Mature tooling".to_string(),
+        );
+        decision.created = DateTime::parse_from_rfc3339("2024-01-15T10:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let path = ObsidianArtifactWriter::new(config).write_decision(&decision).unwrap();
+        assert_eq!(
+            fs::read_to_string(path).unwrap(),
+            "---\ntitle: Use Postgres\n---\n\n> [!tip] Use Postgres (2024-01-15)\n\n\
+             Postgres fits the workload\n\nWhy: Mature tooling\n"
+        );
+    }
+
+    #[test]
+    fn test_template_rejects_unknown_variables() {
+        let vars = HashMap::from([("title", "T".to_string())]);
+        assert_eq!(render_template("# {{title}} {{", &vars).unwrap(), "# T {{");
+        assert_eq!(
+            render_template("{{title}} {{owner}} {{ owner }} {{status}}", &vars).unwrap_err(),
+            "Unknown template variable(s): owner, status"
+        );
+    }
+
+    #[test]
+    fn test_traversal_in_output_path_is_rejected() {
+        let vault = temp_vault();
+        let decision = DecisionRecord::new(
+            "Escape".to_string(),
+            "Summary".to_string(),
+            "technical".to_string(),
+            String::new(),
+            String::new(),
+        );
+
+        for output in ["../outside/", "Claude/../../outside", "/etc/cron.d/"] {
+            let mut config = ObsidianConfig::default();
+            config.vault.path = vault.path().join("vault");
+            config.artifacts.output_paths.insert("decisions".to_string(), output.to_string());
+
+            let err = ObsidianArtifactWriter::new(config).write_decision(&decision).unwrap_err();
+            assert!(err.to_string().starts_with("Refusing to write"), "{output}: {err}");
+        }
+        assert!(!vault.path().join("outside").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_out_of_vault_is_rejected() {
+        let root = temp_vault();
+        let vault = root.path().join("vault");
+        let outside = root.path().join("outside");
+        fs::create_dir_all(&vault).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, vault.join("Claude")).unwrap();
+
+        let decision = DecisionRecord::new(
+            "Escape".to_string(),
+            "Summary".to_string(),
+            "technical".to_string(),
+            String::new(),
+            String::new(),
+        );
+        let err = writer_for(&vault).write_decision(&decision).unwrap_err();
+        assert!(
+            err.to_string().starts_with("Refusing to write outside the Obsidian vault"),
+            "{err}"
+        );
+        assert_eq!(fs::read_dir(&outside).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_nested_output_path_inside_vault_is_allowed() {
+        let vault = temp_vault();
+        let mut config = ObsidianConfig::default();
+        config.vault.path = vault.path().to_path_buf();
+        config.artifacts.output_paths
+            .insert("decisions".to_string(), "Projects/./Decisions/".to_string());
+
+        let decision = DecisionRecord::new(
+            "Stay Inside".to_string(),
+            "Summary".to_string(),
+            "technical".to_string(),
+            String::new(),
+            String::new(),
+        );
+        let path = ObsidianArtifactWriter::new(config).write_decision(&decision).unwrap();
+        assert!(path.starts_with(vault.path().join("Projects")));
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_backlink_inserted_at_end_of_existing_section() {
+        let vault = temp_vault();
+        let note = vault.path().join("note.md");
+        fs::write(
+            &note,
+            "# Note\n\n## Claude References\n- [[a|A]] - 2024-01-01\n\n## Later\nText\n",
+        )
+        .unwrap();
+
+        writer_for(vault.path())
+            .inject_backlink_into_file(&note, "b", "B", "2024-01-02", "## Claude References")
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&note).unwrap(),
+            "# Note\n\n## Claude References\n- [[a|A]] - 2024-01-01\n- [[b|B]] - 2024-01-02\n\n## Later\nText\n"
+        );
+        assert!(!backlink_temp_path(&note).exists());
+    }
+
+    #[test]
+    fn test_backlink_appended_when_section_ends_file() {
+        let mut scan = BacklinkScan::default();
+        for line in ["# Note\n", "## Claude References\n", "- [[a|A]] - 2024-01-01"] {
+            scan.feed(line, "## Claude References");
+        }
+        assert_eq!(scan.placement(), BacklinkPlacement::Append);
+        assert_eq!(
+            scan.append_text(BacklinkPlacement::Append, "- [[b|B]]", "## Claude References"),
+            "\n- [[b|B]]\n"
+        );
+    }
 
     #[test]
     fn test_decision_to_slug() {
@@ -1296,6 +1983,29 @@ mod tests {
         assert!(decisions[0].title.contains("Should we use Rust"));
     }
 
+    #[test]
+    fn test_extract_decision_truncates_non_ascii_on_char_boundary() {
+        // 'é' is two bytes, so byte offsets 100/500 fall mid-character
+        let question = format!("a{}", "é".repeat(150));
+        let invocation = ToolInvocation {
+            tool_name: "mcp__pal__consensus".to_string(),
+            tool_input: serde_json::json!({ "question": question }),
+            tool_output: "日本語".repeat(300),
+            timestamp: Utc::now().to_rfc3339(),
+        };
+
+        let decisions = extract_decisions_from_evidence(&[invocation], "TestProject", "s");
+
+        assert_eq!(decisions.len(), 1);
+        let title = decisions[0].title.strip_prefix("Consensus: ").unwrap();
+        assert_eq!(title.chars().count(), 100 + 3);
+        assert!(title.ends_with("é..."));
+        assert_eq!(decisions[0].summary.chars().count(), 500);
+
+        assert_eq!(truncate_chars("añb", 2), "añ");
+        assert_eq!(truncate_chars("añb", 5), "añb");
+    }
+
     #[test]
     fn test_extract_thinkdeep_decision() {
         let invocation = ToolInvocation {
@@ -0,0 +1,175 @@
+//! Model pricing and cost estimation
+//!
+//! Estimates USD spend from API `Usage` so executions can be costed on the
+//! Rust side instead of relying only on `total_cost_usd` from the CLI stream.
+//!
+//! Models are matched by ID prefix (longest wins), so dated IDs such as
+//! `claude-sonnet-4-20250514` resolve to the `claude-sonnet-4` entry.
+
+use std::collections::HashMap;
+
+use crate::api::Usage;
+
+/// Per-model rates in USD per million tokens
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPricing {
+    pub input_per_mtok: f64,
+    pub output_per_mtok: f64,
+    /// Rate for tokens written to the prompt cache
+    pub cache_write_per_mtok: f64,
+    /// Rate for tokens read from the prompt cache
+    pub cache_read_per_mtok: f64,
+}
+
+impl ModelPricing {
+    /// Pricing with the standard cache multipliers (writes 1.25x input,
+    /// reads 0.1x input)
+    pub fn new(input_per_mtok: f64, output_per_mtok: f64) -> Self {
+        Self::with_cache_rates(
+            input_per_mtok,
+            output_per_mtok,
+            input_per_mtok * 1.25,
+            input_per_mtok * 0.1,
+        )
+    }
+
+    pub const fn with_cache_rates(
+        input_per_mtok: f64,
+        output_per_mtok: f64,
+        cache_write_per_mtok: f64,
+        cache_read_per_mtok: f64,
+    ) -> Self {
+        Self {
+            input_per_mtok,
+            output_per_mtok,
+            cache_write_per_mtok,
+            cache_read_per_mtok,
+        }
+    }
+
+    /// Cost of a single request's usage
+    pub fn cost(&self, usage: &Usage) -> f64 {
+        let per_token = |tokens: u32, rate: f64| tokens as f64 * rate / 1_000_000.0;
+
+        per_token(usage.input_tokens, self.input_per_mtok)
+            + per_token(usage.output_tokens, self.output_per_mtok)
+            + per_token(
+                usage.cache_creation_input_tokens.unwrap_or(0),
+                self.cache_write_per_mtok,
+            )
+            + per_token(
+                usage.cache_read_input_tokens.unwrap_or(0),
+                self.cache_read_per_mtok,
+            )
+    }
+}
+
+/// Built-in price table, keyed by model ID prefix
+pub const DEFAULT_PRICES: &[(&str, ModelPricing)] = &[
+    ("claude-opus-4-5", ModelPricing::with_cache_rates(5.0, 25.0, 6.25, 0.5)),
+    ("claude-opus-4", ModelPricing::with_cache_rates(15.0, 75.0, 18.75, 1.5)),
+    ("claude-3-opus", ModelPricing::with_cache_rates(15.0, 75.0, 18.75, 1.5)),
+    ("claude-sonnet-4", ModelPricing::with_cache_rates(3.0, 15.0, 3.75, 0.3)),
+    ("claude-3-7-sonnet", ModelPricing::with_cache_rates(3.0, 15.0, 3.75, 0.3)),
+    ("claude-3-5-sonnet", ModelPricing::with_cache_rates(3.0, 15.0, 3.75, 0.3)),
+    ("claude-haiku-4-5", ModelPricing::with_cache_rates(1.0, 5.0, 1.25, 0.1)),
+    ("claude-3-5-haiku", ModelPricing::with_cache_rates(0.8, 4.0, 1.0, 0.08)),
+    ("claude-3-haiku", ModelPricing::with_cache_rates(0.25, 1.25, 0.3, 0.03)),
+];
+
+/// Find the entry whose key is the longest prefix of `model`
+fn longest_prefix_match<'a>(
+    model: &str,
+    entries: impl Iterator<Item = (&'a str, &'a ModelPricing)>,
+) -> Option<&'a ModelPricing> {
+    entries
+        .filter(|(prefix, _)| model.starts_with(prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, pricing)| pricing)
+}
+
+/// Look up pricing for a model, checking `overrides` before the built-in table
+pub fn pricing_for<'a>(
+    model: &str,
+    overrides: &'a HashMap<String, ModelPricing>,
+) -> Option<&'a ModelPricing> {
+    longest_prefix_match(model, overrides.iter().map(|(k, v)| (k.as_str(), v))).or_else(|| {
+        longest_prefix_match(model, DEFAULT_PRICES.iter().map(|(k, v)| (*k, v)))
+    })
+}
+
+/// Estimated USD cost of `usage` on `model`, or `None` for unknown models
+pub fn estimate_cost(model: &str, usage: &Usage) -> Option<f64> {
+    estimate_cost_with(model, usage, &HashMap::new())
+}
+
+/// Like `estimate_cost`, with per-deployment rates taking precedence over
+/// the built-in table
+pub fn estimate_cost_with(
+    model: &str,
+    usage: &Usage,
+    overrides: &HashMap<String, ModelPricing>,
+) -> Option<f64> {
+    pricing_for(model, overrides).map(|pricing| pricing.cost(usage))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(input: u32, output: u32) -> Usage {
+        Usage {
+            input_tokens: input,
+            output_tokens: output,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_estimate_cost_known_model() {
+        // 1M input @ $3 + 100k output @ $15
+        let cost = estimate_cost("claude-sonnet-4-20250514", &usage(1_000_000, 100_000)).unwrap();
+        assert!((cost - 4.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_estimate_cost_includes_cache_tokens() {
+        let usage = Usage {
+            input_tokens: 1_000,
+            output_tokens: 0,
+            cache_creation_input_tokens: Some(100_000),
+            cache_read_input_tokens: Some(1_000_000),
+        };
+        // 1k @ $3 + 100k @ $3.75 + 1M @ $0.30
+        let cost = estimate_cost("claude-sonnet-4-5", &usage).unwrap();
+        assert!((cost - (0.003 + 0.375 + 0.3)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_longest_prefix_wins() {
+        let opus_45 = estimate_cost("claude-opus-4-5-20251101", &usage(1_000_000, 0)).unwrap();
+        let opus_41 = estimate_cost("claude-opus-4-1-20250805", &usage(1_000_000, 0)).unwrap();
+        assert!((opus_45 - 5.0).abs() < 1e-9);
+        assert!((opus_41 - 15.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_unknown_model_returns_none() {
+        assert!(estimate_cost("gpt-4o", &usage(10, 10)).is_none());
+    }
+
+    #[test]
+    fn test_overrides_take_precedence() {
+        let mut overrides = HashMap::new();
+        overrides.insert("claude-sonnet-4".to_string(), ModelPricing::new(1.0, 2.0));
+        overrides.insert("local-model".to_string(), ModelPricing::new(0.0, 0.0));
+
+        let cost =
+            estimate_cost_with("claude-sonnet-4-20250514", &usage(1_000_000, 1_000_000), &overrides)
+                .unwrap();
+        assert!((cost - 3.0).abs() < 1e-9);
+        assert_eq!(estimate_cost_with("local-model", &usage(5, 5), &overrides), Some(0.0));
+        // Models not overridden still use the built-in table
+        assert!(estimate_cost_with("claude-3-5-haiku-20241022", &usage(1, 1), &overrides).is_some());
+    }
+}
@@ -9,12 +9,21 @@
 //! - Test Results: Did tests pass?
 //! - Code Coverage: Is coverage sufficient?
 //! - Build Status: Does the build pass?
+//! - Lint: Is the code free of linter warnings?
 
 use crate::evidence::EvidenceCollector;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use tracing::warn;
+
+/// Allowed deviation of the weight sum from 1.0.
+const WEIGHT_SUM_EPSILON: f64 = 1e-3;
 
 /// Quality score bands for categorization.
+///
+/// The default cutoffs are listed below; see [`BandThresholds`] to change them.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum QualityBand {
@@ -31,19 +40,9 @@ pub enum QualityBand {
 }
 
 impl QualityBand {
-    /// Convert score to quality band.
+    /// Convert score to quality band using the default thresholds.
     pub fn from_score(score: f64) -> Self {
-        if score >= 90.0 {
-            QualityBand::Excellent
-        } else if score >= 70.0 {
-            QualityBand::Good
-        } else if score >= 50.0 {
-            QualityBand::Acceptable
-        } else if score >= 30.0 {
-            QualityBand::NeedsWork
-        } else {
-            QualityBand::Poor
-        }
+        BandThresholds::default().band_for(score)
     }
 
     /// Get string value for the band.
@@ -58,6 +57,53 @@ impl QualityBand {
     }
 }
 
+/// Minimum score for each quality band. Anything below `needs_work` is
+/// [`QualityBand::Poor`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BandThresholds {
+    pub excellent: f64,
+    pub good: f64,
+    pub acceptable: f64,
+    pub needs_work: f64,
+}
+
+impl Default for BandThresholds {
+    fn default() -> Self {
+        Self {
+            excellent: 90.0,
+            good: 70.0,
+            acceptable: 50.0,
+            needs_work: 30.0,
+        }
+    }
+}
+
+impl BandThresholds {
+    /// Band for `score` under these thresholds.
+    pub fn band_for(&self, score: f64) -> QualityBand {
+        if score >= self.excellent {
+            QualityBand::Excellent
+        } else if score >= self.good {
+            QualityBand::Good
+        } else if score >= self.acceptable {
+            QualityBand::Acceptable
+        } else if score >= self.needs_work {
+            QualityBand::NeedsWork
+        } else {
+            QualityBand::Poor
+        }
+    }
+
+    /// Thresholds must lie within 0-100 and be non-increasing from
+    /// `excellent` down to `needs_work`.
+    fn is_ordered(&self) -> bool {
+        let cutoffs = [self.excellent, self.good, self.acceptable, self.needs_work];
+        cutoffs.iter().all(|c| (0.0..=100.0).contains(c))
+            && cutoffs.windows(2).all(|pair| pair[0] >= pair[1])
+    }
+}
+
 /// Quality assessment result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QualityAssessment {
@@ -75,8 +121,8 @@ pub struct QualityAssessment {
 
 impl QualityAssessment {
     /// Create assessment from a score.
-    pub fn from_score(score: f64, threshold: f64) -> Self {
-        let band = QualityBand::from_score(score);
+    pub fn from_score(score: f64, threshold: f64, bands: &BandThresholds) -> Self {
+        let band = bands.band_for(score);
         Self {
             score,
             passed: score >= threshold,
@@ -87,8 +133,34 @@ impl QualityAssessment {
     }
 }
 
+/// Errors from loading or validating a [`QualityConfig`].
+#[derive(Debug, thiserror::Error)]
+pub enum QualityConfigError {
+    #[error("Failed to read quality config: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to parse quality config: {0}")]
+    Parse(#[from] serde_yaml::Error),
+
+    #[error("Failed to parse TOML quality config: {0}")]
+    ParseToml(#[from] toml::de::Error),
+
+    #[error("Quality weight {name} is negative: {value}")]
+    NegativeWeight { name: &'static str, value: f64 },
+
+    #[error("Quality weights must sum to 1.0 (got {sum:.3})")]
+    WeightSum { sum: f64 },
+
+    #[error("Quality threshold {name} is out of range: {value}")]
+    ThresholdOutOfRange { name: &'static str, value: f64 },
+
+    #[error("Band thresholds must be within 0-100 and descend from excellent to needs_work: {0:?}")]
+    BandOrder(BandThresholds),
+}
+
 /// Configuration for quality assessment.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct QualityConfig {
     // Dimension weights (must sum to 1.0)
     pub weight_code_changes: f64,
@@ -96,6 +168,8 @@ pub struct QualityConfig {
     pub weight_tests_pass: f64,
     pub weight_coverage: f64,
     pub weight_no_errors: f64,
+    pub weight_build: f64,
+    pub weight_lint: f64,
 
     // Thresholds
     pub min_coverage: f64,       // Minimum coverage percentage
@@ -103,20 +177,143 @@ pub struct QualityConfig {
 
     // Scoring
     pub max_score: f64,
+
+    // Score cutoffs for each quality band
+    pub bands: BandThresholds,
 }
 
 impl Default for QualityConfig {
     fn default() -> Self {
         Self {
-            weight_code_changes: 0.30,
-            weight_tests_run: 0.25,
-            weight_tests_pass: 0.25,
+            weight_code_changes: 0.20,
+            weight_tests_run: 0.20,
+            weight_tests_pass: 0.20,
             weight_coverage: 0.10,
             weight_no_errors: 0.10,
+            weight_build: 0.10,
+            weight_lint: 0.10,
             min_coverage: 80.0,
             quality_threshold: 70.0,
             max_score: 100.0,
+            bands: BandThresholds::default(),
+        }
+    }
+}
+
+impl QualityConfig {
+    pub fn from_yaml(yaml_str: &str) -> Result<Self, QualityConfigError> {
+        Ok(serde_yaml::from_str(yaml_str)?)
+    }
+
+    pub fn from_toml(toml_str: &str) -> Result<Self, QualityConfigError> {
+        Ok(toml::from_str(toml_str)?)
+    }
+
+    /// Load and validate a config file. `.toml` files are parsed as TOML,
+    /// anything else as YAML (which also accepts JSON).
+    pub fn from_file(path: &Path) -> Result<Self, QualityConfigError> {
+        let contents = fs::read_to_string(path)?;
+        let is_toml = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+        let config = if is_toml {
+            Self::from_toml(&contents)?
+        } else {
+            Self::from_yaml(&contents)?
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn weights(&self) -> [(&'static str, f64); 7] {
+        [
+            ("weight_code_changes", self.weight_code_changes),
+            ("weight_tests_run", self.weight_tests_run),
+            ("weight_tests_pass", self.weight_tests_pass),
+            ("weight_coverage", self.weight_coverage),
+            ("weight_no_errors", self.weight_no_errors),
+            ("weight_build", self.weight_build),
+            ("weight_lint", self.weight_lint),
+        ]
+    }
+
+    /// Sum of the seven dimension weights.
+    pub fn weight_sum(&self) -> f64 {
+        self.weights().iter().map(|(_, w)| w).sum()
+    }
+
+    /// Check that every weight is non-negative and that they sum to 1.0,
+    /// that the score thresholds are within range, and that the band
+    /// thresholds are ordered.
+    ///
+    /// Weights that don't sum to 1.0 silently cap (or inflate) the maximum
+    /// reachable score, so this is enforced when loading from a file.
+    pub fn validate(&self) -> Result<(), QualityConfigError> {
+        if self.max_score.is_nan() || self.max_score <= 0.0 {
+            return Err(QualityConfigError::ThresholdOutOfRange {
+                name: "max_score",
+                value: self.max_score,
+            });
         }
+        let thresholds = [
+            ("min_coverage", self.min_coverage, 100.0),
+            ("quality_threshold", self.quality_threshold, self.max_score),
+        ];
+        if let Some((name, value, _)) = thresholds
+            .into_iter()
+            .find(|(_, value, max)| !(0.0..=*max).contains(value))
+        {
+            return Err(QualityConfigError::ThresholdOutOfRange { name, value });
+        }
+        if !self.bands.is_ordered() {
+            return Err(QualityConfigError::BandOrder(self.bands));
+        }
+        if let Some((name, value)) = self.weights().into_iter().find(|(_, w)| *w < 0.0) {
+            return Err(QualityConfigError::NegativeWeight { name, value });
+        }
+        let sum = self.weight_sum();
+        if (sum - 1.0).abs() > WEIGHT_SUM_EPSILON {
+            return Err(QualityConfigError::WeightSum { sum });
+        }
+        Ok(())
+    }
+
+    /// Copy of this config with weights rescaled to sum to 1.0, for callers
+    /// that prefer leniency over [`validate`](Self::validate).
+    ///
+    /// Negative weights are clamped to zero first. If nothing positive is
+    /// left, the default weights are used.
+    pub fn normalized(&self) -> Self {
+        let mut config = self.clone();
+        config.weight_code_changes = config.weight_code_changes.max(0.0);
+        config.weight_tests_run = config.weight_tests_run.max(0.0);
+        config.weight_tests_pass = config.weight_tests_pass.max(0.0);
+        config.weight_coverage = config.weight_coverage.max(0.0);
+        config.weight_no_errors = config.weight_no_errors.max(0.0);
+        config.weight_build = config.weight_build.max(0.0);
+        config.weight_lint = config.weight_lint.max(0.0);
+
+        let sum = config.weight_sum();
+        if sum <= 0.0 {
+            let defaults = Self::default();
+            config.weight_code_changes = defaults.weight_code_changes;
+            config.weight_tests_run = defaults.weight_tests_run;
+            config.weight_tests_pass = defaults.weight_tests_pass;
+            config.weight_coverage = defaults.weight_coverage;
+            config.weight_no_errors = defaults.weight_no_errors;
+            config.weight_build = defaults.weight_build;
+            config.weight_lint = defaults.weight_lint;
+            return config;
+        }
+
+        config.weight_code_changes /= sum;
+        config.weight_tests_run /= sum;
+        config.weight_tests_pass /= sum;
+        config.weight_coverage /= sum;
+        config.weight_no_errors /= sum;
+        config.weight_build /= sum;
+        config.weight_lint /= sum;
+        config
     }
 }
 
@@ -136,11 +333,20 @@ pub fn assess_quality(
 
     let config = config.unwrap_or(&DEFAULT_CONFIG);
 
+    let weight_sum = config.weight_sum();
+    if (weight_sum - 1.0).abs() > WEIGHT_SUM_EPSILON {
+        warn!(
+            "Quality weights sum to {:.3} instead of 1.0; scores will be skewed \
+             (use QualityConfig::validate or normalized)",
+            weight_sum
+        );
+    }
+
     let mut score = 0.0;
     let mut improvements = Vec::new();
     let mut dimension_scores = HashMap::new();
 
-    // Dimension 1: Code Changes (30%)
+    // Dimension 1: Code Changes (20%)
     let code_change_score = score_code_changes(evidence);
     dimension_scores.insert("code_changes".to_string(), code_change_score);
     score += code_change_score * config.weight_code_changes;
@@ -151,7 +357,7 @@ pub fn assess_quality(
         }
     }
 
-    // Dimension 2: Tests Run (25%)
+    // Dimension 2: Tests Run (20%)
     let tests_run_score = score_tests_run(evidence);
     dimension_scores.insert("tests_run".to_string(), tests_run_score);
     score += tests_run_score * config.weight_tests_run;
@@ -160,7 +366,7 @@ pub fn assess_quality(
         improvements.push("Run tests to verify changes work correctly".to_string());
     }
 
-    // Dimension 3: Tests Pass (25%)
+    // Dimension 3: Tests Pass (20%)
     let tests_pass_score = score_tests_pass(evidence);
     dimension_scores.insert("tests_pass".to_string(), tests_pass_score);
     score += tests_pass_score * config.weight_tests_pass;
@@ -193,6 +399,28 @@ pub fn assess_quality(
         improvements.push("Fix errors in test or command output".to_string());
     }
 
+    // Dimension 6: Build (10%)
+    let build_score = score_build(evidence);
+    dimension_scores.insert("build".to_string(), build_score);
+    score += build_score * config.weight_build;
+
+    if let Some(build) = evidence.builds.last().filter(|b| !b.success) {
+        improvements.push(format!("Fix the failing build ({})", build.command));
+    }
+
+    // Dimension 7: Lint (10%)
+    let lint_score = score_lint(evidence);
+    dimension_scores.insert("lint".to_string(), lint_score);
+    score += lint_score * config.weight_lint;
+
+    if lint_score < 100.0 && !evidence.lint_results.is_empty() {
+        improvements.push(format!(
+            "Resolve {} lint warning(s) and {} lint error(s)",
+            evidence.total_lint_warnings(),
+            evidence.total_lint_errors()
+        ));
+    }
+
     // Apply caps for critical failures
     if evidence.tests_run && evidence.total_tests_failed() > evidence.total_tests_passed() {
         // More failing than passing = cap at 40
@@ -209,7 +437,7 @@ pub fn assess_quality(
     QualityAssessment {
         score,
         passed: score >= config.quality_threshold,
-        band: QualityBand::from_score(score),
+        band: config.bands.band_for(score),
         improvements_needed: improvements,
         dimension_scores,
     }
@@ -291,6 +519,28 @@ fn get_average_coverage(evidence: &EvidenceCollector) -> f64 {
     }
 }
 
+/// Score based on the most recent build, so a later successful build
+/// clears an earlier failure.
+fn score_build(evidence: &EvidenceCollector) -> f64 {
+    match evidence.last_build_succeeded() {
+        Some(true) => 100.0,
+        Some(false) => 0.0,
+        None => 50.0, // Neutral if nothing was built
+    }
+}
+
+/// Score based on lint findings per modified file. Clean output scores
+/// 100 and each finding per file (errors count five times) costs 10 points.
+fn score_lint(evidence: &EvidenceCollector) -> f64 {
+    if evidence.lint_results.is_empty() {
+        return 50.0; // Neutral if no linter ran
+    }
+
+    let weighted = evidence.total_lint_warnings() as f64 + evidence.total_lint_errors() as f64 * 5.0;
+    let files = evidence.total_files_modified().max(1) as f64;
+    (100.0 - weighted / files * 10.0).max(0.0)
+}
+
 /// Score based on absence of errors.
 fn score_no_errors(evidence: &EvidenceCollector) -> f64 {
     // Check for errors in test results
@@ -343,10 +593,63 @@ pub fn compare_assessments(
     }
 }
 
+/// Direction of quality across iterations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrendDirection {
+    Improving,
+    Plateau,
+    Regressing,
+}
+
+/// Quality trend over an ordered series of iteration scores.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityTrend {
+    /// Direction of the most recent change
+    pub direction: TrendDirection,
+    /// Score change from each iteration to the next
+    pub deltas: Vec<f64>,
+}
+
+impl QualityTrend {
+    /// Classify the latest score change: a gain of at least
+    /// `min_improvement` is improving, a drop of at least as much is
+    /// regressing, and anything smaller is a plateau. Fewer than two scores
+    /// report a plateau with no deltas.
+    pub fn from_scores(scores: &[f64], min_improvement: f64) -> Self {
+        let deltas: Vec<f64> = scores.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        let threshold = min_improvement.max(0.0);
+
+        let direction = match deltas.last() {
+            Some(&delta) if delta > 0.0 && delta >= threshold => TrendDirection::Improving,
+            Some(&delta) if delta < 0.0 && -delta >= threshold => TrendDirection::Regressing,
+            _ => TrendDirection::Plateau,
+        };
+
+        Self { direction, deltas }
+    }
+
+    /// Same as [`from_scores`](Self::from_scores) over assessment scores.
+    pub fn from_assessments(assessments: &[QualityAssessment], min_improvement: f64) -> Self {
+        let scores: Vec<f64> = assessments.iter().map(|a| a.score).collect();
+        Self::from_scores(&scores, min_improvement)
+    }
+
+    /// Change between the last two iterations.
+    pub fn latest_delta(&self) -> Option<f64> {
+        self.deltas.last().copied()
+    }
+
+    /// True when further iterations are not paying off.
+    pub fn should_stop(&self) -> bool {
+        !self.deltas.is_empty() && self.direction != TrendDirection::Improving
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::evidence::{TestResult, CommandResult};
+    use crate::evidence::{BuildResult, TestResult, CommandResult};
 
     #[test]
     fn test_quality_band_from_score() {
@@ -368,7 +671,7 @@ mod tests {
 
     #[test]
     fn test_quality_assessment_from_score() {
-        let assessment = QualityAssessment::from_score(95.0, 70.0);
+        let assessment = QualityAssessment::from_score(95.0, 70.0, &BandThresholds::default());
         assert_eq!(assessment.score, 95.0);
         assert!(assessment.passed);
         assert_eq!(assessment.band, QualityBand::Excellent);
@@ -376,7 +679,7 @@ mod tests {
 
     #[test]
     fn test_quality_assessment_from_score_below_threshold() {
-        let assessment = QualityAssessment::from_score(60.0, 70.0);
+        let assessment = QualityAssessment::from_score(60.0, 70.0, &BandThresholds::default());
         assert_eq!(assessment.score, 60.0);
         assert!(!assessment.passed);
         assert_eq!(assessment.band, QualityBand::Acceptable);
@@ -389,10 +692,179 @@ mod tests {
             + config.weight_tests_run
             + config.weight_tests_pass
             + config.weight_coverage
-            + config.weight_no_errors;
+            + config.weight_no_errors
+            + config.weight_build
+            + config.weight_lint;
         assert!((total - 1.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_custom_band_thresholds() {
+        let strict = BandThresholds {
+            excellent: 95.0,
+            good: 80.0,
+            ..BandThresholds::default()
+        };
+        assert_eq!(strict.band_for(75.0), QualityBand::Acceptable);
+        assert_eq!(strict.band_for(80.0), QualityBand::Good);
+        assert_eq!(strict.band_for(92.0), QualityBand::Good);
+
+        let assessment = QualityAssessment::from_score(75.0, 70.0, &strict);
+        assert!(assessment.passed);
+        assert_eq!(assessment.band, QualityBand::Acceptable);
+    }
+
+    #[test]
+    fn test_assess_quality_uses_config_bands() {
+        let mut evidence = EvidenceCollector::default();
+        evidence.files_written.push("test.py".to_string());
+
+        let default_band = assess_quality(&evidence, None).band;
+        let config = QualityConfig {
+            bands: BandThresholds {
+                excellent: 0.0,
+                good: 0.0,
+                acceptable: 0.0,
+                needs_work: 0.0,
+            },
+            ..QualityConfig::default()
+        };
+        assert_ne!(default_band, QualityBand::Excellent);
+        assert_eq!(assess_quality(&evidence, Some(&config)).band, QualityBand::Excellent);
+    }
+
+    #[test]
+    fn test_validate_rejects_unordered_bands() {
+        let config = QualityConfig {
+            bands: BandThresholds {
+                good: 95.0,
+                ..BandThresholds::default()
+            },
+            ..QualityConfig::default()
+        };
+        assert!(matches!(config.validate(), Err(QualityConfigError::BandOrder(_))));
+    }
+
+    #[test]
+    fn test_validate_accepts_default() {
+        assert!(QualityConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_weight_sum() {
+        let config = QualityConfig {
+            weight_code_changes: 0.13,
+            ..QualityConfig::default()
+        };
+        match config.validate() {
+            Err(QualityConfigError::WeightSum { sum }) => assert!((sum - 0.93).abs() < 1e-9),
+            other => panic!("expected WeightSum error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_negative_weight() {
+        let config = QualityConfig {
+            weight_coverage: -0.1,
+            weight_no_errors: 0.3,
+            ..QualityConfig::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(QualityConfigError::NegativeWeight { name: "weight_coverage", .. })
+        ));
+    }
+
+    #[test]
+    fn test_normalized_rescales_weights() {
+        let config = QualityConfig {
+            weight_code_changes: 2.0,
+            weight_tests_run: 2.0,
+            weight_tests_pass: 2.0,
+            weight_coverage: 1.0,
+            weight_no_errors: 1.0,
+            weight_build: 1.0,
+            weight_lint: 1.0,
+            ..QualityConfig::default()
+        };
+        let normalized = config.normalized();
+        assert!(normalized.validate().is_ok());
+        assert!((normalized.weight_code_changes - 0.20).abs() < 1e-9);
+        assert!((normalized.weight_coverage - 0.10).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_normalized_all_zero_falls_back_to_defaults() {
+        let config = QualityConfig {
+            weight_code_changes: 0.0,
+            weight_tests_run: 0.0,
+            weight_tests_pass: 0.0,
+            weight_coverage: 0.0,
+            weight_no_errors: 0.0,
+            weight_build: 0.0,
+            weight_lint: 0.0,
+            ..QualityConfig::default()
+        };
+        let normalized = config.normalized();
+        assert_eq!(normalized.weight_code_changes, 0.20);
+        assert!(normalized.validate().is_ok());
+    }
+
+    #[test]
+    fn test_from_yaml_partial_uses_defaults() {
+        let config = QualityConfig::from_yaml("quality_threshold: 85.0\n").unwrap();
+        assert_eq!(config.quality_threshold, 85.0);
+        assert_eq!(config.weight_tests_run, 0.20);
+    }
+
+    #[test]
+    fn test_from_file_valid_toml() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("quality.toml");
+        fs::write(
+            &path,
+            "weight_code_changes = 0.30\nweight_tests_run = 0.10\nquality_threshold = 80.0\n\n\
+             [bands]\nexcellent = 95.0\n",
+        )
+        .unwrap();
+
+        let config = QualityConfig::from_file(&path).unwrap();
+        assert_eq!(config.weight_code_changes, 0.30);
+        assert_eq!(config.quality_threshold, 80.0);
+        assert_eq!(config.bands.excellent, 95.0);
+        assert_eq!(config.bands.good, 70.0);
+    }
+
+    #[test]
+    fn test_validate_rejects_out_of_range_threshold() {
+        let config = QualityConfig {
+            min_coverage: 120.0,
+            ..QualityConfig::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(QualityConfigError::ThresholdOutOfRange { name: "min_coverage", .. })
+        ));
+
+        let config = QualityConfig {
+            quality_threshold: -5.0,
+            ..QualityConfig::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(QualityConfigError::ThresholdOutOfRange { name: "quality_threshold", .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_file_validates_weights() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("quality.yaml");
+        fs::write(&path, "weight_code_changes: 0.5\n").unwrap();
+        let result = QualityConfig::from_file(&path);
+        assert!(matches!(result, Err(QualityConfigError::WeightSum { .. })));
+    }
+
     #[test]
     fn test_empty_evidence_low_score() {
         let evidence = EvidenceCollector::default();
@@ -516,12 +988,65 @@ mod tests {
         assert!(assessment.dimension_scores.contains_key("tests_pass"));
         assert!(assessment.dimension_scores.contains_key("coverage"));
         assert!(assessment.dimension_scores.contains_key("no_errors"));
+        assert!(assessment.dimension_scores.contains_key("build"));
+        assert!(assessment.dimension_scores.contains_key("lint"));
+    }
+
+    #[test]
+    fn test_lint_clean_vs_warning_heavy() {
+        let mut clean = EvidenceCollector::default();
+        clean.record_file_write("a.py".to_string(), 10);
+        clean.record_file_write("b.py".to_string(), 10);
+        clean.record_command("ruff check .".to_string(), "All checks passed!".to_string(), 0, 0);
+        assert_eq!(score_lint(&clean), 100.0);
+
+        let mut noisy = clean.clone();
+        noisy.record_command("ruff check .".to_string(), "Found 8 errors.".to_string(), 1, 0);
+        // 8 warnings over 2 files = 4 per file
+        assert_eq!(score_lint(&noisy), 60.0);
+
+        let assessment = assess_quality(&noisy, None);
+        assert!(assessment.score < assess_quality(&clean, None).score);
+        assert!(assessment
+            .improvements_needed
+            .iter()
+            .any(|s| s.contains("8 lint warning(s)")));
+    }
+
+    #[test]
+    fn test_score_lint_neutral_without_linter() {
+        let evidence = EvidenceCollector::default();
+        assert_eq!(score_lint(&evidence), 50.0);
+    }
+
+    #[test]
+    fn test_build_outcome_affects_score() {
+        let mut evidence = EvidenceCollector::default();
+        evidence.files_written.push("main.rs".to_string());
+        evidence.record_build(BuildResult::new("cargo build".to_string(), true));
+        let passing = assess_quality(&evidence, None);
+        assert_eq!(passing.dimension_scores["build"], 100.0);
+
+        evidence.record_build(BuildResult::new("cargo build".to_string(), false));
+        let failing = assess_quality(&evidence, None);
+        assert_eq!(failing.dimension_scores["build"], 0.0);
+        assert!((passing.score - failing.score - 10.0).abs() < 1e-9);
+        assert!(failing
+            .improvements_needed
+            .iter()
+            .any(|s| s.contains("failing build")));
+    }
+
+    #[test]
+    fn test_score_build_neutral_without_builds() {
+        let evidence = EvidenceCollector::default();
+        assert_eq!(score_build(&evidence), 50.0);
     }
 
     #[test]
     fn test_compare_assessments_improvement() {
-        let prev = QualityAssessment::from_score(50.0, 70.0);
-        let curr = QualityAssessment::from_score(70.0, 70.0);
+        let prev = QualityAssessment::from_score(50.0, 70.0, &BandThresholds::default());
+        let curr = QualityAssessment::from_score(70.0, 70.0, &BandThresholds::default());
 
         let comparison = compare_assessments(&curr, &prev);
 
@@ -532,8 +1057,8 @@ mod tests {
 
     #[test]
     fn test_compare_assessments_regression() {
-        let prev = QualityAssessment::from_score(80.0, 70.0);
-        let curr = QualityAssessment::from_score(60.0, 70.0);
+        let prev = QualityAssessment::from_score(80.0, 70.0, &BandThresholds::default());
+        let curr = QualityAssessment::from_score(60.0, 70.0, &BandThresholds::default());
 
         let comparison = compare_assessments(&curr, &prev);
 
@@ -544,8 +1069,8 @@ mod tests {
 
     #[test]
     fn test_compare_assessments_stagnant() {
-        let prev = QualityAssessment::from_score(70.0, 70.0);
-        let curr = QualityAssessment::from_score(71.0, 70.0);
+        let prev = QualityAssessment::from_score(70.0, 70.0, &BandThresholds::default());
+        let curr = QualityAssessment::from_score(71.0, 70.0, &BandThresholds::default());
 
         let comparison = compare_assessments(&curr, &prev);
 
@@ -555,8 +1080,8 @@ mod tests {
 
     #[test]
     fn test_compare_assessments_band_change() {
-        let prev = QualityAssessment::from_score(60.0, 70.0); // Acceptable
-        let curr = QualityAssessment::from_score(75.0, 70.0); // Good
+        let prev = QualityAssessment::from_score(60.0, 70.0, &BandThresholds::default()); // Acceptable
+        let curr = QualityAssessment::from_score(75.0, 70.0, &BandThresholds::default()); // Good
 
         let comparison = compare_assessments(&curr, &prev);
 
@@ -565,6 +1090,45 @@ mod tests {
         assert_eq!(comparison.current_band, "good");
     }
 
+    #[test]
+    fn test_quality_trend_improving() {
+        let trend = QualityTrend::from_scores(&[40.0, 55.0, 68.0, 80.0], 5.0);
+        assert_eq!(trend.direction, TrendDirection::Improving);
+        assert_eq!(trend.deltas, vec![15.0, 13.0, 12.0]);
+        assert!(!trend.should_stop());
+    }
+
+    #[test]
+    fn test_quality_trend_plateau() {
+        let trend = QualityTrend::from_scores(&[60.0, 62.0, 63.5], 5.0);
+        assert_eq!(trend.direction, TrendDirection::Plateau);
+        assert_eq!(trend.latest_delta(), Some(1.5));
+        assert!(trend.should_stop());
+
+        let flat = QualityTrend::from_scores(&[50.0, 50.0, 50.0], 0.0);
+        assert_eq!(flat.direction, TrendDirection::Plateau);
+    }
+
+    #[test]
+    fn test_quality_trend_regressing() {
+        let assessments: Vec<QualityAssessment> = [75.0, 70.0, 58.0]
+            .iter()
+            .map(|&score| QualityAssessment::from_score(score, 70.0, &BandThresholds::default()))
+            .collect();
+        let trend = QualityTrend::from_assessments(&assessments, 5.0);
+        assert_eq!(trend.direction, TrendDirection::Regressing);
+        assert_eq!(trend.deltas, vec![-5.0, -12.0]);
+        assert!(trend.should_stop());
+    }
+
+    #[test]
+    fn test_quality_trend_single_score() {
+        let trend = QualityTrend::from_scores(&[70.0], 5.0);
+        assert_eq!(trend.direction, TrendDirection::Plateau);
+        assert!(trend.deltas.is_empty());
+        assert!(!trend.should_stop());
+    }
+
     #[test]
     fn test_score_code_changes_no_changes() {
         let evidence = EvidenceCollector::default();
@@ -28,6 +28,7 @@ use std::collections::{HashMap, HashSet};
 use std::fs::{self, File, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError, RwLock};
 
 // ============================================================================
 // Core Data Structures
@@ -54,6 +55,10 @@ pub struct LearnedSkill {
     pub promoted: bool,
     #[serde(default)]
     pub promotion_reason: String,
+    /// When the skill was last applied (RFC 3339); retrieval decays
+    /// relevance from here, or from `learned_at` if never applied
+    #[serde(default)]
+    pub last_applied_at: Option<String>,
 }
 
 impl LearnedSkill {
@@ -202,17 +207,107 @@ pub struct SkillApplication {
     pub feedback: String,
 }
 
+/// Portable collection of skills for moving them between machines
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillBundle {
+    pub version: u32,
+    pub exported_at: String,
+    pub skills: Vec<LearnedSkill>,
+}
+
+impl SkillBundle {
+    pub const VERSION: u32 = 1;
+}
+
+/// Loose match between a query term and a trigger: same stem, one being a
+/// prefix of the other ("auth" / "authentication"), or an edit distance of
+/// at most 2 for longer words (typos). Short words only match exactly so
+/// "go" doesn't match "golang" and "api" doesn't match "app".
+pub fn fuzzy_term_match(term: &str, trigger: &str) -> bool {
+    const MIN_PREFIX_LEN: usize = 3;
+    const MIN_EDIT_LEN: usize = 5;
+
+    if term == trigger || stem(term) == stem(trigger) {
+        return true;
+    }
+
+    let (shorter, longer) = if term.len() <= trigger.len() {
+        (term, trigger)
+    } else {
+        (trigger, term)
+    };
+    let shorter_len = shorter.chars().count();
+
+    (shorter_len >= MIN_PREFIX_LEN && longer.starts_with(shorter))
+        || (shorter_len >= MIN_EDIT_LEN && levenshtein(term, trigger) <= 2)
+}
+
+/// Strip a common English inflection ("testing" -> "test")
+fn stem(word: &str) -> &str {
+    for suffix in ["ing", "ed", "es", "s"] {
+        if let Some(stripped) = word.strip_suffix(suffix) {
+            if stripped.chars().count() >= 3 {
+                return stripped;
+            }
+        }
+    }
+    word
+}
+
+/// Edit distance between two strings, by character
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != *cb);
+            curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// Normalize a pattern for comparison: lowercase words with punctuation
+/// and leading directives ("use", "avoid", "don't", ...) removed, so
+/// "Use global state." and "Avoid global state" both become "global state".
+fn normalize_pattern(pattern: &str) -> String {
+    const DIRECTIVES: &[&str] = &["use", "avoid", "never", "always", "prefer", "dont", "do", "not"];
+
+    let lowered = pattern.to_lowercase().replace(['\'', '\u{2019}'], "");
+    let words: Vec<&str> = lowered
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let start = words.iter().take_while(|w| DIRECTIVES.contains(*w)).count();
+    words[start..].join(" ")
+}
+
+/// Reject skill IDs that would resolve outside the skills directory
+fn validate_skill_id(skill_id: &str) -> Result<()> {
+    let is_plain_name = !matches!(skill_id, "" | "." | "..") && !skill_id.contains(['/', '\\']);
+    if !is_plain_name {
+        anyhow::bail!("Invalid skill id {:?}", skill_id);
+    }
+    Ok(())
+}
+
 // ============================================================================
 // SkillStore - File-based persistent storage
 // ============================================================================
 
 /// File-based persistent storage for learned skills.
 /// Uses YAML files for skills and JSONL for feedback/applications.
-/// Thread-safe with file locking.
+/// Thread-safe with file locking; the in-memory cache sits behind an
+/// `RwLock`, so one store can be shared (e.g. in an `Arc`) across
+/// concurrent executions.
 pub struct SkillStore {
     skills_dir: PathBuf,
     feedback_dir: PathBuf,
-    skills_cache: Option<HashMap<String, LearnedSkill>>,
+    skills_cache: RwLock<Option<Arc<HashMap<String, LearnedSkill>>>>,
 }
 
 impl SkillStore {
@@ -246,7 +341,7 @@ impl SkillStore {
         Ok(Self {
             skills_dir,
             feedback_dir,
-            skills_cache: None,
+            skills_cache: RwLock::new(None),
         })
     }
 
@@ -255,12 +350,31 @@ impl SkillStore {
         Self::new(None, None)
     }
 
-    /// Load all skills from disk into memory
-    fn load_skills(&mut self) -> Result<&HashMap<String, LearnedSkill>> {
-        if self.skills_cache.is_some() {
-            return Ok(self.skills_cache.as_ref().unwrap());
+    /// Load all skills, from the cache if it is populated. The disk read
+    /// happens under the write lock so a concurrent invalidation can't be
+    /// overwritten by a stale load.
+    fn load_skills(&self) -> Result<Arc<HashMap<String, LearnedSkill>>> {
+        let cached = self
+            .skills_cache
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        if let Some(skills) = cached {
+            return Ok(skills);
+        }
+
+        let mut cache = self.skills_cache.write().unwrap_or_else(PoisonError::into_inner);
+        if let Some(skills) = cache.as_ref() {
+            return Ok(Arc::clone(skills));
         }
 
+        let skills = Arc::new(self.read_skills_from_disk()?);
+        *cache = Some(Arc::clone(&skills));
+        Ok(skills)
+    }
+
+    /// Read every skill's metadata from the skills directory
+    fn read_skills_from_disk(&self) -> Result<HashMap<String, LearnedSkill>> {
         let mut skills = HashMap::new();
 
         for entry in fs::read_dir(&self.skills_dir)? {
@@ -298,13 +412,12 @@ impl SkillStore {
             }
         }
 
-        self.skills_cache = Some(skills);
-        Ok(self.skills_cache.as_ref().unwrap())
+        Ok(skills)
     }
 
     /// Invalidate the skills cache after writes
-    fn invalidate_cache(&mut self) {
-        self.skills_cache = None;
+    fn invalidate_cache(&self) {
+        *self.skills_cache.write().unwrap_or_else(PoisonError::into_inner) = None;
     }
 
     /// Write content to file with exclusive lock
@@ -380,7 +493,7 @@ impl SkillStore {
     // --- Skill CRUD Operations ---
 
     /// Save or update a learned skill. Returns true on success.
-    pub fn save_skill(&mut self, skill: &LearnedSkill) -> Result<()> {
+    pub fn save_skill(&self, skill: &LearnedSkill) -> Result<()> {
         let skill_dir = self.skills_dir.join(&skill.skill_id);
         fs::create_dir_all(&skill_dir)?;
 
@@ -395,14 +508,157 @@ impl SkillStore {
         Ok(())
     }
 
+    /// Delete a learned skill's directory. Returns false if no such skill
+    /// exists. Promoted skills are refused; use `force_delete_skill` for
+    /// those.
+    pub fn delete_skill(&self, skill_id: &str) -> Result<bool> {
+        self.remove_skill(skill_id, false)
+    }
+
+    /// Delete a skill's directory even if it has been promoted.
+    pub fn force_delete_skill(&self, skill_id: &str) -> Result<bool> {
+        self.remove_skill(skill_id, true)
+    }
+
+    fn remove_skill(&self, skill_id: &str, force: bool) -> Result<bool> {
+        validate_skill_id(skill_id)?;
+
+        let skill_dir = self.skills_dir.join(skill_id);
+        if !skill_dir.is_dir() {
+            return Ok(false);
+        }
+
+        if !force {
+            if let Some(skill) = self.get_skill(skill_id)? {
+                if skill.promoted {
+                    anyhow::bail!("Refusing to delete promoted skill {}", skill_id);
+                }
+            }
+        }
+
+        fs::remove_dir_all(&skill_dir)
+            .with_context(|| format!("Failed to delete {:?}", skill_dir))?;
+        self.invalidate_cache();
+        Ok(true)
+    }
+
+    /// Delete non-promoted skills whose quality score is below
+    /// `min_quality` or that have been applied fewer than
+    /// `min_applications` times. Returns the deleted skill IDs.
+    pub fn prune_skills(&self, min_quality: f64, min_applications: usize) -> Result<Vec<String>> {
+        let learned: Vec<(String, f64)> = self
+            .load_skills()?
+            .values()
+            .filter(|s| !s.promoted)
+            .map(|s| (s.skill_id.clone(), s.quality_score))
+            .collect();
+
+        let mut deleted = Vec::new();
+        for (skill_id, quality_score) in learned {
+            let low_value = quality_score < min_quality
+                || self.get_skill_effectiveness(&skill_id)?.applications < min_applications;
+            if low_value && self.delete_skill(&skill_id)? {
+                deleted.push(skill_id);
+            }
+        }
+        deleted.sort();
+        Ok(deleted)
+    }
+
+    /// Existing skills in the same domain that contradict `skill`: one
+    /// lists as an anti-pattern what the other recommends (compared after
+    /// [`normalize_pattern`]). Call before `save_skill` to warn about an
+    /// incoherent knowledge base.
+    pub fn find_conflicts(&self, skill: &LearnedSkill) -> Result<Vec<LearnedSkill>> {
+        let normalize_all = |patterns: &[String]| -> HashSet<String> {
+            patterns
+                .iter()
+                .map(|p| normalize_pattern(p))
+                .filter(|p| !p.is_empty())
+                .collect()
+        };
+        let patterns = normalize_all(&skill.patterns);
+        let anti_patterns = normalize_all(&skill.anti_patterns);
+
+        let mut conflicts: Vec<LearnedSkill> = self
+            .load_skills()?
+            .values()
+            .filter(|other| other.skill_id != skill.skill_id && other.domain == skill.domain)
+            .filter(|other| {
+                !patterns.is_disjoint(&normalize_all(&other.anti_patterns))
+                    || !anti_patterns.is_disjoint(&normalize_all(&other.patterns))
+            })
+            .cloned()
+            .collect();
+        conflicts.sort_by(|a, b| a.skill_id.cmp(&b.skill_id));
+        Ok(conflicts)
+    }
+
+    // --- Bundles ---
+
+    /// Write the given skills to a JSON bundle at `out`. Returns the number
+    /// of skills exported; fails if any ID is unknown.
+    pub fn export_bundle(&self, skill_ids: &[String], out: &Path) -> Result<usize> {
+        let skills = self.load_skills()?;
+        let missing: Vec<&str> = skill_ids
+            .iter()
+            .filter(|id| !skills.contains_key(*id))
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            anyhow::bail!("Unknown skill id(s): {}", missing.join(", "));
+        }
+
+        let bundle = SkillBundle {
+            version: SkillBundle::VERSION,
+            exported_at: Utc::now().to_rfc3339(),
+            skills: skill_ids.iter().map(|id| skills[id].clone()).collect(),
+        };
+        self.write_with_lock(out, &serde_json::to_string_pretty(&bundle)?)?;
+        Ok(bundle.skills.len())
+    }
+
+    /// Load skills from a bundle written by `export_bundle`. Existing IDs
+    /// are skipped unless `overwrite` is set. Promotion status is only kept
+    /// when overwriting; otherwise imported skills start unpromoted so they
+    /// go through the local promotion gate. Returns the imported IDs.
+    pub fn import_bundle(&self, path: &Path, overwrite: bool) -> Result<Vec<String>> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read skill bundle {:?}", path))?;
+        let bundle: SkillBundle = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse skill bundle {:?}", path))?;
+        if bundle.version > SkillBundle::VERSION {
+            anyhow::bail!(
+                "Skill bundle version {} is newer than supported version {}",
+                bundle.version,
+                SkillBundle::VERSION
+            );
+        }
+
+        let mut imported = Vec::new();
+        for mut skill in bundle.skills {
+            validate_skill_id(&skill.skill_id)?;
+            if !overwrite {
+                if self.get_skill(&skill.skill_id)?.is_some() {
+                    continue;
+                }
+                skill.promoted = false;
+                skill.promotion_reason.clear();
+            }
+            self.save_skill(&skill)?;
+            imported.push(skill.skill_id);
+        }
+        Ok(imported)
+    }
+
     /// Retrieve a skill by ID
-    pub fn get_skill(&mut self, skill_id: &str) -> Result<Option<LearnedSkill>> {
+    pub fn get_skill(&self, skill_id: &str) -> Result<Option<LearnedSkill>> {
         let skills = self.load_skills()?;
         Ok(skills.get(skill_id).cloned())
     }
 
     /// Get all promoted skills
-    pub fn get_promoted_skills(&mut self) -> Result<Vec<LearnedSkill>> {
+    pub fn get_promoted_skills(&self) -> Result<Vec<LearnedSkill>> {
         let skills = self.load_skills()?;
         let mut promoted: Vec<_> = skills
             .values()
@@ -414,7 +670,7 @@ impl SkillStore {
     }
 
     /// Get skills matching a domain
-    pub fn get_skills_by_domain(&mut self, domain: &str) -> Result<Vec<LearnedSkill>> {
+    pub fn get_skills_by_domain(&self, domain: &str) -> Result<Vec<LearnedSkill>> {
         let skills = self.load_skills()?;
         let mut domain_skills: Vec<_> = skills
             .values()
@@ -425,13 +681,18 @@ impl SkillStore {
         Ok(domain_skills)
     }
 
-    /// Search skills by trigger keywords and filters
+    /// Search skills by trigger keywords and filters.
+    ///
+    /// With `fuzzy`, skills whose triggers don't literally match any query
+    /// term are still returned if a term fuzzily matches one (see
+    /// [`fuzzy_term_match`]). Exact matches always rank above fuzzy ones.
     pub fn search_skills(
-        &mut self,
+        &self,
         query: &str,
         domain: Option<&str>,
         min_quality: f64,
         promoted_only: bool,
+        fuzzy: bool,
     ) -> Result<Vec<LearnedSkill>> {
         let skills = self.load_skills()?;
 
@@ -459,21 +720,29 @@ impl SkillStore {
             .map(|s| s.to_lowercase())
             .collect();
 
-        let mut results: Vec<_> = candidates
-            .into_iter()
-            .filter(|skill| {
-                let skill_triggers: HashSet<String> = skill.triggers
+        let mut exact = Vec::new();
+        let mut fuzzy_matches = Vec::new();
+        for skill in candidates {
+            let skill_triggers: HashSet<String> = skill.triggers
+                .iter()
+                .map(|t| t.to_lowercase())
+                .collect();
+            if !query_terms.is_disjoint(&skill_triggers) {
+                exact.push(skill.clone());
+            } else if fuzzy
+                && query_terms
                     .iter()
-                    .map(|t| t.to_lowercase())
-                    .collect();
-                !query_terms.is_disjoint(&skill_triggers)
-            })
-            .cloned()
-            .collect();
+                    .any(|q| skill_triggers.iter().any(|t| fuzzy_term_match(q, t)))
+            {
+                fuzzy_matches.push(skill.clone());
+            }
+        }
 
-        // Sort by quality
-        results.sort_by(|a, b| b.quality_score.partial_cmp(&a.quality_score).unwrap());
-        Ok(results)
+        // Sort by quality, exact matches first
+        exact.sort_by(|a, b| b.quality_score.partial_cmp(&a.quality_score).unwrap());
+        fuzzy_matches.sort_by(|a, b| b.quality_score.partial_cmp(&a.quality_score).unwrap());
+        exact.extend(fuzzy_matches);
+        Ok(exact)
     }
 
     // --- Iteration Feedback ---
@@ -506,6 +775,11 @@ impl SkillStore {
 
     // --- Skill Application Tracking ---
 
+    /// JSONL file holding a skill's application records
+    fn applications_path(&self, skill_id: &str) -> PathBuf {
+        self.feedback_dir.join(format!("{}_applications.jsonl", skill_id))
+    }
+
     /// Record when a skill was applied and its effectiveness
     pub fn record_skill_application(
         &self,
@@ -515,7 +789,7 @@ impl SkillStore {
         quality_impact: Option<f64>,
         feedback: &str,
     ) -> Result<()> {
-        let app_path = self.feedback_dir.join(format!("{}_applications.jsonl", skill_id));
+        let app_path = self.applications_path(skill_id);
         let application = SkillApplication {
             skill_id: skill_id.to_string(),
             session_id: session_id.to_string(),
@@ -525,12 +799,18 @@ impl SkillStore {
             feedback: feedback.to_string(),
         };
         let data = serde_json::to_value(&application)?;
-        self.append_jsonl(&app_path, &data)
+        self.append_jsonl(&app_path, &data)?;
+
+        if let Some(mut skill) = self.get_skill(skill_id)? {
+            skill.last_applied_at = Some(application.applied_at);
+            self.save_skill(&skill)?;
+        }
+        Ok(())
     }
 
     /// Calculate skill effectiveness metrics
     pub fn get_skill_effectiveness(&self, skill_id: &str) -> Result<SkillEffectiveness> {
-        let app_path = self.feedback_dir.join(format!("{}_applications.jsonl", skill_id));
+        let app_path = self.applications_path(skill_id);
         let records = self.read_jsonl(&app_path)?;
 
         let mut applications = 0;
@@ -680,6 +960,7 @@ impl<'a> SkillExtractor<'a> {
             applicability_conditions: conditions,
             promoted: false,
             promotion_reason: String::new(),
+            last_applied_at: None,
         }))
     }
 
@@ -840,17 +1121,31 @@ impl<'a> SkillExtractor<'a> {
 
 /// Retrieves relevant learned skills for a given task context
 pub struct SkillRetriever<'a> {
-    store: &'a mut SkillStore,
+    store: &'a SkillStore,
+    half_life_days: f64,
 }
 
 impl<'a> SkillRetriever<'a> {
-    pub fn new(store: &'a mut SkillStore) -> Self {
-        Self { store }
+    /// Default half-life for recency decay
+    pub const DEFAULT_HALF_LIFE_DAYS: f64 = 90.0;
+
+    pub fn new(store: &'a SkillStore) -> Self {
+        Self {
+            store,
+            half_life_days: Self::DEFAULT_HALF_LIFE_DAYS,
+        }
+    }
+
+    /// Set how many days of disuse halve a skill's relevance. Zero or a
+    /// non-finite value disables decay.
+    pub fn with_half_life(mut self, half_life_days: f64) -> Self {
+        self.half_life_days = half_life_days;
+        self
     }
 
     /// Retrieve relevant skills for a task
     pub fn retrieve(
-        &mut self,
+        &self,
         task_description: &str,
         file_paths: Option<&[String]>,
         domain: Option<&str>,
@@ -864,6 +1159,7 @@ impl<'a> SkillRetriever<'a> {
             domain,
             50.0,
             promoted_only,
+            true,
         )?;
 
         if candidates.is_empty() {
@@ -956,7 +1252,23 @@ impl<'a> SkillRetriever<'a> {
             }
         }
 
-        score
+        score * self.recency_factor(skill, Utc::now())
+    }
+
+    /// Exponential decay since the skill was last applied (or learned).
+    /// Unparseable timestamps are treated as fresh.
+    fn recency_factor(&self, skill: &LearnedSkill, now: DateTime<Utc>) -> f64 {
+        if !self.half_life_days.is_finite() || self.half_life_days <= 0.0 {
+            return 1.0;
+        }
+
+        let last_used = skill.last_applied_at.as_deref().unwrap_or(&skill.learned_at);
+        let Ok(last_used) = DateTime::parse_from_rfc3339(last_used) else {
+            return 1.0;
+        };
+
+        let age_days = (now - last_used.with_timezone(&Utc)).num_seconds().max(0) as f64 / 86_400.0;
+        0.5_f64.powf(age_days / self.half_life_days)
     }
 }
 
@@ -974,6 +1286,8 @@ impl<'a> PromotionGate<'a> {
     pub const MIN_QUALITY_SCORE: f64 = 85.0;
     pub const MIN_APPLICATIONS: usize = 2;
     pub const MIN_SUCCESS_RATE: f64 = 0.7;
+    /// Number of most recent applications considered for demotion
+    pub const DEMOTION_WINDOW: usize = 5;
 
     pub fn new(store: &'a SkillStore, skills_dir: Option<PathBuf>) -> Self {
         let skills_dir = skills_dir.unwrap_or_else(SkillStore::default_skills_dir);
@@ -1046,6 +1360,7 @@ impl<'a> PromotionGate<'a> {
 
             Ok(skill_md_path.clone())
         })();
+        self.store.invalidate_cache();
 
         match result {
             Ok(path) => Ok(Some(path)),
@@ -1067,8 +1382,94 @@ impl<'a> PromotionGate<'a> {
         }
     }
 
+    /// Evaluate if a promoted skill should be demoted: its success rate over
+    /// the last `DEMOTION_WINDOW` applications (at least `MIN_APPLICATIONS`
+    /// of them) has fallen below half of `MIN_SUCCESS_RATE`
+    pub fn evaluate_demotion(&self, skill: &LearnedSkill) -> Result<(bool, String)> {
+        if !skill.promoted {
+            return Ok((false, "Skill is not promoted".to_string()));
+        }
+
+        let app_path = self.store.applications_path(&skill.skill_id);
+        let records = self.store.read_jsonl(&app_path)?;
+        let recent = &records[records.len().saturating_sub(Self::DEMOTION_WINDOW)..];
+
+        if recent.len() < Self::MIN_APPLICATIONS {
+            return Ok((
+                false,
+                format!(
+                    "Only {} recent applications, need {}",
+                    recent.len(),
+                    Self::MIN_APPLICATIONS
+                ),
+            ));
+        }
+
+        let helpful = recent
+            .iter()
+            .filter(|r| r.get("was_helpful").and_then(|v| v.as_bool()) == Some(true))
+            .count();
+        let success_rate = helpful as f64 / recent.len() as f64;
+        let floor = Self::MIN_SUCCESS_RATE / 2.0;
+
+        if success_rate < floor {
+            Ok((
+                true,
+                format!(
+                    "Recent success rate {:.1}% below {:.0}% over {} applications",
+                    success_rate * 100.0,
+                    floor * 100.0,
+                    recent.len()
+                ),
+            ))
+        } else {
+            Ok((false, "Recent effectiveness is acceptable".to_string()))
+        }
+    }
+
+    /// Revert a promoted skill to learned status. Returns whether the skill
+    /// was demoted; on write failure the skill and its files are restored.
+    pub fn demote(&self, skill: &mut LearnedSkill, reason: Option<&str>) -> Result<bool> {
+        let (should_demote, eval_reason) = self.evaluate_demotion(skill)?;
+
+        if !should_demote {
+            return Ok(false);
+        }
+
+        // Store original state for rollback
+        let original = skill.clone();
+
+        // Update skill status
+        skill.promoted = false;
+        skill.promotion_reason = format!("Demoted: {}", reason.unwrap_or(&eval_reason));
+
+        let skill_dir = self.skills_dir.join(&skill.skill_id);
+        let skill_md_path = skill_dir.join("SKILL.md");
+        let metadata_path = skill_dir.join("metadata.yaml");
+
+        let write = |skill: &LearnedSkill| -> Result<()> {
+            fs::create_dir_all(&skill_dir)?;
+            self.store.write_with_lock(&skill_md_path, &skill.to_skill_md())?;
+            let content = serde_yaml::to_string(skill)?;
+            self.store.write_with_lock(&metadata_path, &content)?;
+            Ok(())
+        };
+
+        // Attempt demotion with rollback on failure
+        let result = write(skill);
+        if let Err(e) = &result {
+            // Rollback
+            *skill = original;
+            let _ = write(skill);
+            eprintln!("[PromotionGate] Failed to demote skill {}: {}", skill.skill_id, e);
+        }
+        self.store.invalidate_cache();
+
+        Ok(result.is_ok())
+    }
+
     /// List skills pending promotion review
-    pub fn list_pending(&self, store: &mut SkillStore) -> Result<Vec<LearnedSkill>> {
+    pub fn list_pending(&self, store: &SkillStore) -> Result<Vec<LearnedSkill>> {
         let skills = store.load_skills()?;
         let mut pending: Vec<_> = skills
             .values()
@@ -1096,7 +1497,7 @@ pub fn learn_from_session(
     domain: &str,
     auto_promote: bool,
 ) -> Result<Option<LearnedSkill>> {
-    let mut store = get_default_store()?;
+    let store = get_default_store()?;
     let extractor = SkillExtractor::new(&store);
 
     let skill = extractor.extract_from_session(session_id, repo_path, domain)?;
@@ -1121,8 +1522,8 @@ pub fn retrieve_skills_for_task(
     file_paths: Option<&[String]>,
     domain: Option<&str>,
 ) -> Result<Vec<LearnedSkill>> {
-    let mut store = get_default_store()?;
-    let mut retriever = SkillRetriever::new(&mut store);
+    let store = get_default_store()?;
+    let retriever = SkillRetriever::new(&store);
 
     let results = retriever.retrieve(task_description, file_paths, domain, 3, false)?;
     Ok(results.into_iter().map(|(skill, _score)| skill).collect())
@@ -1159,12 +1560,13 @@ mod tests {
             applicability_conditions: vec!["Python projects".to_string(), "Has test suite".to_string()],
             promoted: false,
             promotion_reason: String::new(),
+            last_applied_at: None,
         }
     }
 
     #[test]
     fn test_save_and_get_skill() {
-        let (_temp, mut store) = create_temp_store();
+        let (_temp, store) = create_temp_store();
         let skill = sample_skill();
 
         store.save_skill(&skill).unwrap();
@@ -1179,15 +1581,312 @@ mod tests {
 
     #[test]
     fn test_search_skills() {
-        let (_temp, mut store) = create_temp_store();
+        let (_temp, store) = create_temp_store();
         let skill = sample_skill();
 
         store.save_skill(&skill).unwrap();
-        let results = store.search_skills("test", None, 50.0, false).unwrap();
+        let results = store.search_skills("test", None, 50.0, false, false).unwrap();
 
         assert_eq!(results.len(), 1);
     }
 
+    #[test]
+    fn test_delete_skill() {
+        let (_temp, store) = create_temp_store();
+        let skill = sample_skill();
+        store.save_skill(&skill).unwrap();
+
+        assert!(store.delete_skill(&skill.skill_id).unwrap());
+        assert!(store.get_skill(&skill.skill_id).unwrap().is_none());
+        assert!(!store.skills_dir.join(&skill.skill_id).exists());
+
+        // Deleting again reports nothing removed
+        assert!(!store.delete_skill(&skill.skill_id).unwrap());
+        assert!(store.delete_skill("../feedback").is_err());
+    }
+
+    #[test]
+    fn test_delete_promoted_skill_requires_force() {
+        let (_temp, store) = create_temp_store();
+        let mut skill = sample_skill();
+        skill.promoted = true;
+        store.save_skill(&skill).unwrap();
+
+        assert!(store.delete_skill(&skill.skill_id).is_err());
+        assert!(store.get_skill(&skill.skill_id).unwrap().is_some());
+
+        assert!(store.force_delete_skill(&skill.skill_id).unwrap());
+        assert!(store.get_skill(&skill.skill_id).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_prune_skills_spares_promoted() {
+        let (_temp, store) = create_temp_store();
+
+        let mut weak = sample_skill();
+        weak.skill_id = "weak".to_string();
+        weak.quality_score = 40.0;
+
+        let mut unused = sample_skill();
+        unused.skill_id = "unused".to_string();
+
+        let mut proven = sample_skill();
+        proven.skill_id = "proven".to_string();
+
+        let mut promoted = sample_skill();
+        promoted.skill_id = "promoted".to_string();
+        promoted.quality_score = 10.0;
+        promoted.promoted = true;
+
+        for skill in [&weak, &unused, &proven, &promoted] {
+            store.save_skill(skill).unwrap();
+        }
+        for skill_id in ["weak", "proven", "proven"] {
+            store
+                .record_skill_application(skill_id, "session", Some(true), None, "")
+                .unwrap();
+        }
+
+        let deleted = store.prune_skills(60.0, 2).unwrap();
+        assert_eq!(deleted, vec!["unused".to_string(), "weak".to_string()]);
+        assert!(store.get_skill("proven").unwrap().is_some());
+        assert!(store.get_skill("promoted").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_bundle_round_trip() {
+        let (source_temp, source) = create_temp_store();
+        let first = sample_skill();
+        let mut second = sample_skill();
+        second.skill_id = "test-skill-002".to_string();
+        second.promoted = true;
+        second.promotion_reason = "Passed review".to_string();
+        source.save_skill(&first).unwrap();
+        source.save_skill(&second).unwrap();
+
+        let bundle_path = source_temp.path().join("bundle.json");
+        let ids = vec![first.skill_id.clone(), second.skill_id.clone()];
+        assert_eq!(source.export_bundle(&ids, &bundle_path).unwrap(), 2);
+        assert!(source
+            .export_bundle(&["missing".to_string()], &bundle_path.with_extension("bad"))
+            .is_err());
+
+        // Into a fresh store, promotion is dropped unless overwriting
+        let (_temp, store) = create_temp_store();
+        let mut imported = store.import_bundle(&bundle_path, false).unwrap();
+        imported.sort();
+        assert_eq!(imported, ids);
+        assert_eq!(store.get_skill(&first.skill_id).unwrap().unwrap(), first);
+        let unpromoted = store.get_skill(&second.skill_id).unwrap().unwrap();
+        assert!(!unpromoted.promoted);
+        assert!(unpromoted.promotion_reason.is_empty());
+
+        // Existing IDs are skipped without overwrite
+        assert!(store.import_bundle(&bundle_path, false).unwrap().is_empty());
+
+        assert_eq!(store.import_bundle(&bundle_path, true).unwrap().len(), 2);
+        assert_eq!(store.get_skill(&second.skill_id).unwrap().unwrap(), second);
+    }
+
+    #[test]
+    fn test_concurrent_reads_during_writes() {
+        let (_temp, store) = create_temp_store();
+        let store = Arc::new(store);
+        store.save_skill(&sample_skill()).unwrap();
+
+        let writer = {
+            let store = Arc::clone(&store);
+            std::thread::spawn(move || {
+                for i in 0..20 {
+                    let mut skill = sample_skill();
+                    skill.skill_id = format!("written-{i}");
+                    store.save_skill(&skill).unwrap();
+                }
+            })
+        };
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let store = Arc::clone(&store);
+                std::thread::spawn(move || {
+                    for _ in 0..50 {
+                        assert!(store.get_skill("test-skill-001").unwrap().is_some());
+                        assert!(!store.search_skills("pytest", None, 0.0, false, false).unwrap().is_empty());
+                    }
+                })
+            })
+            .collect();
+
+        writer.join().unwrap();
+        for reader in readers {
+            reader.join().unwrap();
+        }
+        assert_eq!(store.get_skills_by_domain("testing").unwrap().len(), 21);
+    }
+
+    #[test]
+    fn test_recently_applied_skill_ranks_higher() {
+        let (_temp, store) = create_temp_store();
+        let mut stale = sample_skill();
+        stale.skill_id = "stale".to_string();
+        let mut fresh = sample_skill();
+        fresh.skill_id = "fresh".to_string();
+        store.save_skill(&stale).unwrap();
+        store.save_skill(&fresh).unwrap();
+
+        store.record_skill_application("fresh", "session-1", None, None, "").unwrap();
+        assert!(store.get_skill("fresh").unwrap().unwrap().last_applied_at.is_some());
+
+        let retriever = SkillRetriever::new(&store);
+        let results = retriever
+            .retrieve("write pytest unit test fixtures", None, None, 5, false)
+            .unwrap();
+        let ranked: Vec<&str> = results.iter().map(|(s, _)| s.skill_id.as_str()).collect();
+        assert_eq!(ranked, vec!["fresh", "stale"]);
+
+        // Without decay the two score identically
+        let results = SkillRetriever::new(&store)
+            .with_half_life(0.0)
+            .retrieve("write pytest unit test fixtures", None, None, 5, false)
+            .unwrap();
+        assert_eq!(results[0].1, results[1].1);
+    }
+
+    #[test]
+    fn test_recency_factor_half_life() {
+        let (_temp, store) = create_temp_store();
+        let retriever = SkillRetriever::new(&store).with_half_life(30.0);
+        let mut skill = sample_skill();
+        let now = DateTime::parse_from_rfc3339("2025-03-02T00:00:00Z").unwrap().with_timezone(&Utc);
+
+        // learned_at is 2025-01-01, 60 days earlier
+        assert!((retriever.recency_factor(&skill, now) - 0.25).abs() < 1e-9);
+
+        skill.last_applied_at = Some("2025-01-31T00:00:00Z".to_string());
+        assert!((retriever.recency_factor(&skill, now) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fuzzy_search_matches_partial_trigger() {
+        let (_temp, store) = create_temp_store();
+        let mut skill = sample_skill();
+        skill.triggers = vec!["authentication".to_string()];
+        store.save_skill(&skill).unwrap();
+
+        assert!(store.search_skills("auth", None, 0.0, false, false).unwrap().is_empty());
+        let results = store.search_skills("auth", None, 0.0, false, true).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].skill_id, skill.skill_id);
+    }
+
+    #[test]
+    fn test_exact_matches_rank_above_fuzzy() {
+        let (_temp, store) = create_temp_store();
+        let mut fuzzy = sample_skill();
+        fuzzy.skill_id = "fuzzy".to_string();
+        fuzzy.triggers = vec!["authentication".to_string()];
+        fuzzy.quality_score = 99.0;
+        let mut exact = sample_skill();
+        exact.skill_id = "exact".to_string();
+        exact.triggers = vec!["auth".to_string()];
+        exact.quality_score = 60.0;
+        store.save_skill(&fuzzy).unwrap();
+        store.save_skill(&exact).unwrap();
+
+        let results = store.search_skills("auth", None, 0.0, false, true).unwrap();
+        let ids: Vec<&str> = results.iter().map(|s| s.skill_id.as_str()).collect();
+        assert_eq!(ids, vec!["exact", "fuzzy"]);
+    }
+
+    #[test]
+    fn test_fuzzy_term_match() {
+        assert!(fuzzy_term_match("auth", "authentication"));
+        assert!(fuzzy_term_match("testing", "tests"));
+        assert!(fuzzy_term_match("kubernetes", "kubernets"));
+        assert!(!fuzzy_term_match("go", "golang"));
+        assert!(!fuzzy_term_match("api", "app"));
+        assert!(!fuzzy_term_match("react", "redux"));
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_find_conflicts() {
+        let (_temp, store) = create_temp_store();
+
+        let mut mocks_everything = sample_skill();
+        mocks_everything.skill_id = "mocks-everything".to_string();
+        mocks_everything.patterns = vec!["Mock external calls".to_string()];
+        mocks_everything.anti_patterns = vec![];
+
+        let mut other_domain = mocks_everything.clone();
+        other_domain.skill_id = "other-domain".to_string();
+        other_domain.domain = "frontend".to_string();
+
+        let mut unrelated = sample_skill();
+        unrelated.skill_id = "unrelated".to_string();
+        unrelated.patterns = vec!["Run tests in parallel".to_string()];
+        unrelated.anti_patterns = vec!["Share state between tests".to_string()];
+
+        for skill in [&mocks_everything, &other_domain, &unrelated] {
+            store.save_skill(skill).unwrap();
+        }
+
+        let mut candidate = sample_skill();
+        candidate.skill_id = "candidate".to_string();
+        candidate.patterns = vec!["Use real services".to_string()];
+        candidate.anti_patterns = vec!["Don't mock external calls.".to_string()];
+
+        let conflicts = store.find_conflicts(&candidate).unwrap();
+        let ids: Vec<&str> = conflicts.iter().map(|s| s.skill_id.as_str()).collect();
+        assert_eq!(ids, vec!["mocks-everything"]);
+
+        candidate.anti_patterns = vec!["Avoid flaky sleeps".to_string()];
+        assert!(store.find_conflicts(&candidate).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_normalize_pattern() {
+        assert_eq!(normalize_pattern("Use global state."), "global state");
+        assert_eq!(normalize_pattern("Avoid  global STATE"), "global state");
+        assert_eq!(normalize_pattern("Do not use global state"), "global state");
+    }
+
+    #[test]
+    fn test_demote_regressed_skill() {
+        let (temp, store) = create_temp_store();
+        let skills_dir = temp.path().join("skills").join("learned");
+        let mut skill = sample_skill();
+        skill.promoted = true;
+        skill.promotion_reason = "Meets all promotion criteria".to_string();
+        store.save_skill(&skill).unwrap();
+
+        // Early successes fall outside the recent window
+        for _ in 0..3 {
+            store.record_skill_application(&skill.skill_id, "s1", Some(true), None, "").unwrap();
+        }
+        let gate = PromotionGate::new(&store, Some(skills_dir));
+        store.record_skill_application(&skill.skill_id, "s2", Some(false), None, "").unwrap();
+        let (should_demote, _) = gate.evaluate_demotion(&skill).unwrap();
+        assert!(!should_demote);
+
+        for _ in 0..4 {
+            store.record_skill_application(&skill.skill_id, "s3", Some(false), None, "").unwrap();
+        }
+        let (should_demote, reason) = gate.evaluate_demotion(&skill).unwrap();
+        assert!(should_demote, "{reason}");
+
+        let mut skill = store.get_skill(&skill.skill_id).unwrap().unwrap();
+        assert!(gate.demote(&mut skill, None).unwrap());
+        assert!(!skill.promoted);
+        assert!(skill.promotion_reason.starts_with("Demoted:"));
+
+        let stored = store.get_skill(&skill.skill_id).unwrap().unwrap();
+        assert!(!stored.promoted);
+        assert!(store.get_promoted_skills().unwrap().is_empty());
+
+        // Already demoted skills are left alone
+        assert!(!gate.demote(&mut skill, None).unwrap());
+    }
+
     #[test]
     fn test_skill_to_md() {
         let skill = sample_skill();
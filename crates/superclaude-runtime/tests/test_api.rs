@@ -3,8 +3,69 @@ Integration tests for Anthropic API client
 */
 
 use superclaude_runtime::api::{
-    AnthropicClient, ContentBlock, CreateMessageRequest, Message, Role, StreamEvent,
+    AnthropicClient, ContentBlock, CreateMessageRequest, Message, RetryConfig, Role, StreamEvent,
 };
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_util::sync::CancellationToken;
+
+/// Read one HTTP request (headers plus `content-length` body)
+async fn read_request(socket: &mut TcpStream) -> String {
+    let mut raw = Vec::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        let n = socket.read(&mut buf).await.unwrap();
+        raw.extend_from_slice(&buf[..n]);
+        let text = String::from_utf8_lossy(&raw);
+        if let Some(header_end) = text.find("\r\n\r\n") {
+            let content_length = text[..header_end]
+                .lines()
+                .find_map(|l| {
+                    let (k, v) = l.split_once(':')?;
+                    k.eq_ignore_ascii_case("content-length")
+                        .then(|| v.trim().parse::<usize>().ok())?
+                })
+                .unwrap_or(0);
+            if raw.len() >= header_end + 4 + content_length {
+                break;
+            }
+        }
+        if n == 0 {
+            break;
+        }
+    }
+    String::from_utf8_lossy(&raw).into_owned()
+}
+
+/// Serve `count` HTTP requests, answering each with the status and JSON
+/// body chosen by `respond`. Yields the raw request texts once done.
+async fn serve(
+    count: usize,
+    respond: fn(&str) -> (u16, String),
+) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = tokio::spawn(async move {
+        let mut requests = Vec::new();
+        for _ in 0..count {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let raw = read_request(&mut socket).await;
+            let (status, body) = respond(&raw);
+            let response = format!(
+                "HTTP/1.1 {} Mock\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            requests.push(raw);
+        }
+        requests
+    });
+
+    (addr, handle)
+}
 
 #[tokio::test]
 #[ignore] // Requires ANTHROPIC_API_KEY
@@ -18,6 +79,7 @@ async fn test_create_message_basic() {
             role: Role::User,
             content: vec![ContentBlock::Text {
                 text: "Say 'Hello, World!' and nothing else.".to_string(),
+                cache_control: None,
             }],
         }],
         ..Default::default()
@@ -45,6 +107,7 @@ async fn test_create_message_stream() {
             role: Role::User,
             content: vec![ContentBlock::Text {
                 text: "Count from 1 to 5.".to_string(),
+                cache_control: None,
             }],
         }],
         ..Default::default()
@@ -94,6 +157,7 @@ async fn test_stream_collect_message() {
             role: Role::User,
             content: vec![ContentBlock::Text {
                 text: "Say hello.".to_string(),
+                cache_control: None,
             }],
         }],
         ..Default::default()
@@ -119,11 +183,13 @@ fn test_api_types_serialization() {
         content: vec![
             ContentBlock::Text {
                 text: "Test message".to_string(),
+                cache_control: None,
             },
             ContentBlock::ToolResult {
                 tool_use_id: "tool_123".to_string(),
                 content: "Result".to_string(),
                 is_error: Some(false),
+                cache_control: None,
             },
         ],
     };
@@ -146,9 +212,10 @@ fn test_request_builder() {
             role: Role::User,
             content: vec![ContentBlock::Text {
                 text: "Hello".to_string(),
+                cache_control: None,
             }],
         }],
-        system: Some("You are a helpful assistant.".to_string()),
+        system: Some("You are a helpful assistant.".into()),
         temperature: Some(0.7),
         ..Default::default()
     };
@@ -159,6 +226,184 @@ fn test_request_builder() {
     assert_eq!(request.temperature, Some(0.7));
 }
 
+#[tokio::test]
+async fn test_count_tokens_mock_server() {
+    let (addr, server) = serve(1, |_| (200, r#"{"input_tokens": 1234}"#.to_string())).await;
+    let client = AnthropicClient::new("test_key".to_string(), addr, "2023-06-01".to_string())
+        .expect("Client creation failed");
+
+    let request = CreateMessageRequest {
+        messages: vec![Message {
+            role: Role::User,
+            content: vec![ContentBlock::text("How many tokens is this?")],
+        }],
+        ..Default::default()
+    };
+
+    let count = client.count_tokens(&request).await.expect("count_tokens failed");
+    assert_eq!(count, 1234);
+
+    let raw = server.await.unwrap().remove(0).to_lowercase();
+    assert!(raw.starts_with("post /v1/messages/count_tokens "));
+    assert!(raw.contains("x-api-key: test_key"));
+    assert!(raw.contains("anthropic-version: 2023-06-01"));
+    assert!(raw.contains("how many tokens is this?"));
+    assert!(!raw.contains("max_tokens"));
+}
+
+#[tokio::test]
+async fn test_model_fallback_on_overload() {
+    // The primary model always 529s; the fallback answers
+    let (addr, server) = serve(3, |raw| {
+        if raw.contains(r#""model":"claude-sonnet-4""#) {
+            (
+                529,
+                r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#
+                    .to_string(),
+            )
+        } else {
+            (
+                200,
+                r#"{"id":"msg_1","type":"message","role":"assistant","content":[{"type":"text","text":"hi"}],"model":"claude-3-5-haiku","stop_reason":"end_turn","stop_sequence":null,"usage":{"input_tokens":3,"output_tokens":1}}"#
+                    .to_string(),
+            )
+        }
+    })
+    .await;
+
+    let client = AnthropicClient::new("test_key".to_string(), addr, "2023-06-01".to_string())
+        .expect("Client creation failed")
+        .with_retry_config(RetryConfig {
+            max_retries: 1,
+            initial_delay_ms: 1,
+            max_delay_ms: 1,
+            backoff_multiplier: 1.0,
+        })
+        .with_model_fallbacks(vec!["claude-sonnet-4", "claude-3-5-haiku"]);
+
+    let request = CreateMessageRequest {
+        model: "claude-sonnet-4".to_string(),
+        messages: vec![Message {
+            role: Role::User,
+            content: vec![ContentBlock::text("Hello")],
+        }],
+        ..Default::default()
+    };
+
+    let response = client.create_message(request).await.expect("fallback failed");
+    assert_eq!(response.model, "claude-3-5-haiku");
+
+    // Primary tried once plus one retry, then the fallback
+    let requests = server.await.unwrap();
+    assert!(requests[0].contains(r#""model":"claude-sonnet-4""#));
+    assert!(requests[1].contains(r#""model":"claude-sonnet-4""#));
+    assert!(requests[2].contains(r#""model":"claude-3-5-haiku""#));
+}
+
+#[tokio::test]
+async fn test_stream_cancellation() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = format!("http://{}", listener.local_addr().unwrap());
+
+    // Send the start of a response, then hold the connection open until
+    // the client goes away
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        read_request(&mut socket).await;
+        let events = [
+            ("message_start", r#"{"type":"message_start","message":{"id":"msg_c","type":"message","role":"assistant","content":[],"model":"claude-sonnet-4-20250514","usage":{"input_tokens":4,"output_tokens":0}}}"#),
+            ("content_block_start", r#"{"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#),
+            ("content_block_delta", r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hel"}}"#),
+        ];
+        let mut response =
+            String::from("HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\nconnection: close\r\n\r\n");
+        for (name, data) in events {
+            response.push_str(&format!("event: {}\ndata: {}\n\n", name, data));
+        }
+        socket.write_all(response.as_bytes()).await.unwrap();
+
+        let mut buf = [0u8; 64];
+        matches!(socket.read(&mut buf).await, Ok(0) | Err(_))
+    });
+
+    let client = AnthropicClient::new("test_key".to_string(), addr, "2023-06-01".to_string())
+        .expect("Client creation failed");
+    let request = CreateMessageRequest {
+        messages: vec![Message {
+            role: Role::User,
+            content: vec![ContentBlock::text("Hello")],
+        }],
+        ..Default::default()
+    };
+
+    let token = CancellationToken::new();
+    let mut stream = client
+        .create_message_stream(request)
+        .await
+        .expect("Stream request failed")
+        .with_cancellation(token.clone());
+
+    for _ in 0..3 {
+        stream.next().await.expect("event").expect("event ok");
+    }
+
+    token.cancel();
+    let next = tokio::time::timeout(std::time::Duration::from_secs(1), stream.next())
+        .await
+        .expect("next() should return promptly after cancellation");
+    assert!(next.is_none());
+    assert!(stream.next().await.is_none());
+
+    let partial = stream.cancel();
+    assert_eq!(partial.id, "msg_c");
+    assert!(matches!(&partial.content[0], ContentBlock::Text { text, .. } if text == "Hel"));
+
+    let closed = tokio::time::timeout(std::time::Duration::from_secs(5), server)
+        .await
+        .expect("connection should be closed after cancellation")
+        .unwrap();
+    assert!(closed);
+}
+
+#[tokio::test]
+async fn test_builder_connect_timeout() {
+    // 10.255.255.1 is non-routable, so the TCP handshake never completes
+    let client = AnthropicClient::builder("test_key")
+        .api_base("http://10.255.255.1")
+        .connect_timeout(std::time::Duration::from_millis(1))
+        .request_timeout(std::time::Duration::from_secs(30))
+        .pool_max_idle_per_host(2)
+        .retry_config(RetryConfig {
+            max_retries: 0,
+            ..Default::default()
+        })
+        .build()
+        .expect("Client creation failed");
+
+    let request = CreateMessageRequest {
+        messages: vec![Message {
+            role: Role::User,
+            content: vec![ContentBlock::text("Hello")],
+        }],
+        ..Default::default()
+    };
+
+    let result = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        client.create_message(request),
+    )
+    .await
+    .expect("connect timeout should fire long before the request timeout");
+
+    let err = result.expect_err("connection should not succeed");
+    let reqwest_err = err
+        .downcast_ref::<reqwest::Error>()
+        .expect("expected a transport error");
+    // Usually a connect timeout; sandboxes that reset outbound connections
+    // fail the send instead. Either way no HTTP response arrived.
+    assert!(reqwest_err.status().is_none(), "{reqwest_err:?}");
+}
+
 #[tokio::test]
 #[ignore] // Requires mock server or live API
 async fn test_retry_on_rate_limit() {
@@ -185,6 +430,7 @@ async fn test_error_handling() {
             role: Role::User,
             content: vec![ContentBlock::Text {
                 text: "Test".to_string(),
+                cache_control: None,
             }],
         }],
         ..Default::default()
@@ -6,7 +6,7 @@ use std::sync::{Arc, Mutex};
 use superclaude_runtime::evidence::EvidenceCollector;
 use superclaude_runtime::hooks::{
     create_evidence_hooks, create_logging_hooks, create_safety_hooks, create_sdk_hooks, merge_hooks,
-    HookInput, HookOutput,
+    HookInput, HookMatcher, HookOutput,
 };
 
 /// Helper to create a HookInput for testing
@@ -22,6 +22,7 @@ fn make_hook_input(
         tool_response: Value::Null,
         session_id: "test-session".to_string(),
         stop_hook_active: false,
+        cwd: String::new(),
     }
 }
 
@@ -281,12 +282,42 @@ async fn test_evidence_hooks_subagent_stop() {
 
     assert_eq!(config.subagent_stop.len(), 1);
 
-    let input = make_hook_input("SubagentStop", "", HashMap::new());
-    config.subagent_stop[0](input, Some("tool-123".to_string()), HashMap::new()).await;
+    // The result comes from the Task tool's PostToolUse, correlated with its
+    // PreToolUse by tool_use_id; SubagentStop alone records nothing
+    let task_input = |event: &str| {
+        make_hook_input(
+            event,
+            "Task",
+            HashMap::from([
+                ("subagent_type".to_string(), json!("code-reviewer")),
+                ("description".to_string(), json!("Review the parser")),
+            ]),
+        )
+    };
+    let task_hook = |matchers: &[HookMatcher]| {
+        matchers
+            .iter()
+            .position(|m| m.matcher.as_deref() == Some("Task"))
+            .expect("Should have Task matcher")
+    };
+    let start = &config.pre_tool_use[task_hook(&config.pre_tool_use)].hooks[0];
+    let result = &config.post_tool_use[task_hook(&config.post_tool_use)].hooks[0];
+
+    start(task_input("PreToolUse"), Some("tool-123".to_string()), HashMap::new()).await;
+    config.subagent_stop[0](make_hook_input("SubagentStop", "", HashMap::new()), None, HashMap::new())
+        .await;
+    assert_eq!(evidence.lock().unwrap().subagents_spawned, 0);
+
+    let mut done = task_input("PostToolUse");
+    done.tool_response = json!({"content": [{"type": "text", "text": "Looks good"}]});
+    result(done, Some("tool-123".to_string()), HashMap::new()).await;
 
     let ev = evidence.lock().unwrap();
     assert_eq!(ev.subagents_spawned, 1);
     assert_eq!(ev.subagent_results.len(), 1);
+    assert_eq!(ev.subagent_results[0].subagent_type, "code-reviewer");
+    assert_eq!(ev.subagent_results[0].result_summary, "Looks good");
+    assert!(ev.subagent_results[0].success);
 }
 
 #[tokio::test]
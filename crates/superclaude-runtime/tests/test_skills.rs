@@ -12,7 +12,7 @@ fn test_skill_store_save_and_get() {
     let skills_dir = temp_dir.path().join("skills").join("learned");
     let feedback_dir = temp_dir.path().join("feedback");
 
-    let mut store = SkillStore::new(Some(skills_dir), Some(feedback_dir)).unwrap();
+    let store = SkillStore::new(Some(skills_dir), Some(feedback_dir)).unwrap();
 
     let skill = LearnedSkill {
         skill_id: "test-skill-001".to_string(),
@@ -31,6 +31,7 @@ fn test_skill_store_save_and_get() {
         applicability_conditions: vec!["Python projects".to_string()],
         promoted: false,
         promotion_reason: String::new(),
+        last_applied_at: None,
     };
 
     store.save_skill(&skill).unwrap();
@@ -48,7 +49,7 @@ fn test_skill_search() {
     let skills_dir = temp_dir.path().join("skills").join("learned");
     let feedback_dir = temp_dir.path().join("feedback");
 
-    let mut store = SkillStore::new(Some(skills_dir), Some(feedback_dir)).unwrap();
+    let store = SkillStore::new(Some(skills_dir), Some(feedback_dir)).unwrap();
 
     let skill = LearnedSkill {
         skill_id: "test-skill-002".to_string(),
@@ -67,10 +68,11 @@ fn test_skill_search() {
         applicability_conditions: vec!["Has test suite".to_string()],
         promoted: false,
         promotion_reason: String::new(),
+        last_applied_at: None,
     };
 
     store.save_skill(&skill).unwrap();
-    let results = store.search_skills("pytest", None, 50.0, false).unwrap();
+    let results = store.search_skills("pytest", None, 50.0, false, false).unwrap();
 
     assert_eq!(results.len(), 1);
     assert_eq!(results[0].skill_id, skill.skill_id);
@@ -166,6 +168,7 @@ fn test_skill_to_md() {
         applicability_conditions: vec!["Condition 1".to_string()],
         promoted: false,
         promotion_reason: String::new(),
+        last_applied_at: None,
     };
 
     let md = skill.to_skill_md();
@@ -234,7 +237,7 @@ fn test_skill_retriever() {
     let skills_dir = temp_dir.path().join("skills").join("learned");
     let feedback_dir = temp_dir.path().join("feedback");
 
-    let mut store = SkillStore::new(Some(skills_dir), Some(feedback_dir)).unwrap();
+    let store = SkillStore::new(Some(skills_dir), Some(feedback_dir)).unwrap();
 
     let skill = LearnedSkill {
         skill_id: "retrieve-test".to_string(),
@@ -253,11 +256,12 @@ fn test_skill_retriever() {
         applicability_conditions: vec!["Backend API".to_string()],
         promoted: false,
         promotion_reason: String::new(),
+        last_applied_at: None,
     };
 
     store.save_skill(&skill).unwrap();
 
-    let mut retriever = SkillRetriever::new(&mut store);
+    let retriever = SkillRetriever::new(&store);
     let results = retriever.retrieve(
         "implement authentication and login",
         None,
@@ -295,6 +299,7 @@ fn test_promotion_gate() {
         applicability_conditions: vec!["Condition 1".to_string()],
         promoted: false,
         promotion_reason: String::new(),
+        last_applied_at: None,
     };
 
     // Add applications to meet threshold